@@ -0,0 +1,217 @@
+//#######################################################################o
+// bcrypt is a password hashing function built on EksBlowfish, a         |
+// Blowfish key schedule whose cost is tunable: after keying the state   |
+// with the password and a 128-bit salt, the key and the salt are        |
+// alternately mixed in again 2^cost times. The final state encrypts     |
+// "OrpheanBeholderScryDoubt" 64 times to produce the 23 bytes hash.     |
+// Hashes are stored as $2b$<cost>$<salt><hash> with a base64 variant.   |
+//                                                                       |
+// @Refrences:                                                           |
+// Provos, Mazieres: https://www.usenix.org/legacy/events/usenix99/provos/provos.pdf
+// OpenBSD: https://man.openbsd.org/crypt.3                              |
+//#######################################################################o
+
+use crate::block::blowfish::Blowfish;
+use crate::encoder::base64::{bcrypt_b64_decode, bcrypt_b64_encode};
+use crate::error::CryptoError;
+use crate::util::ct_eq;
+
+// Block encrypted by the final state.
+const MAGIC: &[u8; 24] = b"OrpheanBeholderScryDoubt";
+// The password (NUL terminated) is truncated to this length.
+const MAX_PASSWORD_LEN: usize = 72;
+const MIN_COST: u32 = 4;
+const MAX_COST: u32 = 31;
+// Length of an encoded hash: $2b$ + cost + $ + 22 (salt) + 31 (hash).
+const ENCODED_LEN: usize = 60;
+
+////////////////////////////////////////////////////////////////////////////
+/// Computes the raw bcrypt hash of a password.
+///# Arguments
+///* `password`: the password, only its first 72 bytes are used.
+///* `cost`: the base 2 logarithm of the number of iterations, 4 to 31.
+///* `salt`: a random 16 bytes salt.
+///# Returns
+/// The 23 bytes hash or `CryptoError::InvalidParameter` for a bad cost.
+///# Note
+/// '''
+/// As with every C implementation the password is NUL terminated, so
+/// a password containing a NUL byte is truncated at that byte.
+/// '''
+////////////////////////////////////////////////////////////////////////////
+pub fn bcrypt(password: &[u8], cost: u32, salt: &[u8; 16]) -> Result<[u8; 23], CryptoError> {
+    if !(MIN_COST..=MAX_COST).contains(&cost) {
+        return Err(CryptoError::InvalidParameter);
+    }
+    let end = password
+        .iter()
+        .position(|&b| b == 0)
+        .unwrap_or(password.len());
+    let mut key = password[..end].to_vec();
+    key.push(0);
+    key.truncate(MAX_PASSWORD_LEN);
+
+    // EksBlowfishSetup.
+    let mut state = Blowfish::initial();
+    state.expand_key(&key, salt);
+    for _ in 0..1_u64 << cost {
+        state.expand_key(&key, &[0; 16]);
+        state.expand_key(salt, &[0; 16]);
+    }
+
+    let mut words = [0_u32; 6];
+    for (word, bytes) in words.iter_mut().zip(MAGIC.chunks_exact(4)) {
+        *word = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for _ in 0..64 {
+        for pair in words.chunks_exact_mut(2) {
+            (pair[0], pair[1]) = state.encrypt_words(pair[0], pair[1]);
+        }
+    }
+    let mut hash = [0_u8; 24];
+    for (bytes, word) in hash.chunks_exact_mut(4).zip(words.iter()) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    Ok(hash[..23].try_into().unwrap())
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Hashes a password into the modular crypt format.
+///# Arguments
+///* `password`: the password, only its first 72 bytes are used.
+///* `cost`: the base 2 logarithm of the number of iterations, 4 to 31.
+///* `salt`: a random 16 bytes salt, never reused.
+///# Returns
+/// The `$2b$` hash string or `CryptoError::InvalidParameter`.
+///# Example
+/// ```
+/// use cryptolib::bcrypt::{bcrypt_hash, bcrypt_verify};
+///
+/// let hash = bcrypt_hash(b"hunter2", 4, &[7; 16]).unwrap();
+/// assert!(hash.starts_with("$2b$04$"));
+/// assert!(bcrypt_verify(b"hunter2", &hash).is_ok());
+/// assert!(bcrypt_verify(b"hunter3", &hash).is_err());
+/// ```
+////////////////////////////////////////////////////////////////////////////
+pub fn bcrypt_hash(password: &[u8], cost: u32, salt: &[u8; 16]) -> Result<String, CryptoError> {
+    let hash = bcrypt(password, cost, salt)?;
+    let mut out = format!("$2b${:02}$", cost).into_bytes();
+    out.extend(bcrypt_b64_encode(salt));
+    out.extend(bcrypt_b64_encode(&hash));
+    Ok(String::from_utf8(out).expect("base64 is ascii"))
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Checks a password against a `$2a$`, `$2b$` or `$2y$` hash string.
+///# Returns
+/// `CryptoError::AuthenticationFailed` if the password doesn't match
+/// or `CryptoError::InvalidParameter` if the hash is malformed.
+///# Note
+/// '''
+/// The three prefixes are handled the same way, they only differ in
+/// bugs of old implementations for passwords longer than 255 bytes
+/// or with non ASCII characters that aren't reproduced here.
+/// '''
+////////////////////////////////////////////////////////////////////////////
+pub fn bcrypt_verify(password: &[u8], hash: &str) -> Result<(), CryptoError> {
+    let (cost, salt, expected) = parse(hash.as_bytes()).ok_or(CryptoError::InvalidParameter)?;
+    let computed = bcrypt(password, cost, &salt)?;
+    if !ct_eq(&computed, &expected) {
+        return Err(CryptoError::AuthenticationFailed);
+    }
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Splits an encoded hash into its cost, salt and hash.
+//////////////////////////////////////////////////////////////////////
+fn parse(hash: &[u8]) -> Option<(u32, [u8; 16], Vec<u8>)> {
+    if hash.len() != ENCODED_LEN || !matches!(&hash[..4], b"$2a$" | b"$2b$" | b"$2y$") {
+        return None;
+    }
+    if !hash[4..6].iter().all(u8::is_ascii_digit) || hash[6] != b'$' {
+        return None;
+    }
+    let cost = ((hash[4] - b'0') * 10 + hash[5] - b'0') as u32;
+    let salt = bcrypt_b64_decode(&hash[7..29])?.try_into().ok()?;
+    let expected = bcrypt_b64_decode(&hash[29..])?;
+    Some((cost, salt, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bcrypt_verify() {
+        // Checked against OpenBSD / libxcrypt crypt(3).
+        for (password, hash) in [
+            (
+                &b""[..],
+                "$2a$06$DCq7YPn5Rq63x1Lad4cll.TV4S6ytwfsfvkgY8jIucDrjc8deX1s.",
+            ),
+            (
+                b"abcdefghijklmnopqrstuvwxyz",
+                "$2a$06$.rCVZVOThsIa97pEDOxvGuRRgzG64bvtJ0938xuqzv18d3ZpQhstC",
+            ),
+            (
+                b"~!@#$%^&*()      ~!@#$%^&*()PNBFRD",
+                "$2a$06$fPIsBO8qRqkjj273rfaOI.HtSV9jLDpTbZn782DC6/t7qT67P6FfO",
+            ),
+            (
+                b"U*U",
+                "$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW",
+            ),
+            (
+                b"U*U*",
+                "$2y$05$CCCCCCCCCCCCCCCCCCCCC.VGOzA784oUp/Z0DY336zx7pLYAy0lwK",
+            ),
+        ] {
+            assert_eq!(bcrypt_verify(password, hash), Ok(()));
+            assert_eq!(
+                bcrypt_verify(b"wrong", hash),
+                Err(CryptoError::AuthenticationFailed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_bcrypt_hash() {
+        let salt = bcrypt_b64_decode(b"abcdefghijklmnopqrstuu").unwrap();
+        let salt: [u8; 16] = salt.try_into().unwrap();
+        // Only the first 72 bytes count.
+        let expected = "$2b$04$abcdefghijklmnopqrstuuBzzIgyKkz7xMWYSzkIjUSnxEQFQ0WNe";
+        assert_eq!(bcrypt_hash(&[b'a'; 72], 4, &salt).unwrap(), expected);
+        assert_eq!(bcrypt_hash(&[b'a'; 100], 4, &salt).unwrap(), expected);
+        assert_eq!(
+            bcrypt_hash(&[b'a'; 71], 4, &salt).unwrap(),
+            "$2b$04$abcdefghijklmnopqrstuuMCu.k1vM/ywQwiONaEn3oEMlZoCVBd6"
+        );
+    }
+
+    #[test]
+    fn test_bcrypt_errors() {
+        assert_eq!(
+            bcrypt(b"password", 3, &[0; 16]).err(),
+            Some(CryptoError::InvalidParameter)
+        );
+        assert_eq!(
+            bcrypt(b"password", 32, &[0; 16]).err(),
+            Some(CryptoError::InvalidParameter)
+        );
+        let hash = "$2b$04$AAAAAAAAAAAAAAAAAAAAAuq7ZxAcLLdeEkSuboO3ALersjkBbgC1G";
+        assert_eq!(bcrypt_verify(b"password", hash), Ok(()));
+        for malformed in [
+            "$2x$04$AAAAAAAAAAAAAAAAAAAAAuq7ZxAcLLdeEkSuboO3ALersjkBbgC1G",
+            "$2b$4$AAAAAAAAAAAAAAAAAAAAAuq7ZxAcLLdeEkSuboO3ALersjkBbgC1G",
+            "$2b$04$AAAAAAAAAAAAAAAAAAAAAuq7ZxAcLLdeEkSuboO3ALersjkBbgC1",
+            "$2b$04$AAAAAAAAAAAAAAAAAAAAAu+7ZxAcLLdeEkSuboO3ALersjkBbgC1G",
+            "$2b$99$AAAAAAAAAAAAAAAAAAAAAuq7ZxAcLLdeEkSuboO3ALersjkBbgC1G",
+        ] {
+            assert_eq!(
+                bcrypt_verify(b"password", malformed),
+                Err(CryptoError::InvalidParameter)
+            );
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//#######################################################################o
+// The Advanced Encryption Standard (AES) is a substitution-permutation  |
+// network operating on 128-bit blocks with keys of 128, 192 or 256      |
+// bits, the key size selects the number of rounds (10, 12 or 14).       |
+// Each round is made of the SubBytes, ShiftRows, MixColumns and         |
+// AddRoundKey transformations applied to a 4x4 bytes state.             |
+// Two backends are available, AES-NI on x86_64 cpus that support it     |
+// and a portable constant-time implementation otherwise, the backend    |
+// is selected at runtime when the key is set.                           |
+//                                                                       |
+// @Refrences:                                                           |
+// FIPS 197: https://doi.org/10.6028/NIST.FIPS.197-upd1                  |
+// AESAVS: https://csrc.nist.gov/projects/cryptographic-algorithm-validation-program
+//#######################################################################o
+
+#[cfg(target_arch = "x86_64")]
+mod ni;
+mod soft;
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// Round constants used by the key expansion.
+const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+// Maximum number of rounds (AES-256).
+const MAX_ROUNDS: usize = 14;
+
+/// A 128-bit AES state or round key, stored column by column.
+type Block = [u8; 16];
+
+////////////////////////////////////////////////////////////
+/// `AesBackend` represent the implementation used by an
+/// `Aes` cipher to process the blocks.
+/// # Values
+/// Soft: portable constant-time implementation.
+/// AesNi: x86_64 AES-NI instructions.
+////////////////////////////////////////////////////////////
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AesBackend {
+    Soft,
+    AesNi,
+}
+
+impl AesBackend {
+    //////////////////////////////////////////////////////////////////
+    /// Returns the fastest backend supported by the running cpu.
+    //////////////////////////////////////////////////////////////////
+    pub fn detect() -> AesBackend {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if ni::is_supported() {
+                return AesBackend::AesNi;
+            }
+        }
+        AesBackend::Soft
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Expands the cipher `key` into the `rounds + 1` encryption round keys.
+//////////////////////////////////////////////////////////////////////
+fn expand_key(key: &[u8], rounds: usize) -> [Block; MAX_ROUNDS + 1] {
+    let nk = key.len() / 4;
+    let total_words = 4 * (rounds + 1);
+    let mut words = [[0_u8; 4]; 4 * (MAX_ROUNDS + 1)];
+    for (i, word) in key.chunks_exact(4).enumerate() {
+        words[i].copy_from_slice(word);
+    }
+    for i in nk..total_words {
+        let mut temp = words[i - 1];
+        if i % nk == 0 {
+            // RotWord, SubWord and the round constant.
+            temp.rotate_left(1);
+            soft::sub_bytes(&mut temp);
+            temp[0] ^= RCON[i / nk - 1];
+        } else if nk > 6 && i % nk == 4 {
+            soft::sub_bytes(&mut temp);
+        }
+        for j in 0..4 {
+            words[i][j] = words[i - nk][j] ^ temp[j];
+        }
+    }
+    let mut round_keys = [[0_u8; 16]; MAX_ROUNDS + 1];
+    for (round, round_key) in round_keys.iter_mut().enumerate().take(rounds + 1) {
+        for c in 0..4 {
+            round_key[4 * c..4 * c + 4].copy_from_slice(&words[4 * round + c]);
+        }
+    }
+    round_keys
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Aes` holds the expanded encryption and decryption key schedules of
+/// an AES-128, AES-192 or AES-256 key.
+///
+/// Decryption uses the equivalent inverse cipher (FIPS 197 §5.3.5) so
+/// its round keys have InvMixColumns already applied to them.
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Aes {
+    rounds: usize,
+    enc_keys: [Block; MAX_ROUNDS + 1],
+    dec_keys: [Block; MAX_ROUNDS + 1],
+    backend: AesBackend,
+}
+
+impl Aes {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an AES cipher from a `key` using the fastest backend
+    /// supported by the cpu.
+    ///# Arguments
+    ///* `key`: 16, 24 or 32 bytes which select AES-128, AES-192 or AES-256.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Aes, CryptoError> {
+        Aes::with_backend(key, AesBackend::detect())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an AES cipher from a `key` using the given `backend`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ///# Note
+    /// '''
+    /// If the cpu doesn't support the requested backend the portable
+    /// one is used, `Aes::backend` tells which one was selected.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_backend(key: &[u8], backend: AesBackend) -> Result<Aes, CryptoError> {
+        let rounds = match key.len() {
+            16 => 10,
+            24 => 12,
+            32 => 14,
+            _ => return Err(CryptoError::InvalidKeyLength),
+        };
+        let enc_keys = expand_key(key, rounds);
+        let mut dec_keys = [[0_u8; 16]; MAX_ROUNDS + 1];
+        dec_keys[0] = enc_keys[rounds];
+        dec_keys[rounds] = enc_keys[0];
+        for round in 1..rounds {
+            dec_keys[round] = enc_keys[rounds - round];
+            soft::inv_mix_columns(&mut dec_keys[round]);
+        }
+        let backend = match backend {
+            AesBackend::AesNi if AesBackend::detect() == AesBackend::AesNi => AesBackend::AesNi,
+            _ => AesBackend::Soft,
+        };
+        Ok(Aes {
+            rounds,
+            enc_keys,
+            dec_keys,
+            backend,
+        })
+    }
+
+    /// Returns the key size in bytes (16, 24 or 32).
+    pub fn key_size(&self) -> usize {
+        4 * (self.rounds - 6)
+    }
+
+    /// Returns the backend used by this cipher.
+    pub fn backend(&self) -> AesBackend {
+        self.backend
+    }
+
+    /// Returns the encrypted 128-bit block.
+    pub fn encrypt(&self, plain_text: &[u8; 16]) -> [u8; 16] {
+        let mut block = *plain_text;
+        self.encrypt_blocks(&mut block);
+        block
+    }
+
+    /// Returns the decrypted 128-bit block.
+    pub fn decrypt(&self, cipher_text: &[u8; 16]) -> [u8; 16] {
+        let mut block = *cipher_text;
+        self.decrypt_blocks(&mut block);
+        block
+    }
+}
+
+impl BlockCipher for Aes {
+    const BLOCK_SIZE: usize = 16;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), 16, "aes block must be 16 bytes");
+        self.encrypt_blocks(block);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), 16, "aes block must be 16 bytes");
+        self.decrypt_blocks(block);
+    }
+
+    fn encrypt_blocks(&self, blocks: &mut [u8]) {
+        assert!(blocks.len().is_multiple_of(16), "partial aes block");
+        match self.backend {
+            #[cfg(target_arch = "x86_64")]
+            // SAFETY: the AesNi backend is only selected when the cpu supports it.
+            AesBackend::AesNi => unsafe { ni::encrypt_blocks(&self.enc_keys, self.rounds, blocks) },
+            _ => {
+                for block in blocks.chunks_exact_mut(16) {
+                    let block: &mut Block = block.try_into().unwrap();
+                    soft::encrypt(&self.enc_keys, self.rounds, block);
+                }
+            }
+        }
+    }
+
+    fn decrypt_blocks(&self, blocks: &mut [u8]) {
+        assert!(blocks.len().is_multiple_of(16), "partial aes block");
+        match self.backend {
+            #[cfg(target_arch = "x86_64")]
+            // SAFETY: the AesNi backend is only selected when the cpu supports it.
+            AesBackend::AesNi => unsafe { ni::decrypt_blocks(&self.dec_keys, self.rounds, blocks) },
+            _ => {
+                for block in blocks.chunks_exact_mut(16) {
+                    let block: &mut Block = block.try_into().unwrap();
+                    soft::decrypt(&self.dec_keys, self.rounds, block);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    // Backends supported by the cpu running the tests.
+    fn backends() -> Vec<AesBackend> {
+        let mut backends = vec![AesBackend::Soft];
+        if AesBackend::detect() == AesBackend::AesNi {
+            backends.push(AesBackend::AesNi);
+        }
+        backends
+    }
+
+    fn check_kat(key: &str, plain: &str, cipher: &str) {
+        for backend in backends() {
+            let aes = Aes::with_backend(&hex(key), backend).unwrap();
+            assert_eq!(aes.backend(), backend);
+            let mut block = hex(plain);
+            aes.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher), "{:?}", backend);
+            aes.decrypt_block(&mut block);
+            assert_eq!(block, hex(plain), "{:?}", backend);
+        }
+    }
+
+    #[test]
+    fn test_key_expansion() {
+        // FIPS 197 Appendix A.
+        let w = expand_key(&hex("2b7e151628aed2a6abf7158809cf4f3c"), 10);
+        assert_eq!(w[10].to_vec(), hex("d014f9a8c9ee2589e13f0cc8b6630ca6"));
+        let w = expand_key(&hex("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b"), 12);
+        assert_eq!(w[12].to_vec(), hex("e98ba06f448c773c8ecc720401002202"));
+        let w = expand_key(
+            &hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4"),
+            14,
+        );
+        assert_eq!(w[14].to_vec(), hex("fe4890d1e6188d0b046df344706c631e"));
+    }
+
+    #[test]
+    fn test_fips197_examples() {
+        // FIPS 197 Appendix C.
+        check_kat(
+            "000102030405060708090a0b0c0d0e0f",
+            "00112233445566778899aabbccddeeff",
+            "69c4e0d86a7b0430d8cdb78070b4c55a",
+        );
+        check_kat(
+            "000102030405060708090a0b0c0d0e0f1011121314151617",
+            "00112233445566778899aabbccddeeff",
+            "dda97ca4864cdfe06eaf70a0ec0d7191",
+        );
+        check_kat(
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "00112233445566778899aabbccddeeff",
+            "8ea2b7ca516745bfeafc49904b496089",
+        );
+    }
+
+    #[test]
+    fn test_aesavs() {
+        // GFSbox.
+        check_kat(
+            "00000000000000000000000000000000",
+            "f34481ec3cc627bacd5dc3fb08f273e6",
+            "0336763e966d92595a567cc9ce537f5e",
+        );
+        check_kat(
+            "000000000000000000000000000000000000000000000000",
+            "1b077a6af4b7f98229de786d7516b639",
+            "275cfc0413d8ccb70513c3859b1d0f72",
+        );
+        check_kat(
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "014730f80ac625fe84f026c60bfd547d",
+            "5c9d844ed46f9885085e5d6a4f94c7d7",
+        );
+        // KeySbox.
+        check_kat(
+            "10a58869d74be5a374cf867cfb473859",
+            "00000000000000000000000000000000",
+            "6d251e6944b051e04eaa6fb4dbf78465",
+        );
+        check_kat(
+            "e9f065d7c13573587f7875357dfbb16c53489f6a4bd0f7cd",
+            "00000000000000000000000000000000",
+            "0956259c9cd5cfd0181cca53380cde06",
+        );
+        check_kat(
+            "c47b0294dbbbee0fec4757f22ffeee3587ca4730c3d33b691df38bab076bc558",
+            "00000000000000000000000000000000",
+            "46f2fb342d6f0ab477476fc501242c5f",
+        );
+        // VarTxt and VarKey.
+        check_kat(
+            "00000000000000000000000000000000",
+            "80000000000000000000000000000000",
+            "3ad78e726c1ec02b7ebfe92b23d9ec34",
+        );
+        check_kat(
+            "80000000000000000000000000000000",
+            "00000000000000000000000000000000",
+            "0edd33d3c621e546455bd8ba1418bec8",
+        );
+        check_kat(
+            "8000000000000000000000000000000000000000000000000000000000000000",
+            "00000000000000000000000000000000",
+            "e35a6dcb19b201a01ebcfa8aa22b5759",
+        );
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        assert_eq!(
+            Aes::new(&[0; 15]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Aes::new(&[0; 33]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(Aes::new(&[0; 24]).unwrap().key_size(), 24);
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        // 37 blocks cover both the interleaved and the single block paths.
+        let mut data = vec![0_u8; 16 * 37];
+        let mut x = 0x2545f491_u32;
+        for b in data.iter_mut() {
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            *b = x as u8;
+        }
+        for key_len in [16, 24, 32] {
+            let key = &data[..key_len];
+            let reference = Aes::with_backend(key, AesBackend::Soft).unwrap();
+            let mut expected = data.clone();
+            for block in expected.chunks_exact_mut(16) {
+                reference.encrypt_block(block);
+            }
+            for backend in backends() {
+                let aes = Aes::with_backend(key, backend).unwrap();
+                let mut blocks = data.clone();
+                aes.encrypt_blocks(&mut blocks);
+                assert_eq!(blocks, expected, "{:?}", backend);
+                aes.decrypt_blocks(&mut blocks);
+                assert_eq!(blocks, data, "{:?}", backend);
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//#######################################################################o
+// AES using the x86_64 AES-NI instructions.                             |
+// AESENC/AESDEC perform a full round in a single instruction, and as    |
+// their latency is higher than their throughput up to 8 independent    |
+// blocks are interleaved to keep the pipeline busy.                     |
+// AESDEC implements the equivalent inverse cipher, so it uses the same  |
+// decryption round keys as the portable backend.                        |
+//                                                                       |
+// @Refrences:                                                           |
+// Intel AES New Instructions Set white paper (Gueron, 2010).            |
+//#######################################################################o
+
+use core::arch::x86_64::{
+    __m128i, _mm_aesdec_si128, _mm_aesdeclast_si128, _mm_aesenc_si128, _mm_aesenclast_si128,
+    _mm_loadu_si128, _mm_setzero_si128, _mm_storeu_si128, _mm_xor_si128,
+};
+
+use super::{Block, MAX_ROUNDS};
+
+// Number of blocks processed together.
+const PARALLEL_BLOCKS: usize = 8;
+
+/// Returns true if the cpu supports the AES-NI instructions.
+pub(super) fn is_supported() -> bool {
+    std::is_x86_feature_detected!("aes") && std::is_x86_feature_detected!("sse2")
+}
+
+#[target_feature(enable = "sse2")]
+fn load_keys(round_keys: &[Block; MAX_ROUNDS + 1], rounds: usize) -> [__m128i; MAX_ROUNDS + 1] {
+    let mut keys = [_mm_setzero_si128(); MAX_ROUNDS + 1];
+    for (key, round_key) in keys.iter_mut().zip(round_keys.iter()).take(rounds + 1) {
+        // SAFETY: `round_key` is 16 bytes long and the load is unaligned.
+        *key = unsafe { _mm_loadu_si128(round_key.as_ptr() as *const __m128i) };
+    }
+    keys
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Encrypts consecutive 16 bytes blocks in place.
+///# Safety
+/// The caller must make sure the cpu supports AES-NI (`is_supported`).
+//////////////////////////////////////////////////////////////////////
+#[target_feature(enable = "aes,sse2")]
+pub(super) unsafe fn encrypt_blocks(
+    round_keys: &[Block; MAX_ROUNDS + 1],
+    rounds: usize,
+    blocks: &mut [u8],
+) {
+    let keys = load_keys(round_keys, rounds);
+    let mut chunks = blocks.chunks_exact_mut(16 * PARALLEL_BLOCKS);
+    for chunk in &mut chunks {
+        let mut state = [keys[0]; PARALLEL_BLOCKS];
+        for (i, s) in state.iter_mut().enumerate() {
+            let ptr = chunk[16 * i..].as_ptr() as *const __m128i;
+            *s = _mm_xor_si128(_mm_loadu_si128(ptr), keys[0]);
+        }
+        for key in &keys[1..rounds] {
+            for s in state.iter_mut() {
+                *s = _mm_aesenc_si128(*s, *key);
+            }
+        }
+        for (i, s) in state.iter().enumerate() {
+            let ptr = chunk[16 * i..].as_mut_ptr() as *mut __m128i;
+            _mm_storeu_si128(ptr, _mm_aesenclast_si128(*s, keys[rounds]));
+        }
+    }
+    for block in chunks.into_remainder().chunks_exact_mut(16) {
+        let mut s = _mm_xor_si128(_mm_loadu_si128(block.as_ptr() as *const __m128i), keys[0]);
+        for key in &keys[1..rounds] {
+            s = _mm_aesenc_si128(s, *key);
+        }
+        _mm_storeu_si128(
+            block.as_mut_ptr() as *mut __m128i,
+            _mm_aesenclast_si128(s, keys[rounds]),
+        );
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Decrypts consecutive 16 bytes blocks in place.
+///# Safety
+/// The caller must make sure the cpu supports AES-NI (`is_supported`).
+//////////////////////////////////////////////////////////////////////
+#[target_feature(enable = "aes,sse2")]
+pub(super) unsafe fn decrypt_blocks(
+    round_keys: &[Block; MAX_ROUNDS + 1],
+    rounds: usize,
+    blocks: &mut [u8],
+) {
+    let keys = load_keys(round_keys, rounds);
+    let mut chunks = blocks.chunks_exact_mut(16 * PARALLEL_BLOCKS);
+    for chunk in &mut chunks {
+        let mut state = [keys[0]; PARALLEL_BLOCKS];
+        for (i, s) in state.iter_mut().enumerate() {
+            let ptr = chunk[16 * i..].as_ptr() as *const __m128i;
+            *s = _mm_xor_si128(_mm_loadu_si128(ptr), keys[0]);
+        }
+        for key in &keys[1..rounds] {
+            for s in state.iter_mut() {
+                *s = _mm_aesdec_si128(*s, *key);
+            }
+        }
+        for (i, s) in state.iter().enumerate() {
+            let ptr = chunk[16 * i..].as_mut_ptr() as *mut __m128i;
+            _mm_storeu_si128(ptr, _mm_aesdeclast_si128(*s, keys[rounds]));
+        }
+    }
+    for block in chunks.into_remainder().chunks_exact_mut(16) {
+        let mut s = _mm_xor_si128(_mm_loadu_si128(block.as_ptr() as *const __m128i), keys[0]);
+        for key in &keys[1..rounds] {
+            s = _mm_aesdec_si128(s, *key);
+        }
+        _mm_storeu_si128(
+            block.as_mut_ptr() as *mut __m128i,
+            _mm_aesdeclast_si128(s, keys[rounds]),
+        );
+    }
+}
@@ -0,0 +1,261 @@
+//#######################################################################o
+// Portable constant-time AES.                                           |
+// A table driven S-box leaks the state through the cache timings, so    |
+// here the S-box is computed instead of looked up: the bytes are        |
+// bitsliced (bit `i` of every byte goes in the word `i`) and the        |
+// inversion in GF(2^8) is done with a fixed chain of bitsliced          |
+// multiplications, followed by the affine transformation.               |
+// The linear layers only use shifts, xors and masks so no operation     |
+// depends on the value of a secret.                                     |
+//                                                                       |
+// @Refrences:                                                           |
+// FIPS 197 §5.1.1: https://doi.org/10.6028/NIST.FIPS.197-upd1           |
+// Käsper & Schwabe, Faster and Timing-Attack Resistant AES-GCM (2009).  |
+//#######################################################################o
+
+use super::{Block, MAX_ROUNDS};
+
+/// Bit planes of up to 32 bytes, `planes[i]` holds the bit `i` of each byte.
+type Planes = [u32; 8];
+
+fn bitslice(bytes: &[u8]) -> Planes {
+    let mut planes = [0_u32; 8];
+    for (j, &b) in bytes.iter().enumerate() {
+        for (i, plane) in planes.iter_mut().enumerate() {
+            *plane |= (((b >> i) & 1) as u32) << j;
+        }
+    }
+    planes
+}
+
+fn unbitslice(planes: &Planes, bytes: &mut [u8]) {
+    for (j, b) in bytes.iter_mut().enumerate() {
+        let mut value = 0;
+        for (i, plane) in planes.iter().enumerate() {
+            value |= (((plane >> j) & 1) as u8) << i;
+        }
+        *b = value;
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies bitsliced elements of GF(2^8) modulo
+/// x^8 + x^4 + x^3 + x + 1.
+//////////////////////////////////////////////////////////////////////
+fn sliced_mul(a: &Planes, b: &Planes) -> Planes {
+    let mut prod = [0_u32; 15];
+    for i in 0..8 {
+        for j in 0..8 {
+            prod[i + j] ^= a[i] & b[j];
+        }
+    }
+    // x^k = x^(k-4) + x^(k-5) + x^(k-7) + x^(k-8) for k >= 8.
+    for k in (8..15).rev() {
+        let high = prod[k];
+        prod[k - 4] ^= high;
+        prod[k - 5] ^= high;
+        prod[k - 7] ^= high;
+        prod[k - 8] ^= high;
+    }
+    let mut res = [0_u32; 8];
+    res.copy_from_slice(&prod[..8]);
+    res
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Returns x^254 which is the multiplicative inverse of x
+/// (and 0 for 0), computed with the addition chain
+/// 2, 3, 6, 12, 15, 30, 60, 120, 240, 252, 254.
+//////////////////////////////////////////////////////////////////////
+fn sliced_inv(x: &Planes) -> Planes {
+    let x2 = sliced_mul(x, x);
+    let x3 = sliced_mul(&x2, x);
+    let x6 = sliced_mul(&x3, &x3);
+    let x12 = sliced_mul(&x6, &x6);
+    let x15 = sliced_mul(&x12, &x3);
+    let x30 = sliced_mul(&x15, &x15);
+    let x60 = sliced_mul(&x30, &x30);
+    let x120 = sliced_mul(&x60, &x60);
+    let x240 = sliced_mul(&x120, &x120);
+    let x252 = sliced_mul(&x240, &x12);
+    sliced_mul(&x252, &x2)
+}
+
+/// Returns a plane with all bits set when the bit `i` of `c` is set.
+fn const_plane(c: u8, i: usize) -> u32 {
+    0_u32.wrapping_sub(((c >> i) & 1) as u32)
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Applies the AES S-box to every byte of `bytes` (at most 32).
+//////////////////////////////////////////////////////////////////////
+pub(super) fn sub_bytes(bytes: &mut [u8]) {
+    let inv = sliced_inv(&bitslice(bytes));
+    let mut out = [0_u32; 8];
+    for (i, plane) in out.iter_mut().enumerate() {
+        *plane = inv[i]
+            ^ inv[(i + 4) % 8]
+            ^ inv[(i + 5) % 8]
+            ^ inv[(i + 6) % 8]
+            ^ inv[(i + 7) % 8]
+            ^ const_plane(0x63, i);
+    }
+    unbitslice(&out, bytes);
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Applies the inverse AES S-box to every byte of `bytes` (at most 32).
+//////////////////////////////////////////////////////////////////////
+pub(super) fn inv_sub_bytes(bytes: &mut [u8]) {
+    let planes = bitslice(bytes);
+    let mut affine = [0_u32; 8];
+    for (i, plane) in affine.iter_mut().enumerate() {
+        *plane =
+            planes[(i + 2) % 8] ^ planes[(i + 5) % 8] ^ planes[(i + 7) % 8] ^ const_plane(0x05, i);
+    }
+    unbitslice(&sliced_inv(&affine), bytes);
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies `b` by x (i.e. {02}) in GF(2^8).
+//////////////////////////////////////////////////////////////////////
+fn xtime(b: u8) -> u8 {
+    (b << 1) ^ (((b >> 7) & 1) * 0x1b)
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies `a` by the constant `b` in GF(2^8).
+//////////////////////////////////////////////////////////////////////
+fn gf_mul(mut a: u8, b: u8) -> u8 {
+    let mut res = 0;
+    for i in 0..8 {
+        res ^= a & 0_u8.wrapping_sub((b >> i) & 1);
+        a = xtime(a);
+    }
+    res
+}
+
+fn add_round_key(state: &mut Block, round_key: &Block) {
+    for (s, k) in state.iter_mut().zip(round_key.iter()) {
+        *s ^= k;
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Cyclically shifts the row `r` of the state `r` bytes to the left.
+//////////////////////////////////////////////////////////////////////
+fn shift_rows(state: &mut Block) {
+    let old = *state;
+    for r in 1..4 {
+        for c in 0..4 {
+            state[r + 4 * c] = old[r + 4 * ((c + r) % 4)];
+        }
+    }
+}
+
+fn inv_shift_rows(state: &mut Block) {
+    let old = *state;
+    for r in 1..4 {
+        for c in 0..4 {
+            state[r + 4 * ((c + r) % 4)] = old[r + 4 * c];
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies each column of the state by the polynomial
+/// {03}x^3 + {01}x^2 + {01}x + {02}.
+//////////////////////////////////////////////////////////////////////
+fn mix_columns(state: &mut Block) {
+    for col in state.chunks_exact_mut(4) {
+        let (a0, a1, a2, a3) = (col[0], col[1], col[2], col[3]);
+        let all = a0 ^ a1 ^ a2 ^ a3;
+        col[0] ^= all ^ xtime(a0 ^ a1);
+        col[1] ^= all ^ xtime(a1 ^ a2);
+        col[2] ^= all ^ xtime(a2 ^ a3);
+        col[3] ^= all ^ xtime(a3 ^ a0);
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies each column of the state by the polynomial
+/// {0b}x^3 + {0d}x^2 + {09}x + {0e}.
+//////////////////////////////////////////////////////////////////////
+pub(super) fn inv_mix_columns(state: &mut Block) {
+    for col in state.chunks_exact_mut(4) {
+        let (a0, a1, a2, a3) = (col[0], col[1], col[2], col[3]);
+        col[0] = gf_mul(a0, 0x0e) ^ gf_mul(a1, 0x0b) ^ gf_mul(a2, 0x0d) ^ gf_mul(a3, 0x09);
+        col[1] = gf_mul(a0, 0x09) ^ gf_mul(a1, 0x0e) ^ gf_mul(a2, 0x0b) ^ gf_mul(a3, 0x0d);
+        col[2] = gf_mul(a0, 0x0d) ^ gf_mul(a1, 0x09) ^ gf_mul(a2, 0x0e) ^ gf_mul(a3, 0x0b);
+        col[3] = gf_mul(a0, 0x0b) ^ gf_mul(a1, 0x0d) ^ gf_mul(a2, 0x09) ^ gf_mul(a3, 0x0e);
+    }
+}
+
+/// Encrypts a block with the expanded encryption round keys.
+pub(super) fn encrypt(round_keys: &[Block; MAX_ROUNDS + 1], rounds: usize, block: &mut Block) {
+    add_round_key(block, &round_keys[0]);
+    for round_key in &round_keys[1..rounds] {
+        sub_bytes(block);
+        shift_rows(block);
+        mix_columns(block);
+        add_round_key(block, round_key);
+    }
+    sub_bytes(block);
+    shift_rows(block);
+    add_round_key(block, &round_keys[rounds]);
+}
+
+/// Decrypts a block with the equivalent inverse cipher round keys.
+pub(super) fn decrypt(round_keys: &[Block; MAX_ROUNDS + 1], rounds: usize, block: &mut Block) {
+    add_round_key(block, &round_keys[0]);
+    for round_key in &round_keys[1..rounds] {
+        inv_sub_bytes(block);
+        inv_shift_rows(block);
+        inv_mix_columns(block);
+        add_round_key(block, round_key);
+    }
+    inv_sub_bytes(block);
+    inv_shift_rows(block);
+    add_round_key(block, &round_keys[rounds]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The first and last rows of the FIPS 197 S-box.
+    const S_BOX_ROW_0: [u8; 16] = [
+        0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab,
+        0x76,
+    ];
+    const S_BOX_ROW_F: [u8; 16] = [
+        0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb,
+        0x16,
+    ];
+
+    #[test]
+    fn test_sub_bytes() {
+        let mut row: Vec<u8> = (0x00..0x10).collect();
+        sub_bytes(&mut row);
+        assert_eq!(row, S_BOX_ROW_0);
+        let mut row: Vec<u8> = (0xf0..=0xff).collect();
+        sub_bytes(&mut row);
+        assert_eq!(row, S_BOX_ROW_F);
+    }
+
+    #[test]
+    fn test_sbox_is_a_permutation() {
+        let all: Vec<u8> = (0..=255).collect();
+        let mut seen = [false; 256];
+        for chunk in all.chunks(32) {
+            let mut bytes = chunk.to_vec();
+            sub_bytes(&mut bytes);
+            for &b in &bytes {
+                seen[b as usize] = true;
+            }
+            inv_sub_bytes(&mut bytes);
+            assert_eq!(bytes, chunk);
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+}
@@ -0,0 +1,302 @@
+//#######################################################################o
+// ARIA is the Korean standard block cipher (KS X 1213), a substitution- |
+// permutation network on 128-bit blocks with 12, 14 or 16 rounds for    |
+// 128, 192 and 256-bit keys. Odd rounds apply the S-box layer SL1 and   |
+// even rounds SL2 (built from the AES S-box, another S-box and their    |
+// inverses), followed by an involutive 16x16 binary diffusion matrix.   |
+// The round keys are rotations of four 128-bit words computed from the  |
+// key with a 3 rounds Feistel network using the round functions.        |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 5794: https://www.rfc-editor.org/rfc/rfc5794                      |
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// S-boxes: SB1 is the AES S-box, SB3 and SB4 are the inverses of SB1
+// and SB2.
+const SB1: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+const SB2: [u8; 256] = [
+    0xe2, 0x4e, 0x54, 0xfc, 0x94, 0xc2, 0x4a, 0xcc, 0x62, 0x0d, 0x6a, 0x46, 0x3c, 0x4d, 0x8b, 0xd1,
+    0x5e, 0xfa, 0x64, 0xcb, 0xb4, 0x97, 0xbe, 0x2b, 0xbc, 0x77, 0x2e, 0x03, 0xd3, 0x19, 0x59, 0xc1,
+    0x1d, 0x06, 0x41, 0x6b, 0x55, 0xf0, 0x99, 0x69, 0xea, 0x9c, 0x18, 0xae, 0x63, 0xdf, 0xe7, 0xbb,
+    0x00, 0x73, 0x66, 0xfb, 0x96, 0x4c, 0x85, 0xe4, 0x3a, 0x09, 0x45, 0xaa, 0x0f, 0xee, 0x10, 0xeb,
+    0x2d, 0x7f, 0xf4, 0x29, 0xac, 0xcf, 0xad, 0x91, 0x8d, 0x78, 0xc8, 0x95, 0xf9, 0x2f, 0xce, 0xcd,
+    0x08, 0x7a, 0x88, 0x38, 0x5c, 0x83, 0x2a, 0x28, 0x47, 0xdb, 0xb8, 0xc7, 0x93, 0xa4, 0x12, 0x53,
+    0xff, 0x87, 0x0e, 0x31, 0x36, 0x21, 0x58, 0x48, 0x01, 0x8e, 0x37, 0x74, 0x32, 0xca, 0xe9, 0xb1,
+    0xb7, 0xab, 0x0c, 0xd7, 0xc4, 0x56, 0x42, 0x26, 0x07, 0x98, 0x60, 0xd9, 0xb6, 0xb9, 0x11, 0x40,
+    0xec, 0x20, 0x8c, 0xbd, 0xa0, 0xc9, 0x84, 0x04, 0x49, 0x23, 0xf1, 0x4f, 0x50, 0x1f, 0x13, 0xdc,
+    0xd8, 0xc0, 0x9e, 0x57, 0xe3, 0xc3, 0x7b, 0x65, 0x3b, 0x02, 0x8f, 0x3e, 0xe8, 0x25, 0x92, 0xe5,
+    0x15, 0xdd, 0xfd, 0x17, 0xa9, 0xbf, 0xd4, 0x9a, 0x7e, 0xc5, 0x39, 0x67, 0xfe, 0x76, 0x9d, 0x43,
+    0xa7, 0xe1, 0xd0, 0xf5, 0x68, 0xf2, 0x1b, 0x34, 0x70, 0x05, 0xa3, 0x8a, 0xd5, 0x79, 0x86, 0xa8,
+    0x30, 0xc6, 0x51, 0x4b, 0x1e, 0xa6, 0x27, 0xf6, 0x35, 0xd2, 0x6e, 0x24, 0x16, 0x82, 0x5f, 0xda,
+    0xe6, 0x75, 0xa2, 0xef, 0x2c, 0xb2, 0x1c, 0x9f, 0x5d, 0x6f, 0x80, 0x0a, 0x72, 0x44, 0x9b, 0x6c,
+    0x90, 0x0b, 0x5b, 0x33, 0x7d, 0x5a, 0x52, 0xf3, 0x61, 0xa1, 0xf7, 0xb0, 0xd6, 0x3f, 0x7c, 0x6d,
+    0xed, 0x14, 0xe0, 0xa5, 0x3d, 0x22, 0xb3, 0xf8, 0x89, 0xde, 0x71, 0x1a, 0xaf, 0xba, 0xb5, 0x81,
+];
+
+const SB3: [u8; 256] = [
+    0x52, 0x09, 0x6a, 0xd5, 0x30, 0x36, 0xa5, 0x38, 0xbf, 0x40, 0xa3, 0x9e, 0x81, 0xf3, 0xd7, 0xfb,
+    0x7c, 0xe3, 0x39, 0x82, 0x9b, 0x2f, 0xff, 0x87, 0x34, 0x8e, 0x43, 0x44, 0xc4, 0xde, 0xe9, 0xcb,
+    0x54, 0x7b, 0x94, 0x32, 0xa6, 0xc2, 0x23, 0x3d, 0xee, 0x4c, 0x95, 0x0b, 0x42, 0xfa, 0xc3, 0x4e,
+    0x08, 0x2e, 0xa1, 0x66, 0x28, 0xd9, 0x24, 0xb2, 0x76, 0x5b, 0xa2, 0x49, 0x6d, 0x8b, 0xd1, 0x25,
+    0x72, 0xf8, 0xf6, 0x64, 0x86, 0x68, 0x98, 0x16, 0xd4, 0xa4, 0x5c, 0xcc, 0x5d, 0x65, 0xb6, 0x92,
+    0x6c, 0x70, 0x48, 0x50, 0xfd, 0xed, 0xb9, 0xda, 0x5e, 0x15, 0x46, 0x57, 0xa7, 0x8d, 0x9d, 0x84,
+    0x90, 0xd8, 0xab, 0x00, 0x8c, 0xbc, 0xd3, 0x0a, 0xf7, 0xe4, 0x58, 0x05, 0xb8, 0xb3, 0x45, 0x06,
+    0xd0, 0x2c, 0x1e, 0x8f, 0xca, 0x3f, 0x0f, 0x02, 0xc1, 0xaf, 0xbd, 0x03, 0x01, 0x13, 0x8a, 0x6b,
+    0x3a, 0x91, 0x11, 0x41, 0x4f, 0x67, 0xdc, 0xea, 0x97, 0xf2, 0xcf, 0xce, 0xf0, 0xb4, 0xe6, 0x73,
+    0x96, 0xac, 0x74, 0x22, 0xe7, 0xad, 0x35, 0x85, 0xe2, 0xf9, 0x37, 0xe8, 0x1c, 0x75, 0xdf, 0x6e,
+    0x47, 0xf1, 0x1a, 0x71, 0x1d, 0x29, 0xc5, 0x89, 0x6f, 0xb7, 0x62, 0x0e, 0xaa, 0x18, 0xbe, 0x1b,
+    0xfc, 0x56, 0x3e, 0x4b, 0xc6, 0xd2, 0x79, 0x20, 0x9a, 0xdb, 0xc0, 0xfe, 0x78, 0xcd, 0x5a, 0xf4,
+    0x1f, 0xdd, 0xa8, 0x33, 0x88, 0x07, 0xc7, 0x31, 0xb1, 0x12, 0x10, 0x59, 0x27, 0x80, 0xec, 0x5f,
+    0x60, 0x51, 0x7f, 0xa9, 0x19, 0xb5, 0x4a, 0x0d, 0x2d, 0xe5, 0x7a, 0x9f, 0x93, 0xc9, 0x9c, 0xef,
+    0xa0, 0xe0, 0x3b, 0x4d, 0xae, 0x2a, 0xf5, 0xb0, 0xc8, 0xeb, 0xbb, 0x3c, 0x83, 0x53, 0x99, 0x61,
+    0x17, 0x2b, 0x04, 0x7e, 0xba, 0x77, 0xd6, 0x26, 0xe1, 0x69, 0x14, 0x63, 0x55, 0x21, 0x0c, 0x7d,
+];
+
+const SB4: [u8; 256] = [
+    0x30, 0x68, 0x99, 0x1b, 0x87, 0xb9, 0x21, 0x78, 0x50, 0x39, 0xdb, 0xe1, 0x72, 0x09, 0x62, 0x3c,
+    0x3e, 0x7e, 0x5e, 0x8e, 0xf1, 0xa0, 0xcc, 0xa3, 0x2a, 0x1d, 0xfb, 0xb6, 0xd6, 0x20, 0xc4, 0x8d,
+    0x81, 0x65, 0xf5, 0x89, 0xcb, 0x9d, 0x77, 0xc6, 0x57, 0x43, 0x56, 0x17, 0xd4, 0x40, 0x1a, 0x4d,
+    0xc0, 0x63, 0x6c, 0xe3, 0xb7, 0xc8, 0x64, 0x6a, 0x53, 0xaa, 0x38, 0x98, 0x0c, 0xf4, 0x9b, 0xed,
+    0x7f, 0x22, 0x76, 0xaf, 0xdd, 0x3a, 0x0b, 0x58, 0x67, 0x88, 0x06, 0xc3, 0x35, 0x0d, 0x01, 0x8b,
+    0x8c, 0xc2, 0xe6, 0x5f, 0x02, 0x24, 0x75, 0x93, 0x66, 0x1e, 0xe5, 0xe2, 0x54, 0xd8, 0x10, 0xce,
+    0x7a, 0xe8, 0x08, 0x2c, 0x12, 0x97, 0x32, 0xab, 0xb4, 0x27, 0x0a, 0x23, 0xdf, 0xef, 0xca, 0xd9,
+    0xb8, 0xfa, 0xdc, 0x31, 0x6b, 0xd1, 0xad, 0x19, 0x49, 0xbd, 0x51, 0x96, 0xee, 0xe4, 0xa8, 0x41,
+    0xda, 0xff, 0xcd, 0x55, 0x86, 0x36, 0xbe, 0x61, 0x52, 0xf8, 0xbb, 0x0e, 0x82, 0x48, 0x69, 0x9a,
+    0xe0, 0x47, 0x9e, 0x5c, 0x04, 0x4b, 0x34, 0x15, 0x79, 0x26, 0xa7, 0xde, 0x29, 0xae, 0x92, 0xd7,
+    0x84, 0xe9, 0xd2, 0xba, 0x5d, 0xf3, 0xc5, 0xb0, 0xbf, 0xa4, 0x3b, 0x71, 0x44, 0x46, 0x2b, 0xfc,
+    0xeb, 0x6f, 0xd5, 0xf6, 0x14, 0xfe, 0x7c, 0x70, 0x5a, 0x7d, 0xfd, 0x2f, 0x18, 0x83, 0x16, 0xa5,
+    0x91, 0x1f, 0x05, 0x95, 0x74, 0xa9, 0xc1, 0x5b, 0x4a, 0x85, 0x6d, 0x13, 0x07, 0x4f, 0x4e, 0x45,
+    0xb2, 0x0f, 0xc9, 0x1c, 0xa6, 0xbc, 0xec, 0x73, 0x90, 0x7b, 0xcf, 0x59, 0x8f, 0xa1, 0xf9, 0x2d,
+    0xf2, 0xb1, 0x00, 0x94, 0x37, 0x9f, 0xd0, 0x2e, 0x9c, 0x6e, 0x28, 0x3f, 0x80, 0xf0, 0x3d, 0xd3,
+    0x25, 0x8a, 0xb5, 0xe7, 0x42, 0xb3, 0xc7, 0xea, 0xf7, 0x4c, 0x11, 0x33, 0x03, 0xa2, 0xac, 0x60,
+];
+
+// Key schedule constants.
+const C: [u128; 3] = [
+    0x517cc1b727220a94fe13abe8fa9a6ee0,
+    0x6db14acc9e21c820ff28b1d5ef5de2b0,
+    0xdb92371d2126e9700324977504e8c90e,
+];
+
+// Inputs of each output byte of the diffusion layer A.
+const DIFFUSION: [[usize; 7]; 16] = [
+    [3, 4, 6, 8, 9, 13, 14],
+    [2, 5, 7, 8, 9, 12, 15],
+    [1, 4, 6, 10, 11, 12, 15],
+    [0, 5, 7, 10, 11, 13, 14],
+    [0, 2, 5, 8, 11, 14, 15],
+    [1, 3, 4, 9, 10, 14, 15],
+    [0, 2, 7, 9, 10, 12, 13],
+    [1, 3, 6, 8, 11, 12, 13],
+    [0, 1, 4, 7, 10, 13, 15],
+    [0, 1, 5, 6, 11, 12, 14],
+    [2, 3, 5, 6, 8, 13, 15],
+    [2, 3, 4, 7, 9, 12, 14],
+    [1, 2, 6, 7, 9, 11, 12],
+    [0, 3, 6, 7, 8, 10, 13],
+    [0, 3, 4, 5, 9, 11, 14],
+    [1, 2, 4, 5, 8, 10, 15],
+];
+
+type Block = [u8; 16];
+
+/// The diffusion layer, an involution.
+fn a(x: &Block) -> Block {
+    let mut y = [0_u8; 16];
+    for (out, inputs) in y.iter_mut().zip(DIFFUSION.iter()) {
+        *out = inputs.iter().fold(0, |acc, &i| acc ^ x[i]);
+    }
+    y
+}
+
+/// The substitution layer of odd rounds.
+fn sl1(x: &Block) -> Block {
+    let boxes = [&SB1, &SB2, &SB3, &SB4];
+    let mut y = [0_u8; 16];
+    for (i, out) in y.iter_mut().enumerate() {
+        *out = boxes[i % 4][x[i] as usize];
+    }
+    y
+}
+
+/// The substitution layer of even rounds, the inverse of `sl1`.
+fn sl2(x: &Block) -> Block {
+    let boxes = [&SB3, &SB4, &SB1, &SB2];
+    let mut y = [0_u8; 16];
+    for (i, out) in y.iter_mut().enumerate() {
+        *out = boxes[i % 4][x[i] as usize];
+    }
+    y
+}
+
+fn xor(x: &Block, y: &Block) -> Block {
+    let mut z = *x;
+    for (a, b) in z.iter_mut().zip(y) {
+        *a ^= b;
+    }
+    z
+}
+
+/// The odd round function.
+fn fo(d: &Block, key: &Block) -> Block {
+    a(&sl1(&xor(d, key)))
+}
+
+/// The even round function.
+fn fe(d: &Block, key: &Block) -> Block {
+    a(&sl2(&xor(d, key)))
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Aria` is a keyed ARIA cipher.
+///# Example
+/// ```
+/// use cryptolib::block::aria::Aria;
+/// use cryptolib::block::BlockCipher;
+///
+/// let aria = Aria::new(&[0x42; 16]).unwrap();
+/// let mut block = *b"sixteen byte blk";
+/// aria.encrypt_block(&mut block);
+/// aria.decrypt_block(&mut block);
+/// assert_eq!(&block, b"sixteen byte blk");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Aria {
+    encrypt_keys: [Block; 17],
+    decrypt_keys: [Block; 17],
+    rounds: usize,
+}
+
+impl Aria {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an ARIA cipher from a 16, 24 or 32 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Aria, CryptoError> {
+        let (rounds, ck) = match key.len() {
+            16 => (12, [C[0], C[1], C[2]]),
+            24 => (14, [C[1], C[2], C[0]]),
+            32 => (16, [C[2], C[0], C[1]]),
+            _ => return Err(CryptoError::InvalidKeyLength),
+        };
+        let mut kr = [0_u8; 16];
+        kr[..key.len() - 16].copy_from_slice(&key[16..]);
+        let w0: Block = key[..16].try_into().unwrap();
+        let w1 = xor(&fo(&w0, &ck[0].to_be_bytes()), &kr);
+        let w2 = xor(&fe(&w1, &ck[1].to_be_bytes()), &w0);
+        let w3 = xor(&fo(&w2, &ck[2].to_be_bytes()), &w1);
+        let w = [w0, w1, w2, w3].map(u128::from_be_bytes);
+
+        // ek(4j + i) = W(i) ^ (W(i + 1) rotated), the rotations being
+        // >>> 19, >>> 31, <<< 61, <<< 31 and <<< 19.
+        let rotations = [128 - 19, 128 - 31, 61, 31, 19];
+        let mut encrypt_keys = [[0_u8; 16]; 17];
+        for (n, key) in encrypt_keys.iter_mut().take(rounds + 1).enumerate() {
+            let (j, i) = (n / 4, n % 4);
+            *key = (w[i] ^ w[(i + 1) % 4].rotate_left(rotations[j])).to_be_bytes();
+        }
+        let mut decrypt_keys = [[0_u8; 16]; 17];
+        decrypt_keys[0] = encrypt_keys[rounds];
+        for i in 1..rounds {
+            decrypt_keys[i] = a(&encrypt_keys[rounds - i]);
+        }
+        decrypt_keys[rounds] = encrypt_keys[0];
+        Ok(Aria {
+            encrypt_keys,
+            decrypt_keys,
+            rounds,
+        })
+    }
+
+    fn crypt(&self, block: &mut [u8], keys: &[Block; 17]) {
+        let mut p: Block = (&*block).try_into().expect("aria block must be 16 bytes");
+        for (i, key) in keys[..self.rounds - 1].iter().enumerate() {
+            p = if i % 2 == 0 { fo(&p, key) } else { fe(&p, key) };
+        }
+        let c = xor(&sl2(&xor(&p, &keys[self.rounds - 1])), &keys[self.rounds]);
+        block.copy_from_slice(&c);
+    }
+}
+
+impl BlockCipher for Aria {
+    const BLOCK_SIZE: usize = 16;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        self.crypt(block, &self.encrypt_keys);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        self.crypt(block, &self.decrypt_keys);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::mode::gcm::Gcm;
+    use crate::block::mode::Aead;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_aria_rfc5794() {
+        // RFC 5794 Appendix A.
+        let plain = hex("00112233445566778899aabbccddeeff");
+        let key: Vec<u8> = (0..32).collect();
+        for (key_len, cipher) in [
+            (16, "d718fbd6ab644c739da95f3be6451778"),
+            (24, "26449c1805dbe7aa25a468ce263a9e79"),
+            (32, "f92bd7c79fb72e2f2b8f80c1972d24fc"),
+        ] {
+            let aria = Aria::new(&key[..key_len]).unwrap();
+            let mut block = plain.clone();
+            aria.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher));
+            aria.decrypt_block(&mut block);
+            assert_eq!(block, plain);
+        }
+        assert_eq!(
+            Aria::new(&[0; 20]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+
+    #[test]
+    fn test_aria_gcm() {
+        // ARIA-GCM as used by RFC 8269, checked against OpenSSL.
+        let gcm = Gcm::new(Aria::new(&[0x33; 16]).unwrap()).unwrap();
+        let sealed = gcm
+            .encrypt(&[0x44; 12], b"header", b"aria in gcm mode!")
+            .unwrap();
+        assert_eq!(
+            sealed,
+            hex("ed0743accd800b7aad2c46f399bb03fe2a41d9cf43a6ea19558125b47583e35d5e")
+        );
+        assert_eq!(
+            gcm.decrypt(&[0x44; 12], b"header", &sealed).unwrap(),
+            b"aria in gcm mode!"
+        );
+    }
+}
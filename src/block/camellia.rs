@@ -0,0 +1,334 @@
+//#######################################################################o
+// Camellia is an 18 rounds (128-bit keys) or 24 rounds (192 and         |
+// 256-bit keys) Feistel network on 128-bit blocks. The round function   |
+// F is made of four 8-bit S-boxes and a byte-oriented linear layer,     |
+// every 6 rounds the FL and FL^-1 functions, keyed bitwise operations,  |
+// are inserted, and the block is whitened before and after the rounds.  |
+// The subkeys are rotations of the key and of the 128-bit values KA     |
+// and KB derived from it by 4 or 6 rounds of F.                         |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 3713: https://www.rfc-editor.org/rfc/rfc3713                      |
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// The S-box s1, the three others are derived from it.
+const SBOX1: [u8; 256] = [
+    0x70, 0x82, 0x2c, 0xec, 0xb3, 0x27, 0xc0, 0xe5, 0xe4, 0x85, 0x57, 0x35, 0xea, 0x0c, 0xae, 0x41,
+    0x23, 0xef, 0x6b, 0x93, 0x45, 0x19, 0xa5, 0x21, 0xed, 0x0e, 0x4f, 0x4e, 0x1d, 0x65, 0x92, 0xbd,
+    0x86, 0xb8, 0xaf, 0x8f, 0x7c, 0xeb, 0x1f, 0xce, 0x3e, 0x30, 0xdc, 0x5f, 0x5e, 0xc5, 0x0b, 0x1a,
+    0xa6, 0xe1, 0x39, 0xca, 0xd5, 0x47, 0x5d, 0x3d, 0xd9, 0x01, 0x5a, 0xd6, 0x51, 0x56, 0x6c, 0x4d,
+    0x8b, 0x0d, 0x9a, 0x66, 0xfb, 0xcc, 0xb0, 0x2d, 0x74, 0x12, 0x2b, 0x20, 0xf0, 0xb1, 0x84, 0x99,
+    0xdf, 0x4c, 0xcb, 0xc2, 0x34, 0x7e, 0x76, 0x05, 0x6d, 0xb7, 0xa9, 0x31, 0xd1, 0x17, 0x04, 0xd7,
+    0x14, 0x58, 0x3a, 0x61, 0xde, 0x1b, 0x11, 0x1c, 0x32, 0x0f, 0x9c, 0x16, 0x53, 0x18, 0xf2, 0x22,
+    0xfe, 0x44, 0xcf, 0xb2, 0xc3, 0xb5, 0x7a, 0x91, 0x24, 0x08, 0xe8, 0xa8, 0x60, 0xfc, 0x69, 0x50,
+    0xaa, 0xd0, 0xa0, 0x7d, 0xa1, 0x89, 0x62, 0x97, 0x54, 0x5b, 0x1e, 0x95, 0xe0, 0xff, 0x64, 0xd2,
+    0x10, 0xc4, 0x00, 0x48, 0xa3, 0xf7, 0x75, 0xdb, 0x8a, 0x03, 0xe6, 0xda, 0x09, 0x3f, 0xdd, 0x94,
+    0x87, 0x5c, 0x83, 0x02, 0xcd, 0x4a, 0x90, 0x33, 0x73, 0x67, 0xf6, 0xf3, 0x9d, 0x7f, 0xbf, 0xe2,
+    0x52, 0x9b, 0xd8, 0x26, 0xc8, 0x37, 0xc6, 0x3b, 0x81, 0x96, 0x6f, 0x4b, 0x13, 0xbe, 0x63, 0x2e,
+    0xe9, 0x79, 0xa7, 0x8c, 0x9f, 0x6e, 0xbc, 0x8e, 0x29, 0xf5, 0xf9, 0xb6, 0x2f, 0xfd, 0xb4, 0x59,
+    0x78, 0x98, 0x06, 0x6a, 0xe7, 0x46, 0x71, 0xba, 0xd4, 0x25, 0xab, 0x42, 0x88, 0xa2, 0x8d, 0xfa,
+    0x72, 0x07, 0xb9, 0x55, 0xf8, 0xee, 0xac, 0x0a, 0x36, 0x49, 0x2a, 0x68, 0x3c, 0x38, 0xf1, 0xa4,
+    0x40, 0x28, 0xd3, 0x7b, 0xbb, 0xc9, 0x43, 0xc1, 0x15, 0xe3, 0xad, 0xf4, 0x77, 0xc7, 0x80, 0x9e,
+];
+
+// Key schedule constants.
+const SIGMA: [u64; 6] = [
+    0xa09e667f3bcc908b,
+    0xb67ae8584caa73b2,
+    0xc6ef372fe94f82be,
+    0x54ff53a5f1d36f1c,
+    0x10e527fade682d1d,
+    0xb05688c2b3e6c1fd,
+];
+
+fn sbox1(x: u8) -> u8 {
+    SBOX1[x as usize]
+}
+
+fn sbox2(x: u8) -> u8 {
+    SBOX1[x as usize].rotate_left(1)
+}
+
+fn sbox3(x: u8) -> u8 {
+    SBOX1[x as usize].rotate_left(7)
+}
+
+fn sbox4(x: u8) -> u8 {
+    SBOX1[x.rotate_left(1) as usize]
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The F function: S-boxes then the P linear layer.
+//////////////////////////////////////////////////////////////////////
+fn f(input: u64, key: u64) -> u64 {
+    let [t1, t2, t3, t4, t5, t6, t7, t8] = (input ^ key).to_be_bytes();
+    let (t1, t2, t3, t4) = (sbox1(t1), sbox2(t2), sbox3(t3), sbox4(t4));
+    let (t5, t6, t7, t8) = (sbox2(t5), sbox3(t6), sbox4(t7), sbox1(t8));
+    u64::from_be_bytes([
+        t1 ^ t3 ^ t4 ^ t6 ^ t7 ^ t8,
+        t1 ^ t2 ^ t4 ^ t5 ^ t7 ^ t8,
+        t1 ^ t2 ^ t3 ^ t5 ^ t6 ^ t8,
+        t2 ^ t3 ^ t4 ^ t5 ^ t6 ^ t7,
+        t1 ^ t2 ^ t6 ^ t7 ^ t8,
+        t2 ^ t3 ^ t5 ^ t7 ^ t8,
+        t3 ^ t4 ^ t5 ^ t6 ^ t8,
+        t1 ^ t4 ^ t5 ^ t6 ^ t7,
+    ])
+}
+
+fn fl(x: u64, key: u64) -> u64 {
+    let (mut x1, mut x2) = ((x >> 32) as u32, x as u32);
+    let (k1, k2) = ((key >> 32) as u32, key as u32);
+    x2 ^= (x1 & k1).rotate_left(1);
+    x1 ^= x2 | k2;
+    ((x1 as u64) << 32) | x2 as u64
+}
+
+fn fl_inv(y: u64, key: u64) -> u64 {
+    let (mut y1, mut y2) = ((y >> 32) as u32, y as u32);
+    let (k1, k2) = ((key >> 32) as u32, key as u32);
+    y1 ^= y2 | k2;
+    y2 ^= (y1 & k1).rotate_left(1);
+    ((y1 as u64) << 32) | y2 as u64
+}
+
+/// Splits the 128-bit `x` rotated by `n` bits into two subkeys.
+fn halves(x: u128, n: u32) -> [u64; 2] {
+    let r = x.rotate_left(n);
+    [(r >> 64) as u64, r as u64]
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Camellia` is a keyed Camellia cipher.
+///# Example
+/// ```
+/// use cryptolib::block::camellia::Camellia;
+/// use cryptolib::block::BlockCipher;
+///
+/// let camellia = Camellia::new(&[0x42; 16]).unwrap();
+/// let mut block = *b"sixteen byte blk";
+/// camellia.encrypt_block(&mut block);
+/// camellia.decrypt_block(&mut block);
+/// assert_eq!(&block, b"sixteen byte blk");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Camellia {
+    // Whitening keys kw1..kw4.
+    kw: [u64; 4],
+    // Round keys k1..k24, only 18 are used with 128-bit keys.
+    k: [u64; 24],
+    // FL/FL^-1 keys ke1..ke6, only 4 are used with 128-bit keys.
+    ke: [u64; 6],
+    rounds: usize,
+}
+
+impl Camellia {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a Camellia cipher from a 16, 24 or 32 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Camellia, CryptoError> {
+        let (kl, kr) = match key.len() {
+            16 => (u128::from_be_bytes(key.try_into().unwrap()), 0),
+            24 => {
+                let right = u64::from_be_bytes(key[16..].try_into().unwrap());
+                (
+                    u128::from_be_bytes(key[..16].try_into().unwrap()),
+                    ((right as u128) << 64) | !right as u128,
+                )
+            }
+            32 => (
+                u128::from_be_bytes(key[..16].try_into().unwrap()),
+                u128::from_be_bytes(key[16..].try_into().unwrap()),
+            ),
+            _ => return Err(CryptoError::InvalidKeyLength),
+        };
+        let mut d1 = ((kl ^ kr) >> 64) as u64;
+        let mut d2 = (kl ^ kr) as u64;
+        d2 ^= f(d1, SIGMA[0]);
+        d1 ^= f(d2, SIGMA[1]);
+        d1 ^= (kl >> 64) as u64;
+        d2 ^= kl as u64;
+        d2 ^= f(d1, SIGMA[2]);
+        d1 ^= f(d2, SIGMA[3]);
+        let ka = ((d1 as u128) << 64) | d2 as u128;
+
+        let mut camellia = Camellia {
+            kw: [0; 4],
+            k: [0; 24],
+            ke: [0; 6],
+            rounds: 18,
+        };
+        // Copies the halves of `src` rotated by `n` bits into `dst`.
+        let set = |dst: &mut [u64], src: u128, n: u32| dst.copy_from_slice(&halves(src, n));
+        if key.len() == 16 {
+            set(&mut camellia.kw[..2], kl, 0);
+            set(&mut camellia.k[0..2], ka, 0);
+            set(&mut camellia.k[2..4], kl, 15);
+            set(&mut camellia.k[4..6], ka, 15);
+            set(&mut camellia.ke[0..2], ka, 30);
+            set(&mut camellia.k[6..8], kl, 45);
+            camellia.k[8] = halves(ka, 45)[0];
+            camellia.k[9] = halves(kl, 60)[1];
+            set(&mut camellia.k[10..12], ka, 60);
+            set(&mut camellia.ke[2..4], kl, 77);
+            set(&mut camellia.k[12..14], kl, 94);
+            set(&mut camellia.k[14..16], ka, 94);
+            set(&mut camellia.k[16..18], kl, 111);
+            set(&mut camellia.kw[2..], ka, 111);
+        } else {
+            let mut d1 = ((ka ^ kr) >> 64) as u64;
+            let mut d2 = (ka ^ kr) as u64;
+            d2 ^= f(d1, SIGMA[4]);
+            d1 ^= f(d2, SIGMA[5]);
+            let kb = ((d1 as u128) << 64) | d2 as u128;
+            camellia.rounds = 24;
+            set(&mut camellia.kw[..2], kl, 0);
+            set(&mut camellia.k[0..2], kb, 0);
+            set(&mut camellia.k[2..4], kr, 15);
+            set(&mut camellia.k[4..6], ka, 15);
+            set(&mut camellia.ke[0..2], kr, 30);
+            set(&mut camellia.k[6..8], kb, 30);
+            set(&mut camellia.k[8..10], kl, 45);
+            set(&mut camellia.k[10..12], ka, 45);
+            set(&mut camellia.ke[2..4], kl, 60);
+            set(&mut camellia.k[12..14], kr, 60);
+            set(&mut camellia.k[14..16], kb, 60);
+            set(&mut camellia.k[16..18], kl, 77);
+            set(&mut camellia.ke[4..6], ka, 77);
+            set(&mut camellia.k[18..20], kr, 94);
+            set(&mut camellia.k[20..22], ka, 94);
+            set(&mut camellia.k[22..24], kl, 111);
+            set(&mut camellia.kw[2..], kb, 111);
+        }
+        Ok(camellia)
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Encrypts (or decrypts, with the subkeys in reverse order) the
+    /// 128-bit `block`.
+    //////////////////////////////////////////////////////////////////
+    fn crypt(&self, block: &mut [u8], decrypt: bool) {
+        let rounds = self.rounds;
+        let fl_layers = rounds / 6 - 1;
+        let (kw_in, kw_out) = if decrypt {
+            (&self.kw[2..], &self.kw[..2])
+        } else {
+            (&self.kw[..2], &self.kw[2..])
+        };
+        let k = |i: usize| {
+            if decrypt {
+                self.k[rounds - 1 - i]
+            } else {
+                self.k[i]
+            }
+        };
+        let ke = |i: usize| {
+            if decrypt {
+                self.ke[2 * fl_layers - 1 - i]
+            } else {
+                self.ke[i]
+            }
+        };
+
+        let input: [u8; 16] = (&*block)
+            .try_into()
+            .expect("camellia block must be 16 bytes");
+        let m = u128::from_be_bytes(input);
+        let mut d1 = (m >> 64) as u64 ^ kw_in[0];
+        let mut d2 = m as u64 ^ kw_in[1];
+        for i in (0..rounds).step_by(2) {
+            if i > 0 && i % 6 == 0 {
+                let layer = i / 6 - 1;
+                d1 = fl(d1, ke(2 * layer));
+                d2 = fl_inv(d2, ke(2 * layer + 1));
+            }
+            d2 ^= f(d1, k(i));
+            d1 ^= f(d2, k(i + 1));
+        }
+        d2 ^= kw_out[0];
+        d1 ^= kw_out[1];
+        let c = ((d2 as u128) << 64) | d1 as u128;
+        block.copy_from_slice(&c.to_be_bytes());
+    }
+}
+
+impl BlockCipher for Camellia {
+    const BLOCK_SIZE: usize = 16;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        self.crypt(block, false);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        self.crypt(block, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::mode::cbc::Cbc;
+    use crate::block::mode::ctr::Ctr;
+    use crate::stream::StreamCipher;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_camellia_rfc3713() {
+        // RFC 3713 Appendix A.
+        let plain = hex("0123456789abcdeffedcba9876543210");
+        for (key, cipher) in [
+            (
+                "0123456789abcdeffedcba9876543210",
+                "67673138549669730857065648eabe43",
+            ),
+            (
+                "0123456789abcdeffedcba98765432100011223344556677",
+                "b4993401b3e996f84ee5cee7d79b09b9",
+            ),
+            (
+                "0123456789abcdeffedcba987654321000112233445566778899aabbccddeeff",
+                "9acc237dff16d76c20ef7c919e3a7509",
+            ),
+        ] {
+            let camellia = Camellia::new(&hex(key)).unwrap();
+            let mut block = plain.clone();
+            camellia.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher));
+            camellia.decrypt_block(&mut block);
+            assert_eq!(block, plain);
+        }
+        assert_eq!(
+            Camellia::new(&[0; 8]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+
+    #[test]
+    fn test_camellia_modes() {
+        // Checked against OpenSSL.
+        let key: Vec<u8> = (0..32).collect();
+        let plain: Vec<u8> = (0..48).map(|i| i * 3).collect();
+        for (key_len, cbc_cipher, ctr_cipher) in [
+            (16, "3c6160009640844ce5aedea0cb0bd3cc4b77018fe7b8a2ed75372ca52278cab887298d45ed0b35dbbf17336554951706", "1ee9286eef894c239065be3a6698c02116e05ff4be6861bfa810d4a62d01e982d9e980e5d9c2c4df4c85b2e231"),
+            (24, "5954747ad184013b274cbf2fa5420ba4e83a73591754f897b5c9045988d4c9a0378269986511ba238f74c8820bbf66e0", "a2830794745a5b6430840cfa7d78849646b7f8674b3ac5a24a4ba4ea7d37c7696c5670c9dc620a315bb17a8713"),
+            (32, "bde92160c2ee360e32fbeb1fde0215bc1d18ee279df62ca8ed1af228cd1a57f33370a961cd45d409cb1379e890622c94", "ac881fd28c892f34042ae8b2d2b2b2cfc65a669b9c1235d8b78899353e1a35288d277e6df784523e32522c8b3e"),
+        ] {
+            let cbc = Cbc::new(Camellia::new(&key[..key_len]).unwrap());
+            let mut buffer = plain.clone();
+            cbc.encrypt(&[0x5a; 16], &mut buffer).unwrap();
+            assert_eq!(buffer, hex(cbc_cipher));
+            cbc.decrypt(&[0x5a; 16], &mut buffer).unwrap();
+            assert_eq!(buffer, plain);
+            let mut ctr = Ctr::new(Camellia::new(&key[..key_len]).unwrap(), &[0xa5; 16]).unwrap();
+            let mut buffer = plain[..45].to_vec();
+            ctr.apply_keystream(&mut buffer).unwrap();
+            assert_eq!(buffer, hex(ctr_cipher));
+        }
+    }
+}
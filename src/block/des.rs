@@ -17,6 +17,9 @@
 *        # at the end of the 16-rounds the inverse of the initial plain block permutation is applied (optional)
 */
 
+use super::BlockCipher;
+use crate::error::CryptoError;
+
 // Permutations
 
 const REDUC_PERM: [u8; 56] = [
@@ -133,6 +136,7 @@ fn permute_bit32(from: &u32, to: &mut u32, from_pos: u8, to_pos: u8) {
 /// Applies the Initial Permutation to the given 64 bits
 /// plain_text block `pt_block`, and returns the result of the permutation.
 //////////////////////////////////////////////////////////////////////
+#[allow(clippy::needless_return)]
 fn init_permutation(pt_block: u64) -> u64 {
     let mut perm_block: u64 = 0;
     let mut i = 0;
@@ -147,6 +151,7 @@ fn init_permutation(pt_block: u64) -> u64 {
 /// Applies the Final Permutation to the given 64 bits
 /// cipher_text block `ct_block`, and returns the result of the permutation.
 //////////////////////////////////////////////////////////////////////
+#[allow(clippy::needless_return)]
 fn final_permutation(ct_block: u64) -> u64 {
     let mut perm_block: u64 = 0;
     let mut i = 0;
@@ -164,6 +169,7 @@ fn final_permutation(ct_block: u64) -> u64 {
 /// For simple example a circular left rotation of the binary string
 /// 100111 by 2 produces the bit string 011110.
 ////////////////////////////////////////////////////////////////////
+#[allow(clippy::needless_return)]
 fn circ_rot_left(mut halve: u32, shift: u8) -> u32 {
     let temp: u32 = halve >> (28 - shift);
     halve <<= shift;
@@ -175,6 +181,7 @@ fn circ_rot_left(mut halve: u32, shift: u8) -> u32 {
 /// Returns an array of the 16 rounds keys computed form the given
 /// `key`.
 ////////////////////////////////////////////////////////////////////
+#[allow(clippy::unnecessary_cast, clippy::needless_return)]
 fn prepare_key(key: u64) -> [u64; 16] {
     // key reduction.
     let mut redu_key: u64 = 0;
@@ -220,6 +227,7 @@ fn prepare_key(key: u64) -> [u64; 16] {
 /// 4. Perform P-Box permutation on the result.
 /// 5. Finally xor `l_block` with the output of the P-Box permutation.
 ///////////////////////////////////////////////////////////////////////////////
+#[allow(unused_parens, clippy::assign_op_pattern, clippy::needless_return)]
 fn des_round(l_block: u32, r_block: u32, round_key: u64) -> u32 {
     // expansion permutation to expand its size to 48-bit.
     let mut exp_block = 0;
@@ -270,51 +278,104 @@ fn des_round(l_block: u32, r_block: u32, round_key: u64) -> u32 {
 }
 
 ////////////////////////////////////////////////////////////////////////
-/// Returns the encrypted 64-bit block.
+/// Runs the 16 des rounds on the 64-bit `block` using the round keys
+/// in the given order, `reverse` selects the decryption order.
 ///////////////////////////////////////////////////////////////////////
-pub fn des_encrypt(plain_text: u64, key: u64) -> u64 {
+fn des_crypt(block: u64, round_key: &[u64; 16], reverse: bool) -> u64 {
     // initial permutation.
-    let plain_text = init_permutation(plain_text);
-    // prepare keys for the rounds
-    let round_key = prepare_key(key);
+    let block = init_permutation(block);
     // block spliting.
-    let mut left: u32 = (plain_text >> 32) as u32;
-    let mut right: u32 = (plain_text & 0xFFFFFFFF_u64) as u32;
+    let mut left: u32 = (block >> 32) as u32;
+    let mut right: u32 = (block & 0xFFFFFFFF_u64) as u32;
     let mut temp_right = right;
     let mut i = 0;
     while i < 16 {
-        right = des_round(left, right, round_key[i]);
+        let key = if reverse {
+            round_key[15 - i]
+        } else {
+            round_key[i]
+        };
+        right = des_round(left, right, key);
         left = temp_right;
         temp_right = right;
         i += 1;
     }
 
     // combine the 2 halves (R16L16) and apply the final permutation
-    let cipher_text = ((right as u64) << 32) + (left as u64);
-    return final_permutation(cipher_text);
+    let out_block = ((right as u64) << 32) + (left as u64);
+    final_permutation(out_block)
+}
+
+////////////////////////////////////////////////////////////////////////
+/// Returns the encrypted 64-bit block.
+///////////////////////////////////////////////////////////////////////
+pub fn des_encrypt(plain_text: u64, key: u64) -> u64 {
+    Des::from_u64(key).encrypt_u64(plain_text)
 }
 
 ////////////////////////////////////////////////////////////////////////
 /// Returns the decrypted 64-bit block.
 ///////////////////////////////////////////////////////////////////////
 pub fn des_decrypt(cipher_text: u64, key: u64) -> u64 {
-    let cipher_text = init_permutation(cipher_text);
-    let round_key = prepare_key(key);
-    // block spliting.
-    let mut left: u32 = (cipher_text >> 32) as u32;
-    let mut right: u32 = (cipher_text & 0xFFFFFFFF_u64) as u32;
-    let mut temp_right = right;
-    let mut i = 0;
-    while i < 16 {
-        right = des_round(left, right, round_key[15 - i]);
-        left = temp_right;
-        temp_right = right;
-        i += 1;
+    Des::from_u64(key).decrypt_u64(cipher_text)
+}
+
+////////////////////////////////////////////////////////////////////////
+/// `Des` holds the 16 round keys derived from a 64-bit des key,
+/// so that the key schedule runs only once per key.
+///# Note
+/// '''
+/// As in the standard the parity bits of the key (the lowest bit
+/// of each byte) are ignored.
+/// '''
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Des {
+    round_keys: [u64; 16],
+}
+
+impl Des {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a des cipher from an 8 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength` if the key
+    /// isn't 8 bytes long.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Des, CryptoError> {
+        let key: [u8; 8] = key.try_into().map_err(|_| CryptoError::InvalidKeyLength)?;
+        Ok(Des::from_u64(u64::from_be_bytes(key)))
     }
 
-    // combine the 2 halves (R16L16) and apply the final permutation
-    let cipher_text = ((right as u64) << 32) + (left as u64);
-    return final_permutation(cipher_text);
+    /// Creates a des cipher from a 64-bit `key`.
+    pub fn from_u64(key: u64) -> Des {
+        Des {
+            round_keys: prepare_key(key),
+        }
+    }
+
+    /// Returns the encrypted 64-bit block.
+    pub fn encrypt_u64(&self, plain_text: u64) -> u64 {
+        des_crypt(plain_text, &self.round_keys, false)
+    }
+
+    /// Returns the decrypted 64-bit block.
+    pub fn decrypt_u64(&self, cipher_text: u64) -> u64 {
+        des_crypt(cipher_text, &self.round_keys, true)
+    }
+}
+
+impl BlockCipher for Des {
+    const BLOCK_SIZE: usize = 8;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let input: [u8; 8] = (&*block).try_into().expect("des block must be 8 bytes");
+        block.copy_from_slice(&self.encrypt_u64(u64::from_be_bytes(input)).to_be_bytes());
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let input: [u8; 8] = (&*block).try_into().expect("des block must be 8 bytes");
+        block.copy_from_slice(&self.decrypt_u64(u64::from_be_bytes(input)).to_be_bytes());
+    }
 }
 
 #[cfg(test)]
@@ -364,4 +425,15 @@ mod tests {
         key = 0x123456789ABCDEF;
         assert_eq!(des_decrypt(cipher, key), 0x123456789ABCDE7);
     }
+
+    #[test]
+    fn test_des_block_cipher() {
+        let des = Des::new(&[0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1]).unwrap();
+        let mut block = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF];
+        des.encrypt_block(&mut block);
+        assert_eq!(block, [0x85, 0xE8, 0x13, 0x54, 0x0F, 0x0A, 0xB4, 0x05]);
+        des.decrypt_block(&mut block);
+        assert_eq!(block, [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF]);
+        assert_eq!(Des::new(&[0; 7]).err(), Some(CryptoError::InvalidKeyLength));
+    }
 }
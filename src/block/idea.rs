@@ -0,0 +1,193 @@
+//#######################################################################o
+// IDEA (International Data Encryption Algorithm) encrypts 64-bit blocks |
+// with a 128-bit key in 8.5 rounds mixing three incompatible group      |
+// operations on 16-bit words: xor, addition modulo 2^16 and             |
+// multiplication modulo 2^16 + 1 (0 standing for 2^16). The 52 subkeys  |
+// are taken from the key rotated left by 25 bits at a time, decryption  |
+// uses their additive and multiplicative inverses.                      |
+// It's the cipher of PGP 2.x, kept here for legacy data.                |
+//                                                                       |
+// @Refrences:                                                           |
+// Lai, Massey: https://doi.org/10.1007/3-540-46877-3_35                 |
+// RFC 3058: https://www.rfc-editor.org/rfc/rfc3058                      |
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+type SubKeys = [u16; 52];
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplication modulo 2^16 + 1, 0 stands for 2^16.
+//////////////////////////////////////////////////////////////////////
+fn mul(a: u16, b: u16) -> u16 {
+    let a = if a == 0 { 0x10000 } else { a as u32 };
+    let b = if b == 0 { 0x10000 } else { b as u32 };
+    ((a as u64 * b as u64) % 0x10001) as u16
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplicative inverse modulo 2^16 + 1, computed as x^(2^16 - 1).
+//////////////////////////////////////////////////////////////////////
+fn mul_inv(x: u16) -> u16 {
+    let mut result = 1;
+    let mut base = x;
+    let mut e = 0xffff_u32;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        e >>= 1;
+    }
+    result
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Idea` is a keyed IDEA cipher.
+///# Example
+/// ```
+/// use cryptolib::block::idea::Idea;
+/// use cryptolib::block::BlockCipher;
+///
+/// let idea = Idea::new(&[0x42; 16]).unwrap();
+/// let mut block = *b"8 bytes!";
+/// idea.encrypt_block(&mut block);
+/// idea.decrypt_block(&mut block);
+/// assert_eq!(&block, b"8 bytes!");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Idea {
+    encrypt_keys: SubKeys,
+    decrypt_keys: SubKeys,
+}
+
+impl Idea {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an IDEA cipher from a 16 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Idea, CryptoError> {
+        let key: [u8; 16] = key.try_into().map_err(|_| CryptoError::InvalidKeyLength)?;
+        let mut key = u128::from_be_bytes(key);
+        let mut e = [0_u16; 52];
+        for chunk in e.chunks_mut(8) {
+            for (i, z) in chunk.iter_mut().enumerate() {
+                *z = (key >> (112 - 16 * i)) as u16;
+            }
+            key = key.rotate_left(25);
+        }
+
+        // The decryption round k uses the inverses of the keys of the
+        // encryption round 8 - k, the additive keys of the inner rounds
+        // are swapped to undo the swap of the middle words.
+        let mut d = [0_u16; 52];
+        for k in 0..9 {
+            let base = 48 - 6 * k;
+            let (add1, add2) = if k == 0 || k == 8 {
+                (e[base + 1], e[base + 2])
+            } else {
+                (e[base + 2], e[base + 1])
+            };
+            d[6 * k] = mul_inv(e[base]);
+            d[6 * k + 1] = add1.wrapping_neg();
+            d[6 * k + 2] = add2.wrapping_neg();
+            d[6 * k + 3] = mul_inv(e[base + 3]);
+            if k < 8 {
+                d[6 * k + 4] = e[base - 2];
+                d[6 * k + 5] = e[base - 1];
+            }
+        }
+        Ok(Idea {
+            encrypt_keys: e,
+            decrypt_keys: d,
+        })
+    }
+}
+
+fn crypt(block: &mut [u8], z: &SubKeys) {
+    assert_eq!(block.len(), 8, "idea block must be 8 bytes");
+    let word = |i: usize| u16::from_be_bytes([block[2 * i], block[2 * i + 1]]);
+    let (mut x1, mut x2, mut x3, mut x4) = (word(0), word(1), word(2), word(3));
+    for round in z[..48].chunks_exact(6) {
+        x1 = mul(x1, round[0]);
+        x2 = x2.wrapping_add(round[1]);
+        x3 = x3.wrapping_add(round[2]);
+        x4 = mul(x4, round[3]);
+        // The multiplication-addition structure.
+        let t0 = mul(x1 ^ x3, round[4]);
+        let t1 = mul((x2 ^ x4).wrapping_add(t0), round[5]);
+        let t0 = t0.wrapping_add(t1);
+        x1 ^= t1;
+        x4 ^= t0;
+        (x2, x3) = (x3 ^ t1, x2 ^ t0);
+    }
+    // The output transformation undoes the last swap.
+    let out = [
+        mul(x1, z[48]),
+        x3.wrapping_add(z[49]),
+        x2.wrapping_add(z[50]),
+        mul(x4, z[51]),
+    ];
+    for (bytes, w) in block.chunks_exact_mut(2).zip(out) {
+        bytes.copy_from_slice(&w.to_be_bytes());
+    }
+}
+
+impl BlockCipher for Idea {
+    const BLOCK_SIZE: usize = 8;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        crypt(block, &self.encrypt_keys);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        crypt(block, &self.decrypt_keys);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_idea() {
+        // Lai's example, then vectors checked against OpenSSL.
+        for (key, plain, cipher) in [
+            (
+                "00010002000300040005000600070008",
+                "0000000100020003",
+                "11fbed2b01986de5",
+            ),
+            (
+                "80000000000000000000000000000000",
+                "0000000000000000",
+                "b1f5f7f87901370f",
+            ),
+            (
+                "00000000000000000000000000000000",
+                "8000000000000000",
+                "8001000180008000",
+            ),
+            (
+                "000102030405060708090a0b0c0d0e0f",
+                "0011223344556677",
+                "f526ab9a62c0d258",
+            ),
+        ] {
+            let idea = Idea::new(&hex(key)).unwrap();
+            let mut block = hex(plain);
+            idea.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher), "key {}", key);
+            idea.decrypt_block(&mut block);
+            assert_eq!(block, hex(plain));
+        }
+        assert_eq!(
+            Idea::new(&[0; 8]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+}
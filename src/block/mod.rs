@@ -1,2 +1,76 @@
+pub mod aes;
+pub mod aria;
+pub mod blowfish;
+pub mod camellia;
+pub mod cast5;
 pub mod des;
+pub mod idea;
+pub mod mode;
+pub mod present;
+pub mod rc2;
+pub mod seed;
+pub mod serpent;
+pub mod simon;
+pub mod sm4;
+pub mod speck;
+pub mod three_way;
+pub mod threefish;
 pub mod triple_des;
+pub mod twofish;
+pub mod xtea;
+
+////////////////////////////////////////////////////////////////////////////
+/// `BlockCipher` is the interface shared by every block cipher of the
+/// crate, a cipher is keyed once when it's created and then used to
+/// transform fixed size blocks in place.
+///# Note
+/// '''
+/// Blocks are processed as big-endian byte sequences, passing a block
+/// whose length isn't `BLOCK_SIZE` is a programming error and panics.
+/// '''
+////////////////////////////////////////////////////////////////////////////
+pub trait BlockCipher {
+    /// Size of a block in bytes.
+    const BLOCK_SIZE: usize;
+
+    /// Encrypts a single block in place.
+    fn encrypt_block(&self, block: &mut [u8]);
+
+    /// Decrypts a single block in place.
+    fn decrypt_block(&self, block: &mut [u8]);
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts consecutive blocks in place, ciphers that can process
+    /// several blocks at once override it.
+    ///# Note
+    /// '''
+    /// The length of `blocks` must be a multiple of `BLOCK_SIZE`.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    fn encrypt_blocks(&self, blocks: &mut [u8]) {
+        assert!(
+            blocks.len().is_multiple_of(Self::BLOCK_SIZE),
+            "partial block"
+        );
+        for block in blocks.chunks_exact_mut(Self::BLOCK_SIZE) {
+            self.encrypt_block(block);
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts consecutive blocks in place.
+    ///# Note
+    /// '''
+    /// The length of `blocks` must be a multiple of `BLOCK_SIZE`.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    fn decrypt_blocks(&self, blocks: &mut [u8]) {
+        assert!(
+            blocks.len().is_multiple_of(Self::BLOCK_SIZE),
+            "partial block"
+        );
+        for block in blocks.chunks_exact_mut(Self::BLOCK_SIZE) {
+            self.decrypt_block(block);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//#######################################################################o
+// The Cipher Block Chaining mode (CBC) xors each plaintext block with   |
+// the previous ciphertext block (the IV for the first one) before       |
+// encrypting it. Encryption is sequential but decryption can process    |
+// the blocks in parallel.                                               |
+// The data must be a multiple of the block size, PKCS#7 padding is      |
+// available for messages of any length.                                 |
+// CBC only provides confidentiality, the IV must be unpredictable.      |
+//                                                                       |
+// @Refrences:                                                           |
+// SP 800-38A: https://doi.org/10.6028/NIST.SP.800-38A                   |
+//#######################################################################o
+
+use super::{pkcs7_pad, pkcs7_unpad};
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::util::xor_in_place;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Cbc` is the CBC mode over any block cipher.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::block::mode::cbc::Cbc;
+///
+/// let cbc = Cbc::new(Aes::new(&[0x42; 16]).unwrap());
+/// let cipher_text = cbc.encrypt_padded(&[7; 16], b"secret").unwrap();
+/// assert_eq!(cbc.decrypt_padded(&[7; 16], &cipher_text).unwrap(), b"secret");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Cbc<C: BlockCipher> {
+    cipher: C,
+}
+
+impl<C: BlockCipher> Cbc<C> {
+    /// Creates a CBC instance from a keyed block cipher.
+    pub fn new(cipher: C) -> Cbc<C> {
+        Cbc { cipher }
+    }
+
+    fn check(iv: &[u8], buffer: &[u8]) -> Result<(), CryptoError> {
+        if iv.len() != C::BLOCK_SIZE {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        if !buffer.len().is_multiple_of(C::BLOCK_SIZE) {
+            return Err(CryptoError::InvalidDataLength);
+        }
+        Ok(())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts `buffer` in place.
+    ///# Arguments
+    ///* `iv`: a `BLOCK_SIZE` bytes unpredictable IV.
+    ///* `buffer`: the plaintext, a multiple of `BLOCK_SIZE` bytes.
+    ///# Returns
+    /// `CryptoError::InvalidNonceLength` or `InvalidDataLength` if the
+    /// lengths are wrong.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt(&self, iv: &[u8], buffer: &mut [u8]) -> Result<(), CryptoError> {
+        Self::check(iv, buffer)?;
+        let mut previous = iv;
+        for block in buffer.chunks_exact_mut(C::BLOCK_SIZE) {
+            xor_in_place(block, previous);
+            self.cipher.encrypt_block(block);
+            previous = block;
+        }
+        Ok(())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts `buffer` in place.
+    ///# Returns
+    /// `CryptoError::InvalidNonceLength` or `InvalidDataLength` if the
+    /// lengths are wrong.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt(&self, iv: &[u8], buffer: &mut [u8]) -> Result<(), CryptoError> {
+        Self::check(iv, buffer)?;
+        let cipher_text = buffer.to_vec();
+        self.cipher.decrypt_blocks(buffer);
+        let previous = iv
+            .iter()
+            .chain(&cipher_text[..cipher_text.len().saturating_sub(iv.len())]);
+        for (b, p) in buffer.iter_mut().zip(previous) {
+            *b ^= p;
+        }
+        Ok(())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Pads `plain_text` with PKCS#7 and encrypts it.
+    ///# Returns
+    /// The ciphertext or `CryptoError::InvalidNonceLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt_padded(&self, iv: &[u8], plain_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut out = pkcs7_pad(plain_text, C::BLOCK_SIZE);
+        self.encrypt(iv, &mut out)?;
+        Ok(out)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts `cipher_text` and removes its PKCS#7 padding.
+    ///# Returns
+    /// The plaintext or `CryptoError::InvalidPadding`.
+    ///# Note
+    /// '''
+    /// Reporting padding errors to a remote party allows padding
+    /// oracle attacks, the ciphertext should be authenticated first.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt_padded(&self, iv: &[u8], cipher_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut out = cipher_text.to_vec();
+        self.decrypt(iv, &mut out)?;
+        let len = pkcs7_unpad(&out, C::BLOCK_SIZE)?;
+        out.truncate(len);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::block::des::Des;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_cbc_aes() {
+        // SP 800-38A F.2.1 and F.2.2.
+        let cbc = Cbc::new(Aes::new(&hex("2b7e151628aed2a6abf7158809cf4f3c")).unwrap());
+        let iv = hex("000102030405060708090a0b0c0d0e0f");
+        let plain = hex(
+            "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51
+             30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        );
+        let mut buffer = plain.clone();
+        cbc.encrypt(&iv, &mut buffer).unwrap();
+        assert_eq!(
+            buffer,
+            hex(
+                "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2
+                 73bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7"
+            )
+        );
+        cbc.decrypt(&iv, &mut buffer).unwrap();
+        assert_eq!(buffer, plain);
+    }
+
+    #[test]
+    fn test_cbc_padded() {
+        // Checked against OpenSSL.
+        let cbc = Cbc::new(Aes::new(&hex("2b7e151628aed2a6abf7158809cf4f3c")).unwrap());
+        let cipher_text = cbc
+            .encrypt_padded(&[0; 16], b"sixteen byte msg and more")
+            .unwrap();
+        assert_eq!(
+            cipher_text,
+            hex("210bd9f65d9f17399d1df7977bec4447c9bb3f87b1acf2be33af92b809392407")
+        );
+        assert_eq!(
+            cbc.decrypt_padded(&[0; 16], &cipher_text).unwrap(),
+            b"sixteen byte msg and more"
+        );
+        // An aligned message gets a full block of padding.
+        assert_eq!(cbc.encrypt_padded(&[0; 16], &[1; 16]).unwrap().len(), 32);
+        assert_eq!(cbc.encrypt_padded(&[0; 16], b"").unwrap().len(), 16);
+        // Over an 8 bytes block cipher.
+        let cbc = Cbc::new(Des::new(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap());
+        let cipher_text = cbc.encrypt_padded(&[9; 8], b"des in cbc mode").unwrap();
+        assert_eq!(cipher_text.len(), 16);
+        assert_eq!(
+            cbc.decrypt_padded(&[9; 8], &cipher_text).unwrap(),
+            b"des in cbc mode"
+        );
+    }
+
+    #[test]
+    fn test_cbc_errors() {
+        let cbc = Cbc::new(Aes::new(&[0; 16]).unwrap());
+        assert_eq!(
+            cbc.encrypt(&[0; 8], &mut [0; 16]).err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+        assert_eq!(
+            cbc.encrypt(&[0; 16], &mut [0; 15]).err(),
+            Some(CryptoError::InvalidDataLength)
+        );
+        assert_eq!(
+            cbc.decrypt_padded(&[0; 16], &[]).err(),
+            Some(CryptoError::InvalidPadding)
+        );
+        // Every padding byte is checked.
+        for bad_pad in [[0_u8; 16], [17; 16], {
+            let mut pad = [3; 16];
+            pad[14] = 2;
+            pad
+        }] {
+            let mut cipher_text = bad_pad;
+            cbc.encrypt(&[0; 16], &mut cipher_text).unwrap();
+            assert_eq!(
+                cbc.decrypt_padded(&[0; 16], &cipher_text).err(),
+                Some(CryptoError::InvalidPadding)
+            );
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//#######################################################################o
+// The Counter with CBC-MAC mode (CCM) authenticates the additional data |
+// and the plaintext with a CBC-MAC, then encrypts the plaintext and the |
+// MAC in counter mode. The nonce length N (7 to 13 bytes) sets the size |
+// L = 15 - N of the length field, a message is at most 2^(8L) bytes.    |
+// CCM* (IEEE 802.15.4) also allows a zero length tag, in which case the |
+// message is only encrypted.                                            |
+//                                                                       |
+// @Refrences:                                                           |
+// NIST SP 800-38C: https://doi.org/10.6028/NIST.SP.800-38C              |
+// RFC 3610: https://www.rfc-editor.org/rfc/rfc3610                      |
+// IEEE 802.15.4-2020 Annex B.                                           |
+//#######################################################################o
+
+use super::{apply_ctr, Aead};
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::util::{ct_eq, xor_in_place};
+
+////////////////////////////////////////////////////////////////////////////
+/// `Ccm` is the CCM mode over a 128-bit block cipher.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::block::mode::ccm::Ccm;
+/// use cryptolib::block::mode::Aead;
+///
+/// let ccm = Ccm::new(Aes::new(&[0x42; 16]).unwrap(), 13, 8).unwrap();
+/// let sealed = ccm.encrypt(&[0; 13], b"header", b"secret").unwrap();
+/// assert_eq!(ccm.decrypt(&[0; 13], b"header", &sealed).unwrap(), b"secret");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Ccm<C: BlockCipher> {
+    cipher: C,
+    nonce_len: usize,
+    tag_len: usize,
+}
+
+impl<C: BlockCipher> Ccm<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a CCM instance.
+    ///# Arguments
+    ///* `cipher`: a keyed 128-bit block cipher.
+    ///* `nonce_len`: the nonce length in bytes, from 7 to 13.
+    ///* `tag_len`: the tag length in bytes, 4, 6, 8, 10, 12, 14 or 16.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidBlockSize`, `InvalidNonceLength`
+    /// or `InvalidTagLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(cipher: C, nonce_len: usize, tag_len: usize) -> Result<Ccm<C>, CryptoError> {
+        if tag_len == 0 {
+            return Err(CryptoError::InvalidTagLength);
+        }
+        Ccm::new_star(cipher, nonce_len, tag_len)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a CCM* instance, which is CCM where `tag_len` may also
+    /// be 0.
+    ///# Note
+    /// '''
+    /// With a zero length tag the messages are encrypted but not
+    /// authenticated, this is only meant for the 802.15.4 security
+    /// levels that rely on another integrity check.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn new_star(cipher: C, nonce_len: usize, tag_len: usize) -> Result<Ccm<C>, CryptoError> {
+        if C::BLOCK_SIZE != 16 {
+            return Err(CryptoError::InvalidBlockSize);
+        }
+        if !(7..=13).contains(&nonce_len) {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        if tag_len != 0 && !(tag_len.is_multiple_of(2) && (4..=16).contains(&tag_len)) {
+            return Err(CryptoError::InvalidTagLength);
+        }
+        Ok(Ccm {
+            cipher,
+            nonce_len,
+            tag_len,
+        })
+    }
+
+    /// Returns the tag length in bytes.
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    /// Size of the message length field.
+    fn length_size(&self) -> usize {
+        15 - self.nonce_len
+    }
+
+    fn check_lengths(&self, nonce: &[u8], text_len: usize) -> Result<(), CryptoError> {
+        if nonce.len() != self.nonce_len {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        let length_size = self.length_size();
+        if length_size < 8 && text_len as u64 >> (8 * length_size) != 0 {
+            return Err(CryptoError::MessageTooLong);
+        }
+        Ok(())
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Returns the counter block A_i = flags || nonce || i for a
+    /// small `i`.
+    //////////////////////////////////////////////////////////////////
+    fn counter_block(&self, nonce: &[u8], i: u8) -> [u8; 16] {
+        let mut block = [0_u8; 16];
+        block[0] = (self.length_size() - 1) as u8;
+        block[1..=self.nonce_len].copy_from_slice(nonce);
+        block[15] = i;
+        block
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Computes the CBC-MAC of B_0, the encoded `aad` and `plain_text`.
+    //////////////////////////////////////////////////////////////////
+    fn cbc_mac(&self, nonce: &[u8], aad: &[u8], plain_text: &[u8]) -> [u8; 16] {
+        let mut x = [0_u8; 16];
+        x[0] = ((!aad.is_empty() as u8) << 6)
+            | ((self.tag_len.saturating_sub(2) / 2) as u8) << 3
+            | (self.length_size() - 1) as u8;
+        x[1..=self.nonce_len].copy_from_slice(nonce);
+        let text_len = (plain_text.len() as u64).to_be_bytes();
+        x[1 + self.nonce_len..].copy_from_slice(&text_len[8 - self.length_size()..]);
+        self.cipher.encrypt_block(&mut x);
+
+        let mut aad = aad;
+        if !aad.is_empty() {
+            // The length of the additional data is encoded on 2, 6 or 10
+            // bytes followed by the start of the data.
+            let mut first = [0_u8; 16];
+            let len = aad.len() as u64;
+            let header_len = if len < 0xff00 {
+                first[..2].copy_from_slice(&(len as u16).to_be_bytes());
+                2
+            } else if len <= u32::MAX as u64 {
+                first[..2].copy_from_slice(&[0xff, 0xfe]);
+                first[2..6].copy_from_slice(&(len as u32).to_be_bytes());
+                6
+            } else {
+                first[..2].copy_from_slice(&[0xff, 0xff]);
+                first[2..10].copy_from_slice(&len.to_be_bytes());
+                10
+            };
+            let take = aad.len().min(16 - header_len);
+            first[header_len..header_len + take].copy_from_slice(&aad[..take]);
+            aad = &aad[take..];
+            xor_in_place(&mut x, &first);
+            self.cipher.encrypt_block(&mut x);
+        }
+        // The data is padded with zeros to a multiple of the block size.
+        for chunk in aad.chunks(16).chain(plain_text.chunks(16)) {
+            xor_in_place(&mut x, chunk);
+            self.cipher.encrypt_block(&mut x);
+        }
+        x
+    }
+
+    /// Returns the encrypted tag of a message.
+    fn tag(&self, nonce: &[u8], aad: &[u8], plain_text: &[u8]) -> Vec<u8> {
+        if self.tag_len == 0 {
+            return Vec::new();
+        }
+        let mut tag = self.cbc_mac(nonce, aad, plain_text);
+        let mut s0 = self.counter_block(nonce, 0);
+        self.cipher.encrypt_block(&mut s0);
+        xor_in_place(&mut tag, &s0);
+        tag[..self.tag_len].to_vec()
+    }
+
+    fn apply_keystream(&self, nonce: &[u8], data: &mut [u8]) {
+        let mut counter = self.counter_block(nonce, 1);
+        apply_ctr(&self.cipher, &mut counter, self.length_size(), data);
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts `buffer` in place.
+    ///# Arguments
+    ///* `nonce`: a `nonce_len` bytes nonce, never reused with a key.
+    ///* `aad`: additional data that is authenticated but not encrypted.
+    ///* `buffer`: the plaintext, replaced by the ciphertext.
+    ///# Returns
+    /// The authentication tag.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt_detached(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        self.check_lengths(nonce, buffer.len())?;
+        let tag = self.tag(nonce, aad, buffer);
+        self.apply_keystream(nonce, buffer);
+        Ok(tag)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts `buffer` in place and verifies the `tag`.
+    ///# Returns
+    /// `CryptoError::AuthenticationFailed` if the tag doesn't match,
+    /// in which case `buffer` is restored to the ciphertext.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt_detached(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), CryptoError> {
+        self.check_lengths(nonce, buffer.len())?;
+        self.apply_keystream(nonce, buffer);
+        if !ct_eq(&self.tag(nonce, aad, buffer), tag) {
+            self.apply_keystream(nonce, buffer);
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+}
+
+impl<C: BlockCipher> Aead for Ccm<C> {
+    fn encrypt(&self, nonce: &[u8], aad: &[u8], plain_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut out = plain_text.to_vec();
+        let tag = self.encrypt_detached(nonce, aad, &mut out)?;
+        out.extend_from_slice(&tag);
+        Ok(out)
+    }
+
+    fn decrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        cipher_text: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        if cipher_text.len() < self.tag_len {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        let (data, tag) = cipher_text.split_at(cipher_text.len() - self.tag_len);
+        let mut out = data.to_vec();
+        self.decrypt_detached(nonce, aad, &mut out, tag)?;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::test_utils::hex;
+
+    const KEY: &str = "404142434445464748494a4b4c4d4e4f";
+
+    fn check(ccm: &Ccm<Aes>, nonce: &str, aad: &[u8], plain: &[u8], sealed: &str) {
+        let out = ccm.encrypt(&hex(nonce), aad, plain).unwrap();
+        assert_eq!(out, hex(sealed));
+        assert_eq!(ccm.decrypt(&hex(nonce), aad, &out).unwrap(), plain);
+    }
+
+    #[test]
+    fn test_ccm_sp800_38c() {
+        // SP 800-38C Appendix C, examples 1 to 4.
+        let aes = Aes::new(&hex(KEY)).unwrap();
+        let plain: Vec<u8> = (0x20..0x40).collect();
+        let aad: Vec<u8> = (0..=255).cycle().take(65536).collect();
+        check(
+            &Ccm::new(aes.clone(), 7, 4).unwrap(),
+            "10111213141516",
+            &aad[..8],
+            &plain[..4],
+            "7162015b4dac255d",
+        );
+        check(
+            &Ccm::new(aes.clone(), 8, 6).unwrap(),
+            "1011121314151617",
+            &aad[..16],
+            &plain[..16],
+            "d2a1f0e051ea5f62081a7792073d593d1fc64fbfaccd",
+        );
+        check(
+            &Ccm::new(aes.clone(), 12, 8).unwrap(),
+            "101112131415161718191a1b",
+            &aad[..20],
+            &plain[..24],
+            "e3b201a9f5b71a7a9b1ceaeccd97e70b6176aad9a4428aa5484392fbc1b09951",
+        );
+        check(
+            &Ccm::new(aes, 13, 14).unwrap(),
+            "101112131415161718191a1b1c",
+            &aad,
+            &plain,
+            "69915dad1e84c6376a68c2967e4dab615ae0fd1faec44cc484828529463ccf72
+             b4ac6bec93e8598e7f0dadbcea5b",
+        );
+    }
+
+    #[test]
+    fn test_ccm_rfc3610() {
+        // RFC 3610 packet vectors #1 and #2.
+        let aes = Aes::new(&hex("c0c1c2c3c4c5c6c7c8c9cacbcccdcecf")).unwrap();
+        let ccm = Ccm::new(aes, 13, 8).unwrap();
+        let packet: Vec<u8> = (0..0x20).collect();
+        check(
+            &ccm,
+            "00000003020100a0a1a2a3a4a5",
+            &packet[..8],
+            &packet[8..0x1f],
+            "588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0",
+        );
+        check(
+            &ccm,
+            "00000004030201a0a1a2a3a4a5",
+            &packet[..8],
+            &packet[8..],
+            "72c91a36e135f8cf291ca894085c87e3cc15c439c9e43a3ba091d56e10400916",
+        );
+    }
+
+    #[test]
+    fn test_ccm_star() {
+        // Without a tag CCM* only encrypts, with the same keystream as CCM.
+        let aes = Aes::new(&hex("c0c1c2c3c4c5c6c7c8c9cacbcccdcecf")).unwrap();
+        let nonce = hex("00000003020100a0a1a2a3a4a5");
+        let plain: Vec<u8> = (8..0x1f).collect();
+        let ccm_star = Ccm::new_star(aes.clone(), 13, 0).unwrap();
+        let sealed = ccm_star.encrypt(&nonce, b"aad", &plain).unwrap();
+        assert_eq!(
+            sealed,
+            hex("588c979a61c663d2f066d0c2c0f989806d5f6b61dac384")
+        );
+        assert_eq!(ccm_star.decrypt(&nonce, b"aad", &sealed).unwrap(), plain);
+        assert!(Ccm::new_star(aes.clone(), 13, 4).is_ok());
+        assert_eq!(
+            Ccm::new(aes, 13, 0).err(),
+            Some(CryptoError::InvalidTagLength)
+        );
+    }
+
+    #[test]
+    fn test_ccm_errors() {
+        let aes = Aes::new(&hex(KEY)).unwrap();
+        assert!(Ccm::new(aes.clone(), 6, 8).is_err());
+        assert!(Ccm::new(aes.clone(), 14, 8).is_err());
+        assert!(Ccm::new(aes.clone(), 13, 5).is_err());
+        assert!(Ccm::new(aes.clone(), 13, 18).is_err());
+
+        let ccm = Ccm::new(aes, 13, 8).unwrap();
+        let nonce = [7; 13];
+        assert_eq!(
+            ccm.encrypt(&nonce[..12], b"", b"").err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+        // A 13 bytes nonce leaves 2 bytes for the message length.
+        assert!(ccm.encrypt(&nonce, b"", &[0; 0xffff]).is_ok());
+        assert_eq!(
+            ccm.encrypt(&nonce, b"", &[0; 0x10000]).err(),
+            Some(CryptoError::MessageTooLong)
+        );
+
+        let mut sealed = ccm.encrypt(&nonce, b"aad", b"plain text").unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x80;
+        assert_eq!(
+            ccm.decrypt(&nonce, b"aad", &sealed).err(),
+            Some(CryptoError::AuthenticationFailed)
+        );
+        let mut buffer = sealed[..10].to_vec();
+        assert!(ccm
+            .decrypt_detached(&nonce, b"aad", &mut buffer, &sealed[10..])
+            .is_err());
+        assert_eq!(buffer, sealed[..10].to_vec());
+    }
+}
@@ -0,0 +1,206 @@
+//#######################################################################o
+// The Counter mode (CTR) turns a block cipher into a stream cipher:     |
+// the keystream is the encryption of successive counter blocks and is   |
+// xored with the data, so encryption and decryption are the same        |
+// operation, any length is supported and blocks are independent.        |
+// The whole counter block is incremented as a big-endian integer.       |
+// A counter block must never be reused with the same key.               |
+// The position is a function of the counter so seeking is immediate.    |
+//                                                                       |
+// @Refrences:                                                           |
+// SP 800-38A: https://doi.org/10.6028/NIST.SP.800-38A                   |
+//#######################################################################o
+
+use super::{add_be, apply_ctr, increment_be};
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::stream::{advance, StreamCipher};
+use crate::util::xor_in_place;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Ctr` is the CTR mode over any block cipher, it keeps its position
+/// so the data can be processed in pieces of any size.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::block::mode::ctr::Ctr;
+/// use cryptolib::stream::StreamCipher;
+///
+/// let mut ctr = Ctr::new(Aes::new(&[0x42; 16]).unwrap(), &[0; 16]).unwrap();
+/// let mut data = *b"any length";
+/// ctr.apply_keystream(&mut data).unwrap();
+/// ctr.seek(0).unwrap();
+/// ctr.apply_keystream(&mut data).unwrap();
+/// assert_eq!(&data, b"any length");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Ctr<C: BlockCipher> {
+    cipher: C,
+    // The next counter block to encrypt.
+    counter: Vec<u8>,
+    // The last keystream block and how many of its bytes were used.
+    keystream: Vec<u8>,
+    used: usize,
+    // The initial counter block, the position is counted from it.
+    start: Vec<u8>,
+    pos: u64,
+}
+
+impl<C: BlockCipher> Ctr<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a CTR instance.
+    ///# Arguments
+    ///* `cipher`: the keyed block cipher.
+    ///* `counter`: the initial counter block, `BLOCK_SIZE` bytes long.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidNonceLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(cipher: C, counter: &[u8]) -> Result<Ctr<C>, CryptoError> {
+        if counter.len() != C::BLOCK_SIZE {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        Ok(Ctr {
+            cipher,
+            counter: counter.to_vec(),
+            keystream: vec![0; C::BLOCK_SIZE],
+            used: C::BLOCK_SIZE,
+            start: counter.to_vec(),
+            pos: 0,
+        })
+    }
+}
+
+impl<C: BlockCipher> StreamCipher for Ctr<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts or decrypts `data` in place. The whole block is the
+    /// counter so only the `u64` byte position can overflow.
+    ////////////////////////////////////////////////////////////////////
+    fn apply_keystream(&mut self, data: &mut [u8]) -> Result<(), CryptoError> {
+        self.pos = advance(self.pos, data.len(), None)?;
+        let block_size = C::BLOCK_SIZE;
+        // Leftover bytes of the last keystream block.
+        let n = data.len().min(block_size - self.used);
+        let (head, data) = data.split_at_mut(n);
+        xor_in_place(head, &self.keystream[self.used..]);
+        self.used += n;
+
+        let full = data.len() / block_size * block_size;
+        let (blocks, tail) = data.split_at_mut(full);
+        apply_ctr(&self.cipher, &mut self.counter, block_size, blocks);
+        if !tail.is_empty() {
+            self.keystream.copy_from_slice(&self.counter);
+            self.cipher.encrypt_block(&mut self.keystream);
+            increment_be(&mut self.counter);
+            xor_in_place(tail, &self.keystream);
+            self.used = tail.len();
+        }
+        Ok(())
+    }
+
+    fn seek(&mut self, pos: u64) -> Result<(), CryptoError> {
+        let block_size = C::BLOCK_SIZE as u64;
+        self.counter.copy_from_slice(&self.start);
+        add_be(&mut self.counter, pos / block_size);
+        self.used = C::BLOCK_SIZE;
+        let offset = (pos % block_size) as usize;
+        if offset != 0 {
+            self.keystream.copy_from_slice(&self.counter);
+            self.cipher.encrypt_block(&mut self.keystream);
+            increment_be(&mut self.counter);
+            self.used = offset;
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    fn current_pos(&self) -> u64 {
+        self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::block::des::Des;
+    use crate::block::triple_des::TripleDes;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_ctr_aes() {
+        // SP 800-38A F.5.1, processed in uneven pieces.
+        let aes = Aes::new(&hex("2b7e151628aed2a6abf7158809cf4f3c")).unwrap();
+        let counter = hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");
+        let plain = hex(
+            "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51
+             30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        );
+        let expected = hex(
+            "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff
+             5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee",
+        );
+        for split in [0, 1, 15, 16, 17, 40, 64] {
+            let mut ctr = Ctr::new(aes.clone(), &counter).unwrap();
+            let mut buffer = plain.clone();
+            let (a, b) = buffer.split_at_mut(split);
+            ctr.apply_keystream(a).unwrap();
+            ctr.apply_keystream(b).unwrap();
+            assert_eq!(buffer, expected, "split at {}", split);
+            // Seeking anywhere, backwards included.
+            for pos in [63, 16, 0, 17, 33] {
+                ctr.seek(pos as u64).unwrap();
+                let mut rest = plain[pos..].to_vec();
+                ctr.apply_keystream(&mut rest).unwrap();
+                assert_eq!(rest, expected[pos..], "seek to {}", pos);
+                assert_eq!(ctr.current_pos(), 64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ctr_des() {
+        // DES and Triple DES, the counter block wraps after the second
+        // block. Checked against the libgcrypt block ciphers.
+        let des = Des::new(&hex("0123456789abcdef")).unwrap();
+        let mut ctr = Ctr::new(des, &hex("fffffffffffffffe")).unwrap();
+        let mut buffer = *b"Now is the time for all good men";
+        ctr.apply_keystream(&mut buffer).unwrap();
+        assert_eq!(
+            buffer.to_vec(),
+            hex("f2201e6b2ca51527311603229a02bb26b3bb3dd74104512d97e33844b5fe9bdd")
+        );
+        let key = hex("0123456789abcdef23456789abcdef01456789abcdef0123");
+        let mut ctr = Ctr::new(TripleDes::new(&key).unwrap(), &hex("fffffffffffffffe")).unwrap();
+        ctr.seek(8).unwrap();
+        let mut buffer = *b"he time for all good men";
+        ctr.apply_keystream(&mut buffer).unwrap();
+        assert_eq!(
+            buffer.to_vec(),
+            hex("95c0c1df4949d70928d501bcf8e7a74039d196e8c2c05c22")
+        );
+    }
+
+    #[test]
+    fn test_ctr_wrap() {
+        // The whole block is the counter, checked against OpenSSL.
+        let aes = Aes::new(&hex("2b7e151628aed2a6abf7158809cf4f3c")).unwrap();
+        let mut ctr = Ctr::new(aes.clone(), &[0xff; 16]).unwrap();
+        let mut buffer = [0_u8; 40];
+        ctr.apply_keystream(&mut buffer[..7]).unwrap();
+        ctr.apply_keystream(&mut buffer[7..]).unwrap();
+        assert_eq!(
+            buffer.to_vec(),
+            hex("8af2860142f786f409307c1a3f7eaaac7df76b0c1ab899b33e42f047b91b546f57127d4034b1bebf")
+        );
+        // Seeking adds the block index to the counter with the same wrap.
+        ctr.seek(20).unwrap();
+        let mut rest = [0_u8; 20];
+        ctr.apply_keystream(&mut rest).unwrap();
+        assert_eq!(rest, buffer[20..]);
+        assert_eq!(
+            Ctr::new(aes, &[0; 12]).err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//#######################################################################o
+// The EAX mode encrypts in counter mode and authenticates with three    |
+// CMAC (OMAC) computations, separated by a one block prefix t:          |
+//   N = OMAC_0(nonce), H = OMAC_1(aad), C = CTR_N(plaintext)            |
+//   tag = N ^ H ^ OMAC_2(C)                                             |
+// The nonce can have any length and the mode works with 64-bit and      |
+// 128-bit block ciphers.                                                |
+//                                                                       |
+// @Refrences:                                                           |
+// Bellare, Rogaway & Wagner, The EAX Mode of Operation (2004).          |
+//#######################################################################o
+
+use super::{apply_ctr, Aead};
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::mac::cmac::Cmac;
+use crate::util::{ct_eq, xor_in_place};
+
+////////////////////////////////////////////////////////////////////////////
+/// `Eax` is the EAX mode over a 64-bit or 128-bit block cipher.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::block::mode::eax::Eax;
+/// use cryptolib::block::mode::Aead;
+///
+/// let eax = Eax::new(Aes::new(&[0x42; 16]).unwrap(), 16).unwrap();
+/// let sealed = eax.encrypt(b"any nonce", b"header", b"secret").unwrap();
+/// assert_eq!(eax.decrypt(b"any nonce", b"header", &sealed).unwrap(), b"secret");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Eax<C: BlockCipher> {
+    cmac: Cmac<C>,
+    tag_len: usize,
+}
+
+impl<C: BlockCipher> Eax<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an EAX instance.
+    ///# Arguments
+    ///* `cipher`: a keyed 64-bit or 128-bit block cipher.
+    ///* `tag_len`: the tag length in bytes, from 1 to the block size.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidBlockSize`/`InvalidTagLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(cipher: C, tag_len: usize) -> Result<Eax<C>, CryptoError> {
+        let cmac = Cmac::new(cipher)?;
+        if !(1..=C::BLOCK_SIZE).contains(&tag_len) {
+            return Err(CryptoError::InvalidTagLength);
+        }
+        Ok(Eax { cmac, tag_len })
+    }
+
+    /// Returns the tag length in bytes.
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    /// Computes OMAC_t(data), the CMAC of [t]_n || data.
+    fn omac(&self, t: u8, data: &[u8]) -> [u8; 16] {
+        let mut prefix = [0_u8; 16];
+        prefix[C::BLOCK_SIZE - 1] = t;
+        self.cmac.compute_parts(&[&prefix[..C::BLOCK_SIZE], data])
+    }
+
+    fn tag(&self, nonce_mac: &[u8; 16], aad: &[u8], cipher_text: &[u8]) -> Vec<u8> {
+        let mut tag = self.omac(2, cipher_text);
+        xor_in_place(&mut tag, nonce_mac);
+        xor_in_place(&mut tag, &self.omac(1, aad));
+        tag[..self.tag_len].to_vec()
+    }
+
+    fn apply_keystream(&self, nonce_mac: &[u8; 16], data: &mut [u8]) {
+        let mut counter = *nonce_mac;
+        let counter = &mut counter[..C::BLOCK_SIZE];
+        apply_ctr(self.cmac.cipher(), counter, C::BLOCK_SIZE, data);
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts `buffer` in place.
+    ///# Arguments
+    ///* `nonce`: a nonce of any length, never reused with a key.
+    ///* `aad`: additional data that is authenticated but not encrypted.
+    ///* `buffer`: the plaintext, replaced by the ciphertext.
+    ///# Returns
+    /// The authentication tag.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt_detached(&self, nonce: &[u8], aad: &[u8], buffer: &mut [u8]) -> Vec<u8> {
+        let nonce_mac = self.omac(0, nonce);
+        self.apply_keystream(&nonce_mac, buffer);
+        self.tag(&nonce_mac, aad, buffer)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Verifies the `tag` then decrypts `buffer` in place.
+    ///# Returns
+    /// `CryptoError::AuthenticationFailed` if the tag doesn't match,
+    /// in which case `buffer` is left untouched.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt_detached(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), CryptoError> {
+        let nonce_mac = self.omac(0, nonce);
+        if !ct_eq(&self.tag(&nonce_mac, aad, buffer), tag) {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        self.apply_keystream(&nonce_mac, buffer);
+        Ok(())
+    }
+}
+
+impl<C: BlockCipher> Aead for Eax<C> {
+    fn encrypt(&self, nonce: &[u8], aad: &[u8], plain_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut out = plain_text.to_vec();
+        let tag = self.encrypt_detached(nonce, aad, &mut out);
+        out.extend_from_slice(&tag);
+        Ok(out)
+    }
+
+    fn decrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        cipher_text: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        if cipher_text.len() < self.tag_len {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        let (data, tag) = cipher_text.split_at(cipher_text.len() - self.tag_len);
+        let mut out = data.to_vec();
+        self.decrypt_detached(nonce, aad, &mut out, tag)?;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::block::triple_des::TripleDes;
+    use crate::test_utils::hex;
+
+    fn check(key: &str, nonce: &str, header: &str, plain: &str, sealed: &str) {
+        let eax = Eax::new(Aes::new(&hex(key)).unwrap(), 16).unwrap();
+        let out = eax.encrypt(&hex(nonce), &hex(header), &hex(plain)).unwrap();
+        assert_eq!(out, hex(sealed));
+        assert_eq!(
+            eax.decrypt(&hex(nonce), &hex(header), &out).unwrap(),
+            hex(plain)
+        );
+    }
+
+    #[test]
+    fn test_eax_vectors() {
+        // Test vectors from the EAX paper.
+        check(
+            "233952dee4d5ed5f9b9c6d6ff80ff478",
+            "62ec67f9c3a4a407fcb2a8c49031a8b3",
+            "6bfb914fd07eae6b",
+            "",
+            "e037830e8389f27b025a2d6527e79d01",
+        );
+        check(
+            "91945d3f4dcbee0bf45ef52255f095a4",
+            "becaf043b0a23d843194ba972c66debd",
+            "fa3bfd4806eb53fa",
+            "f7fb",
+            "19dd5c4c9331049d0bdab0277408f67967e5",
+        );
+        check(
+            "01f74ad64077f2e704c0f60ada3dd523",
+            "70c3db4f0d26368400a10ed05d2bff5e",
+            "234a3463c1264ac6",
+            "1a47cb4933",
+            "d851d5bae03a59f238a23e39199dc9266626c40f80",
+        );
+        check(
+            "8395fcf1e95bebd697bd010bc766aac3",
+            "22e7add93cfc6393c57ec0b3c17d6b44",
+            "126735fcc320d25a",
+            "ca40d7446e545ffaed3bd12a740a659ffbbb3ceab7",
+            "cb8920f87a6c75cff39627b56e3ed197c552d295a7cfc46afc253b4652b1af37
+             95b124ab6e",
+        );
+    }
+
+    #[test]
+    fn test_eax_tag_length() {
+        let aes = Aes::new(&hex("91945d3f4dcbee0bf45ef52255f095a4")).unwrap();
+        let eax = Eax::new(aes.clone(), 8).unwrap();
+        let nonce = hex("becaf043b0a23d843194ba972c66debd");
+        let sealed = eax
+            .encrypt(&nonce, &hex("fa3bfd4806eb53fa"), &hex("f7fb"))
+            .unwrap();
+        assert_eq!(sealed, hex("19dd5c4c9331049d0bda"));
+        assert!(Eax::new(aes.clone(), 0).is_err());
+        assert!(Eax::new(aes, 17).is_err());
+    }
+
+    #[test]
+    fn test_eax_64_bit_block() {
+        let key = hex("0123456789abcdef23456789abcdef01456789abcdef0123");
+        let eax = Eax::new(TripleDes::new(&key).unwrap(), 8).unwrap();
+        let plain = b"a message longer than a few 64-bit blocks";
+        let sealed = eax.encrypt(b"nonce", b"header", plain).unwrap();
+        assert_eq!(sealed.len(), plain.len() + 8);
+        assert_eq!(eax.decrypt(b"nonce", b"header", &sealed).unwrap(), plain);
+        assert!(eax.decrypt(b"nonce", b"other", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_eax_authentication_failure() {
+        let eax = Eax::new(Aes::new(&[1; 32]).unwrap(), 16).unwrap();
+        let mut sealed = eax.encrypt(b"nonce", b"aad", b"plain text").unwrap();
+        sealed[0] ^= 1;
+        assert_eq!(
+            eax.decrypt(b"nonce", b"aad", &sealed).err(),
+            Some(CryptoError::AuthenticationFailed)
+        );
+        assert!(eax.decrypt(b"nonce", b"aad", &sealed[..15]).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//#######################################################################o
+// The Electronic Codebook mode (ECB) encrypts each block independently  |
+// with the same key, so equal plaintext blocks give equal ciphertext    |
+// blocks and the structure of the data leaks.                           |
+// It's only provided for interoperability and for test vectors, the     |
+// data must be a multiple of the block size unless PKCS#7 padding is    |
+// used.                                                                 |
+//                                                                       |
+// @Refrences:                                                           |
+// SP 800-38A: https://doi.org/10.6028/NIST.SP.800-38A                   |
+//#######################################################################o
+
+use super::{pkcs7_pad, pkcs7_unpad};
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Ecb` is the ECB mode over any block cipher.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::block::mode::ecb::Ecb;
+///
+/// let ecb = Ecb::new(Aes::new(&[0x42; 16]).unwrap());
+/// let cipher_text = ecb.encrypt_padded(b"secret");
+/// assert_eq!(ecb.decrypt_padded(&cipher_text).unwrap(), b"secret");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Ecb<C: BlockCipher> {
+    cipher: C,
+}
+
+impl<C: BlockCipher> Ecb<C> {
+    /// Creates an ECB instance from a keyed block cipher.
+    pub fn new(cipher: C) -> Ecb<C> {
+        Ecb { cipher }
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts `buffer` in place.
+    ///# Returns
+    /// `CryptoError::InvalidDataLength` if `buffer` isn't a multiple of
+    /// `BLOCK_SIZE` bytes.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt(&self, buffer: &mut [u8]) -> Result<(), CryptoError> {
+        if !buffer.len().is_multiple_of(C::BLOCK_SIZE) {
+            return Err(CryptoError::InvalidDataLength);
+        }
+        self.cipher.encrypt_blocks(buffer);
+        Ok(())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts `buffer` in place.
+    ///# Returns
+    /// `CryptoError::InvalidDataLength` if `buffer` isn't a multiple of
+    /// `BLOCK_SIZE` bytes.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt(&self, buffer: &mut [u8]) -> Result<(), CryptoError> {
+        if !buffer.len().is_multiple_of(C::BLOCK_SIZE) {
+            return Err(CryptoError::InvalidDataLength);
+        }
+        self.cipher.decrypt_blocks(buffer);
+        Ok(())
+    }
+
+    /// Pads `plain_text` with PKCS#7 and encrypts it.
+    pub fn encrypt_padded(&self, plain_text: &[u8]) -> Vec<u8> {
+        let mut out = pkcs7_pad(plain_text, C::BLOCK_SIZE);
+        self.cipher.encrypt_blocks(&mut out);
+        out
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts `cipher_text` and removes its PKCS#7 padding.
+    ///# Returns
+    /// The plaintext, `CryptoError::InvalidDataLength` or
+    /// `CryptoError::InvalidPadding`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt_padded(&self, cipher_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut out = cipher_text.to_vec();
+        self.decrypt(&mut out)?;
+        let len = pkcs7_unpad(&out, C::BLOCK_SIZE)?;
+        out.truncate(len);
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_ecb_aes() {
+        // SP 800-38A F.1.1.
+        let ecb = Ecb::new(Aes::new(&hex("2b7e151628aed2a6abf7158809cf4f3c")).unwrap());
+        let plain = hex(
+            "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51
+             30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        );
+        let mut buffer = plain.clone();
+        ecb.encrypt(&mut buffer).unwrap();
+        assert_eq!(
+            buffer,
+            hex(
+                "3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf
+                 43b1cd7f598ece23881b00e3ed0306887b0c785e27e8ad3f8223207104725dd4"
+            )
+        );
+        ecb.decrypt(&mut buffer).unwrap();
+        assert_eq!(buffer, plain);
+        assert_eq!(
+            ecb.encrypt(&mut [0; 17]),
+            Err(CryptoError::InvalidDataLength)
+        );
+        assert_eq!(
+            ecb.decrypt_padded(&[0; 15]).err(),
+            Some(CryptoError::InvalidDataLength)
+        );
+    }
+}
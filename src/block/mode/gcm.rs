@@ -0,0 +1,576 @@
+//#######################################################################o
+// The Galois/Counter Mode (GCM) turns a 128-bit block cipher into an    |
+// authenticated encryption scheme: the data is encrypted in counter     |
+// mode and the additional data and the ciphertext are authenticated     |
+// with GHASH, a polynomial hash over GF(2^128) keyed with E_K(0^128).   |
+// GMAC is GCM used with an empty plaintext, it only authenticates.      |
+//                                                                       |
+// @Refrences:                                                           |
+// NIST SP 800-38D: https://doi.org/10.6028/NIST.SP.800-38D              |
+// McGrew & Viega, The Galois/Counter Mode of Operation (GCM) test cases.|
+//#######################################################################o
+
+use super::polyval::GHash;
+use super::Aead;
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::util::{ct_eq, xor_in_place};
+
+// Largest plaintext length in bytes (2^39 - 256 bits).
+const MAX_TEXT_LEN: u64 = (1 << 36) - 32;
+// Largest additional data length in bytes (2^64 - 1 bits).
+const MAX_AAD_LEN: u64 = (1 << 61) - 1;
+// Number of counter blocks encrypted together.
+const PARALLEL_BLOCKS: usize = 8;
+
+//////////////////////////////////////////////////////////////////////
+/// Increments the rightmost 32 bits of the counter block modulo 2^32.
+//////////////////////////////////////////////////////////////////////
+fn inc32(counter: &mut [u8; 16]) {
+    let ctr = u32::from_be_bytes(counter[12..].try_into().unwrap()).wrapping_add(1);
+    counter[12..].copy_from_slice(&ctr.to_be_bytes());
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Gcm` is the Galois/Counter Mode over a 128-bit block cipher.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::block::mode::gcm::Gcm;
+/// use cryptolib::block::mode::Aead;
+///
+/// let gcm = Gcm::new(Aes::new(&[0x42; 16]).unwrap()).unwrap();
+/// let sealed = gcm.encrypt(&[0; 12], b"header", b"secret").unwrap();
+/// assert_eq!(gcm.decrypt(&[0; 12], b"header", &sealed).unwrap(), b"secret");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Gcm<C: BlockCipher> {
+    cipher: C,
+    h: [u8; 16],
+    tag_len: usize,
+}
+
+impl<C: BlockCipher> Gcm<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a GCM instance producing 128-bit tags.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidBlockSize` if the cipher
+    /// doesn't use 128-bit blocks.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(cipher: C) -> Result<Gcm<C>, CryptoError> {
+        Gcm::with_tag_len(cipher, 16)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a GCM instance producing truncated tags.
+    ///# Arguments
+    ///* `cipher`: a keyed 128-bit block cipher.
+    ///* `tag_len`: the tag length in bytes, from 12 to 16.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidBlockSize`/`InvalidTagLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_tag_len(cipher: C, tag_len: usize) -> Result<Gcm<C>, CryptoError> {
+        if C::BLOCK_SIZE != 16 {
+            return Err(CryptoError::InvalidBlockSize);
+        }
+        if !(12..=16).contains(&tag_len) {
+            return Err(CryptoError::InvalidTagLength);
+        }
+        let mut h = [0_u8; 16];
+        cipher.encrypt_block(&mut h);
+        Ok(Gcm { cipher, h, tag_len })
+    }
+
+    /// Returns the tag length in bytes.
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Derives the pre-counter block J0 from the `iv` and
+    /// authenticates the `aad`.
+    //////////////////////////////////////////////////////////////////
+    fn start(&self, iv: &[u8], aad: &[u8]) -> Result<GcmState<'_, C>, CryptoError> {
+        if iv.is_empty() {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        if aad.len() as u64 > MAX_AAD_LEN {
+            return Err(CryptoError::MessageTooLong);
+        }
+        let mut j0 = [0_u8; 16];
+        if iv.len() == 12 {
+            j0[..12].copy_from_slice(iv);
+            j0[15] = 1;
+        } else {
+            let mut ghash = GHash::new(&self.h);
+            ghash.update(iv);
+            ghash.pad();
+            let mut len_block = [0_u8; 16];
+            len_block[8..].copy_from_slice(&(8 * iv.len() as u64).to_be_bytes());
+            ghash.update(&len_block);
+            j0 = ghash.finalize();
+        }
+        let mut tag_mask = j0;
+        self.cipher.encrypt_block(&mut tag_mask);
+        let mut counter = j0;
+        inc32(&mut counter);
+        let mut ghash = GHash::new(&self.h);
+        ghash.update(aad);
+        ghash.pad();
+        Ok(GcmState {
+            gcm: self,
+            counter,
+            tag_mask,
+            keystream: [0; 16],
+            keystream_used: 16,
+            ghash,
+            aad_len: aad.len() as u64,
+            text_len: 0,
+        })
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts `buffer` in place.
+    ///# Arguments
+    ///* `iv`: the initialization vector, 12 bytes is recommended but
+    ///  any non empty length is accepted.
+    ///* `aad`: additional data that is authenticated but not encrypted.
+    ///* `buffer`: the plaintext, replaced by the ciphertext.
+    ///# Returns
+    /// The authentication tag.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt_detached(
+        &self,
+        iv: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let mut encryptor = self.encryptor(iv, aad)?;
+        encryptor.update(buffer)?;
+        Ok(encryptor.finalize())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Verifies the `tag` then decrypts `buffer` in place.
+    ///# Returns
+    /// `CryptoError::AuthenticationFailed` if the tag doesn't match,
+    /// in which case `buffer` is left untouched.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt_detached(
+        &self,
+        iv: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), CryptoError> {
+        let mut state = self.start(iv, aad)?;
+        state.add_text_len(buffer.len())?;
+        state.ghash.update(buffer);
+        if !ct_eq(&state.tag()[..self.tag_len], tag) {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        state.apply_keystream(buffer);
+        Ok(())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Starts a streaming encryption, the plaintext is then passed in
+    /// pieces of any length to `GcmEncryptor::update`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encryptor(&self, iv: &[u8], aad: &[u8]) -> Result<GcmEncryptor<'_, C>, CryptoError> {
+        Ok(GcmEncryptor {
+            state: self.start(iv, aad)?,
+        })
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Starts a streaming decryption.
+    ///# Note
+    /// '''
+    /// The decrypted pieces are returned before the tag is checked by
+    /// `GcmDecryptor::finalize`, they must not be used until it
+    /// succeeds.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn decryptor(&self, iv: &[u8], aad: &[u8]) -> Result<GcmDecryptor<'_, C>, CryptoError> {
+        Ok(GcmDecryptor {
+            state: self.start(iv, aad)?,
+        })
+    }
+}
+
+impl<C: BlockCipher> Aead for Gcm<C> {
+    fn encrypt(&self, nonce: &[u8], aad: &[u8], plain_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut out = plain_text.to_vec();
+        let tag = self.encrypt_detached(nonce, aad, &mut out)?;
+        out.extend_from_slice(&tag);
+        Ok(out)
+    }
+
+    fn decrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        cipher_text: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        if cipher_text.len() < self.tag_len {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        let (data, tag) = cipher_text.split_at(cipher_text.len() - self.tag_len);
+        let mut out = data.to_vec();
+        self.decrypt_detached(nonce, aad, &mut out, tag)?;
+        Ok(out)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// State of a GCM operation: the counter, the unused keystream bytes
+/// of the last counter block and the GHASH of the data seen so far.
+////////////////////////////////////////////////////////////////////////////
+struct GcmState<'a, C: BlockCipher> {
+    gcm: &'a Gcm<C>,
+    counter: [u8; 16],
+    tag_mask: [u8; 16],
+    keystream: [u8; 16],
+    keystream_used: usize,
+    ghash: GHash,
+    aad_len: u64,
+    text_len: u64,
+}
+
+impl<C: BlockCipher> GcmState<'_, C> {
+    fn add_text_len(&mut self, len: usize) -> Result<(), CryptoError> {
+        self.text_len = self
+            .text_len
+            .checked_add(len as u64)
+            .filter(|&total| total <= MAX_TEXT_LEN)
+            .ok_or(CryptoError::MessageTooLong)?;
+        Ok(())
+    }
+
+    /// XORs `data` with the next bytes of the counter mode keystream.
+    fn apply_keystream(&mut self, data: &mut [u8]) {
+        let mut pos = 0;
+        if self.keystream_used < 16 {
+            pos = data.len().min(16 - self.keystream_used);
+            xor_in_place(&mut data[..pos], &self.keystream[self.keystream_used..]);
+            self.keystream_used += pos;
+        }
+        let mut keystream = [0_u8; 16 * PARALLEL_BLOCKS];
+        while data.len() - pos >= 16 {
+            let blocks = ((data.len() - pos) / 16).min(PARALLEL_BLOCKS);
+            for counter_block in keystream[..16 * blocks].chunks_exact_mut(16) {
+                counter_block.copy_from_slice(&self.counter);
+                inc32(&mut self.counter);
+            }
+            self.gcm
+                .cipher
+                .encrypt_blocks(&mut keystream[..16 * blocks]);
+            xor_in_place(&mut data[pos..pos + 16 * blocks], &keystream);
+            pos += 16 * blocks;
+        }
+        if pos < data.len() {
+            self.keystream = self.counter;
+            inc32(&mut self.counter);
+            self.gcm.cipher.encrypt_block(&mut self.keystream);
+            xor_in_place(&mut data[pos..], &self.keystream);
+            self.keystream_used = data.len() - pos;
+        }
+    }
+
+    /// Returns the full 128-bit tag of the data seen so far.
+    fn tag(&self) -> [u8; 16] {
+        let mut ghash = self.ghash.clone();
+        ghash.pad();
+        let mut len_block = [0_u8; 16];
+        len_block[..8].copy_from_slice(&(8 * self.aad_len).to_be_bytes());
+        len_block[8..].copy_from_slice(&(8 * self.text_len).to_be_bytes());
+        ghash.update(&len_block);
+        let mut tag = ghash.finalize();
+        xor_in_place(&mut tag, &self.tag_mask);
+        tag
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `GcmEncryptor` encrypts a message given in several pieces.
+////////////////////////////////////////////////////////////////////////////
+pub struct GcmEncryptor<'a, C: BlockCipher> {
+    state: GcmState<'a, C>,
+}
+
+impl<C: BlockCipher> GcmEncryptor<'_, C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts the next piece of the plaintext in place.
+    ///# Returns
+    /// `CryptoError::MessageTooLong` once the message exceeds the
+    /// 2^39 - 256 bits limit of GCM.
+    ////////////////////////////////////////////////////////////////////
+    pub fn update(&mut self, data: &mut [u8]) -> Result<(), CryptoError> {
+        self.state.add_text_len(data.len())?;
+        self.state.apply_keystream(data);
+        self.state.ghash.update(data);
+        Ok(())
+    }
+
+    /// Returns the authentication tag of the message.
+    pub fn finalize(self) -> Vec<u8> {
+        self.state.tag()[..self.state.gcm.tag_len].to_vec()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `GcmDecryptor` decrypts a message given in several pieces.
+////////////////////////////////////////////////////////////////////////////
+pub struct GcmDecryptor<'a, C: BlockCipher> {
+    state: GcmState<'a, C>,
+}
+
+impl<C: BlockCipher> GcmDecryptor<'_, C> {
+    /// Decrypts the next piece of the ciphertext in place.
+    pub fn update(&mut self, data: &mut [u8]) -> Result<(), CryptoError> {
+        self.state.add_text_len(data.len())?;
+        self.state.ghash.update(data);
+        self.state.apply_keystream(data);
+        Ok(())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Checks the authentication `tag` of the whole message.
+    ///# Returns
+    /// `CryptoError::AuthenticationFailed` if the tag doesn't match.
+    ////////////////////////////////////////////////////////////////////
+    pub fn finalize(self, tag: &[u8]) -> Result<(), CryptoError> {
+        if ct_eq(&self.state.tag()[..self.state.gcm.tag_len], tag) {
+            Ok(())
+        } else {
+            Err(CryptoError::AuthenticationFailed)
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Gmac` authenticates data without encrypting it (GCM with an empty
+/// plaintext), a fresh `iv` must be used for each message.
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Gmac<C: BlockCipher> {
+    gcm: Gcm<C>,
+}
+
+impl<C: BlockCipher> Gmac<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a GMAC instance producing `tag_len` bytes tags (12 to 16).
+    ///# Returns
+    /// The mac or `CryptoError::InvalidBlockSize`/`InvalidTagLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(cipher: C, tag_len: usize) -> Result<Gmac<C>, CryptoError> {
+        Ok(Gmac {
+            gcm: Gcm::with_tag_len(cipher, tag_len)?,
+        })
+    }
+
+    /// Returns the authentication tag of `data`.
+    pub fn compute(&self, iv: &[u8], data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Ok(self.gcm.encryptor(iv, data)?.finalize())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Checks the authentication `tag` of `data`.
+    ///# Returns
+    /// `CryptoError::AuthenticationFailed` if the tag doesn't match.
+    ////////////////////////////////////////////////////////////////////
+    pub fn verify(&self, iv: &[u8], data: &[u8], tag: &[u8]) -> Result<(), CryptoError> {
+        self.gcm.decryptor(iv, data)?.finalize(tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::{Aes, AesBackend};
+    use crate::block::des::Des;
+    use crate::test_utils::hex;
+
+    const KEY: &str = "feffe9928665731c6d6a8f9467308308";
+    const PLAIN: &str = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72
+                         1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39";
+    const AAD: &str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+
+    fn check(key: &str, iv: &str, aad: &str, plain: &str, cipher: &str, tag: &str) {
+        let gcm = Gcm::new(Aes::new(&hex(key)).unwrap()).unwrap();
+        let sealed = gcm.encrypt(&hex(iv), &hex(aad), &hex(plain)).unwrap();
+        assert_eq!(sealed, [hex(cipher), hex(tag)].concat());
+        assert_eq!(
+            gcm.decrypt(&hex(iv), &hex(aad), &sealed).unwrap(),
+            hex(plain)
+        );
+    }
+
+    #[test]
+    fn test_gcm_spec_vectors() {
+        // Test cases 1 to 4.
+        check(
+            "00000000000000000000000000000000",
+            "000000000000000000000000",
+            "",
+            "",
+            "",
+            "58e2fccefa7e3061367f1d57a4e7455a",
+        );
+        check(
+            "00000000000000000000000000000000",
+            "000000000000000000000000",
+            "",
+            "00000000000000000000000000000000",
+            "0388dace60b6a392f328c2b971b2fe78",
+            "ab6e47d42cec13bdf53a67b21257bddf",
+        );
+        check(
+            KEY,
+            "cafebabefacedbaddecaf888",
+            "",
+            &format!("{}1aafd255", PLAIN),
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e
+             21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091473f5985",
+            "4d5c2af327cd64a62cf35abd2ba6fab4",
+        );
+        check(
+            KEY,
+            "cafebabefacedbaddecaf888",
+            AAD,
+            PLAIN,
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e
+             21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+            "5bc94fbc3221a5db94fae95ae7121a47",
+        );
+        // Test case 16 (AES-256).
+        check(
+            &format!("{}{}", KEY, KEY),
+            "cafebabefacedbaddecaf888",
+            AAD,
+            PLAIN,
+            "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa
+             8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662",
+            "76fc6ece0f4e1768cddf8853bb2d551b",
+        );
+    }
+
+    #[test]
+    fn test_gcm_iv_lengths() {
+        // Test case 5 (64-bit IV) and 6 (480-bit IV).
+        check(
+            KEY,
+            "cafebabefacedbad",
+            AAD,
+            PLAIN,
+            "61353b4c2806934a777ff51fa22a4755699b2a714fcdc6f83766e5f97b6c7423
+             73806900e49f24b22b097544d4896b424989b5e1ebac0f07c23f4598",
+            "3612d2e79e3b0785561be14aaca2fccb",
+        );
+        check(
+            KEY,
+            "9313225df88406e555909c5aff5269aa6a7a9538534f7da1e4c303d2a318a728
+             c3c0c95156809539fcf0e2429a6b525416aedbf5a0de6a57a637b39b",
+            AAD,
+            PLAIN,
+            "8ce24998625615b603a033aca13fb894be9112a5c3a211a8ba262a3cca7e2ca7
+             01e4a9a4fba43c90ccdcb281d48c7c6fd62875d2aca417034c34aee5",
+            "619cc5aefffe0bfa462af43c1699d050",
+        );
+        let gcm = Gcm::new(Aes::new(&hex(KEY)).unwrap()).unwrap();
+        assert_eq!(
+            gcm.encrypt(&[], b"", b"data").err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+    }
+
+    #[test]
+    fn test_gcm_truncated_tag() {
+        let gcm = Gcm::with_tag_len(Aes::new(&hex(KEY)).unwrap(), 12).unwrap();
+        let iv = hex("cafebabefacedbaddecaf888");
+        let sealed = gcm.encrypt(&iv, &hex(AAD), &hex(PLAIN)).unwrap();
+        assert_eq!(sealed[60..].to_vec(), hex("5bc94fbc3221a5db94fae95a"));
+        assert_eq!(gcm.decrypt(&iv, &hex(AAD), &sealed).unwrap(), hex(PLAIN));
+        assert!(Gcm::with_tag_len(Aes::new(&hex(KEY)).unwrap(), 11).is_err());
+        assert!(Gcm::with_tag_len(Aes::new(&hex(KEY)).unwrap(), 17).is_err());
+    }
+
+    #[test]
+    fn test_gcm_authentication_failure() {
+        let gcm = Gcm::new(Aes::new(&hex(KEY)).unwrap()).unwrap();
+        let iv = hex("cafebabefacedbaddecaf888");
+        let mut sealed = gcm.encrypt(&iv, &hex(AAD), &hex(PLAIN)).unwrap();
+        sealed[3] ^= 1;
+        assert_eq!(
+            gcm.decrypt(&iv, &hex(AAD), &sealed).err(),
+            Some(CryptoError::AuthenticationFailed)
+        );
+        sealed[3] ^= 1;
+        assert!(gcm.decrypt(&iv, b"other aad", &sealed).is_err());
+        assert!(gcm.decrypt(&iv, &hex(AAD), &sealed[..10]).is_err());
+        // The buffer isn't decrypted when the tag is wrong.
+        let mut buffer = sealed[..60].to_vec();
+        assert!(gcm
+            .decrypt_detached(&iv, &hex(AAD), &mut buffer, &[0; 16])
+            .is_err());
+        assert_eq!(buffer, sealed[..60].to_vec());
+    }
+
+    #[test]
+    fn test_gcm_streaming() {
+        let expected = hex(
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e
+             21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091",
+        );
+        let iv = hex("cafebabefacedbaddecaf888");
+        for backend in [AesBackend::Soft, AesBackend::detect()] {
+            let gcm = Gcm::new(Aes::with_backend(&hex(KEY), backend).unwrap()).unwrap();
+            let mut data = hex(PLAIN);
+            let mut encryptor = gcm.encryptor(&iv, &hex(AAD)).unwrap();
+            let (a, rest) = data.split_at_mut(1);
+            let (b, rest) = rest.split_at_mut(20);
+            let (c, d) = rest.split_at_mut(16);
+            for piece in [a, b, c, d] {
+                encryptor.update(piece).unwrap();
+            }
+            assert_eq!(
+                encryptor.finalize(),
+                hex("5bc94fbc3221a5db94fae95ae7121a47")
+            );
+            assert_eq!(data, expected);
+
+            let mut decryptor = gcm.decryptor(&iv, &hex(AAD)).unwrap();
+            for piece in data.chunks_mut(7) {
+                decryptor.update(piece).unwrap();
+            }
+            decryptor
+                .finalize(&hex("5bc94fbc3221a5db94fae95ae7121a47"))
+                .unwrap();
+            assert_eq!(data, hex(PLAIN));
+        }
+    }
+
+    #[test]
+    fn test_gmac() {
+        let gmac = Gmac::new(Aes::new(&hex(KEY)).unwrap(), 16).unwrap();
+        let iv = hex("cafebabefacedbaddecaf888");
+        let data = hex("feedfacedeadbeeffeedfacedeadbeefabaddad200112233445566778899");
+        let tag = gmac.compute(&iv, &data).unwrap();
+        assert_eq!(tag, hex("55099d506ce51a24b5fdb2a8d49e38f3"));
+        assert!(gmac.verify(&iv, &data, &tag).is_ok());
+        assert_eq!(
+            gmac.verify(&iv, &data[1..], &tag).err(),
+            Some(CryptoError::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn test_gcm_block_size() {
+        assert_eq!(
+            Gcm::new(Des::from_u64(0)).err(),
+            Some(CryptoError::InvalidBlockSize)
+        );
+    }
+}
@@ -0,0 +1,481 @@
+//#######################################################################o
+// AES-GCM-SIV is a nonce misuse resistant AEAD: a repeated nonce only   |
+// reveals whether the same message was encrypted twice.                 |
+// For each nonce a message authentication key and an encryption key     |
+// are derived from the key generating key, the tag is the encryption    |
+// of POLYVAL(aad, plaintext, lengths) xor nonce and is also used as the |
+// initial counter block of the CTR mode (32-bit little-endian counter). |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 8452: https://datatracker.ietf.org/doc/html/rfc8452               |
+//#######################################################################o
+
+use super::polyval::Polyval;
+use super::Aead;
+use crate::block::aes::Aes;
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::util::{ct_eq, xor_in_place};
+
+// Largest plaintext and additional data length in bytes.
+const MAX_TEXT_LEN: u64 = 1 << 36;
+// Number of counter blocks encrypted together.
+const PARALLEL_BLOCKS: usize = 8;
+
+////////////////////////////////////////////////////////////////////////////
+/// `AesGcmSiv` is AES-GCM-SIV with a 128-bit or 256-bit key, 96-bit
+/// nonces and 128-bit tags.
+///# Example
+/// ```
+/// use cryptolib::block::mode::gcm_siv::AesGcmSiv;
+/// use cryptolib::block::mode::Aead;
+///
+/// let gcm_siv = AesGcmSiv::new(&[0x42; 32]).unwrap();
+/// let sealed = gcm_siv.encrypt(&[0; 12], b"header", b"secret").unwrap();
+/// assert_eq!(gcm_siv.decrypt(&[0; 12], b"header", &sealed).unwrap(), b"secret");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct AesGcmSiv {
+    key_generating: Aes,
+}
+
+impl AesGcmSiv {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an AES-GCM-SIV instance from a 16 or 32 bytes key.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<AesGcmSiv, CryptoError> {
+        if key.len() != 16 && key.len() != 32 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        Ok(AesGcmSiv {
+            key_generating: Aes::new(key)?,
+        })
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Derives the POLYVAL key and the AES encryption key of `nonce`
+    /// from the first halves of AES(LE32(i) || nonce).
+    //////////////////////////////////////////////////////////////////
+    fn derive_keys(&self, nonce: &[u8]) -> Result<([u8; 16], Aes), CryptoError> {
+        if nonce.len() != 12 {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        let key_size = self.key_generating.key_size();
+        let mut keys = [0_u8; 48];
+        for (i, half) in keys[..16 + key_size].chunks_exact_mut(8).enumerate() {
+            let mut block = [0_u8; 16];
+            block[..4].copy_from_slice(&(i as u32).to_le_bytes());
+            block[4..].copy_from_slice(nonce);
+            half.copy_from_slice(&self.key_generating.encrypt(&block)[..8]);
+        }
+        let auth_key: [u8; 16] = keys[..16].try_into().unwrap();
+        let enc_key = &keys[16..16 + key_size];
+        Ok((
+            auth_key,
+            Aes::with_backend(enc_key, self.key_generating.backend())?,
+        ))
+    }
+
+    fn check_lengths(aad: &[u8], text_len: usize) -> Result<(), CryptoError> {
+        if aad.len() as u64 > MAX_TEXT_LEN || text_len as u64 > MAX_TEXT_LEN {
+            return Err(CryptoError::MessageTooLong);
+        }
+        Ok(())
+    }
+
+    fn tag(
+        auth_key: &[u8; 16],
+        aes: &Aes,
+        nonce: &[u8],
+        aad: &[u8],
+        plain_text: &[u8],
+    ) -> [u8; 16] {
+        let mut polyval = Polyval::new(auth_key);
+        polyval.update(aad);
+        polyval.pad();
+        polyval.update(plain_text);
+        polyval.pad();
+        let mut len_block = [0_u8; 16];
+        len_block[..8].copy_from_slice(&(8 * aad.len() as u64).to_le_bytes());
+        len_block[8..].copy_from_slice(&(8 * plain_text.len() as u64).to_le_bytes());
+        polyval.update(&len_block);
+        let mut s = polyval.finalize();
+        xor_in_place(&mut s[..12], nonce);
+        s[15] &= 0x7f;
+        aes.encrypt(&s)
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// XORs `data` with the keystream starting at the counter block
+    /// tag | 0x80 << 120, only the first 32 bits (little-endian) are
+    /// incremented.
+    //////////////////////////////////////////////////////////////////
+    fn apply_keystream(aes: &Aes, tag: &[u8; 16], data: &mut [u8]) {
+        let mut counter = *tag;
+        counter[15] |= 0x80;
+        let mut ctr = u32::from_le_bytes(counter[..4].try_into().unwrap());
+        let mut keystream = [0_u8; 16 * PARALLEL_BLOCKS];
+        for chunk in data.chunks_mut(keystream.len()) {
+            let len = chunk.len().div_ceil(16) * 16;
+            for block in keystream[..len].chunks_exact_mut(16) {
+                counter[..4].copy_from_slice(&ctr.to_le_bytes());
+                block.copy_from_slice(&counter);
+                ctr = ctr.wrapping_add(1);
+            }
+            aes.encrypt_blocks(&mut keystream[..len]);
+            xor_in_place(chunk, &keystream);
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts `buffer` in place.
+    ///# Arguments
+    ///* `nonce`: a 12 bytes nonce.
+    ///* `aad`: additional data that is authenticated but not encrypted.
+    ///* `buffer`: the plaintext, replaced by the ciphertext.
+    ///# Returns
+    /// The authentication tag.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt_detached(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<[u8; 16], CryptoError> {
+        AesGcmSiv::check_lengths(aad, buffer.len())?;
+        let (auth_key, aes) = self.derive_keys(nonce)?;
+        let tag = AesGcmSiv::tag(&auth_key, &aes, nonce, aad, buffer);
+        AesGcmSiv::apply_keystream(&aes, &tag, buffer);
+        Ok(tag)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts `buffer` in place and verifies the `tag`.
+    ///# Returns
+    /// `CryptoError::AuthenticationFailed` if the tag doesn't match,
+    /// in which case `buffer` is restored to the ciphertext.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt_detached(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), CryptoError> {
+        AesGcmSiv::check_lengths(aad, buffer.len())?;
+        let (auth_key, aes) = self.derive_keys(nonce)?;
+        let tag: [u8; 16] = tag
+            .try_into()
+            .map_err(|_| CryptoError::AuthenticationFailed)?;
+        AesGcmSiv::apply_keystream(&aes, &tag, buffer);
+        if !ct_eq(&AesGcmSiv::tag(&auth_key, &aes, nonce, aad, buffer), &tag) {
+            AesGcmSiv::apply_keystream(&aes, &tag, buffer);
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+}
+
+impl Aead for AesGcmSiv {
+    fn encrypt(&self, nonce: &[u8], aad: &[u8], plain_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut out = plain_text.to_vec();
+        let tag = self.encrypt_detached(nonce, aad, &mut out)?;
+        out.extend_from_slice(&tag);
+        Ok(out)
+    }
+
+    fn decrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        cipher_text: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        if cipher_text.len() < 16 {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        let (data, tag) = cipher_text.split_at(cipher_text.len() - 16);
+        let mut out = data.to_vec();
+        self.decrypt_detached(nonce, aad, &mut out, tag)?;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    // RFC 8452 Appendix C.1 and C.2, nonce 030000000000000000000000:
+    // (aad, plaintext, ciphertext || tag).
+    const VECTORS_128: [(&str, &str, &str); 16] = [
+        ("", "", "dc20e2d83f25705bb49e439eca56de25"),
+        (
+            "",
+            "0100000000000000",
+            "b5d839330ac7b786578782fff6013b815b287c22493a364c",
+        ),
+        (
+            "",
+            "010000000000000000000000",
+            "7323ea61d05932260047d942a4978db357391a0bc4fdec8b0d106639",
+        ),
+        (
+            "",
+            "01000000000000000000000000000000",
+            "743f7c8077ab25f8624e2e948579cf77303aaf90f6fe21199c6068577437a0c4",
+        ),
+        (
+            "",
+            "0100000000000000000000000000000002000000000000000000000000000000",
+            "84e07e62ba83a6585417245d7ec413a9fe427d6315c09b57ce45f2e3936a9445
+             1a8e45dcd4578c667cd86847bf6155ff",
+        ),
+        (
+            "",
+            "0100000000000000000000000000000002000000000000000000000000000000
+             03000000000000000000000000000000",
+            "3fd24ce1f5a67b75bf2351f181a475c7b800a5b4d3dcf70106b1eea82fa1d64d
+             f42bf7226122fa92e17a40eeaac1201b5e6e311dbf395d35b0fe39c2714388f8",
+        ),
+        (
+            "",
+            "0100000000000000000000000000000002000000000000000000000000000000
+             0300000000000000000000000000000004000000000000000000000000000000",
+            "2433668f1058190f6d43e360f4f35cd8e475127cfca7028ea8ab5c20f7ab2af0
+             2516a2bdcbc08d521be37ff28c152bba36697f25b4cd169c6590d1dd39566d3f
+             8a263dd317aa88d56bdf3936dba75bb8",
+        ),
+        (
+            "01",
+            "0200000000000000",
+            "1e6daba35669f4273b0a1a2560969cdf790d99759abd1508",
+        ),
+        (
+            "01",
+            "020000000000000000000000",
+            "296c7889fd99f41917f4462008299c5102745aaa3a0c469fad9e075a",
+        ),
+        (
+            "01",
+            "02000000000000000000000000000000",
+            "e2b0c5da79a901c1745f700525cb335b8f8936ec039e4e4bb97ebd8c4457441f",
+        ),
+        (
+            "01",
+            "0200000000000000000000000000000003000000000000000000000000000000",
+            "620048ef3c1e73e57e02bb8562c416a319e73e4caac8e96a1ecb2933145a1d71
+             e6af6a7f87287da059a71684ed3498e1",
+        ),
+        (
+            "01",
+            "0200000000000000000000000000000003000000000000000000000000000000
+             04000000000000000000000000000000",
+            "50c8303ea93925d64090d07bd109dfd9515a5a33431019c17d93465999a8b005
+             3201d723120a8562b838cdff25bf9d1e6a8cc3865f76897c2e4b245cf31c51f2",
+        ),
+        (
+            "01",
+            "0200000000000000000000000000000003000000000000000000000000000000
+             0400000000000000000000000000000005000000000000000000000000000000",
+            "2f5c64059db55ee0fb847ed513003746aca4e61c711b5de2e7a77ffd02da42fe
+             ec601910d3467bb8b36ebbaebce5fba30d36c95f48a3e7980f0e7ac299332a80
+             cdc46ae475563de037001ef84ae21744",
+        ),
+        (
+            "010000000000000000000000",
+            "02000000",
+            "a8fe3e8707eb1f84fb28f8cb73de8e99e2f48a14",
+        ),
+        (
+            "010000000000000000000000000000000200",
+            "0300000000000000000000000000000004000000",
+            "6bb0fecf5ded9b77f902c7d5da236a4391dd029724afc9805e976f451e6d87f6
+             fe106514",
+        ),
+        (
+            "0100000000000000000000000000000002000000",
+            "030000000000000000000000000000000400",
+            "44d0aaf6fb2f1f34add5e8064e83e12a2adabff9b2ef00fb47920cc72a0c0f13
+             b9fd",
+        ),
+    ];
+    const VECTORS_256: [(&str, &str, &str); 16] = [
+        ("", "", "07f5f4169bbf55a8400cd47ea6fd400f"),
+        (
+            "",
+            "0100000000000000",
+            "c2ef328e5c71c83b843122130f7364b761e0b97427e3df28",
+        ),
+        (
+            "",
+            "010000000000000000000000",
+            "9aab2aeb3faa0a34aea8e2b18ca50da9ae6559e48fd10f6e5c9ca17e",
+        ),
+        (
+            "",
+            "01000000000000000000000000000000",
+            "85a01b63025ba19b7fd3ddfc033b3e76c9eac6fa700942702e90862383c6c366",
+        ),
+        (
+            "",
+            "0100000000000000000000000000000002000000000000000000000000000000",
+            "4a6a9db4c8c6549201b9edb53006cba821ec9cf850948a7c86c68ac7539d027f
+             e819e63abcd020b006a976397632eb5d",
+        ),
+        (
+            "",
+            "0100000000000000000000000000000002000000000000000000000000000000
+             03000000000000000000000000000000",
+            "c00d121893a9fa603f48ccc1ca3c57ce7499245ea0046db16c53c7c66fe717e3
+             9cf6c748837b61f6ee3adcee17534ed5790bc96880a99ba804bd12c0e6a22cc4",
+        ),
+        (
+            "",
+            "0100000000000000000000000000000002000000000000000000000000000000
+             0300000000000000000000000000000004000000000000000000000000000000",
+            "c2d5160a1f8683834910acdafc41fbb1632d4a353e8b905ec9a5499ac34f96c7
+             e1049eb080883891a4db8caaa1f99dd004d80487540735234e3744512c6f90ce
+             112864c269fc0d9d88c61fa47e39aa08",
+        ),
+        (
+            "01",
+            "0200000000000000",
+            "1de22967237a813291213f267e3b452f02d01ae33e4ec854",
+        ),
+        (
+            "01",
+            "020000000000000000000000",
+            "163d6f9cc1b346cd453a2e4cc1a4a19ae800941ccdc57cc8413c277f",
+        ),
+        (
+            "01",
+            "02000000000000000000000000000000",
+            "c91545823cc24f17dbb0e9e807d5ec17b292d28ff61189e8e49f3875ef91aff7",
+        ),
+        (
+            "01",
+            "0200000000000000000000000000000003000000000000000000000000000000",
+            "07dad364bfc2b9da89116d7bef6daaaf6f255510aa654f920ac81b94e8bad365
+             aea1bad12702e1965604374aab96dbbc",
+        ),
+        (
+            "01",
+            "0200000000000000000000000000000003000000000000000000000000000000
+             04000000000000000000000000000000",
+            "c67a1f0f567a5198aa1fcc8e3f21314336f7f51ca8b1af61feac35a86416fa47
+             fbca3b5f749cdf564527f2314f42fe2503332742b228c647173616cfd44c54eb",
+        ),
+        (
+            "01",
+            "0200000000000000000000000000000003000000000000000000000000000000
+             0400000000000000000000000000000005000000000000000000000000000000",
+            "67fd45e126bfb9a79930c43aad2d36967d3f0e4d217c1e551f59727870beefc9
+             8cb933a8fce9de887b1e40799988db1fc3f91880ed405b2dd298318858467c89
+             5bde0285037c5de81e5b570a049b62a0",
+        ),
+        (
+            "010000000000000000000000",
+            "02000000",
+            "22b3f4cd1835e517741dfddccfa07fa4661b74cf",
+        ),
+        (
+            "010000000000000000000000000000000200",
+            "0300000000000000000000000000000004000000",
+            "43dd0163cdb48f9fe3212bf61b201976067f342bb879ad976d8242acc188ab59
+             cabfe307",
+        ),
+        (
+            "0100000000000000000000000000000002000000",
+            "030000000000000000000000000000000400",
+            "462401724b5ce6588d5a54aae5375513a075cfcdf5042112aa29685c912fc205
+             6543",
+        ),
+    ];
+
+    fn check(key: &str, nonce: &str, aad: &str, plain: &str, sealed: &str) {
+        let gcm_siv = AesGcmSiv::new(&hex(key)).unwrap();
+        let out = gcm_siv
+            .encrypt(&hex(nonce), &hex(aad), &hex(plain))
+            .unwrap();
+        assert_eq!(out, hex(sealed));
+        assert_eq!(
+            gcm_siv.decrypt(&hex(nonce), &hex(aad), &out).unwrap(),
+            hex(plain)
+        );
+    }
+
+    #[test]
+    fn test_gcm_siv_aes_128() {
+        for (aad, plain, sealed) in VECTORS_128 {
+            check(
+                "01000000000000000000000000000000",
+                "030000000000000000000000",
+                aad,
+                plain,
+                sealed,
+            );
+        }
+    }
+
+    #[test]
+    fn test_gcm_siv_aes_256() {
+        for (aad, plain, sealed) in VECTORS_256 {
+            check(
+                "0100000000000000000000000000000000000000000000000000000000000000",
+                "030000000000000000000000",
+                aad,
+                plain,
+                sealed,
+            );
+        }
+    }
+
+    #[test]
+    fn test_gcm_siv_counter_wrap() {
+        // RFC 8452 Appendix C.3, the 32-bit counter wraps around.
+        let zero_key = "0000000000000000000000000000000000000000000000000000000000000000";
+        check(
+            zero_key,
+            "000000000000000000000000",
+            "",
+            "000000000000000000000000000000004db923dc793ee6497c76dcc03a98e108",
+            "f3f80f2cf0cb2dd9c5984fcda908456cc537703b5ba70324a6793a7bf218d3ea
+             ffffffff000000000000000000000000",
+        );
+        check(
+            zero_key,
+            "000000000000000000000000",
+            "",
+            "eb3640277c7ffd1303c7a542d02d3e4c0000000000000000",
+            "18ce4f0b8cb4d0cac65fea8f79257b20888e53e72299e56dffffffff000000000000000000000000",
+        );
+    }
+
+    #[test]
+    fn test_gcm_siv_errors() {
+        assert_eq!(
+            AesGcmSiv::new(&[0; 24]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        let gcm_siv = AesGcmSiv::new(&[0; 16]).unwrap();
+        assert_eq!(
+            gcm_siv.encrypt(&[0; 16], b"", b"").err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+        let mut sealed = gcm_siv.encrypt(&[0; 12], b"aad", b"plain text").unwrap();
+        sealed[2] ^= 4;
+        assert_eq!(
+            gcm_siv.decrypt(&[0; 12], b"aad", &sealed).err(),
+            Some(CryptoError::AuthenticationFailed)
+        );
+        assert!(gcm_siv.decrypt(&[0; 12], b"aad", &sealed[..15]).is_err());
+        // A repeated nonce gives the same ciphertext for the same message only.
+        let a = gcm_siv.encrypt(&[0; 12], b"", b"message a").unwrap();
+        let b = gcm_siv.encrypt(&[0; 12], b"", b"message b").unwrap();
+        assert_eq!(a, gcm_siv.encrypt(&[0; 12], b"", b"message a").unwrap());
+        assert_ne!(a[..8], b[..8]);
+    }
+}
@@ -0,0 +1,356 @@
+//#######################################################################o
+// The key wrapping modes protect the confidentiality and the integrity  |
+// of key material. The data is split in semiblocks (half blocks) and    |
+// the wrapping function W runs 6 passes of the block cipher over them,  |
+// starting from an integrity check value (ICV) that unwrapping has to   |
+// find again.                                                           |
+// * KW uses a 128-bit cipher (AES) and needs a multiple of 8 bytes.     |
+// * KWP prepends the data length and pads it, so any length is wrapped. |
+// * TKW is KW over the 64-bit Triple DES with 32-bit semiblocks.        |
+//                                                                       |
+// @Refrences:                                                           |
+// NIST SP 800-38F: https://doi.org/10.6028/NIST.SP.800-38F              |
+// RFC 3394: https://www.rfc-editor.org/rfc/rfc3394                      |
+// RFC 5649: https://www.rfc-editor.org/rfc/rfc5649                      |
+//#######################################################################o
+
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::util::ct_eq;
+
+// ICV1 of KW, truncated to the semiblock size for TKW.
+const ICV_KW: [u8; 8] = [0xa6; 8];
+// ICV2 of KWP, followed by the 32-bit data length.
+const ICV_KWP: [u8; 4] = [0xa6, 0x59, 0x59, 0xa6];
+// Largest number of semiblocks of TKW, 6n must fit in 32 bits.
+const MAX_TKW_SEMIBLOCKS: usize = 1 << 28;
+
+////////////////////////////////////////////////////////////////////////////
+/// `KeyWrap` wraps keys with KW and KWP over a 128-bit block cipher, or
+/// with TKW over Triple DES.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::block::mode::kw::KeyWrap;
+///
+/// let kw = KeyWrap::new(Aes::new(&[0x42; 32]).unwrap()).unwrap();
+/// let wrapped = kw.wrap(&[7; 32]).unwrap();
+/// assert_eq!(wrapped.len(), 40);
+/// assert_eq!(kw.unwrap(&wrapped).unwrap(), [7; 32]);
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct KeyWrap<C: BlockCipher> {
+    cipher: C,
+}
+
+impl<C: BlockCipher> KeyWrap<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a key wrapping instance with the key encryption key
+    /// `cipher`, a 64-bit block cipher gives TKW.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidBlockSize` if the cipher
+    /// doesn't use 64-bit or 128-bit blocks.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(cipher: C) -> Result<KeyWrap<C>, CryptoError> {
+        if C::BLOCK_SIZE != 8 && C::BLOCK_SIZE != 16 {
+            return Err(CryptoError::InvalidBlockSize);
+        }
+        Ok(KeyWrap { cipher })
+    }
+
+    /// Size of a semiblock in bytes.
+    fn semiblock(&self) -> usize {
+        C::BLOCK_SIZE / 2
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// The wrapping function W, `data` holds the ICV semiblock
+    /// followed by at least two semiblocks and is wrapped in place.
+    //////////////////////////////////////////////////////////////////
+    fn w(&self, data: &mut [u8]) {
+        let sb = self.semiblock();
+        let n = data.len() / sb - 1;
+        let mut block = [0_u8; 16];
+        let block = &mut block[..C::BLOCK_SIZE];
+        block[..sb].copy_from_slice(&data[..sb]);
+        for j in 0..6 {
+            for i in 1..=n {
+                block[sb..].copy_from_slice(&data[i * sb..(i + 1) * sb]);
+                self.cipher.encrypt_block(block);
+                let t = ((n * j + i) as u64).to_be_bytes();
+                for (a, t) in block[..sb].iter_mut().zip(&t[8 - sb..]) {
+                    *a ^= t;
+                }
+                data[i * sb..(i + 1) * sb].copy_from_slice(&block[sb..]);
+            }
+        }
+        data[..sb].copy_from_slice(&block[..sb]);
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// The unwrapping function W^-1, the recovered ICV is left in the
+    /// first semiblock of `data`.
+    //////////////////////////////////////////////////////////////////
+    fn w_inverse(&self, data: &mut [u8]) {
+        let sb = self.semiblock();
+        let n = data.len() / sb - 1;
+        let mut block = [0_u8; 16];
+        let block = &mut block[..C::BLOCK_SIZE];
+        block[..sb].copy_from_slice(&data[..sb]);
+        for j in (0..6).rev() {
+            for i in (1..=n).rev() {
+                let t = ((n * j + i) as u64).to_be_bytes();
+                for (a, t) in block[..sb].iter_mut().zip(&t[8 - sb..]) {
+                    *a ^= t;
+                }
+                block[sb..].copy_from_slice(&data[i * sb..(i + 1) * sb]);
+                self.cipher.decrypt_block(block);
+                data[i * sb..(i + 1) * sb].copy_from_slice(&block[sb..]);
+            }
+        }
+        data[..sb].copy_from_slice(&block[..sb]);
+    }
+
+    fn check_semiblocks(&self, semiblocks: usize) -> Result<(), CryptoError> {
+        if C::BLOCK_SIZE == 8 && semiblocks > MAX_TKW_SEMIBLOCKS {
+            return Err(CryptoError::MessageTooLong);
+        }
+        Ok(())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Wraps `key_data` with KW (or TKW).
+    ///# Returns
+    /// The wrapped key, one semiblock longer than `key_data`, or
+    /// `CryptoError::InvalidDataLength` if `key_data` isn't made of at
+    /// least two semiblocks.
+    ////////////////////////////////////////////////////////////////////
+    pub fn wrap(&self, key_data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let sb = self.semiblock();
+        if key_data.len() < 2 * sb || !key_data.len().is_multiple_of(sb) {
+            return Err(CryptoError::InvalidDataLength);
+        }
+        self.check_semiblocks(key_data.len() / sb)?;
+        let mut out = ICV_KW[..sb].to_vec();
+        out.extend_from_slice(key_data);
+        self.w(&mut out);
+        Ok(out)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Unwraps a key wrapped by `wrap`.
+    ///# Returns
+    /// The key data, `CryptoError::InvalidDataLength` if the length
+    /// isn't valid or `CryptoError::AuthenticationFailed` if the
+    /// integrity check fails.
+    ////////////////////////////////////////////////////////////////////
+    pub fn unwrap(&self, wrapped: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let sb = self.semiblock();
+        if wrapped.len() < 3 * sb || !wrapped.len().is_multiple_of(sb) {
+            return Err(CryptoError::InvalidDataLength);
+        }
+        self.check_semiblocks(wrapped.len() / sb - 1)?;
+        let mut data = wrapped.to_vec();
+        self.w_inverse(&mut data);
+        if !ct_eq(&data[..sb], &ICV_KW[..sb]) {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        Ok(data.split_off(sb))
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Wraps `key_data` of any length with KWP.
+    ///# Returns
+    /// The wrapped key or `CryptoError::InvalidBlockSize` for a 64-bit
+    /// cipher (KWP isn't defined for TKW), `InvalidDataLength` for empty
+    /// data and `MessageTooLong` above 2^32 - 1 bytes.
+    ////////////////////////////////////////////////////////////////////
+    pub fn wrap_with_padding(&self, key_data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if C::BLOCK_SIZE != 16 {
+            return Err(CryptoError::InvalidBlockSize);
+        }
+        if key_data.is_empty() {
+            return Err(CryptoError::InvalidDataLength);
+        }
+        let mli = u32::try_from(key_data.len()).map_err(|_| CryptoError::MessageTooLong)?;
+        let mut out = ICV_KWP.to_vec();
+        out.extend_from_slice(&mli.to_be_bytes());
+        out.extend_from_slice(key_data);
+        out.resize(8 + key_data.len().div_ceil(8) * 8, 0);
+        if out.len() == 16 {
+            // A single semiblock of data is encrypted as one block.
+            self.cipher.encrypt_block(&mut out);
+        } else {
+            self.w(&mut out);
+        }
+        Ok(out)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Unwraps a key wrapped by `wrap_with_padding`.
+    ///# Returns
+    /// The key data, `CryptoError::InvalidDataLength` if the length
+    /// isn't valid or `CryptoError::AuthenticationFailed` if the ICV,
+    /// the length or the padding doesn't check out.
+    ////////////////////////////////////////////////////////////////////
+    pub fn unwrap_with_padding(&self, wrapped: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if C::BLOCK_SIZE != 16 {
+            return Err(CryptoError::InvalidBlockSize);
+        }
+        if wrapped.len() < 16 || !wrapped.len().is_multiple_of(8) {
+            return Err(CryptoError::InvalidDataLength);
+        }
+        let mut data = wrapped.to_vec();
+        if data.len() == 16 {
+            self.cipher.decrypt_block(&mut data);
+        } else {
+            self.w_inverse(&mut data);
+        }
+        let padded_len = data.len() - 8;
+        let mli = u32::from_be_bytes(data[4..8].try_into().unwrap()) as usize;
+        let valid_len = mli <= padded_len && mli + 8 > padded_len;
+        let padding = &data[8 + mli.min(padded_len)..];
+        let valid_padding = padding.iter().fold(0, |acc, b| acc | b) == 0;
+        if !(ct_eq(&data[..4], &ICV_KWP) & valid_len & valid_padding) {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        data.truncate(8 + mli);
+        Ok(data.split_off(8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::block::triple_des::TripleDes;
+    use crate::test_utils::hex;
+
+    const KEY_DATA: &str = "00112233445566778899aabbccddeeff000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn test_kw_rfc3394() {
+        // RFC 3394 section 4, (KEK length, key data length, wrapped key).
+        let kek: Vec<u8> = (0..32).collect();
+        let key_data = hex(KEY_DATA);
+        for (kek_len, data_len, wrapped) in [
+            (16, 16, "1fa68b0a8112b447aef34bd8fb5a7b829d3e862371d2cfe5"),
+            (24, 16, "96778b25ae6ca435f92b5b97c050aed2468ab8a17ad84e5d"),
+            (32, 16, "64e8c3f9ce0f5ba263e9777905818a2a93c8191e7d6e8ae7"),
+            (
+                24,
+                24,
+                "031d33264e15d33268f24ec260743edce1c6c7ddee725a936ba814915c6762d2",
+            ),
+            (
+                32,
+                24,
+                "a8f9bc1612c68b3ff6e6f4fbe30e71e4769c8b80a32cb8958cd5d17d6b254da1",
+            ),
+            (
+                32,
+                32,
+                "28c9f404c4b810f4cbccb35cfb87f8263f5786e2d80ed326cbc7f0e71a99f43b
+                 fb988b9b7a02dd21",
+            ),
+        ] {
+            let kw = KeyWrap::new(Aes::new(&kek[..kek_len]).unwrap()).unwrap();
+            assert_eq!(kw.wrap(&key_data[..data_len]).unwrap(), hex(wrapped));
+            assert_eq!(kw.unwrap(&hex(wrapped)).unwrap(), key_data[..data_len]);
+        }
+    }
+
+    #[test]
+    fn test_kwp_rfc5649() {
+        // RFC 5649 section 6.
+        let kek = hex("5840df6e29b02af1ab493b705bf16ea1ae8338f4dcc176a8");
+        let kw = KeyWrap::new(Aes::new(&kek).unwrap()).unwrap();
+        for (key_data, wrapped) in [
+            (
+                "c37b7e6492584340bed12207808941155068f738",
+                "138bdeaa9b8fa7fc61f97742e72248ee5ae6ae5360d1ae6a5f54f373fa543b6a",
+            ),
+            ("466f7250617369", "afbeb0f07dfbf5419200f2ccb50bb24f"),
+        ] {
+            assert_eq!(kw.wrap_with_padding(&hex(key_data)).unwrap(), hex(wrapped));
+            assert_eq!(
+                kw.unwrap_with_padding(&hex(wrapped)).unwrap(),
+                hex(key_data)
+            );
+        }
+        for len in 1..=33 {
+            let key_data = vec![0x5a; len];
+            let wrapped = kw.wrap_with_padding(&key_data).unwrap();
+            assert_eq!(wrapped.len(), 8 + len.div_ceil(8) * 8);
+            assert_eq!(kw.unwrap_with_padding(&wrapped).unwrap(), key_data);
+        }
+    }
+
+    #[test]
+    fn test_tkw() {
+        // Checked against a model of SP 800-38F TKW over the Triple DES
+        // of pyca/cryptography.
+        let kek = hex("0123456789abcdef23456789abcdef01456789abcdef0123");
+        let tkw = KeyWrap::new(TripleDes::new(&kek).unwrap()).unwrap();
+        for (key_data, wrapped) in [
+            ("0011223344556677", "5f72f34727157ee1caa002b9"),
+            ("000102030405060708090a0b", "5d97520df2ebf8ced38d9c0b285d886e"),
+            (
+                "00112233445566778899aabbccddeeff",
+                "c6d37d2683d1dc27efd2391e41fca7b769bea7f6",
+            ),
+        ] {
+            assert_eq!(tkw.wrap(&hex(key_data)).unwrap(), hex(wrapped));
+            assert_eq!(tkw.unwrap(&hex(wrapped)).unwrap(), hex(key_data));
+        }
+        assert_eq!(
+            tkw.wrap_with_padding(&[0; 8]).err(),
+            Some(CryptoError::InvalidBlockSize)
+        );
+    }
+
+    #[test]
+    fn test_unwrap_integrity() {
+        let kw = KeyWrap::new(Aes::new(&[1; 16]).unwrap()).unwrap();
+        let mut wrapped = kw.wrap(&[2; 24]).unwrap();
+        wrapped[10] ^= 1;
+        assert_eq!(kw.unwrap(&wrapped), Err(CryptoError::AuthenticationFailed));
+        // A KW wrapped key isn't accepted by KWP and the other way around.
+        wrapped[10] ^= 1;
+        assert_eq!(
+            kw.unwrap_with_padding(&wrapped),
+            Err(CryptoError::AuthenticationFailed)
+        );
+        let wrapped = kw.wrap_with_padding(&[2; 24]).unwrap();
+        assert_eq!(kw.unwrap(&wrapped), Err(CryptoError::AuthenticationFailed));
+        let mut wrapped = kw.wrap_with_padding(&[3; 5]).unwrap();
+        wrapped[0] ^= 1;
+        assert_eq!(
+            kw.unwrap_with_padding(&wrapped),
+            Err(CryptoError::AuthenticationFailed)
+        );
+
+        let kek = hex("0123456789abcdef23456789abcdef01456789abcdef0123");
+        let tkw = KeyWrap::new(TripleDes::new(&kek).unwrap()).unwrap();
+        let mut wrapped = tkw.wrap(&[4; 16]).unwrap();
+        wrapped[19] ^= 0x10;
+        assert_eq!(tkw.unwrap(&wrapped), Err(CryptoError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_wrap_lengths() {
+        let kw = KeyWrap::new(Aes::new(&[1; 16]).unwrap()).unwrap();
+        assert_eq!(kw.wrap(&[0; 8]), Err(CryptoError::InvalidDataLength));
+        assert_eq!(kw.wrap(&[0; 20]), Err(CryptoError::InvalidDataLength));
+        assert_eq!(kw.unwrap(&[0; 16]), Err(CryptoError::InvalidDataLength));
+        assert_eq!(
+            kw.wrap_with_padding(&[]),
+            Err(CryptoError::InvalidDataLength)
+        );
+        assert_eq!(
+            kw.unwrap_with_padding(&[0; 20]),
+            Err(CryptoError::InvalidDataLength)
+        );
+    }
+}
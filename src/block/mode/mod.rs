@@ -0,0 +1,142 @@
+pub mod cbc;
+pub mod ccm;
+pub mod ctr;
+pub mod eax;
+pub mod ecb;
+pub mod gcm;
+pub mod gcm_siv;
+pub mod kw;
+pub mod ocb;
+pub mod ofb;
+pub mod polyval;
+pub mod siv;
+pub mod xts;
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+use crate::util::{ct_eq, xor_in_place};
+
+////////////////////////////////////////////////////////////////////////////
+/// `Aead` is the interface of the authenticated encryption modes, the
+/// ciphertext returned by `encrypt` is followed by the authentication
+/// tag and `decrypt` expects the same layout (SIV is the exception, its
+/// synthetic IV comes first as in RFC 5297).
+////////////////////////////////////////////////////////////////////////////
+pub trait Aead {
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts and authenticates `plain_text` and authenticates `aad`.
+    ///# Returns
+    /// The ciphertext followed by the tag, or an error if the nonce
+    /// or the data length isn't supported.
+    ////////////////////////////////////////////////////////////////////
+    fn encrypt(&self, nonce: &[u8], aad: &[u8], plain_text: &[u8]) -> Result<Vec<u8>, CryptoError>;
+
+    ////////////////////////////////////////////////////////////////////
+    /// Verifies and decrypts a ciphertext produced by `encrypt`.
+    ///# Returns
+    /// The plaintext or `CryptoError::AuthenticationFailed` if the tag
+    /// doesn't match, in which case no plaintext is released.
+    ////////////////////////////////////////////////////////////////////
+    fn decrypt(&self, nonce: &[u8], aad: &[u8], cipher_text: &[u8])
+        -> Result<Vec<u8>, CryptoError>;
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Increments the big-endian integer `counter` modulo 2^(8*len).
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn increment_be(counter: &mut [u8]) {
+    for b in counter.iter_mut().rev() {
+        *b = b.wrapping_add(1);
+        if *b != 0 {
+            break;
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Adds `n` to the big-endian integer `counter` modulo 2^(8*len).
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn add_be(counter: &mut [u8], n: u64) {
+    let mut carry = n as u128;
+    for b in counter.iter_mut().rev() {
+        if carry == 0 {
+            break;
+        }
+        carry += *b as u128;
+        *b = carry as u8;
+        carry >>= 8;
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// XORs `data` with the counter mode keystream E(counter),
+/// E(counter + 1), ... and leaves `counter` on the next unused value.
+///# Arguments
+///* `cipher`: the keyed block cipher.
+///* `counter`: the first counter block, `BLOCK_SIZE` bytes long.
+///* `counter_len`: the number of trailing bytes of the block that are
+///  incremented, the leading bytes stay fixed.
+///* `data`: the data to encrypt or decrypt in place.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn apply_ctr<C: BlockCipher>(
+    cipher: &C,
+    counter: &mut [u8],
+    counter_len: usize,
+    data: &mut [u8],
+) {
+    let block_size = C::BLOCK_SIZE;
+    let split = block_size - counter_len;
+    // Several counter blocks are encrypted at once for the ciphers that
+    // process blocks in parallel.
+    let mut keystream = [0_u8; 128];
+    debug_assert!(keystream.len().is_multiple_of(block_size));
+    for chunk in data.chunks_mut(keystream.len()) {
+        let len = chunk.len().div_ceil(block_size) * block_size;
+        for block in keystream[..len].chunks_exact_mut(block_size) {
+            block.copy_from_slice(counter);
+            increment_be(&mut counter[split..]);
+        }
+        cipher.encrypt_blocks(&mut keystream[..len]);
+        xor_in_place(chunk, &keystream);
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Pads `data` to a multiple of `block_size` with PKCS#7 (RFC 5652):
+/// n bytes of value n are appended, a full block if already aligned.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn pkcs7_pad(data: &[u8], block_size: usize) -> Vec<u8> {
+    let pad_len = block_size - data.len() % block_size;
+    let mut padded = Vec::with_capacity(data.len() + pad_len);
+    padded.extend_from_slice(data);
+    padded.resize(data.len() + pad_len, pad_len as u8);
+    padded
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Returns the length of `data` without its PKCS#7 padding.
+///# Returns
+/// The unpadded length or `CryptoError::InvalidPadding`, the whole
+/// last block is checked so that the time doesn't depend on where
+/// the padding is wrong.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn pkcs7_unpad(data: &[u8], block_size: usize) -> Result<usize, CryptoError> {
+    if data.is_empty() || !data.len().is_multiple_of(block_size) {
+        return Err(CryptoError::InvalidPadding);
+    }
+    let last = &data[data.len() - block_size..];
+    let pad_len = last[block_size - 1] as usize;
+    let mut expected = vec![0_u8; block_size];
+    for (i, e) in expected.iter_mut().enumerate() {
+        // Only the trailing `pad_len` bytes are compared.
+        *e = if i >= block_size.saturating_sub(pad_len) {
+            pad_len as u8
+        } else {
+            last[i]
+        };
+    }
+    if pad_len == 0 || pad_len > block_size || !ct_eq(last, &expected) {
+        return Err(CryptoError::InvalidPadding);
+    }
+    Ok(data.len() - pad_len)
+}
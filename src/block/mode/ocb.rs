@@ -0,0 +1,435 @@
+//#######################################################################o
+// OCB3 is a single pass authenticated encryption mode: each block is    |
+// encrypted as C_i = E_K(P_i ^ Offset_i) ^ Offset_i, the offsets being  |
+// derived from the nonce and the key dependent values L_i, and the tag  |
+// is the encryption of the checksum (xor of the plaintext blocks)       |
+// xored with a PMAC-like hash of the additional data.                   |
+// One block cipher call per block makes it about as fast as CTR mode.   |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 7253: https://www.rfc-editor.org/rfc/rfc7253                      |
+//#######################################################################o
+
+use super::Aead;
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::mac::cmac::dbl;
+use crate::util::{ct_eq, xor_in_place};
+
+// Number of precomputed L_i, one per possible value of ntz(i) for a
+// 64-bit block index i.
+const L_TABLE_SIZE: usize = 64;
+// Number of blocks encrypted together.
+const PARALLEL_BLOCKS: usize = 8;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Ocb` is the OCB3 mode over a 128-bit block cipher.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::block::mode::ocb::Ocb;
+/// use cryptolib::block::mode::Aead;
+///
+/// let ocb = Ocb::new(Aes::new(&[0x42; 16]).unwrap(), 16).unwrap();
+/// let sealed = ocb.encrypt(&[0; 12], b"header", b"secret").unwrap();
+/// assert_eq!(ocb.decrypt(&[0; 12], b"header", &sealed).unwrap(), b"secret");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Ocb<C: BlockCipher> {
+    cipher: C,
+    tag_len: usize,
+    l_star: [u8; 16],
+    l_dollar: [u8; 16],
+    l: [[u8; 16]; L_TABLE_SIZE],
+}
+
+impl<C: BlockCipher> Ocb<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an OCB3 instance.
+    ///# Arguments
+    ///* `cipher`: a keyed 128-bit block cipher.
+    ///* `tag_len`: the tag length in bytes, from 1 to 16.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidBlockSize`/`InvalidTagLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(cipher: C, tag_len: usize) -> Result<Ocb<C>, CryptoError> {
+        if C::BLOCK_SIZE != 16 {
+            return Err(CryptoError::InvalidBlockSize);
+        }
+        if !(1..=16).contains(&tag_len) {
+            return Err(CryptoError::InvalidTagLength);
+        }
+        let mut l_star = [0_u8; 16];
+        cipher.encrypt_block(&mut l_star);
+        let l_dollar = dbl(&l_star);
+        let mut l = [[0_u8; 16]; L_TABLE_SIZE];
+        l[0] = dbl(&l_dollar);
+        for i in 1..L_TABLE_SIZE {
+            l[i] = dbl(&l[i - 1]);
+        }
+        Ok(Ocb {
+            cipher,
+            tag_len,
+            l_star,
+            l_dollar,
+            l,
+        })
+    }
+
+    /// Returns the tag length in bytes.
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    /// Returns L_ntz(i), the value xored into the offset of block i.
+    fn l_ntz(&self, i: u64) -> [u8; 16] {
+        self.l[i.trailing_zeros() as usize]
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Derives Offset_0 from the nonce: Ktop = E(nonce block with the
+    /// bottom 6 bits cleared), Stretch = Ktop || (Ktop[0..8] ^
+    /// Ktop[1..9]) and the offset is Stretch[bottom..bottom + 128].
+    //////////////////////////////////////////////////////////////////
+    fn initial_offset(&self, nonce: &[u8]) -> Result<[u8; 16], CryptoError> {
+        if nonce.is_empty() || nonce.len() > 15 {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        let mut block = [0_u8; 16];
+        block[0] = (((self.tag_len * 8) % 128) << 1) as u8;
+        block[15 - nonce.len()] |= 1;
+        block[16 - nonce.len()..].copy_from_slice(nonce);
+        let bottom = (block[15] & 0x3f) as usize;
+        block[15] &= 0xc0;
+        self.cipher.encrypt_block(&mut block);
+        let mut stretch = [0_u8; 25];
+        stretch[..16].copy_from_slice(&block);
+        for i in 0..8 {
+            stretch[16 + i] = block[i] ^ block[i + 1];
+        }
+        let (byte, bit) = (bottom / 8, bottom % 8);
+        let mut offset = [0_u8; 16];
+        for (i, o) in offset.iter_mut().enumerate() {
+            // The extra zero byte of `stretch` keeps the shift in bounds.
+            let pair = u16::from_be_bytes([stretch[byte + i], stretch[byte + i + 1]]);
+            *o = (pair >> (8 - bit)) as u8;
+        }
+        Ok(offset)
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Computes HASH(K, A), a PMAC-like sum of the additional data.
+    //////////////////////////////////////////////////////////////////
+    fn hash(&self, aad: &[u8]) -> [u8; 16] {
+        let mut sum = [0_u8; 16];
+        let mut offset = [0_u8; 16];
+        let mut blocks = [0_u8; 16 * PARALLEL_BLOCKS];
+        let mut chunks = aad.chunks_exact(16 * PARALLEL_BLOCKS);
+        let mut i = 0;
+        for chunk in chunks.by_ref() {
+            blocks.copy_from_slice(chunk);
+            for block in blocks.chunks_exact_mut(16) {
+                i += 1;
+                xor_in_place(&mut offset, &self.l_ntz(i));
+                xor_in_place(block, &offset);
+            }
+            self.cipher.encrypt_blocks(&mut blocks);
+            for block in blocks.chunks_exact(16) {
+                xor_in_place(&mut sum, block);
+            }
+        }
+        let mut rest = chunks.remainder().chunks_exact(16);
+        for chunk in rest.by_ref() {
+            i += 1;
+            xor_in_place(&mut offset, &self.l_ntz(i));
+            let mut block: [u8; 16] = chunk.try_into().unwrap();
+            xor_in_place(&mut block, &offset);
+            self.cipher.encrypt_block(&mut block);
+            xor_in_place(&mut sum, &block);
+        }
+        let last = rest.remainder();
+        if !last.is_empty() {
+            xor_in_place(&mut offset, &self.l_star);
+            let mut block = [0_u8; 16];
+            block[..last.len()].copy_from_slice(last);
+            block[last.len()] = 0x80;
+            xor_in_place(&mut block, &offset);
+            self.cipher.encrypt_block(&mut block);
+            xor_in_place(&mut sum, &block);
+        }
+        sum
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Encrypts or decrypts `buffer` in place and returns the tag,
+    /// the checksum being taken over the plaintext.
+    //////////////////////////////////////////////////////////////////
+    fn process(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+        encrypt: bool,
+    ) -> Result<[u8; 16], CryptoError> {
+        let mut offset = self.initial_offset(nonce)?;
+        let mut checksum = [0_u8; 16];
+        let mut offsets = [0_u8; 16 * PARALLEL_BLOCKS];
+        let full = buffer.len() / 16 * 16;
+        let (blocks, last) = buffer.split_at_mut(full);
+        let mut i = 0;
+        for chunk in blocks.chunks_mut(16 * PARALLEL_BLOCKS) {
+            let offsets = &mut offsets[..chunk.len()];
+            for o in offsets.chunks_exact_mut(16) {
+                i += 1;
+                xor_in_place(&mut offset, &self.l_ntz(i));
+                o.copy_from_slice(&offset);
+            }
+            if encrypt {
+                for block in chunk.chunks_exact(16) {
+                    xor_in_place(&mut checksum, block);
+                }
+            }
+            xor_in_place(chunk, offsets);
+            if encrypt {
+                self.cipher.encrypt_blocks(chunk);
+            } else {
+                self.cipher.decrypt_blocks(chunk);
+            }
+            xor_in_place(chunk, offsets);
+            if !encrypt {
+                for block in chunk.chunks_exact(16) {
+                    xor_in_place(&mut checksum, block);
+                }
+            }
+        }
+        if !last.is_empty() {
+            xor_in_place(&mut offset, &self.l_star);
+            let mut pad = offset;
+            self.cipher.encrypt_block(&mut pad);
+            let mut padded = [0_u8; 16];
+            if encrypt {
+                padded[..last.len()].copy_from_slice(last);
+            }
+            xor_in_place(last, &pad);
+            if !encrypt {
+                padded[..last.len()].copy_from_slice(last);
+            }
+            padded[last.len()] = 0x80;
+            xor_in_place(&mut checksum, &padded);
+        }
+        let mut tag = checksum;
+        xor_in_place(&mut tag, &offset);
+        xor_in_place(&mut tag, &self.l_dollar);
+        self.cipher.encrypt_block(&mut tag);
+        xor_in_place(&mut tag, &self.hash(aad));
+        Ok(tag)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts `buffer` in place.
+    ///# Arguments
+    ///* `nonce`: a nonce of 1 to 15 bytes (12 is recommended), never
+    ///  reused with a key.
+    ///* `aad`: additional data that is authenticated but not encrypted.
+    ///* `buffer`: the plaintext, replaced by the ciphertext.
+    ///# Returns
+    /// The authentication tag.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt_detached(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let tag = self.process(nonce, aad, buffer, true)?;
+        Ok(tag[..self.tag_len].to_vec())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts `buffer` in place and verifies the `tag`.
+    ///# Returns
+    /// `CryptoError::AuthenticationFailed` if the tag doesn't match,
+    /// in which case `buffer` is restored to the ciphertext.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt_detached(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), CryptoError> {
+        let expected = self.process(nonce, aad, buffer, false)?;
+        if !ct_eq(&expected[..self.tag_len], tag) {
+            self.process(nonce, aad, buffer, true)?;
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+}
+
+impl<C: BlockCipher> Aead for Ocb<C> {
+    fn encrypt(&self, nonce: &[u8], aad: &[u8], plain_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut out = plain_text.to_vec();
+        let tag = self.encrypt_detached(nonce, aad, &mut out)?;
+        out.extend_from_slice(&tag);
+        Ok(out)
+    }
+
+    fn decrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        cipher_text: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        if cipher_text.len() < self.tag_len {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        let (data, tag) = cipher_text.split_at(cipher_text.len() - self.tag_len);
+        let mut out = data.to_vec();
+        self.decrypt_detached(nonce, aad, &mut out, tag)?;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_ocb_sample_results() {
+        // RFC 7253 Appendix A, nonce BBAA998877665544332211xx:
+        // (aad length, plaintext length, ciphertext || tag).
+        let ocb = Ocb::new(
+            Aes::new(&hex("000102030405060708090a0b0c0d0e0f")).unwrap(),
+            16,
+        )
+        .unwrap();
+        let data: Vec<u8> = (0..40).collect();
+        let samples = [
+            (0, 0, "785407bfffc8ad9edcc5520ac9111ee6"),
+            (8, 8, "6820b3657b6f615a5725bda0d3b4eb3a257c9af1f8f03009"),
+            (8, 0, "81017f8203f081277152fade694a0a00"),
+            (0, 8, "45dd69f8f5aae72414054cd1f35d82760b2cd00d2f99bfa9"),
+            (16, 16, "571d535b60b277188be5147170a9a22c3ad7a4ff3835b8c5701c1ccec8fc3358"),
+            (16, 0, "8cf761b6902ef764462ad86498ca6b97"),
+            (0, 16, "5ce88ec2e0692706a915c00aeb8b2396f40e1c743f52436bdf06d8fa1eca343d"),
+            (24, 24, "1ca2207308c87c010756104d8840ce1952f09673a448a122c92c62241051f57356d7f3c90bb0e07f"),
+            (24, 0, "6dc225a071fc1b9f7c69f93b0f1e10de"),
+            (0, 24, "221bd0de7fa6fe993eccd769460a0af2d6cded0c395b1c3ce725f32494b9f914d85c0b1eb38357ff"),
+            (32, 32, "bd6f6c496201c69296c11efd138a467abd3c707924b964deaffc40319af5a48540fbba186c5553c68ad9f592a79a4240"),
+            (32, 0, "fe80690bee8a485d11f32965bc9d2a32"),
+            (0, 32, "2942bfc773bda23cabc6acfd9bfd5835bd300f0973792ef46040c53f1432bcdfb5e1dde3bc18a5f840b52e653444d5df"),
+            (40, 40, "d5ca91748410c1751ff8a2f618255b68a0a12e093ff454606e59f9c1d0ddc54b65e8628e568bad7aed07ba06a4a69483a7035490c5769e60"),
+            (40, 0, "c5cd9d1850c141e358649994ee701b68"),
+            (0, 40, "4412923493c57d5de0d700f753cce0d1d2d95060122e9f15a5ddbfc5787e50b5cc55ee507bcb084e479ad363ac366b95a98ca5f3000b1479"),
+        ];
+        for (i, (aad_len, plain_len, sealed)) in samples.into_iter().enumerate() {
+            let mut nonce = hex("bbaa99887766554433221100");
+            nonce[11] = i as u8;
+            let out = ocb
+                .encrypt(&nonce, &data[..aad_len], &data[..plain_len])
+                .unwrap();
+            assert_eq!(out, hex(sealed), "sample {}", i + 1);
+            assert_eq!(
+                ocb.decrypt(&nonce, &data[..aad_len], &out).unwrap(),
+                data[..plain_len]
+            );
+        }
+    }
+
+    #[test]
+    fn test_ocb_taglen_96() {
+        // RFC 7253 Appendix A, last sample with a 96-bit tag.
+        let ocb = Ocb::new(
+            Aes::new(&hex("0f0e0d0c0b0a09080706050403020100")).unwrap(),
+            12,
+        )
+        .unwrap();
+        let data: Vec<u8> = (0..40).collect();
+        let sealed = ocb
+            .encrypt(&hex("bbaa9988776655443322110d"), &data, &data)
+            .unwrap();
+        assert_eq!(
+            sealed,
+            hex(
+                "1792a4e31e0755fb03e31b22116e6c2ddf9efd6e33d536f1a0124b0a55bae884
+                 ed93481529c76b6ad0c515f4d1cdd4fdac4f02aa"
+            )
+        );
+    }
+
+    #[test]
+    fn test_ocb_iterated() {
+        // RFC 7253 Appendix A, iterated over 128 messages per key and tag
+        // length.
+        for (key_len, tag_len, output) in [
+            (16, 16, "67e944d23256c5e0b6c61fa22fdf1ea2"),
+            (24, 16, "f673f2c3e7174aae7bae986ca9f29e17"),
+            (32, 16, "d90eb8e9c977c88b79dd793d7ffa161c"),
+            (16, 12, "77a3d8e73589158d25d01209"),
+            (24, 12, "05d56ead2752c86be6932c5e"),
+            (32, 12, "5458359ac23b0cba9e6330dd"),
+            (16, 8, "192c9b7bd90ba06a"),
+            (24, 8, "0066bc6e0ef34e24"),
+            (32, 8, "7d4ea5d445501cbe"),
+        ] {
+            let mut key = vec![0_u8; key_len];
+            key[key_len - 1] = (tag_len * 8) as u8;
+            let ocb = Ocb::new(Aes::new(&key).unwrap(), tag_len).unwrap();
+            let nonce = |n: u32| {
+                let mut nonce = [0_u8; 12];
+                nonce[8..].copy_from_slice(&n.to_be_bytes());
+                nonce
+            };
+            let mut c = Vec::new();
+            for i in 0..128 {
+                let s = vec![0_u8; i as usize];
+                c.extend(ocb.encrypt(&nonce(3 * i + 1), &s, &s).unwrap());
+                c.extend(ocb.encrypt(&nonce(3 * i + 2), &[], &s).unwrap());
+                c.extend(ocb.encrypt(&nonce(3 * i + 3), &s, &[]).unwrap());
+            }
+            assert_eq!(ocb.encrypt(&nonce(385), &c, &[]).unwrap(), hex(output));
+        }
+    }
+
+    #[test]
+    fn test_ocb_errors() {
+        let aes = Aes::new(&[9; 16]).unwrap();
+        assert!(Ocb::new(aes.clone(), 0).is_err());
+        assert!(Ocb::new(aes.clone(), 17).is_err());
+        let ocb = Ocb::new(aes, 16).unwrap();
+        assert_eq!(
+            ocb.encrypt(&[0; 16], b"", b"").err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+        assert_eq!(
+            ocb.encrypt(&[], b"", b"").err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+        let plain: Vec<u8> = (0..200).collect();
+        let mut sealed = ocb.encrypt(&[1; 15], b"aad", &plain).unwrap();
+        assert_eq!(ocb.decrypt(&[1; 15], b"aad", &sealed).unwrap(), plain);
+        sealed[150] ^= 2;
+        assert_eq!(
+            ocb.decrypt(&[1; 15], b"aad", &sealed).err(),
+            Some(CryptoError::AuthenticationFailed)
+        );
+        let mut buffer = sealed[..200].to_vec();
+        assert!(ocb
+            .decrypt_detached(&[1; 15], b"aad", &mut buffer, &sealed[200..])
+            .is_err());
+        assert_eq!(buffer, sealed[..200].to_vec());
+    }
+
+    #[test]
+    fn test_ocb_l_table() {
+        // Block 2^32 is reachable with a 64 GiB message.
+        let ocb = Ocb::new(Aes::new(&[9; 16]).unwrap(), 16).unwrap();
+        assert_eq!(ocb.l_ntz(1 << 32), dbl(&ocb.l_ntz(1 << 31)));
+        assert_eq!(ocb.l_ntz(3 << 62), ocb.l_ntz(1 << 62));
+        assert_eq!(ocb.l_ntz(1 << 63), dbl(&ocb.l_ntz(1 << 62)));
+    }
+}
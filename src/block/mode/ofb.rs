@@ -0,0 +1,159 @@
+//#######################################################################o
+// The Output Feedback mode (OFB) turns a block cipher into a stream     |
+// cipher: the keystream is E(IV), E(E(IV)), ... and is xored with the   |
+// data, so encryption and decryption are the same operation and any     |
+// length is supported. Each keystream block depends on the previous one |
+// so seeking backwards replays the keystream from the IV.               |
+// An IV must never be reused with the same key.                         |
+//                                                                       |
+// @Refrences:                                                           |
+// SP 800-38A: https://doi.org/10.6028/NIST.SP.800-38A                   |
+//#######################################################################o
+
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::stream::{advance, skip, StreamCipher};
+
+////////////////////////////////////////////////////////////////////////////
+/// `Ofb` is the OFB mode over any block cipher, it keeps its position
+/// so the data can be processed in pieces of any size.
+///# Example
+/// ```
+/// use cryptolib::block::des::Des;
+/// use cryptolib::block::mode::ofb::Ofb;
+/// use cryptolib::stream::StreamCipher;
+///
+/// let des = Des::new(&[0x42; 8]).unwrap();
+/// let mut ofb = Ofb::new(des, &[7; 8]).unwrap();
+/// let mut data = *b"any length";
+/// ofb.apply_keystream(&mut data).unwrap();
+/// ofb.seek(0).unwrap();
+/// ofb.apply_keystream(&mut data).unwrap();
+/// assert_eq!(&data, b"any length");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Ofb<C: BlockCipher> {
+    cipher: C,
+    iv: Vec<u8>,
+    // The last keystream block and how many of its bytes were used.
+    keystream: Vec<u8>,
+    used: usize,
+    pos: u64,
+}
+
+impl<C: BlockCipher> Ofb<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an OFB instance.
+    ///# Arguments
+    ///* `cipher`: the keyed block cipher.
+    ///* `iv`: the IV, `BLOCK_SIZE` bytes long.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidNonceLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(cipher: C, iv: &[u8]) -> Result<Ofb<C>, CryptoError> {
+        if iv.len() != C::BLOCK_SIZE {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        Ok(Ofb {
+            cipher,
+            iv: iv.to_vec(),
+            keystream: iv.to_vec(),
+            used: C::BLOCK_SIZE,
+            pos: 0,
+        })
+    }
+}
+
+impl<C: BlockCipher> StreamCipher for Ofb<C> {
+    fn apply_keystream(&mut self, data: &mut [u8]) -> Result<(), CryptoError> {
+        self.pos = advance(self.pos, data.len(), None)?;
+        for b in data {
+            if self.used == C::BLOCK_SIZE {
+                self.cipher.encrypt_block(&mut self.keystream);
+                self.used = 0;
+            }
+            *b ^= self.keystream[self.used];
+            self.used += 1;
+        }
+        Ok(())
+    }
+
+    /// Seeking backwards replays the keystream from the IV.
+    fn seek(&mut self, pos: u64) -> Result<(), CryptoError> {
+        if pos < self.pos {
+            self.keystream.copy_from_slice(&self.iv);
+            self.used = C::BLOCK_SIZE;
+            self.pos = 0;
+        }
+        skip(self, pos - self.pos)
+    }
+
+    fn current_pos(&self) -> u64 {
+        self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::block::des::Des;
+    use crate::block::triple_des::TripleDes;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_ofb_aes() {
+        // SP 800-38A F.4.1, processed in uneven pieces.
+        let aes = Aes::new(&hex("2b7e151628aed2a6abf7158809cf4f3c")).unwrap();
+        let iv = hex("000102030405060708090a0b0c0d0e0f");
+        let plain = hex(
+            "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51
+             30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        );
+        let expected = hex(
+            "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed825
+             9740051e9c5fecf64344f7a82260edcc304c6528f659c77866a510d9c1d6ae5e",
+        );
+        for split in [0, 1, 15, 16, 17, 40, 64] {
+            let mut ofb = Ofb::new(aes.clone(), &iv).unwrap();
+            let mut buffer = plain.clone();
+            let (a, b) = buffer.split_at_mut(split);
+            ofb.apply_keystream(a).unwrap();
+            ofb.apply_keystream(b).unwrap();
+            assert_eq!(buffer, expected, "split at {}", split);
+            for pos in [63, 16, 0, 17, 33] {
+                ofb.seek(pos as u64).unwrap();
+                let mut rest = plain[pos..].to_vec();
+                ofb.apply_keystream(&mut rest).unwrap();
+                assert_eq!(rest, expected[pos..], "seek to {}", pos);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ofb_des() {
+        // FIPS 81 OFB example, and Triple DES checked against OpenSSL.
+        let des = Des::new(&hex("0123456789abcdef")).unwrap();
+        let mut buffer = *b"Now is the time for all good men";
+        let mut ofb = Ofb::new(des, &hex("1234567890abcdef")).unwrap();
+        ofb.apply_keystream(&mut buffer).unwrap();
+        assert_eq!(
+            buffer.to_vec(),
+            hex("f3096249c7f46e5135f24a242eeb3d3f3d6d5be3255af8c31f9715e94dc817cd")
+        );
+        let key = hex("0123456789abcdef23456789abcdef01456789abcdef0123");
+        let tdes = TripleDes::new(&key).unwrap();
+        let mut buffer = *b"Now is the time for all good men";
+        let mut ofb = Ofb::new(tdes.clone(), &hex("1234567890abcdef")).unwrap();
+        ofb.apply_keystream(&mut buffer).unwrap();
+        assert_eq!(
+            buffer.to_vec(),
+            hex("ee7ec75c1a1013019a8a610002668e0787e28af9ec26b889cec0583981568bde")
+        );
+        assert_eq!(
+            Ofb::new(tdes, &[0; 16]).err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//#######################################################################o
+// POLYVAL is the universal hash of AES-GCM-SIV, it works in the field   |
+// GF(2^128) defined by x^128 + x^127 + x^126 + x^121 + 1 with little-   |
+// endian elements, which avoids the bit reflections of GHASH.           |
+// GHASH is computed with the same arithmetic (RFC 8452 Appendix A).     |
+// The multiplication is done either with PCLMULQDQ when the cpu         |
+// supports it or with a portable constant-time carry-less multiply      |
+// built on integer multiplications of sparse operands.                  |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 8452: https://datatracker.ietf.org/doc/html/rfc8452               |
+// BearSSL ghash_ctmul64: https://bearssl.org/constanttime.html          |
+//#######################################################################o
+
+/// An element of the POLYVAL field as (low, high) 64-bit halves.
+type Element = [u64; 2];
+
+//////////////////////////////////////////////////////////////////////
+/// Returns the low 64 bits of the carry-less product of `x` and `y`.
+///
+/// The operands are split in 4 masks keeping 1 bit out of 4 so the
+/// carries of the integer multiplications land in the holes and can
+/// be masked out.
+//////////////////////////////////////////////////////////////////////
+fn bmul64(x: u64, y: u64) -> u64 {
+    const M0: u64 = 0x1111_1111_1111_1111;
+    const M1: u64 = 0x2222_2222_2222_2222;
+    const M2: u64 = 0x4444_4444_4444_4444;
+    const M3: u64 = 0x8888_8888_8888_8888;
+    let (x0, x1, x2, x3) = (x & M0, x & M1, x & M2, x & M3);
+    let (y0, y1, y2, y3) = (y & M0, y & M1, y & M2, y & M3);
+    let z0 = x0.wrapping_mul(y0) ^ x1.wrapping_mul(y3) ^ x2.wrapping_mul(y2) ^ x3.wrapping_mul(y1);
+    let z1 = x0.wrapping_mul(y1) ^ x1.wrapping_mul(y0) ^ x2.wrapping_mul(y3) ^ x3.wrapping_mul(y2);
+    let z2 = x0.wrapping_mul(y2) ^ x1.wrapping_mul(y1) ^ x2.wrapping_mul(y0) ^ x3.wrapping_mul(y3);
+    let z3 = x0.wrapping_mul(y3) ^ x1.wrapping_mul(y2) ^ x2.wrapping_mul(y1) ^ x3.wrapping_mul(y0);
+    (z0 & M0) | (z1 & M1) | (z2 & M2) | (z3 & M3)
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Returns the 256-bit carry-less product of `a` and `b` as 4 words,
+/// using Karatsuba and bit reversal to get the high halves.
+//////////////////////////////////////////////////////////////////////
+fn clmul_soft(a: &Element, b: &Element) -> [u64; 4] {
+    let (a0, a1) = (a[0], a[1]);
+    let (b0, b1) = (b[0], b[1]);
+    let (a0r, a1r) = (a0.reverse_bits(), a1.reverse_bits());
+    let (b0r, b1r) = (b0.reverse_bits(), b1.reverse_bits());
+    let z0 = bmul64(a0, b0);
+    let z1 = bmul64(a1, b1);
+    let z2 = bmul64(a0 ^ a1, b0 ^ b1) ^ z0 ^ z1;
+    let z0h = bmul64(a0r, b0r);
+    let z1h = bmul64(a1r, b1r);
+    let z2h = bmul64(a0r ^ a1r, b0r ^ b1r) ^ z0h ^ z1h;
+    let z0h = z0h.reverse_bits() >> 1;
+    let z1h = z1h.reverse_bits() >> 1;
+    let z2h = z2h.reverse_bits() >> 1;
+    [z0, z0h ^ z2, z1 ^ z2h, z1h]
+}
+
+#[cfg(target_arch = "x86_64")]
+mod clmul {
+    use core::arch::x86_64::{
+        __m128i, _mm_clmulepi64_si128, _mm_set_epi64x, _mm_storeu_si128, _mm_xor_si128,
+    };
+
+    /// Returns true if the cpu supports the PCLMULQDQ instruction.
+    pub(super) fn is_supported() -> bool {
+        std::is_x86_feature_detected!("pclmulqdq") && std::is_x86_feature_detected!("sse2")
+    }
+
+    fn to_words(v: __m128i) -> [u64; 2] {
+        let mut out = [0_u64; 2];
+        // SAFETY: `out` is 16 bytes long and the store is unaligned.
+        unsafe { _mm_storeu_si128(out.as_mut_ptr() as *mut __m128i, v) };
+        out
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Returns the 256-bit carry-less product of `a` and `b`.
+    ///# Safety
+    /// The caller must make sure the cpu supports PCLMULQDQ.
+    //////////////////////////////////////////////////////////////////
+    #[target_feature(enable = "pclmulqdq,sse2")]
+    pub(super) unsafe fn clmul(a: &[u64; 2], b: &[u64; 2]) -> [u64; 4] {
+        let a = _mm_set_epi64x(a[1] as i64, a[0] as i64);
+        let b = _mm_set_epi64x(b[1] as i64, b[0] as i64);
+        let lo = to_words(_mm_clmulepi64_si128(a, b, 0x00));
+        let hi = to_words(_mm_clmulepi64_si128(a, b, 0x11));
+        let mid = to_words(_mm_xor_si128(
+            _mm_clmulepi64_si128(a, b, 0x01),
+            _mm_clmulepi64_si128(a, b, 0x10),
+        ));
+        [lo[0], lo[1] ^ mid[0], hi[0] ^ mid[1], hi[1]]
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Reduces a 256-bit product, returning `v * x^-128` modulo the
+/// POLYVAL polynomial (the POLYVAL "dot" operation).
+//////////////////////////////////////////////////////////////////////
+fn reduce(v: [u64; 4]) -> Element {
+    let [v0, v1, v2, v3] = v;
+    let v2 = v2 ^ v0 ^ (v0 >> 1) ^ (v0 >> 2) ^ (v0 >> 7);
+    let v1 = v1 ^ (v0 << 63) ^ (v0 << 62) ^ (v0 << 57);
+    let v3 = v3 ^ v1 ^ (v1 >> 1) ^ (v1 >> 2) ^ (v1 >> 7);
+    let v2 = v2 ^ (v1 << 63) ^ (v1 << 62) ^ (v1 << 57);
+    [v2, v3]
+}
+
+fn element_from_bytes(bytes: &[u8; 16]) -> Element {
+    [
+        u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+        u64::from_le_bytes(bytes[8..].try_into().unwrap()),
+    ]
+}
+
+fn element_to_bytes(e: &Element) -> [u8; 16] {
+    let mut bytes = [0_u8; 16];
+    bytes[..8].copy_from_slice(&e[0].to_le_bytes());
+    bytes[8..].copy_from_slice(&e[1].to_le_bytes());
+    bytes
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies `bytes` by x in the POLYVAL field (mulX_POLYVAL).
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn mul_x(bytes: &[u8; 16]) -> [u8; 16] {
+    let v = u128::from_le_bytes(*bytes);
+    let carry = 0_u128.wrapping_sub(v >> 127);
+    (v << 1 ^ (carry & 0xc200_0000_0000_0000_0000_0000_0000_0001)).to_le_bytes()
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies `x` by `h` and reduces the product.
+//////////////////////////////////////////////////////////////////////
+fn dot(x: &Element, h: &Element, use_clmul: bool) -> Element {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if use_clmul {
+            // SAFETY: `use_clmul` is only set when the cpu supports PCLMULQDQ.
+            return reduce(unsafe { clmul::clmul(x, h) });
+        }
+    }
+    let _ = use_clmul;
+    reduce(clmul_soft(x, h))
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `BlockBuffer` collects data until a full 16 bytes block is available.
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone, Default)]
+struct BlockBuffer {
+    bytes: [u8; 16],
+    len: usize,
+}
+
+impl BlockBuffer {
+    /// Passes each full block of the buffered data and `data` to `process`.
+    fn update(&mut self, mut data: &[u8], mut process: impl FnMut(&[u8; 16])) {
+        if self.len > 0 {
+            let take = data.len().min(16 - self.len);
+            self.bytes[self.len..self.len + take].copy_from_slice(&data[..take]);
+            self.len += take;
+            data = &data[take..];
+            if self.len < 16 {
+                return;
+            }
+            process(&self.bytes);
+            self.len = 0;
+        }
+        let mut blocks = data.chunks_exact(16);
+        for block in &mut blocks {
+            process(block.try_into().unwrap());
+        }
+        let rest = blocks.remainder();
+        self.bytes[..rest.len()].copy_from_slice(rest);
+        self.len = rest.len();
+    }
+
+    /// Completes an incomplete block with zeros and passes it to `process`.
+    fn pad(&mut self, mut process: impl FnMut(&[u8; 16])) {
+        if self.len > 0 {
+            self.bytes[self.len..].fill(0);
+            process(&self.bytes);
+            self.len = 0;
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Polyval` accumulates 16 bytes blocks into the POLYVAL hash keyed
+/// with `h`, data that isn't a multiple of 16 bytes is buffered until
+/// the next update or until `pad` is called.
+///# Example
+/// ```
+/// use cryptolib::block::mode::polyval::Polyval;
+///
+/// let mut polyval = Polyval::new(&[0x42; 16]);
+/// polyval.update(b"additional data");
+/// polyval.pad();
+/// polyval.update(b"message");
+/// let hash = polyval.finalize();
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Polyval {
+    h: Element,
+    acc: Element,
+    buffer: BlockBuffer,
+    use_clmul: bool,
+}
+
+impl Polyval {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a POLYVAL hash keyed with `h`, using PCLMULQDQ when the
+    /// cpu supports it.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(h: &[u8; 16]) -> Polyval {
+        #[cfg(target_arch = "x86_64")]
+        let use_clmul = clmul::is_supported();
+        #[cfg(not(target_arch = "x86_64"))]
+        let use_clmul = false;
+        Polyval::with_clmul(h, use_clmul)
+    }
+
+    fn with_clmul(h: &[u8; 16], use_clmul: bool) -> Polyval {
+        Polyval {
+            h: element_from_bytes(h),
+            acc: [0, 0],
+            buffer: BlockBuffer::default(),
+            use_clmul,
+        }
+    }
+
+    /// Adds a full block to the hash: acc = (acc + block) * h * x^-128.
+    fn absorb(acc: &mut Element, h: &Element, use_clmul: bool, block: &[u8; 16]) {
+        let x = element_from_bytes(block);
+        *acc = dot(&[acc[0] ^ x[0], acc[1] ^ x[1]], h, use_clmul);
+    }
+
+    /// Adds `data` to the hash, buffering any incomplete block.
+    pub fn update(&mut self, data: &[u8]) {
+        let (acc, h, use_clmul) = (&mut self.acc, &self.h, self.use_clmul);
+        self.buffer
+            .update(data, |block| Polyval::absorb(acc, h, use_clmul, block));
+    }
+
+    /// Completes a buffered incomplete block with zeros and adds it.
+    pub fn pad(&mut self) {
+        let (acc, h, use_clmul) = (&mut self.acc, &self.h, self.use_clmul);
+        self.buffer
+            .pad(|block| Polyval::absorb(acc, h, use_clmul, block));
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Returns the hash of the blocks added so far, a buffered
+    /// incomplete block isn't included until `pad` is called.
+    ////////////////////////////////////////////////////////////////////
+    pub fn finalize(&self) -> [u8; 16] {
+        element_to_bytes(&self.acc)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `GHash` is the universal hash of GCM, computed as
+/// ByteReverse(POLYVAL(mulX_POLYVAL(ByteReverse(H)), ByteReverse(X_i))).
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub(crate) struct GHash {
+    polyval: Polyval,
+    buffer: BlockBuffer,
+}
+
+impl GHash {
+    pub(crate) fn new(h: &[u8; 16]) -> GHash {
+        GHash::from_polyval(h, Polyval::new)
+    }
+
+    fn from_polyval(h: &[u8; 16], polyval: impl Fn(&[u8; 16]) -> Polyval) -> GHash {
+        let mut h_rev = *h;
+        h_rev.reverse();
+        GHash {
+            polyval: polyval(&mul_x(&h_rev)),
+            buffer: BlockBuffer::default(),
+        }
+    }
+
+    fn absorb(polyval: &mut Polyval, block: &[u8; 16]) {
+        let mut rev = *block;
+        rev.reverse();
+        polyval.update(&rev);
+    }
+
+    /// Adds `data` to the hash, buffering any incomplete block.
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        let polyval = &mut self.polyval;
+        self.buffer
+            .update(data, |block| GHash::absorb(polyval, block));
+    }
+
+    /// Completes a buffered incomplete block with zeros and adds it.
+    pub(crate) fn pad(&mut self) {
+        let polyval = &mut self.polyval;
+        self.buffer.pad(|block| GHash::absorb(polyval, block));
+    }
+
+    /// Returns the hash of the blocks added so far.
+    pub(crate) fn finalize(&self) -> [u8; 16] {
+        let mut out = self.polyval.finalize();
+        out.reverse();
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    fn clmul_variants() -> Vec<bool> {
+        let mut variants = vec![false];
+        #[cfg(target_arch = "x86_64")]
+        {
+            if clmul::is_supported() {
+                variants.push(true);
+            }
+        }
+        variants
+    }
+
+    #[test]
+    fn test_polyval() {
+        // RFC 8452 Appendix A.
+        let h: [u8; 16] = hex("25629347589242761d31f826ba4b757b").try_into().unwrap();
+        for use_clmul in clmul_variants() {
+            let mut polyval = Polyval::with_clmul(&h, use_clmul);
+            polyval.update(&hex("4f4f95668c83dfb6401762bb2d01a262"));
+            polyval.update(&hex("d1a24ddd2721d006bbe45f20d3c9f362"));
+            assert_eq!(
+                polyval.finalize().to_vec(),
+                hex("f7a3b47b846119fae5b7866cf5e5b77e"),
+                "clmul: {}",
+                use_clmul
+            );
+        }
+    }
+
+    #[test]
+    fn test_mul_x() {
+        // RFC 8452 Appendix A.
+        let h: [u8; 16] = hex("dcbaa5dd137c188ebb21492c23c9b112").try_into().unwrap();
+        let mut h_rev = h;
+        h_rev.reverse();
+        assert_eq!(
+            mul_x(&h_rev).to_vec(),
+            hex("25629347589242761d31f826ba4b757b")
+        );
+    }
+
+    #[test]
+    fn test_ghash() {
+        // GCM test case 2: GHASH(H, C || len(A) || len(C)).
+        let h: [u8; 16] = hex("66e94bd4ef8a2c3b884cfa59ca342b2e").try_into().unwrap();
+        for use_clmul in clmul_variants() {
+            let mut ghash = GHash::from_polyval(&h, |h| Polyval::with_clmul(h, use_clmul));
+            ghash.update(&hex("0388dace60b6a392f328c2b971b2fe78"));
+            ghash.update(&hex("00000000000000000000000000000080"));
+            assert_eq!(
+                ghash.finalize().to_vec(),
+                hex("f38cbb1ad69223dcc3457ae5b6b0f885"),
+                "clmul: {}",
+                use_clmul
+            );
+        }
+    }
+}
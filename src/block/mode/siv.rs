@@ -0,0 +1,286 @@
+//#######################################################################o
+// The Synthetic Initialization Vector mode (SIV) derives the IV from    |
+// the data itself: S2V, a CMAC based PRF over a vector of strings (the  |
+// additional data components, the nonce and the plaintext), gives a     |
+// 128-bit value V that is both the tag and the counter mode IV.         |
+// Reusing a nonce (or using none at all) only reveals whether the same  |
+// message was encrypted twice, which makes SIV suited to deterministic  |
+// encryption and key wrapping.                                          |
+// The key is split in two halves, one for S2V and one for the CTR mode. |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 5297: https://www.rfc-editor.org/rfc/rfc5297                      |
+//#######################################################################o
+
+use super::{apply_ctr, Aead};
+use crate::block::aes::Aes;
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::mac::cmac::{dbl, Cmac};
+use crate::util::{ct_eq, xor_in_place};
+
+// S2V takes at most 127 strings, the plaintext being the last one.
+const MAX_AAD_COMPONENTS: usize = 126;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Siv` is the SIV mode over a 128-bit block cipher.
+///# Example
+/// ```
+/// use cryptolib::block::mode::siv::Siv;
+///
+/// let siv = Siv::with_aes_key(&[0x42; 32]).unwrap();
+/// let sealed = siv.encrypt(&[b"header", b"nonce"], b"secret").unwrap();
+/// assert_eq!(siv.decrypt(&[b"header", b"nonce"], &sealed).unwrap(), b"secret");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Siv<C: BlockCipher> {
+    cmac: Cmac<C>,
+    ctr_cipher: C,
+}
+
+impl Siv<Aes> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an AES-SIV instance from a 32, 48 or 64 bytes key, the
+    /// first half keys S2V and the second half the CTR mode.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_aes_key(key: &[u8]) -> Result<Siv<Aes>, CryptoError> {
+        if ![32, 48, 64].contains(&key.len()) {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let (mac_key, ctr_key) = key.split_at(key.len() / 2);
+        Siv::new(Aes::new(mac_key)?, Aes::new(ctr_key)?)
+    }
+}
+
+impl<C: BlockCipher> Siv<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a SIV instance.
+    ///# Arguments
+    ///* `mac_cipher`: the 128-bit block cipher keyed with K1, for S2V.
+    ///* `ctr_cipher`: the same cipher keyed with K2, for the CTR mode.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidBlockSize`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(mac_cipher: C, ctr_cipher: C) -> Result<Siv<C>, CryptoError> {
+        if C::BLOCK_SIZE != 16 {
+            return Err(CryptoError::InvalidBlockSize);
+        }
+        Ok(Siv {
+            cmac: Cmac::new(mac_cipher)?,
+            ctr_cipher,
+        })
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Computes S2V over the `aad` components followed by `plain_text`.
+    //////////////////////////////////////////////////////////////////
+    fn s2v(&self, aad: &[&[u8]], plain_text: &[u8]) -> [u8; 16] {
+        let mut d = self.cmac.compute_parts(&[&[0; 16]]);
+        for component in aad {
+            d = dbl(&d);
+            xor_in_place(&mut d, &self.cmac.compute_parts(&[component]));
+        }
+        if plain_text.len() >= 16 {
+            // xorend: D is xored into the last 16 bytes of the plaintext.
+            let (head, tail) = plain_text.split_at(plain_text.len() - 16);
+            xor_in_place(&mut d, tail);
+            self.cmac.compute_parts(&[head, &d])
+        } else {
+            d = dbl(&d);
+            let mut padded = [0_u8; 16];
+            padded[..plain_text.len()].copy_from_slice(plain_text);
+            padded[plain_text.len()] = 0x80;
+            xor_in_place(&mut d, &padded);
+            self.cmac.compute_parts(&[&d])
+        }
+    }
+
+    fn apply_keystream(&self, v: &[u8; 16], data: &mut [u8]) {
+        // Two bits are cleared so that 32 and 64-bit counter
+        // implementations give the same result.
+        let mut counter = *v;
+        counter[8] &= 0x7f;
+        counter[12] &= 0x7f;
+        apply_ctr(&self.ctr_cipher, &mut counter, 16, data);
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts `buffer` in place.
+    ///# Arguments
+    ///* `aad`: up to 126 components authenticated but not encrypted,
+    ///  a nonce is passed as the last component.
+    ///* `buffer`: the plaintext, replaced by the ciphertext.
+    ///# Returns
+    /// The synthetic IV V, or `CryptoError::MessageTooLong` if there
+    /// are too many components.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt_detached(
+        &self,
+        aad: &[&[u8]],
+        buffer: &mut [u8],
+    ) -> Result<[u8; 16], CryptoError> {
+        if aad.len() > MAX_AAD_COMPONENTS {
+            return Err(CryptoError::MessageTooLong);
+        }
+        let v = self.s2v(aad, buffer);
+        self.apply_keystream(&v, buffer);
+        Ok(v)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts `buffer` in place and checks the synthetic IV `v`.
+    ///# Returns
+    /// `CryptoError::AuthenticationFailed` if `v` doesn't match, in
+    /// which case `buffer` is restored to the ciphertext.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt_detached(
+        &self,
+        aad: &[&[u8]],
+        buffer: &mut [u8],
+        v: &[u8],
+    ) -> Result<(), CryptoError> {
+        if aad.len() > MAX_AAD_COMPONENTS {
+            return Err(CryptoError::MessageTooLong);
+        }
+        let v: [u8; 16] = v
+            .try_into()
+            .map_err(|_| CryptoError::AuthenticationFailed)?;
+        self.apply_keystream(&v, buffer);
+        if !ct_eq(&self.s2v(aad, buffer), &v) {
+            self.apply_keystream(&v, buffer);
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        Ok(())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts `plain_text` with several additional data components.
+    ///# Returns
+    /// V || C as in RFC 5297.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt(&self, aad: &[&[u8]], plain_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut out = plain_text.to_vec();
+        let v = self.encrypt_detached(aad, &mut out)?;
+        out.splice(0..0, v);
+        Ok(out)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts a V || C ciphertext produced by `encrypt`.
+    ///# Returns
+    /// The plaintext or `CryptoError::AuthenticationFailed`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt(&self, aad: &[&[u8]], cipher_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if cipher_text.len() < 16 {
+            return Err(CryptoError::AuthenticationFailed);
+        }
+        let (v, data) = cipher_text.split_at(16);
+        let mut out = data.to_vec();
+        self.decrypt_detached(aad, &mut out, v)?;
+        Ok(out)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// As an `Aead` the components are the `aad` then the `nonce` (which
+/// may be empty for deterministic encryption), and the output is
+/// V || C like RFC 5297 rather than the ciphertext followed by the tag.
+////////////////////////////////////////////////////////////////////////////
+impl<C: BlockCipher> Aead for Siv<C> {
+    fn encrypt(&self, nonce: &[u8], aad: &[u8], plain_text: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if nonce.is_empty() {
+            Siv::encrypt(self, &[aad], plain_text)
+        } else {
+            Siv::encrypt(self, &[aad, nonce], plain_text)
+        }
+    }
+
+    fn decrypt(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        cipher_text: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        if nonce.is_empty() {
+            Siv::decrypt(self, &[aad], cipher_text)
+        } else {
+            Siv::decrypt(self, &[aad, nonce], cipher_text)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_siv_deterministic() {
+        // RFC 5297 Appendix A.1.
+        let siv = Siv::with_aes_key(&hex(
+            "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+        ))
+        .unwrap();
+        let aad = hex("101112131415161718191a1b1c1d1e1f2021222324252627");
+        let plain = hex("112233445566778899aabbccddee");
+        let sealed = siv.encrypt(&[&aad], &plain).unwrap();
+        assert_eq!(
+            sealed,
+            hex("85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c")
+        );
+        assert_eq!(siv.decrypt(&[&aad], &sealed).unwrap(), plain);
+        // Through `Aead` without a nonce.
+        assert_eq!(Aead::encrypt(&siv, &[], &aad, &plain).unwrap(), sealed);
+    }
+
+    #[test]
+    fn test_siv_nonce_based() {
+        // RFC 5297 Appendix A.2.
+        let siv = Siv::with_aes_key(&hex(
+            "7f7e7d7c7b7a79787776757473727170404142434445464748494a4b4c4d4e4f",
+        ))
+        .unwrap();
+        let ad1 =
+            hex("00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100");
+        let ad2 = hex("102030405060708090a0");
+        let nonce = hex("09f911029d74e35bd84156c5635688c0");
+        let plain = hex(
+            "7468697320697320736f6d6520706c61696e7465787420746f20656e63727970
+             74207573696e67205349562d414553",
+        );
+        let expected = hex(
+            "7bdb6e3b432667eb06f4d14bff2fbd0fcb900f2fddbe404326601965c889bf17
+             dba77ceb094fa663b7a3f748ba8af829ea64ad544a272e9c485b62a3fd5c0d",
+        );
+        let sealed = siv.encrypt(&[&ad1, &ad2, &nonce], &plain).unwrap();
+        assert_eq!(sealed, expected);
+        assert_eq!(siv.decrypt(&[&ad1, &ad2, &nonce], &sealed).unwrap(), plain);
+        // The order of the components matters.
+        assert_eq!(
+            siv.decrypt(&[&ad2, &ad1, &nonce], &sealed).err(),
+            Some(CryptoError::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn test_siv_errors() {
+        assert_eq!(
+            Siv::with_aes_key(&[0; 16]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        let siv = Siv::with_aes_key(&[0; 64]).unwrap();
+        let mut sealed = siv.encrypt(&[b"aad"], b"").unwrap();
+        assert_eq!(siv.decrypt(&[b"aad"], &sealed).unwrap(), b"");
+        sealed[15] ^= 1;
+        assert!(siv.decrypt(&[b"aad"], &sealed).is_err());
+        assert!(siv.decrypt(&[b"aad"], &sealed[..15]).is_err());
+        let components = vec![&b"aad"[..]; 127];
+        assert_eq!(
+            siv.encrypt(&components, b"").err(),
+            Some(CryptoError::MessageTooLong)
+        );
+    }
+}
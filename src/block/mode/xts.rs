@@ -0,0 +1,345 @@
+//#######################################################################o
+// XTS (XEX-based Tweaked codebook mode with ciphertext Stealing) is the |
+// IEEE 1619 mode for storage devices: each data unit (sector) is        |
+// encrypted independently, the block j of the sector n is processed as  |
+//   C = E_K1(P ^ T) ^ T  with  T = E_K2(n) * alpha^j  in GF(2^128),     |
+// so the ciphertext has the size of the plaintext. A sector whose size  |
+// isn't a multiple of 16 bytes ends with ciphertext stealing.           |
+// XTS provides no authentication.                                       |
+//                                                                       |
+// @Refrences:                                                           |
+// IEEE 1619-2018 Standard for Cryptographic Protection of Data on       |
+// Block-Oriented Storage Devices.                                       |
+// NIST SP 800-38E: https://doi.org/10.6028/NIST.SP.800-38E              |
+//#######################################################################o
+
+use crate::block::aes::Aes;
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::util::{ct_eq, xor_in_place};
+
+// Largest data unit in blocks.
+const MAX_BLOCKS: usize = 1 << 20;
+// Number of blocks encrypted together.
+const PARALLEL_BLOCKS: usize = 8;
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies the tweak by alpha (x) in GF(2^128), the tweak being a
+/// little-endian polynomial reduced by x^128 + x^7 + x^2 + x + 1.
+//////////////////////////////////////////////////////////////////////
+fn mul_alpha(tweak: &mut [u8; 16]) {
+    let t = u128::from_le_bytes(*tweak);
+    let carry = 0_u128.wrapping_sub(t >> 127);
+    *tweak = ((t << 1) ^ (carry & 0x87)).to_le_bytes();
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Xts` is the XTS mode over a 128-bit block cipher.
+///# Example
+/// ```
+/// use cryptolib::block::mode::xts::Xts;
+///
+/// let mut key = [0x42; 64];
+/// key[32..].fill(0x24);
+/// let xts = Xts::with_aes_key(&key).unwrap();
+/// let mut sector = [7_u8; 512];
+/// xts.encrypt_sector(1234, &mut sector).unwrap();
+/// xts.decrypt_sector(1234, &mut sector).unwrap();
+/// assert_eq!(sector, [7_u8; 512]);
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Xts<C: BlockCipher> {
+    data_cipher: C,
+    tweak_cipher: C,
+}
+
+impl Xts<Aes> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an XTS-AES-128 (32 bytes key) or XTS-AES-256 (64 bytes
+    /// key) instance, the first half of the key is the data key and the
+    /// second half the tweak key.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidKeyLength`, also returned when
+    /// both halves are equal as required by SP 800-38E.
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_aes_key(key: &[u8]) -> Result<Xts<Aes>, CryptoError> {
+        if key.len() != 32 && key.len() != 64 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let (data_key, tweak_key) = key.split_at(key.len() / 2);
+        if ct_eq(data_key, tweak_key) {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        Xts::new(Aes::new(data_key)?, Aes::new(tweak_key)?)
+    }
+}
+
+impl<C: BlockCipher> Xts<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an XTS instance.
+    ///# Arguments
+    ///* `data_cipher`: the 128-bit block cipher keyed with K1.
+    ///* `tweak_cipher`: the same cipher keyed with K2.
+    ///# Returns
+    /// The mode or `CryptoError::InvalidBlockSize`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(data_cipher: C, tweak_cipher: C) -> Result<Xts<C>, CryptoError> {
+        if C::BLOCK_SIZE != 16 {
+            return Err(CryptoError::InvalidBlockSize);
+        }
+        Ok(Xts {
+            data_cipher,
+            tweak_cipher,
+        })
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Returns the tweak of the first block of the sector, the
+    /// sector number is encoded in little-endian.
+    //////////////////////////////////////////////////////////////////
+    fn first_tweak(&self, sector: u128) -> [u8; 16] {
+        let mut tweak = sector.to_le_bytes();
+        self.tweak_cipher.encrypt_block(&mut tweak);
+        tweak
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Processes full blocks, starting with the tweak `tweak` which
+    /// is left on the tweak of the next block.
+    //////////////////////////////////////////////////////////////////
+    fn process_blocks(&self, blocks: &mut [u8], tweak: &mut [u8; 16], encrypt: bool) {
+        let mut tweaks = [0_u8; 16 * PARALLEL_BLOCKS];
+        for chunk in blocks.chunks_mut(tweaks.len()) {
+            let tweaks = &mut tweaks[..chunk.len()];
+            for t in tweaks.chunks_exact_mut(16) {
+                t.copy_from_slice(tweak);
+                mul_alpha(tweak);
+            }
+            xor_in_place(chunk, tweaks);
+            if encrypt {
+                self.data_cipher.encrypt_blocks(chunk);
+            } else {
+                self.data_cipher.decrypt_blocks(chunk);
+            }
+            xor_in_place(chunk, tweaks);
+        }
+    }
+
+    fn process_block(&self, block: &mut [u8], tweak: &[u8; 16], encrypt: bool) {
+        xor_in_place(block, tweak);
+        if encrypt {
+            self.data_cipher.encrypt_block(block);
+        } else {
+            self.data_cipher.decrypt_block(block);
+        }
+        xor_in_place(block, tweak);
+    }
+
+    fn process_sector(
+        &self,
+        sector: u128,
+        buffer: &mut [u8],
+        encrypt: bool,
+    ) -> Result<(), CryptoError> {
+        if buffer.len() < 16 {
+            return Err(CryptoError::InvalidDataLength);
+        }
+        if buffer.len().div_ceil(16) > MAX_BLOCKS {
+            return Err(CryptoError::MessageTooLong);
+        }
+        let mut tweak = self.first_tweak(sector);
+        let rest = buffer.len() % 16;
+        if rest == 0 {
+            self.process_blocks(buffer, &mut tweak, encrypt);
+            return Ok(());
+        }
+
+        // Ciphertext stealing: the last full block and the partial block
+        // are handled apart, with the tweaks swapped when decrypting.
+        let full = buffer.len() - rest - 16;
+        let (head, tail) = buffer.split_at_mut(full);
+        self.process_blocks(head, &mut tweak, encrypt);
+        let mut next_tweak = tweak;
+        mul_alpha(&mut next_tweak);
+        let (first_tweak, second_tweak) = if encrypt {
+            (tweak, next_tweak)
+        } else {
+            (next_tweak, tweak)
+        };
+        let (last_full, partial) = tail.split_at_mut(16);
+        self.process_block(last_full, &first_tweak, encrypt);
+        let mut block = [0_u8; 16];
+        block[..rest].copy_from_slice(partial);
+        block[rest..].copy_from_slice(&last_full[rest..]);
+        partial.copy_from_slice(&last_full[..rest]);
+        self.process_block(&mut block, &second_tweak, encrypt);
+        last_full.copy_from_slice(&block);
+        Ok(())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts a sector in place.
+    ///# Arguments
+    ///* `sector`: the data unit sequence number, used as the tweak.
+    ///* `buffer`: the sector, at least 16 bytes and at most 2^20 blocks.
+    ///# Returns
+    /// `CryptoError::InvalidDataLength` if the sector is shorter than a
+    /// block, `CryptoError::MessageTooLong` if it's too long.
+    ////////////////////////////////////////////////////////////////////
+    pub fn encrypt_sector(&self, sector: u128, buffer: &mut [u8]) -> Result<(), CryptoError> {
+        self.process_sector(sector, buffer, true)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Decrypts a sector in place.
+    ///# Returns
+    /// The same errors as `encrypt_sector`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn decrypt_sector(&self, sector: u128, buffer: &mut [u8]) -> Result<(), CryptoError> {
+        self.process_sector(sector, buffer, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    const KEY_1: &str = "2718281828459045235360287471352662497757247093699959574966967627";
+    const KEY_2: &str = "3141592653589793238462643383279502884197169399375105820974944592";
+
+    fn check(key: &str, sector: u128, plain: &[u8], cipher: &[u8]) {
+        let xts = Xts::with_aes_key(&hex(key)).unwrap();
+        check_xts(&xts, sector, plain, cipher);
+    }
+
+    fn check_xts(xts: &Xts<Aes>, sector: u128, plain: &[u8], cipher: &[u8]) {
+        let mut buffer = plain.to_vec();
+        xts.encrypt_sector(sector, &mut buffer).unwrap();
+        assert_eq!(buffer, cipher);
+        xts.decrypt_sector(sector, &mut buffer).unwrap();
+        assert_eq!(buffer, plain);
+    }
+
+    #[test]
+    fn test_xts_aes_128() {
+        // IEEE 1619 vectors 1 to 3, the equal keys of the first one are
+        // only accepted by `Xts::new`.
+        let zero = Aes::new(&[0; 16]).unwrap();
+        check_xts(
+            &Xts::new(zero.clone(), zero).unwrap(),
+            0,
+            &[0; 32],
+            &hex("917cf69ebd68b2ec9b9fe9a3eadda692cd43d2f59598ed858c02c2652fbf922e"),
+        );
+        check(
+            "1111111111111111111111111111111122222222222222222222222222222222",
+            0x33_3333_3333,
+            &[0x44; 32],
+            &hex("c454185e6a16936e39334038acef838bfb186fff7480adc4289382ecd6d394f0"),
+        );
+        check(
+            "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f022222222222222222222222222222222",
+            0x33_3333_3333,
+            &[0x44; 32],
+            &hex("af85336b597afc1a900b2eb21ec949d292df4c047e0b21532186a5971a227a89"),
+        );
+    }
+
+    #[test]
+    fn test_xts_aes_128_sector() {
+        // IEEE 1619 vector 4, a 512 bytes sector.
+        let plain: Vec<u8> = (0..=255).cycle().take(512).collect();
+        check(
+            &format!("{}{}", &KEY_1[..32], &KEY_2[..32]),
+            0,
+            &plain,
+            &hex(
+                "27a7479befa1d476489f308cd4cfa6e2a96e4bbe3208ff25287dd3819616e89c
+                 c78cf7f5e543445f8333d8fa7f56000005279fa5d8b5e4ad40e736ddb4d35412
+                 328063fd2aab53e5ea1e0a9f332500a5df9487d07a5c92cc512c8866c7e860ce
+                 93fdf166a24912b422976146ae20ce846bb7dc9ba94a767aaef20c0d61ad0265
+                 5ea92dc4c4e41a8952c651d33174be51a10c421110e6d81588ede82103a252d8
+                 a750e8768defffed9122810aaeb99f9172af82b604dc4b8e51bcb08235a6f434
+                 1332e4ca60482a4ba1a03b3e65008fc5da76b70bf1690db4eae29c5f1badd03c
+                 5ccf2a55d705ddcd86d449511ceb7ec30bf12b1fa35b913f9f747a8afd1b130e
+                 94bff94effd01a91735ca1726acd0b197c4e5b03393697e126826fb6bbde8ecc
+                 1e08298516e2c9ed03ff3c1b7860f6de76d4cecd94c8119855ef5297ca67e9f3
+                 e7ff72b1e99785ca0a7e7720c5b36dc6d72cac9574c8cbbc2f801e23e56fd344
+                 b07f22154beba0f08ce8891e643ed995c94d9a69c9f1b5f499027a78572aeebd
+                 74d20cc39881c213ee770b1010e4bea718846977ae119f7a023ab58cca0ad752
+                 afe656bb3c17256a9f6e9bf19fdd5a38fc82bbe872c5539edb609ef4f79c203e
+                 bb140f2e583cb2ad15b4aa5b655016a8449277dbd477ef2c8d6c017db738b18d
+                 eb4a427d1923ce3ff262735779a418f20a282df920147beabe421ee5319d0568",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_xts_aes_256() {
+        // IEEE 1619 vector 10, first and last 32 bytes of the sector.
+        let plain: Vec<u8> = (0..=255).cycle().take(512).collect();
+        let xts = Xts::with_aes_key(&hex(&format!("{}{}", KEY_1, KEY_2))).unwrap();
+        let mut buffer = plain.clone();
+        xts.encrypt_sector(0xff, &mut buffer).unwrap();
+        assert_eq!(
+            buffer[..32].to_vec(),
+            hex("1c3b3a102f770386e4836c99e370cf9bea00803f5e482357a4ae12d414a3e63b")
+        );
+        assert_eq!(
+            buffer[480..].to_vec(),
+            hex("773dad38014bd2092fa755c824bb5e54c4f36ffda9fcea70b9c6e693e148c151")
+        );
+        xts.decrypt_sector(0xff, &mut buffer).unwrap();
+        assert_eq!(buffer, plain);
+    }
+
+    #[test]
+    fn test_xts_ciphertext_stealing() {
+        // IEEE 1619 vectors 15 to 18, partial final blocks.
+        let key = "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0";
+        let plain: Vec<u8> = (0..133).collect();
+        for (len, cipher) in [
+            (17, "6c1625db4671522d3d7599601de7ca09ed"),
+            (18, "d069444b7a7e0cab09e24447d24deb1fedbf"),
+            (19, "e5df1351c0544ba1350b3363cd8ef4beedbf9d"),
+            (20, "9d84c813f719aa2c7be3f66171c7c5c2edbf9dac"),
+        ] {
+            check(key, 0x12_3456_789a, &plain[..len], &hex(cipher));
+        }
+        // Several blocks before the stealing, checked against
+        // pyca/cryptography.
+        check(
+            &format!("{}{}", KEY_1, KEY_2),
+            0x1234,
+            &plain,
+            &hex(
+                "c5bb5b6de17b94142c64ddb476e7f6417eaa96a65dcae4113789931aeced3b51
+                 adf8c865d01718759ddb91e640be659da42568446116e53e413b2fd99076c9a9
+                 d95d0c6fc1ceabfb61deccf61a144ddb074747d15999d738407b494c6df3f22e
+                 7b70d8b5d693095690247793c68bd024077247d857560522d72e4eed22711d94
+                 8c1966d77e",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_xts_errors() {
+        assert_eq!(
+            Xts::with_aes_key(&[0; 48]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        for len in [32, 64] {
+            assert_eq!(
+                Xts::with_aes_key(&vec![0x42; len]).err(),
+                Some(CryptoError::InvalidKeyLength)
+            );
+        }
+        let xts = Xts::with_aes_key(&(0..32).collect::<Vec<u8>>()).unwrap();
+        assert_eq!(
+            xts.encrypt_sector(0, &mut [0; 15]),
+            Err(CryptoError::InvalidDataLength)
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//#######################################################################o
+// PRESENT is an ultra-lightweight 64-bit block cipher (ISO/IEC 29192-2) |
+// built as a substitution-permutation network of 31 rounds: add the     |
+// round key, apply a 4-bit S-box to the 16 nibbles and a bit            |
+// permutation moving bit i to bit 16 * i mod 63. The round keys are the |
+// top 64 bits of a key register rotated by 61 bits, partly passed       |
+// through the S-box and xored with the round counter each round.        |
+// Both the 80-bit and the 128-bit key variants are supported.           |
+//                                                                       |
+// @Refrences:                                                           |
+// Bogdanov et al.: https://doi.org/10.1007/978-3-540-74735-2_31         |
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+const ROUNDS: usize = 31;
+
+const SBOX: [u8; 16] = [
+    0xc, 0x5, 0x6, 0xb, 0x9, 0x0, 0xa, 0xd, 0x3, 0xe, 0xf, 0x8, 0x4, 0x7, 0x1, 0x2,
+];
+
+const INV_SBOX: [u8; 16] = invert(SBOX);
+
+const fn invert(sbox: [u8; 16]) -> [u8; 16] {
+    let mut inv = [0_u8; 16];
+    let mut i = 0;
+    while i < 16 {
+        inv[sbox[i] as usize] = i as u8;
+        i += 1;
+    }
+    inv
+}
+
+fn s_layer(state: u64, sbox: &[u8; 16]) -> u64 {
+    let mut out = 0;
+    for i in 0..16 {
+        out |= (sbox[((state >> (4 * i)) & 0xf) as usize] as u64) << (4 * i);
+    }
+    out
+}
+
+// Bit i moves to bit 16 * i mod 63, bit 63 stays.
+fn p_layer(state: u64) -> u64 {
+    let mut out = 0;
+    for i in 0..64 {
+        let to = if i == 63 { 63 } else { (16 * i) % 63 };
+        out |= ((state >> i) & 1) << to;
+    }
+    out
+}
+
+fn inv_p_layer(state: u64) -> u64 {
+    let mut out = 0;
+    for i in 0..64 {
+        let from = if i == 63 { 63 } else { (16 * i) % 63 };
+        out |= ((state >> from) & 1) << i;
+    }
+    out
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Present` is a keyed PRESENT cipher, it doesn't allocate.
+///# Example
+/// ```
+/// use cryptolib::block::present::Present;
+/// use cryptolib::block::BlockCipher;
+///
+/// let present = Present::new(&[0x42; 10]).unwrap();
+/// let mut block = *b"8 bytes!";
+/// present.encrypt_block(&mut block);
+/// present.decrypt_block(&mut block);
+/// assert_eq!(&block, b"8 bytes!");
+/// assert_eq!(present.decrypt_u64(present.encrypt_u64(7)), 7);
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Present {
+    round_keys: [u64; ROUNDS + 1],
+}
+
+impl Present {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a PRESENT cipher from a 10 (PRESENT-80) or 16 bytes
+    /// (PRESENT-128) big-endian `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Present, CryptoError> {
+        let mut round_keys = [0_u64; ROUNDS + 1];
+        match key.len() {
+            10 => {
+                // The 80-bit register in the low bits of a u128.
+                let mut bytes = [0_u8; 16];
+                bytes[6..].copy_from_slice(key);
+                let mut k = u128::from_be_bytes(bytes);
+                let mask = (1_u128 << 80) - 1;
+                for (i, rk) in round_keys.iter_mut().enumerate() {
+                    *rk = (k >> 16) as u64;
+                    k = ((k << 61) | (k >> 19)) & mask;
+                    let top = SBOX[(k >> 76) as usize] as u128;
+                    k = (k & !(0xf << 76)) | (top << 76);
+                    k ^= ((i + 1) as u128) << 15;
+                }
+            }
+            16 => {
+                let mut k = u128::from_be_bytes(key.try_into().unwrap());
+                for (i, rk) in round_keys.iter_mut().enumerate() {
+                    *rk = (k >> 64) as u64;
+                    k = k.rotate_left(61);
+                    let top = ((SBOX[(k >> 124) as usize] as u128) << 124)
+                        | ((SBOX[((k >> 120) & 0xf) as usize] as u128) << 120);
+                    k = (k & !(0xff << 120)) | top;
+                    k ^= ((i + 1) as u128) << 62;
+                }
+            }
+            _ => return Err(CryptoError::InvalidKeyLength),
+        }
+        Ok(Present { round_keys })
+    }
+
+    /// Encrypts a big-endian 64-bit block.
+    pub fn encrypt_u64(&self, mut state: u64) -> u64 {
+        for &k in &self.round_keys[..ROUNDS] {
+            state = p_layer(s_layer(state ^ k, &SBOX));
+        }
+        state ^ self.round_keys[ROUNDS]
+    }
+
+    /// Decrypts a big-endian 64-bit block.
+    pub fn decrypt_u64(&self, mut state: u64) -> u64 {
+        state ^= self.round_keys[ROUNDS];
+        for &k in self.round_keys[..ROUNDS].iter().rev() {
+            state = s_layer(inv_p_layer(state), &INV_SBOX) ^ k;
+        }
+        state
+    }
+}
+
+impl BlockCipher for Present {
+    const BLOCK_SIZE: usize = 8;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let state =
+            u64::from_be_bytes((&*block).try_into().expect("present block must be 8 bytes"));
+        block.copy_from_slice(&self.encrypt_u64(state).to_be_bytes());
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let state =
+            u64::from_be_bytes((&*block).try_into().expect("present block must be 8 bytes"));
+        block.copy_from_slice(&self.decrypt_u64(state).to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_present() {
+        // Appendix I of the paper for PRESENT-80, then PRESENT-128.
+        for (key, plain, cipher) in [
+            (
+                "00000000000000000000",
+                "0000000000000000",
+                "5579c1387b228445",
+            ),
+            (
+                "ffffffffffffffffffff",
+                "0000000000000000",
+                "e72c46c0f5945049",
+            ),
+            (
+                "00000000000000000000",
+                "ffffffffffffffff",
+                "a112ffc72f68417b",
+            ),
+            (
+                "ffffffffffffffffffff",
+                "ffffffffffffffff",
+                "3333dcd3213210d2",
+            ),
+            (
+                "0123456789abcdef0123456789abcdef",
+                "0123456789abcdef",
+                "0e9d28685e671dd6",
+            ),
+        ] {
+            let present = Present::new(&hex(key)).unwrap();
+            let mut block = hex(plain);
+            present.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher), "key {}", key);
+            present.decrypt_block(&mut block);
+            assert_eq!(block, hex(plain));
+        }
+        assert_eq!(
+            Present::new(&[0; 12]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//#######################################################################o
+// RC2 is a 64-bit block cipher with a variable key of 1 to 128 bytes.   |
+// The key is expanded through a table of pi digits into 64 16-bit       |
+// words, an "effective key bits" parameter reducing the strength of     |
+// the expanded key independently of the key length (export grade RC2    |
+// used 40 bits). Encryption is 16 mixing rounds on four 16-bit words    |
+// with two mashing rounds after the 5th and the 11th.                   |
+// Still found in PKCS#12 files (RC2-40-CBC) and old S/MIME messages.    |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 2268: https://www.rfc-editor.org/rfc/rfc2268                      |
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// A random permutation of 0..256 based on the digits of pi.
+const PITABLE: [u8; 256] = [
+    0xd9, 0x78, 0xf9, 0xc4, 0x19, 0xdd, 0xb5, 0xed, 0x28, 0xe9, 0xfd, 0x79, 0x4a, 0xa0, 0xd8, 0x9d,
+    0xc6, 0x7e, 0x37, 0x83, 0x2b, 0x76, 0x53, 0x8e, 0x62, 0x4c, 0x64, 0x88, 0x44, 0x8b, 0xfb, 0xa2,
+    0x17, 0x9a, 0x59, 0xf5, 0x87, 0xb3, 0x4f, 0x13, 0x61, 0x45, 0x6d, 0x8d, 0x09, 0x81, 0x7d, 0x32,
+    0xbd, 0x8f, 0x40, 0xeb, 0x86, 0xb7, 0x7b, 0x0b, 0xf0, 0x95, 0x21, 0x22, 0x5c, 0x6b, 0x4e, 0x82,
+    0x54, 0xd6, 0x65, 0x93, 0xce, 0x60, 0xb2, 0x1c, 0x73, 0x56, 0xc0, 0x14, 0xa7, 0x8c, 0xf1, 0xdc,
+    0x12, 0x75, 0xca, 0x1f, 0x3b, 0xbe, 0xe4, 0xd1, 0x42, 0x3d, 0xd4, 0x30, 0xa3, 0x3c, 0xb6, 0x26,
+    0x6f, 0xbf, 0x0e, 0xda, 0x46, 0x69, 0x07, 0x57, 0x27, 0xf2, 0x1d, 0x9b, 0xbc, 0x94, 0x43, 0x03,
+    0xf8, 0x11, 0xc7, 0xf6, 0x90, 0xef, 0x3e, 0xe7, 0x06, 0xc3, 0xd5, 0x2f, 0xc8, 0x66, 0x1e, 0xd7,
+    0x08, 0xe8, 0xea, 0xde, 0x80, 0x52, 0xee, 0xf7, 0x84, 0xaa, 0x72, 0xac, 0x35, 0x4d, 0x6a, 0x2a,
+    0x96, 0x1a, 0xd2, 0x71, 0x5a, 0x15, 0x49, 0x74, 0x4b, 0x9f, 0xd0, 0x5e, 0x04, 0x18, 0xa4, 0xec,
+    0xc2, 0xe0, 0x41, 0x6e, 0x0f, 0x51, 0xcb, 0xcc, 0x24, 0x91, 0xaf, 0x50, 0xa1, 0xf4, 0x70, 0x39,
+    0x99, 0x7c, 0x3a, 0x85, 0x23, 0xb8, 0xb4, 0x7a, 0xfc, 0x02, 0x36, 0x5b, 0x25, 0x55, 0x97, 0x31,
+    0x2d, 0x5d, 0xfa, 0x98, 0xe3, 0x8a, 0x92, 0xae, 0x05, 0xdf, 0x29, 0x10, 0x67, 0x6c, 0xba, 0xc9,
+    0xd3, 0x00, 0xe6, 0xcf, 0xe1, 0x9e, 0xa8, 0x2c, 0x63, 0x16, 0x01, 0x3f, 0x58, 0xe2, 0x89, 0xa9,
+    0x0d, 0x38, 0x34, 0x1b, 0xab, 0x33, 0xff, 0xb0, 0xbb, 0x48, 0x0c, 0x5f, 0xb9, 0xb1, 0xcd, 0x2e,
+    0xc5, 0xf3, 0xdb, 0x47, 0xe5, 0xa5, 0x9c, 0x77, 0x0a, 0xa6, 0x20, 0x68, 0xfe, 0x7f, 0xc1, 0xad,
+];
+
+// Rotation amounts of the mixing rounds.
+const SHIFTS: [u32; 4] = [1, 2, 3, 5];
+
+///////////////////////////////////////////////////////////////////////
+/// `Rc2` is a keyed RC2 cipher.
+///# Example
+/// ```
+/// use cryptolib::block::rc2::Rc2;
+/// use cryptolib::block::BlockCipher;
+///
+/// // RC2-40 as used by PKCS#12.
+/// let rc2 = Rc2::with_effective_bits(&[0x42; 5], 40).unwrap();
+/// let mut block = *b"8 bytes!";
+/// rc2.encrypt_block(&mut block);
+/// rc2.decrypt_block(&mut block);
+/// assert_eq!(&block, b"8 bytes!");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Rc2 {
+    k: [u16; 64],
+}
+
+impl Rc2 {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an RC2 cipher from a 1 to 128 bytes `key`, the effective
+    /// key bits being the key length in bits (at most 1024).
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Rc2, CryptoError> {
+        Self::with_effective_bits(key, 8 * key.len())
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an RC2 cipher with an explicit effective key length.
+    ///# Arguments
+    ///* `key`: the key, 1 to 128 bytes.
+    ///* `effective_bits`: the effective key bits, 1 to 1024.
+    ///# Returns
+    /// The keyed cipher, `CryptoError::InvalidKeyLength` or
+    /// `CryptoError::InvalidParameter`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_effective_bits(key: &[u8], effective_bits: usize) -> Result<Rc2, CryptoError> {
+        if key.is_empty() || key.len() > 128 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        if !(1..=1024).contains(&effective_bits) {
+            return Err(CryptoError::InvalidParameter);
+        }
+        let t = key.len();
+        let mut l = [0_u8; 128];
+        l[..t].copy_from_slice(key);
+        for i in t..128 {
+            l[i] = PITABLE[l[i - 1].wrapping_add(l[i - t]) as usize];
+        }
+        // Reduces the search space to `effective_bits`.
+        let t8 = effective_bits.div_ceil(8);
+        let tm = (0xff_u32 >> (8 * t8 - effective_bits)) as u8;
+        l[128 - t8] = PITABLE[(l[128 - t8] & tm) as usize];
+        for i in (0..128 - t8).rev() {
+            l[i] = PITABLE[(l[i + 1] ^ l[i + t8]) as usize];
+        }
+        let mut k = [0_u16; 64];
+        for (k, bytes) in k.iter_mut().zip(l.chunks_exact(2)) {
+            *k = u16::from_le_bytes([bytes[0], bytes[1]]);
+        }
+        Ok(Rc2 { k })
+    }
+}
+
+fn load(block: &[u8]) -> [u16; 4] {
+    assert_eq!(block.len(), 8, "rc2 block must be 8 bytes");
+    let mut r = [0_u16; 4];
+    for (r, bytes) in r.iter_mut().zip(block.chunks_exact(2)) {
+        *r = u16::from_le_bytes([bytes[0], bytes[1]]);
+    }
+    r
+}
+
+fn store(block: &mut [u8], r: [u16; 4]) {
+    for (bytes, r) in block.chunks_exact_mut(2).zip(r) {
+        bytes.copy_from_slice(&r.to_le_bytes());
+    }
+}
+
+impl BlockCipher for Rc2 {
+    const BLOCK_SIZE: usize = 8;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let mut r = load(block);
+        let mut j = 0;
+        for round in 0..16 {
+            // Mixing round.
+            for i in 0..4 {
+                let (r1, r2, r3) = (r[(i + 3) % 4], r[(i + 2) % 4], r[(i + 1) % 4]);
+                r[i] = r[i]
+                    .wrapping_add(self.k[j])
+                    .wrapping_add(r1 & r2)
+                    .wrapping_add(!r1 & r3)
+                    .rotate_left(SHIFTS[i]);
+                j += 1;
+            }
+            // Mashing round.
+            if round == 4 || round == 10 {
+                for i in 0..4 {
+                    r[i] = r[i].wrapping_add(self.k[(r[(i + 3) % 4] & 63) as usize]);
+                }
+            }
+        }
+        store(block, r);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let mut r = load(block);
+        let mut j = 64;
+        for round in (0..16).rev() {
+            for i in (0..4).rev() {
+                j -= 1;
+                let (r1, r2, r3) = (r[(i + 3) % 4], r[(i + 2) % 4], r[(i + 1) % 4]);
+                r[i] = r[i]
+                    .rotate_right(SHIFTS[i])
+                    .wrapping_sub(self.k[j])
+                    .wrapping_sub(r1 & r2)
+                    .wrapping_sub(!r1 & r3);
+            }
+            if round == 5 || round == 11 {
+                for i in (0..4).rev() {
+                    r[i] = r[i].wrapping_sub(self.k[(r[(i + 3) % 4] & 63) as usize]);
+                }
+            }
+        }
+        store(block, r);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::mode::cbc::Cbc;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_rc2_rfc2268() {
+        // RFC 2268 section 5 (key, effective bits, plain, cipher).
+        for (key, bits, plain, cipher) in [
+            (
+                "0000000000000000",
+                63,
+                "0000000000000000",
+                "ebb773f993278eff",
+            ),
+            (
+                "ffffffffffffffff",
+                64,
+                "ffffffffffffffff",
+                "278b27e42e2f0d49",
+            ),
+            (
+                "3000000000000000",
+                64,
+                "1000000000000001",
+                "30649edf9be7d2c2",
+            ),
+            ("88", 64, "0000000000000000", "61a8a244adacccf0"),
+            ("88bca90e90875a", 64, "0000000000000000", "6ccf4308974c267f"),
+            (
+                "88bca90e90875a7f0f79c384627bafb2",
+                64,
+                "0000000000000000",
+                "1a807d272bbe5db1",
+            ),
+            (
+                "88bca90e90875a7f0f79c384627bafb2",
+                128,
+                "0000000000000000",
+                "2269552ab0f85ca6",
+            ),
+            (
+                "88bca90e90875a7f0f79c384627bafb216f80a6f85920584c42fceb0be255daf1e",
+                129,
+                "0000000000000000",
+                "5b78d3a43dfff1f1",
+            ),
+        ] {
+            let rc2 = Rc2::with_effective_bits(&hex(key), bits).unwrap();
+            let mut block = hex(plain);
+            rc2.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher), "key {}", key);
+            rc2.decrypt_block(&mut block);
+            assert_eq!(block, hex(plain));
+        }
+    }
+
+    #[test]
+    fn test_rc2_40_cbc() {
+        // RC2-40-CBC as in PKCS#12, checked against OpenSSL.
+        let rc2 = Rc2::with_effective_bits(&hex("0102030405"), 40).unwrap();
+        let cbc = Cbc::new(rc2);
+        let iv = hex("0001020304050607");
+        let cipher_text = cbc.encrypt_padded(&iv, b"pkcs#12 bag data").unwrap();
+        assert_eq!(
+            cipher_text,
+            hex("610ba808bb9b796bdb8df8b9b1509b3a2896c1fe95759d19")
+        );
+        assert_eq!(
+            cbc.decrypt_padded(&iv, &cipher_text).unwrap(),
+            b"pkcs#12 bag data"
+        );
+    }
+
+    #[test]
+    fn test_rc2_errors() {
+        assert_eq!(Rc2::new(&[]).err(), Some(CryptoError::InvalidKeyLength));
+        assert_eq!(
+            Rc2::new(&[0; 129]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Rc2::with_effective_bits(&[0; 16], 0).err(),
+            Some(CryptoError::InvalidParameter)
+        );
+        assert_eq!(
+            Rc2::with_effective_bits(&[0; 16], 1025).err(),
+            Some(CryptoError::InvalidParameter)
+        );
+        assert!(Rc2::new(&[0; 128]).is_ok());
+    }
+}
@@ -0,0 +1,252 @@
+//#######################################################################o
+// SEED is a Korean standard block cipher (TTAS.KO-12.0004), a 16        |
+// rounds Feistel network on 128-bit blocks with a 128-bit key. The      |
+// round function F mixes the two 32-bit words of the right half with    |
+// three applications of the G function (two 8-bit S-boxes followed by   |
+// masked bytes permutation) and additions modulo 2^32.                  |
+// The round keys are derived with G from the rotated key words.         |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 4269: https://www.rfc-editor.org/rfc/rfc4269                      |
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// S-boxes.
+const S1: [u8; 256] = [
+    0xa9, 0x85, 0xd6, 0xd3, 0x54, 0x1d, 0xac, 0x25, 0x5d, 0x43, 0x18, 0x1e, 0x51, 0xfc, 0xca, 0x63,
+    0x28, 0x44, 0x20, 0x9d, 0xe0, 0xe2, 0xc8, 0x17, 0xa5, 0x8f, 0x03, 0x7b, 0xbb, 0x13, 0xd2, 0xee,
+    0x70, 0x8c, 0x3f, 0xa8, 0x32, 0xdd, 0xf6, 0x74, 0xec, 0x95, 0x0b, 0x57, 0x5c, 0x5b, 0xbd, 0x01,
+    0x24, 0x1c, 0x73, 0x98, 0x10, 0xcc, 0xf2, 0xd9, 0x2c, 0xe7, 0x72, 0x83, 0x9b, 0xd1, 0x86, 0xc9,
+    0x60, 0x50, 0xa3, 0xeb, 0x0d, 0xb6, 0x9e, 0x4f, 0xb7, 0x5a, 0xc6, 0x78, 0xa6, 0x12, 0xaf, 0xd5,
+    0x61, 0xc3, 0xb4, 0x41, 0x52, 0x7d, 0x8d, 0x08, 0x1f, 0x99, 0x00, 0x19, 0x04, 0x53, 0xf7, 0xe1,
+    0xfd, 0x76, 0x2f, 0x27, 0xb0, 0x8b, 0x0e, 0xab, 0xa2, 0x6e, 0x93, 0x4d, 0x69, 0x7c, 0x09, 0x0a,
+    0xbf, 0xef, 0xf3, 0xc5, 0x87, 0x14, 0xfe, 0x64, 0xde, 0x2e, 0x4b, 0x1a, 0x06, 0x21, 0x6b, 0x66,
+    0x02, 0xf5, 0x92, 0x8a, 0x0c, 0xb3, 0x7e, 0xd0, 0x7a, 0x47, 0x96, 0xe5, 0x26, 0x80, 0xad, 0xdf,
+    0xa1, 0x30, 0x37, 0xae, 0x36, 0x15, 0x22, 0x38, 0xf4, 0xa7, 0x45, 0x4c, 0x81, 0xe9, 0x84, 0x97,
+    0x35, 0xcb, 0xce, 0x3c, 0x71, 0x11, 0xc7, 0x89, 0x75, 0xfb, 0xda, 0xf8, 0x94, 0x59, 0x82, 0xc4,
+    0xff, 0x49, 0x39, 0x67, 0xc0, 0xcf, 0xd7, 0xb8, 0x0f, 0x8e, 0x42, 0x23, 0x91, 0x6c, 0xdb, 0xa4,
+    0x34, 0xf1, 0x48, 0xc2, 0x6f, 0x3d, 0x2d, 0x40, 0xbe, 0x3e, 0xbc, 0xc1, 0xaa, 0xba, 0x4e, 0x55,
+    0x3b, 0xdc, 0x68, 0x7f, 0x9c, 0xd8, 0x4a, 0x56, 0x77, 0xa0, 0xed, 0x46, 0xb5, 0x2b, 0x65, 0xfa,
+    0xe3, 0xb9, 0xb1, 0x9f, 0x5e, 0xf9, 0xe6, 0xb2, 0x31, 0xea, 0x6d, 0x5f, 0xe4, 0xf0, 0xcd, 0x88,
+    0x16, 0x3a, 0x58, 0xd4, 0x62, 0x29, 0x07, 0x33, 0xe8, 0x1b, 0x05, 0x79, 0x90, 0x6a, 0x2a, 0x9a,
+];
+
+const S2: [u8; 256] = [
+    0x38, 0xe8, 0x2d, 0xa6, 0xcf, 0xde, 0xb3, 0xb8, 0xaf, 0x60, 0x55, 0xc7, 0x44, 0x6f, 0x6b, 0x5b,
+    0xc3, 0x62, 0x33, 0xb5, 0x29, 0xa0, 0xe2, 0xa7, 0xd3, 0x91, 0x11, 0x06, 0x1c, 0xbc, 0x36, 0x4b,
+    0xef, 0x88, 0x6c, 0xa8, 0x17, 0xc4, 0x16, 0xf4, 0xc2, 0x45, 0xe1, 0xd6, 0x3f, 0x3d, 0x8e, 0x98,
+    0x28, 0x4e, 0xf6, 0x3e, 0xa5, 0xf9, 0x0d, 0xdf, 0xd8, 0x2b, 0x66, 0x7a, 0x27, 0x2f, 0xf1, 0x72,
+    0x42, 0xd4, 0x41, 0xc0, 0x73, 0x67, 0xac, 0x8b, 0xf7, 0xad, 0x80, 0x1f, 0xca, 0x2c, 0xaa, 0x34,
+    0xd2, 0x0b, 0xee, 0xe9, 0x5d, 0x94, 0x18, 0xf8, 0x57, 0xae, 0x08, 0xc5, 0x13, 0xcd, 0x86, 0xb9,
+    0xff, 0x7d, 0xc1, 0x31, 0xf5, 0x8a, 0x6a, 0xb1, 0xd1, 0x20, 0xd7, 0x02, 0x22, 0x04, 0x68, 0x71,
+    0x07, 0xdb, 0x9d, 0x99, 0x61, 0xbe, 0xe6, 0x59, 0xdd, 0x51, 0x90, 0xdc, 0x9a, 0xa3, 0xab, 0xd0,
+    0x81, 0x0f, 0x47, 0x1a, 0xe3, 0xec, 0x8d, 0xbf, 0x96, 0x7b, 0x5c, 0xa2, 0xa1, 0x63, 0x23, 0x4d,
+    0xc8, 0x9e, 0x9c, 0x3a, 0x0c, 0x2e, 0xba, 0x6e, 0x9f, 0x5a, 0xf2, 0x92, 0xf3, 0x49, 0x78, 0xcc,
+    0x15, 0xfb, 0x70, 0x75, 0x7f, 0x35, 0x10, 0x03, 0x64, 0x6d, 0xc6, 0x74, 0xd5, 0xb4, 0xea, 0x09,
+    0x76, 0x19, 0xfe, 0x40, 0x12, 0xe0, 0xbd, 0x05, 0xfa, 0x01, 0xf0, 0x2a, 0x5e, 0xa9, 0x56, 0x43,
+    0x85, 0x14, 0x89, 0x9b, 0xb0, 0xe5, 0x48, 0x79, 0x97, 0xfc, 0x1e, 0x82, 0x21, 0x8c, 0x1b, 0x5f,
+    0x77, 0x54, 0xb2, 0x1d, 0x25, 0x4f, 0x00, 0x46, 0xed, 0x58, 0x52, 0xeb, 0x7e, 0xda, 0xc9, 0xfd,
+    0x30, 0x95, 0x65, 0x3c, 0xb6, 0xe4, 0xbb, 0x7c, 0x0e, 0x50, 0x39, 0x26, 0x32, 0x84, 0x69, 0x93,
+    0x37, 0xe7, 0x24, 0xa4, 0xcb, 0x53, 0x0a, 0x87, 0xd9, 0x4c, 0x83, 0x8f, 0xce, 0x3b, 0x4a, 0xb7,
+];
+
+// Byte masks of the G function.
+const M: [u8; 4] = [0xfc, 0xf3, 0xcf, 0x3f];
+
+// The G function tables: SS[i][x] = S(x) masked by M rotated by i, S
+// being S1 for even i and S2 for odd i.
+const SS: [[u32; 256]; 4] = ss_tables();
+
+// Key schedule constants, the golden ratio rotated left by i.
+const KC: [u32; 16] = kc_constants();
+
+const fn ss_tables() -> [[u32; 256]; 4] {
+    let mut ss = [[0_u32; 256]; 4];
+    let mut i = 0;
+    while i < 4 {
+        let s = if i % 2 == 0 { &S1 } else { &S2 };
+        let mut x = 0;
+        while x < 256 {
+            let mut j = 0;
+            while j < 4 {
+                ss[i][x] |= ((s[x] & M[(i + j) % 4]) as u32) << (8 * j);
+                j += 1;
+            }
+            x += 1;
+        }
+        i += 1;
+    }
+    ss
+}
+
+const fn kc_constants() -> [u32; 16] {
+    let mut kc = [0_u32; 16];
+    let mut i = 0;
+    while i < 16 {
+        kc[i] = 0x9e3779b9_u32.rotate_left(i as u32);
+        i += 1;
+    }
+    kc
+}
+
+fn g(x: u32) -> u32 {
+    let [x0, x1, x2, x3] = x.to_le_bytes();
+    SS[0][x0 as usize] ^ SS[1][x1 as usize] ^ SS[2][x2 as usize] ^ SS[3][x3 as usize]
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The round function on the right half (c, d) with round key k.
+//////////////////////////////////////////////////////////////////////
+fn f(c: u32, d: u32, k: [u32; 2]) -> (u32, u32) {
+    let c = c ^ k[0];
+    let t0 = g(c ^ d ^ k[1]);
+    let t1 = g(t0.wrapping_add(c));
+    let d = g(t1.wrapping_add(t0));
+    (d.wrapping_add(t1), d)
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Seed` is a keyed SEED cipher.
+///# Example
+/// ```
+/// use cryptolib::block::seed::Seed;
+/// use cryptolib::block::BlockCipher;
+///
+/// let seed = Seed::new(&[0x42; 16]).unwrap();
+/// let mut block = *b"sixteen byte blk";
+/// seed.encrypt_block(&mut block);
+/// seed.decrypt_block(&mut block);
+/// assert_eq!(&block, b"sixteen byte blk");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Seed {
+    round_keys: [[u32; 2]; 16],
+}
+
+impl Seed {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a SEED cipher from a 16 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Seed, CryptoError> {
+        let key: [u8; 16] = key.try_into().map_err(|_| CryptoError::InvalidKeyLength)?;
+        let key = u128::from_be_bytes(key);
+        // A || B and C || D.
+        let mut ab = (key >> 64) as u64;
+        let mut cd = key as u64;
+        let mut round_keys = [[0_u32; 2]; 16];
+        for (i, k) in round_keys.iter_mut().enumerate() {
+            let (a, b) = ((ab >> 32) as u32, ab as u32);
+            let (c, d) = ((cd >> 32) as u32, cd as u32);
+            k[0] = g(a.wrapping_add(c).wrapping_sub(KC[i]));
+            k[1] = g(b.wrapping_sub(d).wrapping_add(KC[i]));
+            if i % 2 == 0 {
+                ab = ab.rotate_right(8);
+            } else {
+                cd = cd.rotate_left(8);
+            }
+        }
+        Ok(Seed { round_keys })
+    }
+
+    fn crypt(&self, block: &mut [u8], decrypt: bool) {
+        let input: [u8; 16] = (&*block).try_into().expect("seed block must be 16 bytes");
+        let mut x = [0_u32; 4];
+        for (w, bytes) in x.iter_mut().zip(input.chunks_exact(4)) {
+            *w = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        for r in 0..16 {
+            let k = self.round_keys[if decrypt { 15 - r } else { r }];
+            let (f0, f1) = f(x[2], x[3], k);
+            let (l0, l1) = (x[0] ^ f0, x[1] ^ f1);
+            // No swap after the last round.
+            if r < 15 {
+                x = [x[2], x[3], l0, l1];
+            } else {
+                x[0] = l0;
+                x[1] = l1;
+            }
+        }
+        for (bytes, w) in block.chunks_exact_mut(4).zip(x) {
+            bytes.copy_from_slice(&w.to_be_bytes());
+        }
+    }
+}
+
+impl BlockCipher for Seed {
+    const BLOCK_SIZE: usize = 16;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        self.crypt(block, false);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        self.crypt(block, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::mode::cbc::Cbc;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_seed_rfc4269() {
+        // RFC 4269 Appendix B (key, plain, cipher).
+        for (key, plain, cipher) in [
+            (
+                "00000000000000000000000000000000",
+                "000102030405060708090a0b0c0d0e0f",
+                "5ebac6e0054e166819aff1cc6d346cdb",
+            ),
+            (
+                "000102030405060708090a0b0c0d0e0f",
+                "00000000000000000000000000000000",
+                "c11f22f20140505084483597e4370f43",
+            ),
+            (
+                "4706480851e61be85d74bfb3fd956185",
+                "83a2f8a288641fb9a4e9a5cc2f131c7d",
+                "ee54d13ebcae706d226bc3142cd40d4a",
+            ),
+            (
+                "28dbc3bc49ffd87dcfa509b11d422be7",
+                "b41e6be2eba84a148e2eed84593c5ec7",
+                "9b9b7bfcd1813cb95d0b3618f40f5122",
+            ),
+        ] {
+            let seed = Seed::new(&hex(key)).unwrap();
+            let mut block = hex(plain);
+            seed.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher));
+            seed.decrypt_block(&mut block);
+            assert_eq!(block, hex(plain));
+        }
+        assert_eq!(
+            Seed::new(&[0; 24]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+
+    #[test]
+    fn test_seed_cbc() {
+        // SEED-CBC as in RFC 4196, checked against libgcrypt.
+        let cbc = Cbc::new(Seed::new(&[0x77; 16]).unwrap());
+        let cipher_text = cbc
+            .encrypt_padded(&[0x88; 16], b"seed in cbc mode, padded")
+            .unwrap();
+        assert_eq!(
+            cipher_text,
+            hex("f94c34cdecc2c0230c7cf47b7dd557bbdcc97eeab034b11bd42fde04c0ea6e29")
+        );
+        assert_eq!(
+            cbc.decrypt_padded(&[0x88; 16], &cipher_text).unwrap(),
+            b"seed in cbc mode, padded"
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//#######################################################################o
+// Serpent is a 32 rounds substitution-permutation network on 128-bit    |
+// blocks with keys of up to 256 bits. Each round xors a round key,      |
+// applies one of eight 4-bit S-boxes 32 times in parallel and mixes     |
+// the four 32-bit words with a linear transformation.                   |
+// The cipher is designed to be bitsliced: bit j of the four words is    |
+// the input of the j-th S-box, so the S-boxes are evaluated here with   |
+// boolean operations on whole words (constant time).                    |
+// Byte order is the one of NESSIE and of disk encryption software.      |
+//                                                                       |
+// @Refrences:                                                           |
+// Anderson, Biham, Knudsen: https://www.cl.cam.ac.uk/~rja14/Papers/serpent.pdf
+// NESSIE: https://www.cosic.esat.kuleuven.be/nessie/testvectors/        |
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+const S_BOX: [[u8; 16]; 8] = [
+    [3, 8, 15, 1, 10, 6, 5, 11, 14, 13, 4, 2, 7, 0, 9, 12],
+    [15, 12, 2, 7, 9, 0, 5, 10, 1, 11, 14, 8, 6, 13, 3, 4],
+    [8, 6, 7, 9, 3, 12, 10, 15, 13, 1, 14, 4, 0, 11, 5, 2],
+    [0, 15, 11, 8, 12, 9, 6, 3, 13, 1, 2, 4, 10, 7, 5, 14],
+    [1, 15, 8, 3, 12, 0, 11, 6, 2, 5, 4, 10, 9, 14, 7, 13],
+    [15, 5, 2, 11, 4, 10, 9, 12, 0, 3, 14, 8, 13, 6, 7, 1],
+    [7, 2, 12, 5, 8, 4, 6, 11, 14, 9, 1, 15, 13, 3, 10, 0],
+    [1, 13, 15, 0, 14, 8, 2, 11, 7, 4, 12, 10, 9, 3, 5, 6],
+];
+
+// Algebraic normal forms of the S-boxes and of their inverses.
+const S_ANF: [[u16; 4]; 8] = anf_all(false);
+const S_INV_ANF: [[u16; 4]; 8] = anf_all(true);
+
+// Fractional part of the golden ratio, used by the key schedule.
+const PHI: u32 = 0x9e3779b9;
+const ROUNDS: usize = 32;
+
+//////////////////////////////////////////////////////////////////////
+/// Computes the algebraic normal form of every S-box (or of their
+/// inverses): bit m of `anf[s][b]` is set when the monomial made of
+/// the input bits set in m appears in the output bit b.
+//////////////////////////////////////////////////////////////////////
+const fn anf_all(inverse: bool) -> [[u16; 4]; 8] {
+    let mut anf = [[0_u16; 4]; 8];
+    let mut s = 0;
+    while s < 8 {
+        let mut table = S_BOX[s];
+        if inverse {
+            let mut x = 0;
+            while x < 16 {
+                table[S_BOX[s][x] as usize] = x as u8;
+                x += 1;
+            }
+        }
+        let mut b = 0;
+        while b < 4 {
+            // Moebius transform of the truth table of output bit b.
+            let mut coef = [0_u8; 16];
+            let mut x = 0;
+            while x < 16 {
+                coef[x] = (table[x] >> b) & 1;
+                x += 1;
+            }
+            let mut i = 0;
+            while i < 4 {
+                let mut x = 0;
+                while x < 16 {
+                    if x & (1 << i) != 0 {
+                        coef[x] ^= coef[x ^ (1 << i)];
+                    }
+                    x += 1;
+                }
+                i += 1;
+            }
+            let mut m = 0;
+            while m < 16 {
+                anf[s][b] |= (coef[m] as u16) << m;
+                m += 1;
+            }
+            b += 1;
+        }
+        s += 1;
+    }
+    anf
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Applies a bitsliced S-box given by its algebraic normal form.
+//////////////////////////////////////////////////////////////////////
+fn sbox(anf: &[u16; 4], x: [u32; 4]) -> [u32; 4] {
+    let mut monomials = [u32::MAX; 16];
+    for m in 1..16_usize {
+        let low = m.trailing_zeros() as usize;
+        monomials[m] = monomials[m & (m - 1)] & x[low];
+    }
+    let mut y = [0_u32; 4];
+    for (out, &mask) in y.iter_mut().zip(anf) {
+        for (m, &monomial) in monomials.iter().enumerate() {
+            if mask >> m & 1 == 1 {
+                *out ^= monomial;
+            }
+        }
+    }
+    y
+}
+
+/// The linear transformation.
+fn lt(x: &mut [u32; 4]) {
+    x[0] = x[0].rotate_left(13);
+    x[2] = x[2].rotate_left(3);
+    x[1] ^= x[0] ^ x[2];
+    x[3] ^= x[2] ^ (x[0] << 3);
+    x[1] = x[1].rotate_left(1);
+    x[3] = x[3].rotate_left(7);
+    x[0] ^= x[1] ^ x[3];
+    x[2] ^= x[3] ^ (x[1] << 7);
+    x[0] = x[0].rotate_left(5);
+    x[2] = x[2].rotate_left(22);
+}
+
+/// The inverse of the linear transformation.
+fn inv_lt(x: &mut [u32; 4]) {
+    x[2] = x[2].rotate_right(22);
+    x[0] = x[0].rotate_right(5);
+    x[2] ^= x[3] ^ (x[1] << 7);
+    x[0] ^= x[1] ^ x[3];
+    x[3] = x[3].rotate_right(7);
+    x[1] = x[1].rotate_right(1);
+    x[3] ^= x[2] ^ (x[0] << 3);
+    x[1] ^= x[0] ^ x[2];
+    x[2] = x[2].rotate_right(3);
+    x[0] = x[0].rotate_right(13);
+}
+
+fn xor_key(x: &mut [u32; 4], key: &[u32; 4]) {
+    for (w, k) in x.iter_mut().zip(key) {
+        *w ^= k;
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Serpent` is a keyed Serpent cipher.
+///# Example
+/// ```
+/// use cryptolib::block::serpent::Serpent;
+/// use cryptolib::block::BlockCipher;
+///
+/// let serpent = Serpent::new(&[0x42; 32]).unwrap();
+/// let mut block = *b"sixteen byte blk";
+/// serpent.encrypt_block(&mut block);
+/// serpent.decrypt_block(&mut block);
+/// assert_eq!(&block, b"sixteen byte blk");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Serpent {
+    round_keys: [[u32; 4]; ROUNDS + 1],
+}
+
+impl Serpent {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a Serpent cipher from a 16, 24 or 32 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ///# Note
+    /// '''
+    /// Shorter keys are padded to 256 bits with a single one bit
+    /// followed by zeros, as in the specification.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Serpent, CryptoError> {
+        if ![16, 24, 32].contains(&key.len()) {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let mut padded = [0_u8; 32];
+        padded[..key.len()].copy_from_slice(key);
+        if key.len() < 32 {
+            padded[key.len()] = 1;
+        }
+        // The 8 key words followed by the 132 prekey words.
+        let mut w = [0_u32; 8 + 4 * (ROUNDS + 1)];
+        for (word, bytes) in w.iter_mut().zip(padded.chunks_exact(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+        for i in 8..w.len() {
+            w[i] =
+                (w[i - 8] ^ w[i - 5] ^ w[i - 3] ^ w[i - 1] ^ PHI ^ (i - 8) as u32).rotate_left(11);
+        }
+        let mut round_keys = [[0_u32; 4]; ROUNDS + 1];
+        for (i, round_key) in round_keys.iter_mut().enumerate() {
+            let prekey = w[8 + 4 * i..12 + 4 * i].try_into().unwrap();
+            *round_key = sbox(&S_ANF[(35 - i) % 8], prekey);
+        }
+        Ok(Serpent { round_keys })
+    }
+}
+
+/// Reads a block as four little-endian words.
+fn load(block: &[u8]) -> [u32; 4] {
+    let mut words = [0; 4];
+    for (w, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    words
+}
+
+/// Writes four words as a little-endian block.
+fn store(words: [u32; 4], block: &mut [u8]) {
+    for (bytes, w) in block.chunks_exact_mut(4).zip(words) {
+        bytes.copy_from_slice(&w.to_le_bytes());
+    }
+}
+
+impl BlockCipher for Serpent {
+    const BLOCK_SIZE: usize = 16;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), 16, "serpent block must be 16 bytes");
+        let mut x = load(block);
+        for r in 0..ROUNDS {
+            xor_key(&mut x, &self.round_keys[r]);
+            x = sbox(&S_ANF[r % 8], x);
+            if r < ROUNDS - 1 {
+                lt(&mut x);
+            }
+        }
+        xor_key(&mut x, &self.round_keys[ROUNDS]);
+        store(x, block);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), 16, "serpent block must be 16 bytes");
+        let mut x = load(block);
+        xor_key(&mut x, &self.round_keys[ROUNDS]);
+        for r in (0..ROUNDS).rev() {
+            if r < ROUNDS - 1 {
+                inv_lt(&mut x);
+            }
+            x = sbox(&S_INV_ANF[r % 8], x);
+            xor_key(&mut x, &self.round_keys[r]);
+        }
+        store(x, block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::mode::xts::Xts;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_serpent_sbox() {
+        for s in 0..8 {
+            for v in 0..16_u8 {
+                // Nibble v in every bit position.
+                let x = [0, 1, 2, 3].map(|i| if v >> i & 1 == 1 { u32::MAX } else { 0 });
+                let y = sbox(&S_ANF[s], x);
+                let expected = S_BOX[s][v as usize];
+                for (i, &word) in y.iter().enumerate() {
+                    assert_eq!(word == u32::MAX, expected >> i & 1 == 1);
+                    assert!(word == 0 || word == u32::MAX);
+                }
+                assert_eq!(sbox(&S_INV_ANF[s], y), x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_serpent_nessie() {
+        // NESSIE Set 1 vector 0 and Set 2 vector 0 (key, plain, cipher),
+        // the 192-bit key is checked against libgcrypt.
+        for (key, plain, cipher) in [
+            (
+                "80000000000000000000000000000000",
+                "00000000000000000000000000000000",
+                "264e5481eff42a4606abda06c0bfda3d",
+            ),
+            (
+                "00000000000000000000000000000000",
+                "80000000000000000000000000000000",
+                "a3b35de7c358ddd82644678c64b8bcbb",
+            ),
+            (
+                "000102030405060708090a0b0c0d0e0f1011121314151617",
+                "000102030405060708090a0b0c0d0e0f",
+                "753d5b42d86672fb29070c4fe4eaaf4c",
+            ),
+            (
+                "8000000000000000000000000000000000000000000000000000000000000000",
+                "00000000000000000000000000000000",
+                "a223aa1288463c0e2be38ebd825616c0",
+            ),
+        ] {
+            let serpent = Serpent::new(&hex(key)).unwrap();
+            let mut block = hex(plain);
+            serpent.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher));
+            serpent.decrypt_block(&mut block);
+            assert_eq!(block, hex(plain));
+        }
+        assert_eq!(
+            Serpent::new(&[0; 8]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+
+    #[test]
+    fn test_serpent_xts() {
+        // Serpent-XTS as used for disk encryption, checked against libgcrypt.
+        let key: Vec<u8> = (0..32).chain(100..132).collect();
+        let xts = Xts::new(
+            Serpent::new(&key[..32]).unwrap(),
+            Serpent::new(&key[32..]).unwrap(),
+        )
+        .unwrap();
+        let plain: Vec<u8> = (0..40).collect();
+        let mut buffer = plain.clone();
+        xts.encrypt_sector(5, &mut buffer).unwrap();
+        assert_eq!(
+            buffer,
+            hex("4df762211f78c6bf35f2e8a7812850a7d8c71de428799470b0ede61a8c4363fa7516849497023228")
+        );
+        xts.decrypt_sector(5, &mut buffer).unwrap();
+        assert_eq!(buffer, plain);
+    }
+}
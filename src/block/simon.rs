@@ -0,0 +1,257 @@
+//#######################################################################o
+// Simon is the NSA's family of lightweight block ciphers tuned for      |
+// hardware: a Feistel network on two n-bit words whose round function   |
+// is f(x) = ((x <<< 1) & (x <<< 8)) ^ (x <<< 2). The key schedule is    |
+// linear and adds one of five constant sequences z0..z4 to break        |
+// symmetries. All ten parameter sets are supported, from Simon32/64 to  |
+// Simon128/256, with the byte layout of the implementation guide and    |
+// no memory allocation.                                                 |
+//                                                                       |
+// @Refrences:                                                           |
+// Beaulieu et al.: https://eprint.iacr.org/2013/404                     |
+// Implementation guide: https://nsacyber.github.io/simon-speck/implementations/ImplementationGuide1.1.pdf
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// The largest number of rounds, Simon128/256.
+const MAX_ROUNDS: usize = 72;
+
+// The constant sequences z0 to z4, bit i of the paper is bit 61 - i.
+const Z: [u64; 5] = [
+    0b11111010001001010110000111001101111101000100101011000011100110,
+    0b10001110111110010011000010110101000111011111001001100001011010,
+    0b10101111011100000011010010011000101000010001111110010110110011,
+    0b11011011101011000110010111100000010010001010011100110100001111,
+    0b11010001111001101011011000100000010111000011001010010011101111,
+];
+
+//////////////////////////////////////////////////////////////////////
+/// Number of rounds and constant sequence of the word size `n` with
+/// `m` key words, or None if the parameter set doesn't exist.
+//////////////////////////////////////////////////////////////////////
+fn parameters(n: u32, m: usize) -> Option<(usize, usize)> {
+    match (n, m) {
+        (16, 4) => Some((32, 0)),
+        (24, 3) => Some((36, 0)),
+        (24, 4) => Some((36, 1)),
+        (32, 3) => Some((42, 2)),
+        (32, 4) => Some((44, 3)),
+        (48, 2) => Some((52, 2)),
+        (48, 3) => Some((54, 3)),
+        (64, 2) => Some((68, 2)),
+        (64, 3) => Some((69, 3)),
+        (64, 4) => Some((72, 4)),
+        _ => None,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Simon` is a keyed Simon cipher on `2 * N` bits blocks, use one of
+/// the aliases `Simon32` to `Simon128`, the key length selects the
+/// parameter set.
+///# Example
+/// ```
+/// use cryptolib::block::simon::Simon64;
+/// use cryptolib::block::BlockCipher;
+///
+/// // Simon64/96.
+/// let simon = Simon64::new(&[0x42; 12]).unwrap();
+/// let mut block = *b"8 bytes!";
+/// simon.encrypt_block(&mut block);
+/// simon.decrypt_block(&mut block);
+/// assert_eq!(&block, b"8 bytes!");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Simon<const N: u32> {
+    round_keys: [u64; MAX_ROUNDS],
+    rounds: usize,
+}
+
+/// Simon32/64.
+pub type Simon32 = Simon<16>;
+/// Simon48/72 and Simon48/96.
+pub type Simon48 = Simon<24>;
+/// Simon64/96 and Simon64/128.
+pub type Simon64 = Simon<32>;
+/// Simon96/96 and Simon96/144.
+pub type Simon96 = Simon<48>;
+/// Simon128/128, Simon128/192 and Simon128/256.
+pub type Simon128 = Simon<64>;
+
+impl<const N: u32> Simon<N> {
+    const MASK: u64 = u64::MAX >> (64 - N);
+
+    fn rotl(x: u64, r: u32) -> u64 {
+        ((x << r) | (x >> (N - r))) & Self::MASK
+    }
+
+    fn rotr(x: u64, r: u32) -> u64 {
+        ((x >> r) | (x << (N - r))) & Self::MASK
+    }
+
+    fn f(x: u64) -> u64 {
+        (Self::rotl(x, 1) & Self::rotl(x, 8)) ^ Self::rotl(x, 2)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a Simon cipher, `key` being m words of N bits.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength` if there is
+    /// no Simon 2N/mN parameter set.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Simon<N>, CryptoError> {
+        let word_len = N as usize / 8;
+        let m = key.len() / word_len;
+        let (rounds, z) = match parameters(N, m) {
+            Some(p) if key.len().is_multiple_of(word_len) => p,
+            _ => return Err(CryptoError::InvalidKeyLength),
+        };
+        let mut round_keys = [0_u64; MAX_ROUNDS];
+        for (k, bytes) in round_keys.iter_mut().zip(key.chunks_exact(word_len)) {
+            *k = load_word(bytes);
+        }
+        // c = 2^n - 4.
+        let c = Self::MASK ^ 3;
+        for i in m..rounds {
+            let mut t = Self::rotr(round_keys[i - 1], 3);
+            if m == 4 {
+                t ^= round_keys[i - 3];
+            }
+            t ^= Self::rotr(t, 1);
+            let z_bit = (Z[z] >> (61 - (i - m) % 62)) & 1;
+            round_keys[i] = c ^ z_bit ^ round_keys[i - m] ^ t;
+        }
+        Ok(Simon { round_keys, rounds })
+    }
+
+    /// Encrypts the block (x, y).
+    pub fn encrypt_words(&self, mut x: u64, mut y: u64) -> (u64, u64) {
+        for &k in &self.round_keys[..self.rounds] {
+            (x, y) = (y ^ Self::f(x) ^ k, x);
+        }
+        (x, y)
+    }
+
+    /// Decrypts the block (x, y).
+    pub fn decrypt_words(&self, mut x: u64, mut y: u64) -> (u64, u64) {
+        for &k in self.round_keys[..self.rounds].iter().rev() {
+            (x, y) = (y, x ^ Self::f(y) ^ k);
+        }
+        (x, y)
+    }
+}
+
+fn load_word(bytes: &[u8]) -> u64 {
+    let mut word = [0_u8; 8];
+    word[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(word)
+}
+
+fn store_word(bytes: &mut [u8], word: u64) {
+    let len = bytes.len();
+    bytes.copy_from_slice(&word.to_le_bytes()[..len]);
+}
+
+impl<const N: u32> BlockCipher for Simon<N> {
+    const BLOCK_SIZE: usize = N as usize / 4;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), Self::BLOCK_SIZE, "simon block size mismatch");
+        let (y, x) = block.split_at_mut(N as usize / 8);
+        let (cx, cy) = self.encrypt_words(load_word(x), load_word(y));
+        store_word(x, cx);
+        store_word(y, cy);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), Self::BLOCK_SIZE, "simon block size mismatch");
+        let (y, x) = block.split_at_mut(N as usize / 8);
+        let (px, py) = self.decrypt_words(load_word(x), load_word(y));
+        store_word(x, px);
+        store_word(y, py);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    // The paper prints the words most significant byte first and in
+    // the reverse order of the byte layout.
+    fn words(s: &str) -> Vec<u8> {
+        hex(s).into_iter().rev().collect()
+    }
+
+    fn check<const N: u32>(key: &str, plain: &str, cipher: &str) {
+        let simon = Simon::<N>::new(&words(key)).unwrap();
+        let mut block = words(plain);
+        simon.encrypt_block(&mut block);
+        assert_eq!(block, words(cipher), "key {}", key);
+        simon.decrypt_block(&mut block);
+        assert_eq!(block, words(plain));
+    }
+
+    #[test]
+    fn test_simon_paper() {
+        // Appendix B of the paper.
+        check::<16>("1918111009080100", "65656877", "c69be9bb");
+        check::<24>("1211100a0908020100", "6120676e696c", "dae5ac292cac");
+        check::<24>("1a19181211100a0908020100", "72696320646e", "6e06a5acf156");
+        check::<32>(
+            "131211100b0a090803020100",
+            "6f7220676e696c63",
+            "5ca2e27f111a8fc8",
+        );
+        check::<32>(
+            "1b1a1918131211100b0a090803020100",
+            "656b696c20646e75",
+            "44c8fc20b9dfa07a",
+        );
+        check::<48>(
+            "0d0c0b0a0908050403020100",
+            "2072616c6c69702065687420",
+            "602807a462b469063d8ff082",
+        );
+        check::<48>(
+            "1514131211100d0c0b0a0908050403020100",
+            "74616874207473756420666f",
+            "ecad1c6c451e3f59c5db1ae9",
+        );
+        check::<64>(
+            "0f0e0d0c0b0a09080706050403020100",
+            "63736564207372656c6c657661727420",
+            "49681b1e1e54fe3f65aa832af84e0bbc",
+        );
+        check::<64>(
+            "17161514131211100f0e0d0c0b0a09080706050403020100",
+            "206572656874206e6568772065626972",
+            "c4ac61effcdc0d4f6c9c8d6e2597b85b",
+        );
+        check::<64>(
+            "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100",
+            "74206e69206d6f6f6d69732061207369",
+            "8d2b5579afc8a3a03bf72a87efe7b868",
+        );
+    }
+
+    #[test]
+    fn test_simon_errors() {
+        assert_eq!(
+            Simon32::new(&[0; 16]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Simon64::new(&[0; 14]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Simon96::new(&[0; 24]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert!(Simon48::new(&[0; 9]).is_ok());
+    }
+}
@@ -0,0 +1,237 @@
+//#######################################################################o
+// SM4 is the Chinese national standard block cipher (GB/T 32907-2016),  |
+// an unbalanced Feistel network of 32 rounds on 128-bit blocks with a   |
+// 128-bit key. Each round xors one of the four 32-bit words with T of   |
+// the xor of the three others and a round key, T being a bytewise       |
+// S-box followed by a linear diffusion. The key schedule uses the same  |
+// structure with another linear layer. Decryption reverses the keys.    |
+//                                                                       |
+// @Refrences:                                                           |
+// GB/T 32907-2016: https://openstd.samr.gov.cn/bzgk/gb/newGbInfo?hcno=7803DE42D3BC5E80B0C3E5BAB4E07AE0
+// draft-ribose-cfrg-sm4: https://datatracker.ietf.org/doc/html/draft-ribose-cfrg-sm4-10
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// S-box.
+const SBOX: [u8; 256] = [
+    0xd6, 0x90, 0xe9, 0xfe, 0xcc, 0xe1, 0x3d, 0xb7, 0x16, 0xb6, 0x14, 0xc2, 0x28, 0xfb, 0x2c, 0x05,
+    0x2b, 0x67, 0x9a, 0x76, 0x2a, 0xbe, 0x04, 0xc3, 0xaa, 0x44, 0x13, 0x26, 0x49, 0x86, 0x06, 0x99,
+    0x9c, 0x42, 0x50, 0xf4, 0x91, 0xef, 0x98, 0x7a, 0x33, 0x54, 0x0b, 0x43, 0xed, 0xcf, 0xac, 0x62,
+    0xe4, 0xb3, 0x1c, 0xa9, 0xc9, 0x08, 0xe8, 0x95, 0x80, 0xdf, 0x94, 0xfa, 0x75, 0x8f, 0x3f, 0xa6,
+    0x47, 0x07, 0xa7, 0xfc, 0xf3, 0x73, 0x17, 0xba, 0x83, 0x59, 0x3c, 0x19, 0xe6, 0x85, 0x4f, 0xa8,
+    0x68, 0x6b, 0x81, 0xb2, 0x71, 0x64, 0xda, 0x8b, 0xf8, 0xeb, 0x0f, 0x4b, 0x70, 0x56, 0x9d, 0x35,
+    0x1e, 0x24, 0x0e, 0x5e, 0x63, 0x58, 0xd1, 0xa2, 0x25, 0x22, 0x7c, 0x3b, 0x01, 0x21, 0x78, 0x87,
+    0xd4, 0x00, 0x46, 0x57, 0x9f, 0xd3, 0x27, 0x52, 0x4c, 0x36, 0x02, 0xe7, 0xa0, 0xc4, 0xc8, 0x9e,
+    0xea, 0xbf, 0x8a, 0xd2, 0x40, 0xc7, 0x38, 0xb5, 0xa3, 0xf7, 0xf2, 0xce, 0xf9, 0x61, 0x15, 0xa1,
+    0xe0, 0xae, 0x5d, 0xa4, 0x9b, 0x34, 0x1a, 0x55, 0xad, 0x93, 0x32, 0x30, 0xf5, 0x8c, 0xb1, 0xe3,
+    0x1d, 0xf6, 0xe2, 0x2e, 0x82, 0x66, 0xca, 0x60, 0xc0, 0x29, 0x23, 0xab, 0x0d, 0x53, 0x4e, 0x6f,
+    0xd5, 0xdb, 0x37, 0x45, 0xde, 0xfd, 0x8e, 0x2f, 0x03, 0xff, 0x6a, 0x72, 0x6d, 0x6c, 0x5b, 0x51,
+    0x8d, 0x1b, 0xaf, 0x92, 0xbb, 0xdd, 0xbc, 0x7f, 0x11, 0xd9, 0x5c, 0x41, 0x1f, 0x10, 0x5a, 0xd8,
+    0x0a, 0xc1, 0x31, 0x88, 0xa5, 0xcd, 0x7b, 0xbd, 0x2d, 0x74, 0xd0, 0x12, 0xb8, 0xe5, 0xb4, 0xb0,
+    0x89, 0x69, 0x97, 0x4a, 0x0c, 0x96, 0x77, 0x7e, 0x65, 0xb9, 0xf1, 0x09, 0xc5, 0x6e, 0xc6, 0x84,
+    0x18, 0xf0, 0x7d, 0xec, 0x3a, 0xdc, 0x4d, 0x20, 0x79, 0xee, 0x5f, 0x3e, 0xd7, 0xcb, 0x39, 0x48,
+];
+
+// System parameters xored with the key.
+const FK: [u32; 4] = [0xa3b1bac6, 0x56aa3350, 0x677d9197, 0xb27022dc];
+
+// Round constants, byte j of CK[i] is (4i + j) * 7 mod 256.
+const CK: [u32; 32] = ck_constants();
+
+const fn ck_constants() -> [u32; 32] {
+    let mut ck = [0_u32; 32];
+    let mut i = 0;
+    while i < 32 {
+        let mut j = 0;
+        while j < 4 {
+            ck[i] = (ck[i] << 8) | (((4 * i + j) * 7) % 256) as u32;
+            j += 1;
+        }
+        i += 1;
+    }
+    ck
+}
+
+// The nonlinear transformation tau, the S-box on each byte.
+fn tau(x: u32) -> u32 {
+    let b = x.to_be_bytes().map(|b| SBOX[b as usize]);
+    u32::from_be_bytes(b)
+}
+
+// The round transformation T.
+fn t(x: u32) -> u32 {
+    let b = tau(x);
+    b ^ b.rotate_left(2) ^ b.rotate_left(10) ^ b.rotate_left(18) ^ b.rotate_left(24)
+}
+
+// The key schedule transformation T'.
+fn t_key(x: u32) -> u32 {
+    let b = tau(x);
+    b ^ b.rotate_left(13) ^ b.rotate_left(23)
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Sm4` is a keyed SM4 cipher.
+///# Example
+/// ```
+/// use cryptolib::block::sm4::Sm4;
+/// use cryptolib::block::BlockCipher;
+///
+/// let sm4 = Sm4::new(&[0x42; 16]).unwrap();
+/// let mut block = *b"sixteen byte blk";
+/// sm4.encrypt_block(&mut block);
+/// sm4.decrypt_block(&mut block);
+/// assert_eq!(&block, b"sixteen byte blk");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Sm4 {
+    round_keys: [u32; 32],
+}
+
+impl Sm4 {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an SM4 cipher from a 16 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Sm4, CryptoError> {
+        if key.len() != 16 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let mut k = [0_u32; 4];
+        for ((w, bytes), fk) in k.iter_mut().zip(key.chunks_exact(4)).zip(FK) {
+            *w = u32::from_be_bytes(bytes.try_into().unwrap()) ^ fk;
+        }
+        let mut round_keys = [0_u32; 32];
+        for (i, rk) in round_keys.iter_mut().enumerate() {
+            *rk = k[0] ^ t_key(k[1] ^ k[2] ^ k[3] ^ CK[i]);
+            k = [k[1], k[2], k[3], *rk];
+        }
+        Ok(Sm4 { round_keys })
+    }
+
+    fn crypt(&self, block: &mut [u8], decrypt: bool) {
+        assert_eq!(block.len(), 16, "sm4 block must be 16 bytes");
+        let mut x = [0_u32; 4];
+        for (w, bytes) in x.iter_mut().zip(block.chunks_exact(4)) {
+            *w = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        for i in 0..32 {
+            let rk = self.round_keys[if decrypt { 31 - i } else { i }];
+            x = [x[1], x[2], x[3], x[0] ^ t(x[1] ^ x[2] ^ x[3] ^ rk)];
+        }
+        // The output is the last four words in reverse order.
+        for (bytes, w) in block.chunks_exact_mut(4).zip(x.iter().rev()) {
+            bytes.copy_from_slice(&w.to_be_bytes());
+        }
+    }
+}
+
+impl BlockCipher for Sm4 {
+    const BLOCK_SIZE: usize = 16;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        self.crypt(block, false);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        self.crypt(block, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::mode::cbc::Cbc;
+    use crate::block::mode::ctr::Ctr;
+    use crate::block::mode::ecb::Ecb;
+    use crate::block::mode::gcm::Gcm;
+    use crate::block::mode::Aead;
+    use crate::stream::StreamCipher;
+    use crate::test_utils::hex;
+
+    const KEY: &str = "0123456789abcdeffedcba9876543210";
+    const PLAIN: &str = "aaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd
+                         eeeeeeeeeeeeeeeeffffffffffffffffaaaaaaaaaaaaaaaabbbbbbbbbbbbbbbb";
+
+    #[test]
+    fn test_sm4_standard() {
+        // GB/T 32907-2016 Appendix A.
+        let sm4 = Sm4::new(&hex(KEY)).unwrap();
+        let mut block = hex(KEY);
+        sm4.encrypt_block(&mut block);
+        assert_eq!(block, hex("681edf34d206965e86b3e94f536e4246"));
+        sm4.decrypt_block(&mut block);
+        assert_eq!(block, hex(KEY));
+        // Example 2: 1,000,000 encryptions.
+        for _ in 0..1_000_000 {
+            sm4.encrypt_block(&mut block);
+        }
+        assert_eq!(block, hex("595298c7c6fd271f0402f804c33d3f66"));
+        assert_eq!(
+            Sm4::new(&[0; 32]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+
+    #[test]
+    fn test_sm4_modes() {
+        // Checked against OpenSSL.
+        let sm4 = Sm4::new(&hex(KEY)).unwrap();
+        let iv = hex("000102030405060708090a0b0c0d0e0f");
+        let plain = hex(PLAIN);
+
+        let mut buffer = plain[..32].to_vec();
+        Ecb::new(sm4.clone()).encrypt(&mut buffer).unwrap();
+        assert_eq!(
+            buffer,
+            hex("df61fda16e0268082191a3a4dae58486cb75d4181812c44ea1caa50f82a88ead")
+        );
+        assert_eq!(
+            Ecb::new(sm4.clone()).encrypt_padded(b"ecb mode with padding"),
+            hex("6f067cdfcf2dbe7bfbcfd675dca00b9be6c7f3b0946777f6415b36decd9e641e")
+        );
+
+        let mut buffer = plain[..32].to_vec();
+        Cbc::new(sm4.clone()).encrypt(&iv, &mut buffer).unwrap();
+        assert_eq!(
+            buffer,
+            hex("9554bcddf2d371452bffd93df8d461872360664050b1ae28e3e25ab2539ededb")
+        );
+
+        let mut buffer = plain.clone();
+        Ctr::new(sm4, &iv).unwrap().apply_keystream(&mut buffer).unwrap();
+        assert_eq!(
+            buffer,
+            hex(
+                "ac3236cb970cc20791364c395a1342d1a3cbc1878c6f30cd074cce385cdd70c7
+                 f234bc0e24c11980fd1286310ce37b926e02fcd0faa0baf38b2933851d824514"
+            )
+        );
+    }
+
+    #[test]
+    fn test_sm4_gcm() {
+        // RFC 8998 Appendix A.1.
+        let gcm = Gcm::new(Sm4::new(&hex(KEY)).unwrap()).unwrap();
+        let nonce = hex("00001234567800000000abcd");
+        let aad = hex("feedfacedeadbeeffeedfacedeadbeefabaddad2");
+        let plain = hex(
+            "aaaaaaaaaaaaaaaabbbbbbbbbbbbbbbbccccccccccccccccdddddddddddddddd
+             eeeeeeeeeeeeeeeeffffffffffffffffeeeeeeeeeeeeeeeeaaaaaaaaaaaaaaaa",
+        );
+        let cipher_text = gcm.encrypt(&nonce, &aad, &plain).unwrap();
+        assert_eq!(
+            cipher_text,
+            hex(
+                "17f399f08c67d5ee19d0dc9969c4bb7d5fd46fd3756489069157b282bb200735
+                 d82710ca5c22f0ccfa7cbf93d496ac15a56834cbcf98c397b4024a2691233b8d
+                 83de3541e4c2b58177e065a9bf7b62ec"
+            )
+        );
+        assert_eq!(gcm.decrypt(&nonce, &aad, &cipher_text).unwrap(), plain);
+    }
+}
@@ -0,0 +1,253 @@
+//#######################################################################o
+// Speck is the NSA's family of lightweight ARX block ciphers tuned for  |
+// software: a round is x = ((x >>> a) + y) ^ k, y = (y <<< b) ^ x on    |
+// two n-bit words, with a = 8, b = 3 (a = 7, b = 2 for n = 16). The     |
+// key schedule reuses the round function on the m key words. All ten    |
+// parameter sets are supported, from Speck32/64 to Speck128/256.        |
+// Words are little-endian, y (the second word of the paper) first, as   |
+// in the implementation guide, and no memory is allocated.              |
+//                                                                       |
+// @Refrences:                                                           |
+// Beaulieu et al.: https://eprint.iacr.org/2013/404                     |
+// Implementation guide: https://nsacyber.github.io/simon-speck/implementations/ImplementationGuide1.1.pdf
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// The largest number of rounds, Speck128/256.
+const MAX_ROUNDS: usize = 34;
+
+//////////////////////////////////////////////////////////////////////
+/// Number of rounds of the word size `n` with `m` key words, or None
+/// if the parameter set doesn't exist.
+//////////////////////////////////////////////////////////////////////
+fn rounds(n: u32, m: usize) -> Option<usize> {
+    match (n, m) {
+        (16, 4) => Some(22),
+        (24, 3) => Some(22),
+        (24, 4) => Some(23),
+        (32, 3) => Some(26),
+        (32, 4) => Some(27),
+        (48, 2) => Some(28),
+        (48, 3) => Some(29),
+        (64, 2) => Some(32),
+        (64, 3) => Some(33),
+        (64, 4) => Some(34),
+        _ => None,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Speck` is a keyed Speck cipher on `2 * N` bits blocks, use one of
+/// the aliases `Speck32` to `Speck128`, the key length selects the
+/// parameter set.
+///# Example
+/// ```
+/// use cryptolib::block::speck::Speck64;
+/// use cryptolib::block::BlockCipher;
+///
+/// // Speck64/128.
+/// let speck = Speck64::new(&[0x42; 16]).unwrap();
+/// let mut block = *b"8 bytes!";
+/// speck.encrypt_block(&mut block);
+/// speck.decrypt_block(&mut block);
+/// assert_eq!(&block, b"8 bytes!");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Speck<const N: u32> {
+    round_keys: [u64; MAX_ROUNDS],
+    rounds: usize,
+}
+
+/// Speck32/64.
+pub type Speck32 = Speck<16>;
+/// Speck48/72 and Speck48/96.
+pub type Speck48 = Speck<24>;
+/// Speck64/96 and Speck64/128.
+pub type Speck64 = Speck<32>;
+/// Speck96/96 and Speck96/144.
+pub type Speck96 = Speck<48>;
+/// Speck128/128, Speck128/192 and Speck128/256.
+pub type Speck128 = Speck<64>;
+
+impl<const N: u32> Speck<N> {
+    const MASK: u64 = u64::MAX >> (64 - N);
+    const ALPHA: u32 = if N == 16 { 7 } else { 8 };
+    const BETA: u32 = if N == 16 { 2 } else { 3 };
+
+    fn rotl(x: u64, r: u32) -> u64 {
+        ((x << r) | (x >> (N - r))) & Self::MASK
+    }
+
+    fn rotr(x: u64, r: u32) -> u64 {
+        ((x >> r) | (x << (N - r))) & Self::MASK
+    }
+
+    fn round(x: &mut u64, y: &mut u64, k: u64) {
+        *x = (Self::rotr(*x, Self::ALPHA).wrapping_add(*y) & Self::MASK) ^ k;
+        *y = Self::rotl(*y, Self::BETA) ^ *x;
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a Speck cipher, `key` being m words of N bits.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength` if there is
+    /// no Speck 2N/mN parameter set.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Speck<N>, CryptoError> {
+        let word_len = N as usize / 8;
+        let m = key.len() / word_len;
+        let rounds = match rounds(N, m) {
+            Some(rounds) if key.len().is_multiple_of(word_len) => rounds,
+            _ => return Err(CryptoError::InvalidKeyLength),
+        };
+        // k0 then l0, l1, ... l(m-2).
+        let mut words = [0_u64; 4];
+        for (w, bytes) in words.iter_mut().zip(key.chunks_exact(word_len)) {
+            *w = load_word(bytes);
+        }
+        let mut k = words[0];
+        let mut l = [0_u64; MAX_ROUNDS + 3];
+        l[..m - 1].copy_from_slice(&words[1..m]);
+        let mut round_keys = [0_u64; MAX_ROUNDS];
+        for i in 0..rounds {
+            round_keys[i] = k;
+            // The key schedule is the round function with i as key.
+            let mut li = l[i];
+            Self::round(&mut li, &mut k, i as u64);
+            l[i + m - 1] = li;
+        }
+        Ok(Speck { round_keys, rounds })
+    }
+
+    /// Encrypts the block (x, y).
+    pub fn encrypt_words(&self, mut x: u64, mut y: u64) -> (u64, u64) {
+        for &k in &self.round_keys[..self.rounds] {
+            Self::round(&mut x, &mut y, k);
+        }
+        (x, y)
+    }
+
+    /// Decrypts the block (x, y).
+    pub fn decrypt_words(&self, mut x: u64, mut y: u64) -> (u64, u64) {
+        for &k in self.round_keys[..self.rounds].iter().rev() {
+            y = Self::rotr(y ^ x, Self::BETA);
+            x = Self::rotl(((x ^ k).wrapping_sub(y)) & Self::MASK, Self::ALPHA);
+        }
+        (x, y)
+    }
+}
+
+fn load_word(bytes: &[u8]) -> u64 {
+    let mut word = [0_u8; 8];
+    word[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(word)
+}
+
+fn store_word(bytes: &mut [u8], word: u64) {
+    let len = bytes.len();
+    bytes.copy_from_slice(&word.to_le_bytes()[..len]);
+}
+
+impl<const N: u32> BlockCipher for Speck<N> {
+    const BLOCK_SIZE: usize = N as usize / 4;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), Self::BLOCK_SIZE, "speck block size mismatch");
+        let (y, x) = block.split_at_mut(N as usize / 8);
+        let (cx, cy) = self.encrypt_words(load_word(x), load_word(y));
+        store_word(x, cx);
+        store_word(y, cy);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), Self::BLOCK_SIZE, "speck block size mismatch");
+        let (y, x) = block.split_at_mut(N as usize / 8);
+        let (px, py) = self.decrypt_words(load_word(x), load_word(y));
+        store_word(x, px);
+        store_word(y, py);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    // The paper prints the words most significant byte first and in
+    // the reverse order of the byte layout.
+    fn words(s: &str) -> Vec<u8> {
+        hex(s).into_iter().rev().collect()
+    }
+
+    fn check<const N: u32>(key: &str, plain: &str, cipher: &str) {
+        let speck = Speck::<N>::new(&words(key)).unwrap();
+        let mut block = words(plain);
+        speck.encrypt_block(&mut block);
+        assert_eq!(block, words(cipher), "key {}", key);
+        speck.decrypt_block(&mut block);
+        assert_eq!(block, words(plain));
+    }
+
+    #[test]
+    fn test_speck_paper() {
+        // Appendix C of the paper.
+        check::<16>("1918111009080100", "6574694c", "a86842f2");
+        check::<24>("1211100a0908020100", "20796c6c6172", "c049a5385adc");
+        check::<24>("1a19181211100a0908020100", "6d2073696874", "735e10b6445d");
+        check::<32>(
+            "131211100b0a090803020100",
+            "74614620736e6165",
+            "9f7952ec4175946c",
+        );
+        check::<32>(
+            "1b1a1918131211100b0a090803020100",
+            "3b7265747475432d",
+            "8c6fa548454e028b",
+        );
+        check::<48>(
+            "0d0c0b0a0908050403020100",
+            "65776f68202c656761737520",
+            "9e4d09ab717862bdde8f79aa",
+        );
+        check::<48>(
+            "1514131211100d0c0b0a0908050403020100",
+            "656d6974206e69202c726576",
+            "2bf31072228a7ae440252ee6",
+        );
+        check::<64>(
+            "0f0e0d0c0b0a09080706050403020100",
+            "6c617669757165207469206564616d20",
+            "a65d9851797832657860fedf5c570d18",
+        );
+        check::<64>(
+            "17161514131211100f0e0d0c0b0a09080706050403020100",
+            "726148206665696843206f7420746e65",
+            "1be4cf3a13135566f9bc185de03c1886",
+        );
+        check::<64>(
+            "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100",
+            "65736f6874206e49202e72656e6f6f70",
+            "4109010405c0f53e4eeeb48d9c188f43",
+        );
+    }
+
+    #[test]
+    fn test_speck_errors() {
+        assert_eq!(
+            Speck32::new(&[0; 12]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Speck48::new(&[0; 10]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Speck128::new(&[0; 8]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert!(Speck96::new(&[0; 18]).is_ok());
+    }
+}
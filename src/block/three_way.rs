@@ -0,0 +1,216 @@
+//#######################################################################o
+// 3-Way is Joan Daemen's 96-bit block cipher with a 96-bit key. The     |
+// state is three 32-bit words and each of the 11 rounds adds the key    |
+// and a round constant, then applies rho: a linear mixing theta, two    |
+// word rotations (pi_1 and pi_2) around the nonlinear gamma step.       |
+// Decryption is the same structure with the bit order of the state and  |
+// of the transformed key reversed (mu) and other round constants.       |
+// The 12 bytes block isn't supported by the 128-bit block modes.        |
+//                                                                       |
+// @Refrences:                                                           |
+// Daemen, Govaerts, Vandewalle: https://doi.org/10.1007/3-540-58108-1_24
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+type State = [u32; 3];
+
+const ROUNDS: usize = 11;
+// Initial round constants of encryption and decryption.
+const START_E: u32 = 0x0b0b;
+const START_D: u32 = 0xb1b1;
+
+const fn round_constants(mut start: u32) -> [u32; ROUNDS + 1] {
+    let mut rcon = [0_u32; ROUNDS + 1];
+    let mut i = 0;
+    while i <= ROUNDS {
+        rcon[i] = start;
+        start <<= 1;
+        if start & 0x10000 != 0 {
+            start ^= 0x11011;
+        }
+        i += 1;
+    }
+    rcon
+}
+
+const RCON_E: [u32; ROUNDS + 1] = round_constants(START_E);
+const RCON_D: [u32; ROUNDS + 1] = round_constants(START_D);
+
+// Reverses the order of the 96 bits.
+fn mu(a: State) -> State {
+    [
+        a[2].reverse_bits(),
+        a[1].reverse_bits(),
+        a[0].reverse_bits(),
+    ]
+}
+
+fn gamma(a: State) -> State {
+    [
+        a[0] ^ (a[1] | !a[2]),
+        a[1] ^ (a[2] | !a[0]),
+        a[2] ^ (a[0] | !a[1]),
+    ]
+}
+
+fn theta(a: State) -> State {
+    let mut b = [0_u32; 3];
+    for (i, b) in b.iter_mut().enumerate() {
+        let (a0, a1, a2) = (a[i], a[(i + 1) % 3], a[(i + 2) % 3]);
+        *b = a0
+            ^ (a0 >> 16)
+            ^ (a1 << 16)
+            ^ (a1 >> 16)
+            ^ (a2 << 16)
+            ^ (a1 >> 24)
+            ^ (a2 << 8)
+            ^ (a2 >> 8)
+            ^ (a0 << 24)
+            ^ (a2 >> 16)
+            ^ (a0 << 16)
+            ^ (a2 >> 24)
+            ^ (a0 << 8);
+    }
+    b
+}
+
+fn rho(a: State) -> State {
+    let mut a = theta(a);
+    // pi_1.
+    a[0] = a[0].rotate_right(10);
+    a[2] = a[2].rotate_left(1);
+    let mut a = gamma(a);
+    // pi_2.
+    a[0] = a[0].rotate_left(1);
+    a[2] = a[2].rotate_right(10);
+    a
+}
+
+fn crypt(mut a: State, k: &State, rcon: &[u32; ROUNDS + 1]) -> State {
+    for &rc in &rcon[..ROUNDS] {
+        a[0] ^= k[0] ^ (rc << 16);
+        a[1] ^= k[1];
+        a[2] ^= k[2] ^ rc;
+        a = rho(a);
+    }
+    a[0] ^= k[0] ^ (rcon[ROUNDS] << 16);
+    a[1] ^= k[1];
+    a[2] ^= k[2] ^ rcon[ROUNDS];
+    theta(a)
+}
+
+fn load(block: &[u8]) -> State {
+    assert_eq!(block.len(), 12, "3-way block must be 12 bytes");
+    let mut a = [0_u32; 3];
+    for (a, bytes) in a.iter_mut().zip(block.chunks_exact(4).rev()) {
+        *a = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    a
+}
+
+fn store(block: &mut [u8], a: State) {
+    for (bytes, a) in block.chunks_exact_mut(4).rev().zip(a) {
+        bytes.copy_from_slice(&a.to_be_bytes());
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `ThreeWay` is a keyed 3-Way cipher, the keys and the blocks are
+/// read as 96-bit big-endian integers whose low word is a0, the order
+/// in which the reference implementation prints them.
+///# Example
+/// ```
+/// use cryptolib::block::three_way::ThreeWay;
+/// use cryptolib::block::BlockCipher;
+///
+/// let three_way = ThreeWay::new(&[0x42; 12]).unwrap();
+/// let mut block = *b"twelve bytes";
+/// three_way.encrypt_block(&mut block);
+/// three_way.decrypt_block(&mut block);
+/// assert_eq!(&block, b"twelve bytes");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct ThreeWay {
+    key: State,
+    // mu(theta(key)), the key of the decryption.
+    inverse_key: State,
+}
+
+impl ThreeWay {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a 3-Way cipher from a 12 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<ThreeWay, CryptoError> {
+        if key.len() != 12 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let key = load(key);
+        Ok(ThreeWay {
+            key,
+            inverse_key: mu(theta(key)),
+        })
+    }
+}
+
+impl BlockCipher for ThreeWay {
+    const BLOCK_SIZE: usize = 12;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let a = crypt(load(block), &self.key, &RCON_E);
+        store(block, a);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let a = mu(crypt(mu(load(block)), &self.inverse_key, &RCON_D));
+        store(block, a);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_three_way() {
+        // Vectors of the reference implementation.
+        for (key, plain, cipher) in [
+            (
+                "000000000000000000000000",
+                "000000010000000100000001",
+                "ad21ecf783ae9dc44059c76e",
+            ),
+            (
+                "000000040000000500000006",
+                "000000010000000200000003",
+                "cab920cdd6144138d2f05b5e",
+            ),
+            (
+                "bcdef012456789abdef01234",
+                "012345679abcdef023456789",
+                "7cdb76b29cdddb6d0aa55dbb",
+            ),
+            (
+                "cab920cdd6144138d2f05b5e",
+                "ad21ecf783ae9dc44059c76e",
+                "15b155ed6b13f17c478ea871",
+            ),
+        ] {
+            let three_way = ThreeWay::new(&hex(key)).unwrap();
+            let mut block = hex(plain);
+            three_way.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher), "key {}", key);
+            three_way.decrypt_block(&mut block);
+            assert_eq!(block, hex(plain));
+        }
+        assert_eq!(
+            ThreeWay::new(&[0; 16]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//#######################################################################o
+// Threefish is the tweakable block cipher inside the Skein hash, with   |
+// 256, 512 and 1024-bit blocks and keys and a 128-bit tweak. Each round |
+// applies the MIX function (an addition, a rotation and a xor) to pairs |
+// of 64-bit words then permutes the words, a subkey derived from the    |
+// key words, the tweak words and the subkey number being added every    |
+// four rounds (72 rounds, 80 for Threefish-1024). The tweak gives       |
+// independent permutations per record or position without rekeying.     |
+//                                                                       |
+// @Refrences:                                                           |
+// Skein 1.3: https://www.schneier.com/wp-content/uploads/2015/01/skein.pdf
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// Key schedule constant, the parity word starts from it.
+const C240: u64 = 0x1bd11bdaa9fc1a22;
+
+// Rotation constants R[d mod 8][j] of each block size.
+const R4: [[u32; 8]; 8] = [
+    [14, 16, 0, 0, 0, 0, 0, 0],
+    [52, 57, 0, 0, 0, 0, 0, 0],
+    [23, 40, 0, 0, 0, 0, 0, 0],
+    [5, 37, 0, 0, 0, 0, 0, 0],
+    [25, 33, 0, 0, 0, 0, 0, 0],
+    [46, 12, 0, 0, 0, 0, 0, 0],
+    [58, 22, 0, 0, 0, 0, 0, 0],
+    [32, 32, 0, 0, 0, 0, 0, 0],
+];
+const R8: [[u32; 8]; 8] = [
+    [46, 36, 19, 37, 0, 0, 0, 0],
+    [33, 27, 14, 42, 0, 0, 0, 0],
+    [17, 49, 36, 39, 0, 0, 0, 0],
+    [44, 9, 54, 56, 0, 0, 0, 0],
+    [39, 30, 34, 24, 0, 0, 0, 0],
+    [13, 50, 10, 17, 0, 0, 0, 0],
+    [25, 29, 39, 43, 0, 0, 0, 0],
+    [8, 35, 56, 22, 0, 0, 0, 0],
+];
+const R16: [[u32; 8]; 8] = [
+    [24, 13, 8, 47, 8, 17, 22, 37],
+    [38, 19, 10, 55, 49, 18, 23, 52],
+    [33, 4, 51, 13, 34, 41, 59, 17],
+    [5, 20, 48, 41, 47, 28, 16, 25],
+    [41, 9, 37, 31, 12, 47, 44, 30],
+    [16, 34, 56, 51, 4, 53, 42, 41],
+    [31, 44, 47, 46, 19, 42, 44, 25],
+    [9, 48, 35, 52, 23, 31, 37, 20],
+];
+
+// Word permutations, word i of the next round is word PI[i].
+const PI4: [usize; 4] = [0, 3, 2, 1];
+const PI8: [usize; 8] = [2, 1, 4, 7, 6, 5, 0, 3];
+const PI16: [usize; 16] = [0, 9, 2, 13, 6, 11, 4, 15, 10, 7, 12, 3, 14, 5, 8, 1];
+
+///////////////////////////////////////////////////////////////////////
+/// `Threefish` is a keyed Threefish cipher with `W` words blocks, use
+/// `Threefish256`, `Threefish512` or `Threefish1024`.
+///# Example
+/// ```
+/// use cryptolib::block::threefish::Threefish256;
+/// use cryptolib::block::BlockCipher;
+///
+/// let threefish = Threefish256::new(&[0x42; 32]).unwrap();
+/// let mut record = [7_u8; 32];
+/// // The tweak is the record number.
+/// threefish.encrypt_block_tweaked(&mut record, &12_u128.to_le_bytes());
+/// threefish.decrypt_block_tweaked(&mut record, &12_u128.to_le_bytes());
+/// assert_eq!(record, [7; 32]);
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Threefish<const W: usize> {
+    key: [u64; W],
+    // The extra key word, C240 xored with the key words.
+    parity: u64,
+    // The tweak used by the `BlockCipher` methods.
+    tweak: [u64; 2],
+}
+
+/// Threefish-256.
+pub type Threefish256 = Threefish<4>;
+/// Threefish-512.
+pub type Threefish512 = Threefish<8>;
+/// Threefish-1024.
+pub type Threefish1024 = Threefish<16>;
+
+impl<const W: usize> Threefish<W> {
+    const ROUNDS: usize = if W == 16 { 80 } else { 72 };
+    const R: &'static [[u32; 8]; 8] = match W {
+        4 => &R4,
+        8 => &R8,
+        _ => &R16,
+    };
+    const PI: &'static [usize] = match W {
+        4 => &PI4,
+        8 => &PI8,
+        _ => &PI16,
+    };
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a Threefish cipher with a zero tweak.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength` if `key`
+    /// isn't `8 * W` bytes.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Threefish<W>, CryptoError> {
+        Self::with_tweak(key, &[0; 16])
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a Threefish cipher whose `BlockCipher` methods use
+    /// `tweak`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength` if `key`
+    /// isn't `8 * W` bytes.
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_tweak(key: &[u8], tweak: &[u8; 16]) -> Result<Threefish<W>, CryptoError> {
+        if key.len() != 8 * W {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let mut words = [0_u64; W];
+        for (w, bytes) in words.iter_mut().zip(key.chunks_exact(8)) {
+            *w = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        Ok(Self::from_words(words, tweak_words(tweak)))
+    }
+
+    /// Creates the cipher from the key and tweak words.
+    pub(crate) fn from_words(key: [u64; W], tweak: [u64; 2]) -> Threefish<W> {
+        let parity = key.iter().fold(C240, |p, k| p ^ k);
+        Threefish { key, parity, tweak }
+    }
+
+    fn key_word(&self, i: usize) -> u64 {
+        if i == W {
+            self.parity
+        } else {
+            self.key[i]
+        }
+    }
+
+    // Subkey s with the tweak words t0, t1 and t0 ^ t1.
+    fn subkey(&self, s: usize, t: &[u64; 3]) -> [u64; W] {
+        let mut k = [0_u64; W];
+        for (i, k) in k.iter_mut().enumerate() {
+            *k = self.key_word((s + i) % (W + 1));
+        }
+        k[W - 3] = k[W - 3].wrapping_add(t[s % 3]);
+        k[W - 2] = k[W - 2].wrapping_add(t[(s + 1) % 3]);
+        k[W - 1] = k[W - 1].wrapping_add(s as u64);
+        k
+    }
+
+    /// Encrypts the block words `v` with the tweak words `tweak`.
+    pub(crate) fn encrypt_words(&self, mut v: [u64; W], tweak: [u64; 2]) -> [u64; W] {
+        let t = [tweak[0], tweak[1], tweak[0] ^ tweak[1]];
+        for d in 0..Self::ROUNDS {
+            if d % 4 == 0 {
+                for (v, k) in v.iter_mut().zip(self.subkey(d / 4, &t)) {
+                    *v = v.wrapping_add(k);
+                }
+            }
+            let r = &Self::R[d % 8];
+            for j in 0..W / 2 {
+                v[2 * j] = v[2 * j].wrapping_add(v[2 * j + 1]);
+                v[2 * j + 1] = v[2 * j + 1].rotate_left(r[j]) ^ v[2 * j];
+            }
+            let f = v;
+            for (v, &p) in v.iter_mut().zip(Self::PI) {
+                *v = f[p];
+            }
+        }
+        for (v, k) in v.iter_mut().zip(self.subkey(Self::ROUNDS / 4, &t)) {
+            *v = v.wrapping_add(k);
+        }
+        v
+    }
+
+    /// Decrypts the block words `v` with the tweak words `tweak`.
+    fn decrypt_words(&self, mut v: [u64; W], tweak: [u64; 2]) -> [u64; W] {
+        let t = [tweak[0], tweak[1], tweak[0] ^ tweak[1]];
+        for (v, k) in v.iter_mut().zip(self.subkey(Self::ROUNDS / 4, &t)) {
+            *v = v.wrapping_sub(k);
+        }
+        for d in (0..Self::ROUNDS).rev() {
+            let f = v;
+            for (&fi, &p) in f.iter().zip(Self::PI) {
+                v[p] = fi;
+            }
+            let r = &Self::R[d % 8];
+            for j in 0..W / 2 {
+                v[2 * j + 1] = (v[2 * j + 1] ^ v[2 * j]).rotate_right(r[j]);
+                v[2 * j] = v[2 * j].wrapping_sub(v[2 * j + 1]);
+            }
+            if d % 4 == 0 {
+                for (v, k) in v.iter_mut().zip(self.subkey(d / 4, &t)) {
+                    *v = v.wrapping_sub(k);
+                }
+            }
+        }
+        v
+    }
+
+    /// Encrypts `block` in place with an explicit 16 bytes `tweak`.
+    pub fn encrypt_block_tweaked(&self, block: &mut [u8], tweak: &[u8; 16]) {
+        let v = self.encrypt_words(load(block), tweak_words(tweak));
+        store(block, v);
+    }
+
+    /// Decrypts `block` in place with an explicit 16 bytes `tweak`.
+    pub fn decrypt_block_tweaked(&self, block: &mut [u8], tweak: &[u8; 16]) {
+        let v = self.decrypt_words(load(block), tweak_words(tweak));
+        store(block, v);
+    }
+}
+
+fn tweak_words(tweak: &[u8; 16]) -> [u64; 2] {
+    [
+        u64::from_le_bytes(tweak[..8].try_into().unwrap()),
+        u64::from_le_bytes(tweak[8..].try_into().unwrap()),
+    ]
+}
+
+fn load<const W: usize>(block: &[u8]) -> [u64; W] {
+    assert_eq!(block.len(), 8 * W, "threefish block size mismatch");
+    let mut v = [0_u64; W];
+    for (v, bytes) in v.iter_mut().zip(block.chunks_exact(8)) {
+        *v = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    v
+}
+
+fn store<const W: usize>(block: &mut [u8], v: [u64; W]) {
+    for (bytes, v) in block.chunks_exact_mut(8).zip(v) {
+        bytes.copy_from_slice(&v.to_le_bytes());
+    }
+}
+
+impl<const W: usize> BlockCipher for Threefish<W> {
+    const BLOCK_SIZE: usize = 8 * W;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let v = self.encrypt_words(load(block), self.tweak);
+        store(block, v);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let v = self.decrypt_words(load(block), self.tweak);
+        store(block, v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    fn check<const W: usize>(key: &[u8], tweak: &[u8], plain: &[u8], cipher: &str) {
+        let tweak: [u8; 16] = tweak.try_into().unwrap();
+        let threefish = Threefish::<W>::with_tweak(key, &tweak).unwrap();
+        let mut block = plain.to_vec();
+        threefish.encrypt_block(&mut block);
+        assert_eq!(block, hex(cipher));
+        threefish.decrypt_block(&mut block);
+        assert_eq!(block, plain);
+    }
+
+    #[test]
+    fn test_threefish_zero() {
+        // Skein reference KATs with all zero inputs.
+        check::<4>(
+            &[0; 32],
+            &[0; 16],
+            &[0; 32],
+            "84da2a1f8beaee947066ae3e3103f1ad536db1f4a1192495116b9f3ce6133fd8",
+        );
+        check::<8>(
+            &[0; 64],
+            &[0; 16],
+            &[0; 64],
+            "b1a2bbc6ef6025bc40eb3822161f36e375d1bb0aee3186fbd19e47c5d479947b
+             7bc2f8586e35f0cff7e7f03084b0b7b1f1ab3961a580a3e97eb41ea14a6d7bbe",
+        );
+        check::<16>(
+            &[0; 128],
+            &[0; 16],
+            &[0; 128],
+            "f05c3d0a3d05b304f785ddc7d1e036015c8aa76e2f217b06c6e1544c0bc1a90d
+             f0accb9473c24e0fd54fea68057f43329cb454761d6df5cf7b2e9b3614fbd5a2
+             0b2e4760b40603540d82eabc5482c171c832afbe68406bc39500367a592943fa
+             9a5b4a43286ca3c4cf46104b443143d560a4b230488311df4feef7e1dfe8391e",
+        );
+    }
+
+    #[test]
+    fn test_threefish_tweak() {
+        // Skein reference KATs with a key 10 11 12 ..., the tweak
+        // 00 01 ... 0f and the block ff fe fd ...
+        let key: Vec<u8> = (0x10..0x90).collect();
+        let tweak: Vec<u8> = (0..16).collect();
+        let plain: Vec<u8> = (0x80..=0xff).rev().collect();
+        check::<4>(
+            &key[..32],
+            &tweak,
+            &plain[..32],
+            "e0d091ff0eea8fdfc98192e62ed80ad59d865d08588df476657056b5955e97df",
+        );
+        check::<8>(
+            &key[..64],
+            &tweak,
+            &plain[..64],
+            "e304439626d45a2cb401cad8d636249a6338330eb06d45dd8b36b90e97254779
+             272a0a8d99463504784420ea18c9a725af11dffea10162348927673d5c1caf3d",
+        );
+        check::<16>(
+            &key,
+            &tweak,
+            &plain,
+            "a6654ddbd73cc3b05dd777105aa849bce49372eaaffc5568d254771bab85531c
+             94f780e7ffaae430d5d8af8c70eebbe1760f3b42b737a89cb363490d670314bd
+             8aa41ee63c2e1f45fbd477922f8360b388d6125ea6c7af0ad7056d01796e90c8
+             3313f4150a5716b30ed5f569288ae974ce2b4347926fce57de44512177dd7cde",
+        );
+    }
+}
@@ -1,13 +1,128 @@
 use super::des::{des_decrypt, des_encrypt};
+use super::BlockCipher;
+use crate::error::CryptoError;
 
+#[allow(clippy::needless_return)]
 pub fn triple_des_encrypt(plain_text: u64, key_1: u64, key_2: u64, key_3: u64) -> u64 {
     let mut cipher = des_encrypt(plain_text, key_1);
     cipher = des_decrypt(cipher, key_2);
     return des_encrypt(cipher, key_3);
 }
 
+#[allow(clippy::needless_return)]
 pub fn triple_des_decrypt(plain_text: u64, key_1: u64, key_2: u64, key_3: u64) -> u64 {
     let mut cipher = des_decrypt(plain_text, key_3);
     cipher = des_encrypt(cipher, key_2);
     return des_decrypt(cipher, key_1);
 }
+
+////////////////////////////////////////////////////////////////////////
+/// `TripleDes` is the keyed EDE (encrypt-decrypt-encrypt) form of
+/// `triple_des_encrypt` and `triple_des_decrypt`, through which every
+/// block goes.
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct TripleDes {
+    keys: [u64; 3],
+}
+
+impl TripleDes {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a triple des cipher from a `key` that is the
+    /// concatenation of the des keys.
+    ///# Arguments
+    ///* `key`: 24 bytes for three keys (keying option 1), or 16 bytes
+    ///  for two keys (keying option 2) in which case the first key is
+    ///  reused as the third one.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<TripleDes, CryptoError> {
+        let des_key = |i: usize| u64::from_be_bytes(key[8 * i..8 * i + 8].try_into().unwrap());
+        match key.len() {
+            16 => Ok(TripleDes::from_u64(des_key(0), des_key(1), des_key(0))),
+            24 => Ok(TripleDes::from_u64(des_key(0), des_key(1), des_key(2))),
+            _ => Err(CryptoError::InvalidKeyLength),
+        }
+    }
+
+    /// Creates a triple des cipher from the three 64-bit keys.
+    pub fn from_u64(key_1: u64, key_2: u64, key_3: u64) -> TripleDes {
+        TripleDes {
+            keys: [key_1, key_2, key_3],
+        }
+    }
+
+    /// Returns the encrypted 64-bit block.
+    pub fn encrypt_u64(&self, plain_text: u64) -> u64 {
+        let [key_1, key_2, key_3] = self.keys;
+        triple_des_encrypt(plain_text, key_1, key_2, key_3)
+    }
+
+    /// Returns the decrypted 64-bit block.
+    pub fn decrypt_u64(&self, cipher_text: u64) -> u64 {
+        let [key_1, key_2, key_3] = self.keys;
+        triple_des_decrypt(cipher_text, key_1, key_2, key_3)
+    }
+}
+
+impl BlockCipher for TripleDes {
+    const BLOCK_SIZE: usize = 8;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let input: [u8; 8] = (&*block)
+            .try_into()
+            .expect("triple des block must be 8 bytes");
+        block.copy_from_slice(&self.encrypt_u64(u64::from_be_bytes(input)).to_be_bytes());
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let input: [u8; 8] = (&*block)
+            .try_into()
+            .expect("triple des block must be 8 bytes");
+        block.copy_from_slice(&self.decrypt_u64(u64::from_be_bytes(input)).to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triple_des() {
+        // SP 800-67 example.
+        let tdes = TripleDes::from_u64(0x0123456789ABCDEF, 0x23456789ABCDEF01, 0x456789ABCDEF0123);
+        assert_eq!(
+            tdes.encrypt_u64(u64::from_be_bytes(*b"The qufc")),
+            0xA826FD8CE53B855F
+        );
+        assert_eq!(
+            triple_des_encrypt(
+                u64::from_be_bytes(*b"The qufc"),
+                0x0123456789ABCDEF,
+                0x23456789ABCDEF01,
+                0x456789ABCDEF0123
+            ),
+            0xA826FD8CE53B855F
+        );
+        assert_eq!(
+            tdes.decrypt_u64(0xA826FD8CE53B855F),
+            u64::from_be_bytes(*b"The qufc")
+        );
+    }
+
+    #[test]
+    fn test_triple_des_two_keys() {
+        let tdes = TripleDes::new(&[
+            0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD,
+            0xEF, 0x01,
+        ])
+        .unwrap();
+        let mut block = *b"The qufc";
+        tdes.encrypt_block(&mut block);
+        assert_eq!(block, [0xC4, 0x48, 0x62, 0xF7, 0x0C, 0xF2, 0xFB, 0xDC]);
+        tdes.decrypt_block(&mut block);
+        assert_eq!(&block, b"The qufc");
+        assert!(TripleDes::new(&[0; 8]).is_err());
+    }
+}
@@ -0,0 +1,360 @@
+//#######################################################################o
+// Twofish is a 16 rounds Feistel network on 128-bit blocks with keys    |
+// of 128, 192 or 256 bits. The round function g uses four key           |
+// dependent S-boxes, built from the fixed permutations q0 and q1 and    |
+// half of the key (through a Reed-Solomon code), followed by an MDS     |
+// matrix, and the two outputs are mixed by a pseudo-Hadamard transform. |
+// The key dependent S-boxes are expanded into tables once per key.      |
+//                                                                       |
+// @Refrences:                                                           |
+// Schneier et al.: https://www.schneier.com/wp-content/uploads/2016/02/paper-twofish-paper.pdf
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+// The fixed 8-bit permutations q0 and q1.
+const Q0: [u8; 256] = [
+    0xa9, 0x67, 0xb3, 0xe8, 0x04, 0xfd, 0xa3, 0x76, 0x9a, 0x92, 0x80, 0x78, 0xe4, 0xdd, 0xd1, 0x38,
+    0x0d, 0xc6, 0x35, 0x98, 0x18, 0xf7, 0xec, 0x6c, 0x43, 0x75, 0x37, 0x26, 0xfa, 0x13, 0x94, 0x48,
+    0xf2, 0xd0, 0x8b, 0x30, 0x84, 0x54, 0xdf, 0x23, 0x19, 0x5b, 0x3d, 0x59, 0xf3, 0xae, 0xa2, 0x82,
+    0x63, 0x01, 0x83, 0x2e, 0xd9, 0x51, 0x9b, 0x7c, 0xa6, 0xeb, 0xa5, 0xbe, 0x16, 0x0c, 0xe3, 0x61,
+    0xc0, 0x8c, 0x3a, 0xf5, 0x73, 0x2c, 0x25, 0x0b, 0xbb, 0x4e, 0x89, 0x6b, 0x53, 0x6a, 0xb4, 0xf1,
+    0xe1, 0xe6, 0xbd, 0x45, 0xe2, 0xf4, 0xb6, 0x66, 0xcc, 0x95, 0x03, 0x56, 0xd4, 0x1c, 0x1e, 0xd7,
+    0xfb, 0xc3, 0x8e, 0xb5, 0xe9, 0xcf, 0xbf, 0xba, 0xea, 0x77, 0x39, 0xaf, 0x33, 0xc9, 0x62, 0x71,
+    0x81, 0x79, 0x09, 0xad, 0x24, 0xcd, 0xf9, 0xd8, 0xe5, 0xc5, 0xb9, 0x4d, 0x44, 0x08, 0x86, 0xe7,
+    0xa1, 0x1d, 0xaa, 0xed, 0x06, 0x70, 0xb2, 0xd2, 0x41, 0x7b, 0xa0, 0x11, 0x31, 0xc2, 0x27, 0x90,
+    0x20, 0xf6, 0x60, 0xff, 0x96, 0x5c, 0xb1, 0xab, 0x9e, 0x9c, 0x52, 0x1b, 0x5f, 0x93, 0x0a, 0xef,
+    0x91, 0x85, 0x49, 0xee, 0x2d, 0x4f, 0x8f, 0x3b, 0x47, 0x87, 0x6d, 0x46, 0xd6, 0x3e, 0x69, 0x64,
+    0x2a, 0xce, 0xcb, 0x2f, 0xfc, 0x97, 0x05, 0x7a, 0xac, 0x7f, 0xd5, 0x1a, 0x4b, 0x0e, 0xa7, 0x5a,
+    0x28, 0x14, 0x3f, 0x29, 0x88, 0x3c, 0x4c, 0x02, 0xb8, 0xda, 0xb0, 0x17, 0x55, 0x1f, 0x8a, 0x7d,
+    0x57, 0xc7, 0x8d, 0x74, 0xb7, 0xc4, 0x9f, 0x72, 0x7e, 0x15, 0x22, 0x12, 0x58, 0x07, 0x99, 0x34,
+    0x6e, 0x50, 0xde, 0x68, 0x65, 0xbc, 0xdb, 0xf8, 0xc8, 0xa8, 0x2b, 0x40, 0xdc, 0xfe, 0x32, 0xa4,
+    0xca, 0x10, 0x21, 0xf0, 0xd3, 0x5d, 0x0f, 0x00, 0x6f, 0x9d, 0x36, 0x42, 0x4a, 0x5e, 0xc1, 0xe0,
+];
+const Q1: [u8; 256] = [
+    0x75, 0xf3, 0xc6, 0xf4, 0xdb, 0x7b, 0xfb, 0xc8, 0x4a, 0xd3, 0xe6, 0x6b, 0x45, 0x7d, 0xe8, 0x4b,
+    0xd6, 0x32, 0xd8, 0xfd, 0x37, 0x71, 0xf1, 0xe1, 0x30, 0x0f, 0xf8, 0x1b, 0x87, 0xfa, 0x06, 0x3f,
+    0x5e, 0xba, 0xae, 0x5b, 0x8a, 0x00, 0xbc, 0x9d, 0x6d, 0xc1, 0xb1, 0x0e, 0x80, 0x5d, 0xd2, 0xd5,
+    0xa0, 0x84, 0x07, 0x14, 0xb5, 0x90, 0x2c, 0xa3, 0xb2, 0x73, 0x4c, 0x54, 0x92, 0x74, 0x36, 0x51,
+    0x38, 0xb0, 0xbd, 0x5a, 0xfc, 0x60, 0x62, 0x96, 0x6c, 0x42, 0xf7, 0x10, 0x7c, 0x28, 0x27, 0x8c,
+    0x13, 0x95, 0x9c, 0xc7, 0x24, 0x46, 0x3b, 0x70, 0xca, 0xe3, 0x85, 0xcb, 0x11, 0xd0, 0x93, 0xb8,
+    0xa6, 0x83, 0x20, 0xff, 0x9f, 0x77, 0xc3, 0xcc, 0x03, 0x6f, 0x08, 0xbf, 0x40, 0xe7, 0x2b, 0xe2,
+    0x79, 0x0c, 0xaa, 0x82, 0x41, 0x3a, 0xea, 0xb9, 0xe4, 0x9a, 0xa4, 0x97, 0x7e, 0xda, 0x7a, 0x17,
+    0x66, 0x94, 0xa1, 0x1d, 0x3d, 0xf0, 0xde, 0xb3, 0x0b, 0x72, 0xa7, 0x1c, 0xef, 0xd1, 0x53, 0x3e,
+    0x8f, 0x33, 0x26, 0x5f, 0xec, 0x76, 0x2a, 0x49, 0x81, 0x88, 0xee, 0x21, 0xc4, 0x1a, 0xeb, 0xd9,
+    0xc5, 0x39, 0x99, 0xcd, 0xad, 0x31, 0x8b, 0x01, 0x18, 0x23, 0xdd, 0x1f, 0x4e, 0x2d, 0xf9, 0x48,
+    0x4f, 0xf2, 0x65, 0x8e, 0x78, 0x5c, 0x58, 0x19, 0x8d, 0xe5, 0x98, 0x57, 0x67, 0x7f, 0x05, 0x64,
+    0xaf, 0x63, 0xb6, 0xfe, 0xf5, 0xb7, 0x3c, 0xa5, 0xce, 0xe9, 0x68, 0x44, 0xe0, 0x4d, 0x43, 0x69,
+    0x29, 0x2e, 0xac, 0x15, 0x59, 0xa8, 0x0a, 0x9e, 0x6e, 0x47, 0xdf, 0x34, 0x35, 0x6a, 0xcf, 0xdc,
+    0x22, 0xc9, 0xc0, 0x9b, 0x89, 0xd4, 0xed, 0xab, 0x12, 0xa2, 0x0d, 0x52, 0xbb, 0x02, 0x2f, 0xa9,
+    0xd7, 0x61, 0x1e, 0xb4, 0x50, 0x04, 0xf6, 0xc2, 0x16, 0x25, 0x86, 0x56, 0x55, 0x09, 0xbe, 0x91,
+];
+
+// MDS matrix, over GF(2^8) with the polynomial x^8+x^6+x^5+x^3+1.
+const MDS: [[u8; 4]; 4] = [
+    [0x01, 0xef, 0x5b, 0x5b],
+    [0x5b, 0xef, 0xef, 0x01],
+    [0xef, 0x5b, 0x01, 0xef],
+    [0xef, 0x01, 0xef, 0x5b],
+];
+const MDS_POLY: u16 = 0x169;
+
+// Reed-Solomon matrix, over GF(2^8) with the polynomial x^8+x^6+x^3+x^2+1.
+const RS: [[u8; 8]; 4] = [
+    [0x01, 0xa4, 0x55, 0x87, 0x5a, 0x58, 0xdb, 0x9e],
+    [0xa4, 0x56, 0x82, 0xf3, 0x1e, 0xc6, 0x68, 0xe5],
+    [0x02, 0xa1, 0xfc, 0xc1, 0x47, 0xae, 0x3d, 0x19],
+    [0xa4, 0x55, 0x87, 0x5a, 0x58, 0xdb, 0x9e, 0x03],
+];
+const RS_POLY: u16 = 0x14d;
+
+// Subkeys increment in the key schedule.
+const RHO: u32 = 0x01010101;
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies `a` and `b` in GF(2^8) reduced by `poly`.
+//////////////////////////////////////////////////////////////////////
+fn gf_mul(mut a: u8, mut b: u8, poly: u16) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+        a = ((((a as u16) << 1) ^ (poly * (a as u16 >> 7))) & 0xff) as u8;
+        b >>= 1;
+    }
+    product
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The byte of position `j` in the h function: `x` goes through q0
+/// and q1 interleaved with the bytes `l[..][j]` of the key words.
+//////////////////////////////////////////////////////////////////////
+fn h_byte(mut x: u8, j: usize, l: &[[u8; 4]]) -> u8 {
+    // (q applied first, .., q applied last) for each byte position.
+    const Q_ORDER: [[&[u8; 256]; 4]; 4] = [
+        [&Q1, &Q1, &Q0, &Q0],
+        [&Q0, &Q1, &Q1, &Q0],
+        [&Q0, &Q0, &Q0, &Q1],
+        [&Q1, &Q0, &Q1, &Q1],
+    ];
+    let k = l.len();
+    // For shorter keys the first stages are skipped.
+    for stage in 4 - k..4 {
+        x = Q_ORDER[j][stage][x as usize] ^ l[3 - stage][j];
+    }
+    // The final q.
+    let last = [&Q1, &Q0, &Q1, &Q0][j];
+    last[x as usize]
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The h function: h_byte on every byte then the MDS matrix.
+//////////////////////////////////////////////////////////////////////
+fn h(x: u32, l: &[[u8; 4]]) -> u32 {
+    let bytes = x.to_le_bytes();
+    let mut out = 0;
+    for (j, &b) in bytes.iter().enumerate() {
+        out ^= mds_column(j, h_byte(b, j, l));
+    }
+    out
+}
+
+/// Multiplies `y` by the column `j` of the MDS matrix.
+fn mds_column(j: usize, y: u8) -> u32 {
+    u32::from_le_bytes([
+        gf_mul(MDS[0][j], y, MDS_POLY),
+        gf_mul(MDS[1][j], y, MDS_POLY),
+        gf_mul(MDS[2][j], y, MDS_POLY),
+        gf_mul(MDS[3][j], y, MDS_POLY),
+    ])
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Twofish` is a keyed Twofish cipher.
+///# Example
+/// ```
+/// use cryptolib::block::twofish::Twofish;
+/// use cryptolib::block::BlockCipher;
+///
+/// let twofish = Twofish::new(&[0x42; 32]).unwrap();
+/// let mut block = *b"sixteen byte blk";
+/// twofish.encrypt_block(&mut block);
+/// twofish.decrypt_block(&mut block);
+/// assert_eq!(&block, b"sixteen byte blk");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Twofish {
+    // Whitening and round subkeys.
+    k: [u32; 40],
+    // The key dependent S-boxes combined with the MDS matrix.
+    s: [[u32; 256]; 4],
+}
+
+impl Twofish {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a Twofish cipher from a 16, 24 or 32 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Twofish, CryptoError> {
+        if ![16, 24, 32].contains(&key.len()) {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let k = key.len() / 8;
+        let mut me = [[0_u8; 4]; 4];
+        let mut mo = [[0_u8; 4]; 4];
+        // The S vector is used in reverse order.
+        let mut s_vec = [[0_u8; 4]; 4];
+        for (i, chunk) in key.chunks_exact(8).enumerate() {
+            me[i].copy_from_slice(&chunk[..4]);
+            mo[i].copy_from_slice(&chunk[4..]);
+            for (r, row) in RS.iter().enumerate() {
+                s_vec[k - 1 - i][r] = row
+                    .iter()
+                    .zip(chunk)
+                    .fold(0, |acc, (&m, &b)| acc ^ gf_mul(m, b, RS_POLY));
+            }
+        }
+        let mut subkeys = [0_u32; 40];
+        for i in 0..20 {
+            let a = h(2 * i as u32 * RHO, &me[..k]);
+            let b = h((2 * i as u32 + 1) * RHO, &mo[..k]).rotate_left(8);
+            subkeys[2 * i] = a.wrapping_add(b);
+            subkeys[2 * i + 1] = a.wrapping_add(b).wrapping_add(b).rotate_left(9);
+        }
+        let mut s = [[0_u32; 256]; 4];
+        for (j, table) in s.iter_mut().enumerate() {
+            for (x, entry) in table.iter_mut().enumerate() {
+                *entry = mds_column(j, h_byte(x as u8, j, &s_vec[..k]));
+            }
+        }
+        Ok(Twofish { k: subkeys, s })
+    }
+
+    fn g(&self, x: u32) -> u32 {
+        let [a, b, c, d] = x.to_le_bytes();
+        self.s[0][a as usize]
+            ^ self.s[1][b as usize]
+            ^ self.s[2][c as usize]
+            ^ self.s[3][d as usize]
+    }
+
+    /// The two round function outputs (F0, F1) of round `r`.
+    fn f(&self, r0: u32, r1: u32, r: usize) -> (u32, u32) {
+        let t0 = self.g(r0);
+        let t1 = self.g(r1.rotate_left(8));
+        (
+            t0.wrapping_add(t1).wrapping_add(self.k[2 * r + 8]),
+            t0.wrapping_add(t1)
+                .wrapping_add(t1)
+                .wrapping_add(self.k[2 * r + 9]),
+        )
+    }
+}
+
+/// Reads a block as four little-endian words.
+fn load(block: &[u8]) -> [u32; 4] {
+    let mut words = [0; 4];
+    for (w, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    words
+}
+
+/// Writes four words as a little-endian block.
+fn store(words: [u32; 4], block: &mut [u8]) {
+    for (bytes, w) in block.chunks_exact_mut(4).zip(words) {
+        bytes.copy_from_slice(&w.to_le_bytes());
+    }
+}
+
+impl BlockCipher for Twofish {
+    const BLOCK_SIZE: usize = 16;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), 16, "twofish block must be 16 bytes");
+        let mut x = load(block);
+        for (w, k) in x.iter_mut().zip(&self.k[..4]) {
+            *w ^= k;
+        }
+        for r in (0..16).step_by(2) {
+            let (f0, f1) = self.f(x[0], x[1], r);
+            x[2] = (x[2] ^ f0).rotate_right(1);
+            x[3] = x[3].rotate_left(1) ^ f1;
+            let (f0, f1) = self.f(x[2], x[3], r + 1);
+            x[0] = (x[0] ^ f0).rotate_right(1);
+            x[1] = x[1].rotate_left(1) ^ f1;
+        }
+        let out = [
+            x[2] ^ self.k[4],
+            x[3] ^ self.k[5],
+            x[0] ^ self.k[6],
+            x[1] ^ self.k[7],
+        ];
+        store(out, block);
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        assert_eq!(block.len(), 16, "twofish block must be 16 bytes");
+        let c = load(block);
+        let mut x = [
+            c[2] ^ self.k[6],
+            c[3] ^ self.k[7],
+            c[0] ^ self.k[4],
+            c[1] ^ self.k[5],
+        ];
+        for r in (0..16).rev().step_by(2) {
+            let (f0, f1) = self.f(x[2], x[3], r);
+            x[0] = x[0].rotate_left(1) ^ f0;
+            x[1] = (x[1] ^ f1).rotate_right(1);
+            let (f0, f1) = self.f(x[0], x[1], r - 1);
+            x[2] = x[2].rotate_left(1) ^ f0;
+            x[3] = (x[3] ^ f1).rotate_right(1);
+        }
+        for (w, k) in x.iter_mut().zip(&self.k[..4]) {
+            *w ^= k;
+        }
+        store(x, block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::mode::gcm::Gcm;
+    use crate::block::mode::Aead;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_twofish_kat() {
+        // Twofish paper, ECB_TBL.TXT first entries (all zero plaintext).
+        for (key, cipher) in [
+            (
+                "00000000000000000000000000000000",
+                "9f589f5cf6122c32b6bfec2f2ae8c35a",
+            ),
+            (
+                "0123456789abcdeffedcba98765432100011223344556677",
+                "cfd1d2e5a9be9cdf501f13b892bd2248",
+            ),
+            (
+                "0123456789abcdeffedcba987654321000112233445566778899aabbccddeeff",
+                "37527be0052334b89f0cfccae87cfa20",
+            ),
+        ] {
+            let twofish = Twofish::new(&hex(key)).unwrap();
+            let mut block = [0_u8; 16];
+            twofish.encrypt_block(&mut block);
+            assert_eq!(block.to_vec(), hex(cipher));
+            twofish.decrypt_block(&mut block);
+            assert_eq!(block, [0; 16]);
+        }
+    }
+
+    #[test]
+    fn test_twofish_iterated() {
+        // ECB_TBL.TXT, 49 chained encryptions: the plaintext is the
+        // previous ciphertext and the key is made of the previous
+        // plaintexts.
+        for (key_len, last) in [
+            (16, "5d9d4eeffa9151575524f115815a12e0"),
+            (24, "e75449212beef9f4a390bd860a640941"),
+            (32, "37fe26ff1cf66175f5ddf4c33b97a205"),
+        ] {
+            let mut key = vec![0_u8; key_len];
+            let mut plain = [0_u8; 16];
+            let mut cipher = [0_u8; 16];
+            for _ in 0..49 {
+                Twofish::new(&key).unwrap().encrypt_block(&mut cipher);
+                key.copy_within(..key_len - 16, 16);
+                key[..16].copy_from_slice(&plain);
+                plain = cipher;
+            }
+            assert_eq!(cipher.to_vec(), hex(last));
+        }
+    }
+
+    #[test]
+    fn test_twofish_gcm() {
+        // Checked against libgcrypt.
+        let gcm = Gcm::new(Twofish::new(&[0x11; 32]).unwrap()).unwrap();
+        let sealed = gcm
+            .encrypt(&[0x22; 12], b"header", b"twofish in gcm mode")
+            .unwrap();
+        assert_eq!(
+            sealed,
+            hex("6ec2bbd07e7b73f94cf4da60a79db81c54bd83e712b1c295de452e2e43a4ed50c1fa93")
+        );
+        assert_eq!(
+            gcm.decrypt(&[0x22; 12], b"header", &sealed).unwrap(),
+            b"twofish in gcm mode"
+        );
+        assert_eq!(
+            Twofish::new(&[0; 20]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+}
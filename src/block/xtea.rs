@@ -0,0 +1,146 @@
+//#######################################################################o
+// XTEA (eXtended TEA) is a 64-bit block cipher with a 128-bit key by    |
+// Needham and Wheeler, a Feistel network of 64 rounds using only        |
+// shifts, xors and additions modulo 2^32, which makes it tiny on        |
+// microcontrollers. It fixes the related-key weaknesses of TEA by       |
+// mixing a running sum of the golden ratio constant with the key word   |
+// it selects. Words are big-endian, the round keys are precomputed.     |
+//                                                                       |
+// @Refrences:                                                           |
+// Needham, Wheeler: https://www.cix.co.uk/~klockstone/xtea.pdf          |
+//#######################################################################o
+
+use super::BlockCipher;
+use crate::error::CryptoError;
+
+const DELTA: u32 = 0x9e3779b9;
+const ROUNDS: usize = 64;
+
+// Applies the XTEA round function of `v` with the round key `k`.
+fn f(v: u32, k: u32) -> u32 {
+    (((v << 4) ^ (v >> 5)).wrapping_add(v)) ^ k
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Xtea` is a keyed XTEA cipher, it doesn't allocate.
+///# Example
+/// ```
+/// use cryptolib::block::xtea::Xtea;
+/// use cryptolib::block::BlockCipher;
+///
+/// let xtea = Xtea::new(&[0x42; 16]).unwrap();
+/// let mut block = *b"8 bytes!";
+/// xtea.encrypt_block(&mut block);
+/// xtea.decrypt_block(&mut block);
+/// assert_eq!(&block, b"8 bytes!");
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Xtea {
+    // sum + key[...] of each round.
+    round_keys: [u32; ROUNDS],
+}
+
+impl Xtea {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an XTEA cipher from a 16 bytes `key`.
+    ///# Returns
+    /// The keyed cipher or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Xtea, CryptoError> {
+        if key.len() != 16 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let mut k = [0_u32; 4];
+        for (k, bytes) in k.iter_mut().zip(key.chunks_exact(4)) {
+            *k = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        let mut round_keys = [0_u32; ROUNDS];
+        let mut sum = 0_u32;
+        for pair in round_keys.chunks_exact_mut(2) {
+            pair[0] = sum.wrapping_add(k[(sum & 3) as usize]);
+            sum = sum.wrapping_add(DELTA);
+            pair[1] = sum.wrapping_add(k[((sum >> 11) & 3) as usize]);
+        }
+        Ok(Xtea { round_keys })
+    }
+
+    /// Encrypts the block made of the two words `v`.
+    pub fn encrypt_words(&self, v: [u32; 2]) -> [u32; 2] {
+        let [mut v0, mut v1] = v;
+        for pair in self.round_keys.chunks_exact(2) {
+            v0 = v0.wrapping_add(f(v1, pair[0]));
+            v1 = v1.wrapping_add(f(v0, pair[1]));
+        }
+        [v0, v1]
+    }
+
+    /// Decrypts the block made of the two words `v`.
+    pub fn decrypt_words(&self, v: [u32; 2]) -> [u32; 2] {
+        let [mut v0, mut v1] = v;
+        for pair in self.round_keys.chunks_exact(2).rev() {
+            v1 = v1.wrapping_sub(f(v0, pair[1]));
+            v0 = v0.wrapping_sub(f(v1, pair[0]));
+        }
+        [v0, v1]
+    }
+}
+
+fn load(block: &[u8]) -> [u32; 2] {
+    assert_eq!(block.len(), 8, "xtea block must be 8 bytes");
+    [
+        u32::from_be_bytes(block[..4].try_into().unwrap()),
+        u32::from_be_bytes(block[4..].try_into().unwrap()),
+    ]
+}
+
+fn store(block: &mut [u8], v: [u32; 2]) {
+    block[..4].copy_from_slice(&v[0].to_be_bytes());
+    block[4..].copy_from_slice(&v[1].to_be_bytes());
+}
+
+impl BlockCipher for Xtea {
+    const BLOCK_SIZE: usize = 8;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        store(block, self.encrypt_words(load(block)));
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        store(block, self.decrypt_words(load(block)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_xtea() {
+        // Widely published vectors (Crypto++, Bouncy Castle).
+        for (key, plain, cipher) in [
+            (
+                "000102030405060708090a0b0c0d0e0f",
+                "4142434445464748",
+                "497df3d072612cb5",
+            ),
+            (
+                "00000000000000000000000000000000",
+                "0000000000000000",
+                "dee9d4d8f7131ed9",
+            ),
+        ] {
+            let xtea = Xtea::new(&hex(key)).unwrap();
+            let mut block = hex(plain);
+            xtea.encrypt_block(&mut block);
+            assert_eq!(block, hex(cipher), "key {}", key);
+            xtea.decrypt_block(&mut block);
+            assert_eq!(block, hex(plain));
+        }
+        assert_eq!(
+            Xtea::new(&[0; 15]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+}
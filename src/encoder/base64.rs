@@ -19,6 +19,9 @@ const B64_TABLE:&[u8;65] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxy
 // A URL safe version(RFC 4648§5).
 const B64_URL_TABLE:&[u8;65] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_=";
 
+// The bcrypt table, it's never padded('=' is only there to match the other tables).
+const BCRYPT_TABLE:&[u8;65] = b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789=";
+
 // ASCII to Base64(6 bits) mapping
 const B64_INDEX: [u8; 256] = [
     /* nul, soh, stx, etx, eot, enq, ack, bel, */
@@ -116,6 +119,12 @@ pub fn b64_encode(data: &[u8],no_padding:bool,url_safe:bool) -> Vec<u8> {
         true => &B64_URL_TABLE,
         false => &B64_TABLE,
     };
+    encode_with_table(data, table, no_padding)
+}
+
+// Encodes `data` with the 64 characters of `table`, the 65th being the padding.
+#[allow(clippy::manual_div_ceil, clippy::unnecessary_cast, clippy::needless_return)]
+fn encode_with_table(data: &[u8], table: &[u8; 65], no_padding: bool) -> Vec<u8> {
     let data_len = data.len();
     let output_len = 4 * ((data_len + 2) / 3); // 3-byte blocks to 4-bytes.
     let mut b_enc = 0; // Number of bytes encoded.
@@ -163,6 +172,7 @@ pub fn b64_encode(data: &[u8],no_padding:bool,url_safe:bool) -> Vec<u8> {
 ///# Return
 /// A vector containing the base64 encrypted data.
 ////////////////////////////////////////////////////////////////////////////////
+#[allow(clippy::needless_borrow, clippy::needless_return)]
 pub fn b64_mime_encode(data:&[u8])->Vec<u8>
 {
     let mut enc_data = b64_encode(&data, false, false);    
@@ -192,6 +202,7 @@ pub fn b64_mime_encode(data:&[u8])->Vec<u8>
 /// compliant(standard or url) sequence and for malformed inputs the function returns a malformed outputs.
 /// '''
 ///////////////////////////////////////////////////////////////////////////////////////////////////////
+#[allow(clippy::ptr_arg, clippy::needless_return)]
 pub fn b64_decode(enc_data:&Vec<u8>) -> Option<Vec<u8>>
 {
     let data_len = enc_data.len();
@@ -241,6 +252,7 @@ pub fn b64_decode(enc_data:&Vec<u8>) -> Option<Vec<u8>>
 /// for malformed inputs the function returns a malformed outputs.
 /// '''
 ///////////////////////////////////////////////////////////////////////////////////////////////////////
+#[allow(unused_parens, clippy::needless_return)]
 pub fn b64_mime_decode(enc_data:&[u8])->Option<Vec<u8>>
 {
     // Remove CRLF sequences from the data.
@@ -281,11 +293,43 @@ pub fn b64_mime_decode(enc_data:&[u8])->Option<Vec<u8>>
     return Some(dec_data);
 }
 
+/////////////////////////////////////////////////////////////////////////////////
+///Encode a sequence of bytes with the bcrypt base64 alphabet(./A-Za-z0-9).
+///# Arguments 
+///* `data`: A refrence to the sequence of bytes to be encoded.
+///# Return
+/// A vector containing the unpadded encoded data.
+////////////////////////////////////////////////////////////////////////////////
+pub fn bcrypt_b64_encode(data: &[u8]) -> Vec<u8> {
+    encode_with_table(data, BCRYPT_TABLE, true)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////
+///Decode a sequence encoded with the bcrypt base64 alphabet.
+///# Arguments
+///* `enc_data`: A refrence to the unpadded bcrypt base64 sequence.
+///# Return 
+/// Some vector containing the decoded data or None if `enc_data` is empty, has an impossible
+/// length or contains a character outside of the alphabet.
+///////////////////////////////////////////////////////////////////////////////////////////////////////
+pub fn bcrypt_b64_decode(enc_data: &[u8]) -> Option<Vec<u8>> {
+    if enc_data.len() % 4 == 1 {
+        return None;
+    }
+    // Translate to the standard alphabet and reuse its decoder.
+    let std_data = enc_data
+        .iter()
+        .map(|c| BCRYPT_TABLE[..64].iter().position(|t| t == c).map(|i| B64_TABLE[i]))
+        .collect::<Option<Vec<_>>>()?;
+    b64_decode(&std_data)
+}
+
 #[cfg(test)]
 mod tests
 {
     use super::*;
     #[test]
+    #[allow(clippy::unnecessary_to_owned)]
     fn basic_test()
     {
         assert_eq!(b64_encode(&b"abcd".to_vec(),false,false),b"YWJjZA==".to_vec());
@@ -421,6 +465,21 @@ mod tests
     }
 
     #[test]
+    fn bcrypt_b64_test()
+    {
+        // Salt and hash of "$2a$06$DCq7YPn5Rq63x1Lad4cll.TV4S6ytwfsfvkgY8jIucDrjc8deX1s.".
+        let salt = b"\x14\x4b\x3d\x69\x1a\x7b\x4e\xcf\x39\xcf\x73\x5c\x7f\xa7\xa7\x9c";
+        assert_eq!(bcrypt_b64_encode(salt), b"DCq7YPn5Rq63x1Lad4cll.".to_vec());
+        assert_eq!(bcrypt_b64_decode(b"DCq7YPn5Rq63x1Lad4cll.").unwrap(), salt.to_vec());
+        let hash = bcrypt_b64_decode(b"TV4S6ytwfsfvkgY8jIucDrjc8deX1s.").unwrap();
+        assert_eq!(hash.len(), 23);
+        assert_eq!(bcrypt_b64_encode(&hash), b"TV4S6ytwfsfvkgY8jIucDrjc8deX1s.".to_vec());
+        assert_eq!(bcrypt_b64_decode(b"DCq7YPn5Rq63x1Lad4cl+."), None);
+        assert_eq!(bcrypt_b64_decode(b"DCq7Y"), None);
+    }
+
+    #[test]
+    #[allow(clippy::unnecessary_to_owned)]
     fn b64_mime_encode_test()
     {
         
@@ -443,6 +502,7 @@ mod tests
     }
 
     #[test]
+    #[allow(clippy::unnecessary_to_owned)]
     fn b64_mime_decode_test()
     {
         assert_eq!(
@@ -0,0 +1,49 @@
+use std::fmt;
+
+////////////////////////////////////////////////////////////
+/// `CryptoError` represent the reasons for which an
+/// operation of the crate can fail.
+////////////////////////////////////////////////////////////
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CryptoError {
+    /// The key length isn't supported by the algorithm.
+    InvalidKeyLength,
+    /// The nonce (or IV) length isn't supported by the algorithm.
+    InvalidNonceLength,
+    /// The authentication tag length isn't supported by the algorithm.
+    InvalidTagLength,
+    /// The block size of the cipher isn't supported by the mode.
+    InvalidBlockSize,
+    /// The data length isn't supported by the algorithm.
+    InvalidDataLength,
+    /// The padding of the decrypted data is malformed.
+    InvalidPadding,
+    /// The data is longer than what the algorithm can process.
+    MessageTooLong,
+    /// The stream cipher position would overflow its block counter.
+    CounterOverflow,
+    /// A parameter (cost, encoded hash, ...) is malformed or out of range.
+    InvalidParameter,
+    /// The authentication tag doesn't match the data.
+    AuthenticationFailed,
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            CryptoError::InvalidKeyLength => "invalid key length",
+            CryptoError::InvalidNonceLength => "invalid nonce length",
+            CryptoError::InvalidTagLength => "invalid tag length",
+            CryptoError::InvalidBlockSize => "invalid cipher block size",
+            CryptoError::InvalidDataLength => "invalid data length",
+            CryptoError::InvalidPadding => "invalid padding",
+            CryptoError::MessageTooLong => "message too long",
+            CryptoError::CounterOverflow => "stream cipher counter overflow",
+            CryptoError::InvalidParameter => "invalid parameter",
+            CryptoError::AuthenticationFailed => "authentication failed",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for CryptoError {}
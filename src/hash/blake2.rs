@@ -0,0 +1,617 @@
+//#######################################################################o
+// BLAKE2 is a hash function derived from the ChaCha based BLAKE. The    |
+// compression function mixes a 16 words state (the chaining value, the  |
+// IV, the byte counter and a finalization flag) with the message block  |
+// through the G function, in 12 rounds for BLAKE2b (64-bit words, 128   |
+// bytes blocks) and 10 rounds for BLAKE2s (32-bit words, 64 bytes       |
+// blocks). A parameter block xored into the IV sets the digest length,  |
+// the key length, a salt and a personalization string. A key is hashed  |
+// as a first block of its own, so keyed BLAKE2 is a MAC.                |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 7693: https://www.rfc-editor.org/rfc/rfc7693                      |
+// BLAKE2: https://www.blake2.net/blake2.pdf                             |
+//#######################################################################o
+
+use super::Digest;
+use crate::error::CryptoError;
+
+// Message word permutations of each round, BLAKE2b uses the first two
+// rows again for its last two rounds.
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+// Initial values, the ones of SHA-512 and SHA-256.
+const IV_B: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+const IV_S: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+//////////////////////////////////////////////////////////////////////
+/// Checks the parameters against the limits of a variant: `max_out`
+/// bytes for the digest and the key, `max_salt` bytes for the salt and
+/// the personalization.
+//////////////////////////////////////////////////////////////////////
+fn check_params(
+    key: &[u8],
+    salt: &[u8],
+    personal: &[u8],
+    output_len: usize,
+    max_out: usize,
+    max_salt: usize,
+) -> Result<(), CryptoError> {
+    if key.len() > max_out {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    if output_len == 0 || output_len > max_out || salt.len() > max_salt || personal.len() > max_salt
+    {
+        return Err(CryptoError::InvalidParameter);
+    }
+    Ok(())
+}
+
+// Reads a salt or a personalization string zero-padded to two words.
+fn words_le<const W: usize>(bytes: &[u8]) -> [[u8; W]; 2] {
+    let mut padded = [[0_u8; W]; 2];
+    for (i, b) in bytes.iter().enumerate() {
+        padded[i / W][i % W] = *b;
+    }
+    padded
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function of BLAKE2b.
+///# Arguments
+///* `h`: the chaining value.
+///* `block`: a 128 bytes block.
+///* `t`: the number of message bytes hashed so far, this block included.
+///* `last`: whether it's the last block.
+//////////////////////////////////////////////////////////////////////
+fn compress_b(h: &mut [u64; 8], block: &[u8], t: u128, last: bool) {
+    let mut m = [0_u64; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(8)) {
+        *m = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    let mut v = [0_u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV_B);
+    v[12] ^= t as u64;
+    v[13] ^= (t >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+    let mut g = |a: usize, b: usize, c: usize, d: usize, x: u64, y: u64| {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(32);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(24);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(63);
+    };
+    for round in 0..12 {
+        let s = &SIGMA[round % 10];
+        g(0, 4, 8, 12, m[s[0]], m[s[1]]);
+        g(1, 5, 9, 13, m[s[2]], m[s[3]]);
+        g(2, 6, 10, 14, m[s[4]], m[s[5]]);
+        g(3, 7, 11, 15, m[s[6]], m[s[7]]);
+        g(0, 5, 10, 15, m[s[8]], m[s[9]]);
+        g(1, 6, 11, 12, m[s[10]], m[s[11]]);
+        g(2, 7, 8, 13, m[s[12]], m[s[13]]);
+        g(3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function of BLAKE2s, with a 64 bytes `block` and
+/// a 64-bit counter `t`.
+//////////////////////////////////////////////////////////////////////
+fn compress_s(h: &mut [u32; 8], block: &[u8], t: u64, last: bool) {
+    let mut m = [0_u32; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *m = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    let mut v = [0_u32; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV_S);
+    v[12] ^= t as u32;
+    v[13] ^= (t >> 32) as u32;
+    if last {
+        v[14] = !v[14];
+    }
+    let mut g = |a: usize, b: usize, c: usize, d: usize, x: u32, y: u32| {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(12);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(8);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(7);
+    };
+    for s in &SIGMA {
+        g(0, 4, 8, 12, m[s[0]], m[s[1]]);
+        g(1, 5, 9, 13, m[s[2]], m[s[3]]);
+        g(2, 6, 10, 14, m[s[4]], m[s[5]]);
+        g(3, 7, 11, 15, m[s[6]], m[s[7]]);
+        g(0, 5, 10, 15, m[s[8]], m[s[9]]);
+        g(1, 6, 11, 12, m[s[10]], m[s[11]]);
+        g(2, 7, 8, 13, m[s[12]], m[s[13]]);
+        g(3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Blake2b` is a streaming BLAKE2b hasher with a digest of 1 to 64
+/// bytes, optionally keyed.
+///# Example
+/// ```
+/// use cryptolib::hash::blake2::Blake2b;
+/// use cryptolib::hash::Digest;
+///
+/// let mut mac = Blake2b::new_mac(b"key", 32).unwrap();
+/// mac.update(b"message");
+/// let tag = mac.finalize();
+/// assert_eq!(tag.len(), 32);
+/// assert_eq!(Blake2b::digest(b"abc").len(), 64);
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Blake2b {
+    h: [u64; 8],
+    // The chaining value given by the parameter block and the padded
+    // key, `reset` starts again from them.
+    initial: [u64; 8],
+    key_block: Option<Box<[u8; 128]>>,
+    // Pending message bytes, the last block is kept until finalize.
+    buffer: [u8; 128],
+    buffer_len: usize,
+    // Message bytes processed so far.
+    t: u128,
+    output_len: usize,
+}
+
+impl Blake2b {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a hasher producing `output_len` bytes.
+    ///# Returns
+    /// The hasher or `CryptoError::InvalidParameter` if `output_len`
+    /// isn't between 1 and 64.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(output_len: usize) -> Result<Blake2b, CryptoError> {
+        Self::with_params(&[], &[], &[], output_len)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a keyed BLAKE2b producing `output_len` bytes.
+    ///# Returns
+    /// The MAC, `CryptoError::InvalidKeyLength` if `key` is longer
+    /// than 64 bytes or `CryptoError::InvalidParameter` if `output_len`
+    /// isn't between 1 and 64.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new_mac(key: &[u8], output_len: usize) -> Result<Blake2b, CryptoError> {
+        Self::with_params(key, &[], &[], output_len)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a hasher with all the parameters.
+    ///# Arguments
+    ///* `key`: up to 64 bytes, empty for plain hashing.
+    ///* `salt`: up to 16 bytes, zero-padded.
+    ///* `personal`: a personalization string of up to 16 bytes,
+    ///  zero-padded.
+    ///* `output_len`: the digest length, from 1 to 64 bytes.
+    ///# Returns
+    /// The hasher, `CryptoError::InvalidKeyLength` or
+    /// `CryptoError::InvalidParameter`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_params(
+        key: &[u8],
+        salt: &[u8],
+        personal: &[u8],
+        output_len: usize,
+    ) -> Result<Blake2b, CryptoError> {
+        check_params(key, salt, personal, output_len, 64, 16)?;
+        let mut h = IV_B;
+        h[0] ^= 0x01010000 ^ ((key.len() as u64) << 8) ^ output_len as u64;
+        for (i, word) in words_le::<8>(salt)
+            .into_iter()
+            .chain(words_le::<8>(personal))
+            .enumerate()
+        {
+            h[4 + i] ^= u64::from_le_bytes(word);
+        }
+        let key_block = (!key.is_empty()).then(|| {
+            let mut block = Box::new([0_u8; 128]);
+            block[..key.len()].copy_from_slice(key);
+            block
+        });
+        let mut blake = Blake2b {
+            h,
+            initial: h,
+            key_block,
+            buffer: [0; 128],
+            buffer_len: 0,
+            t: 0,
+            output_len,
+        };
+        blake.reset();
+        Ok(blake)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// The default hasher outputs 64 bytes, `finalize` returns the output
+/// length given at creation.
+////////////////////////////////////////////////////////////////////////////
+impl Digest for Blake2b {
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 128;
+
+    type Output = Vec<u8>;
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            // A full buffer is only processed once more data follows.
+            if self.buffer_len == 128 {
+                self.t += 128;
+                compress_b(&mut self.h, &self.buffer, self.t, false);
+                self.buffer_len = 0;
+            }
+            let take = data.len().min(128 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+        }
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        self.t += self.buffer_len as u128;
+        self.buffer[self.buffer_len..].fill(0);
+        compress_b(&mut self.h, &self.buffer, self.t, true);
+        let mut out: Vec<u8> = self.h.iter().flat_map(|w| w.to_le_bytes()).collect();
+        out.truncate(self.output_len);
+        out
+    }
+
+    /// Starts a new message with the same parameters and key.
+    fn reset(&mut self) {
+        self.h = self.initial;
+        self.t = 0;
+        match &self.key_block {
+            Some(block) => {
+                self.buffer = **block;
+                self.buffer_len = 128;
+            }
+            None => self.buffer_len = 0,
+        }
+    }
+}
+
+impl Default for Blake2b {
+    fn default() -> Self {
+        Self::new(64).unwrap()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Blake2s` is a streaming BLAKE2s hasher with a digest of 1 to 32
+/// bytes, optionally keyed.
+///# Example
+/// ```
+/// use cryptolib::hash::blake2::Blake2s;
+/// use cryptolib::hash::Digest;
+///
+/// let mut blake = Blake2s::with_params(&[], b"saltsalt", b"my app", 20).unwrap();
+/// blake.update(b"message");
+/// assert_eq!(blake.finalize().len(), 20);
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Blake2s {
+    h: [u32; 8],
+    initial: [u32; 8],
+    key_block: Option<Box<[u8; 64]>>,
+    buffer: [u8; 64],
+    buffer_len: usize,
+    t: u64,
+    output_len: usize,
+}
+
+impl Blake2s {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a hasher producing `output_len` bytes.
+    ///# Returns
+    /// The hasher or `CryptoError::InvalidParameter` if `output_len`
+    /// isn't between 1 and 32.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(output_len: usize) -> Result<Blake2s, CryptoError> {
+        Self::with_params(&[], &[], &[], output_len)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a keyed BLAKE2s producing `output_len` bytes.
+    ///# Returns
+    /// The MAC, `CryptoError::InvalidKeyLength` if `key` is longer
+    /// than 32 bytes or `CryptoError::InvalidParameter` if `output_len`
+    /// isn't between 1 and 32.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new_mac(key: &[u8], output_len: usize) -> Result<Blake2s, CryptoError> {
+        Self::with_params(key, &[], &[], output_len)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a hasher with all the parameters.
+    ///# Arguments
+    ///* `key`: up to 32 bytes, empty for plain hashing.
+    ///* `salt`: up to 8 bytes, zero-padded.
+    ///* `personal`: a personalization string of up to 8 bytes,
+    ///  zero-padded.
+    ///* `output_len`: the digest length, from 1 to 32 bytes.
+    ///# Returns
+    /// The hasher, `CryptoError::InvalidKeyLength` or
+    /// `CryptoError::InvalidParameter`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_params(
+        key: &[u8],
+        salt: &[u8],
+        personal: &[u8],
+        output_len: usize,
+    ) -> Result<Blake2s, CryptoError> {
+        check_params(key, salt, personal, output_len, 32, 8)?;
+        let mut h = IV_S;
+        h[0] ^= 0x01010000 ^ ((key.len() as u32) << 8) ^ output_len as u32;
+        for (i, word) in words_le::<4>(salt)
+            .into_iter()
+            .chain(words_le::<4>(personal))
+            .enumerate()
+        {
+            h[4 + i] ^= u32::from_le_bytes(word);
+        }
+        let key_block = (!key.is_empty()).then(|| {
+            let mut block = Box::new([0_u8; 64]);
+            block[..key.len()].copy_from_slice(key);
+            block
+        });
+        let mut blake = Blake2s {
+            h,
+            initial: h,
+            key_block,
+            buffer: [0; 64],
+            buffer_len: 0,
+            t: 0,
+            output_len,
+        };
+        blake.reset();
+        Ok(blake)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// The default hasher outputs 32 bytes, `finalize` returns the output
+/// length given at creation.
+////////////////////////////////////////////////////////////////////////////
+impl Digest for Blake2s {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = Vec<u8>;
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.buffer_len == 64 {
+                self.t += 64;
+                compress_s(&mut self.h, &self.buffer, self.t, false);
+                self.buffer_len = 0;
+            }
+            let take = data.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+        }
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        self.t += self.buffer_len as u64;
+        self.buffer[self.buffer_len..].fill(0);
+        compress_s(&mut self.h, &self.buffer, self.t, true);
+        let mut out: Vec<u8> = self.h.iter().flat_map(|w| w.to_le_bytes()).collect();
+        out.truncate(self.output_len);
+        out
+    }
+
+    /// Starts a new message with the same parameters and key.
+    fn reset(&mut self) {
+        self.h = self.initial;
+        self.t = 0;
+        match &self.key_block {
+            Some(block) => {
+                self.buffer = **block;
+                self.buffer_len = 64;
+            }
+            None => self.buffer_len = 0,
+        }
+    }
+}
+
+impl Default for Blake2s {
+    fn default() -> Self {
+        Self::new(32).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    // The pseudo-random sequence of the RFC 7693 self-test.
+    fn selftest_seq(len: usize, seed: u32) -> Vec<u8> {
+        let (mut a, mut b) = (0xdead4bad_u32.wrapping_mul(seed), 1_u32);
+        (0..len)
+            .map(|_| {
+                let t = a.wrapping_add(b);
+                a = b;
+                b = t;
+                (t >> 24) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_blake2_rfc7693() {
+        // Appendices A and B.
+        assert_eq!(
+            Blake2b::digest(b"abc"),
+            hex(
+                "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1
+                 7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+            )
+        );
+        assert_eq!(
+            Blake2s::digest(b"abc"),
+            hex("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982")
+        );
+
+        // Appendix E: every digest length and some message lengths, with
+        // and without key, hashed together.
+        let mut grand = Blake2b::new(32).unwrap();
+        for out_len in [20, 32, 48, 64] {
+            for in_len in [0, 3, 128, 129, 255, 1024] {
+                let data = selftest_seq(in_len, in_len as u32);
+                let key = selftest_seq(out_len, out_len as u32);
+                for key in [&[][..], &key] {
+                    let mut blake = Blake2b::new_mac(key, out_len).unwrap();
+                    blake.update(&data);
+                    grand.update(&blake.finalize());
+                }
+            }
+        }
+        assert_eq!(
+            grand.finalize(),
+            hex("c23a7800d98123bd10f506c61e29da5603d763b8bbad2e737f5e765a7bccd475")
+        );
+        let mut grand = Blake2s::new(32).unwrap();
+        for out_len in [16, 20, 28, 32] {
+            for in_len in [0, 3, 64, 65, 255, 1024] {
+                let data = selftest_seq(in_len, in_len as u32);
+                let key = selftest_seq(out_len, out_len as u32);
+                for key in [&[][..], &key] {
+                    let mut blake = Blake2s::new_mac(key, out_len).unwrap();
+                    blake.update(&data);
+                    grand.update(&blake.finalize());
+                }
+            }
+        }
+        assert_eq!(
+            grand.finalize(),
+            hex("6a411f08ce25adcdfb02aba641451cec53c598b24f4fc787fbdc88797f4c1dfe")
+        );
+    }
+
+    #[test]
+    fn test_blake2_keyed() {
+        // blake2b-kat.txt and blake2s-kat.txt of the reference code, the
+        // key 00 01 02 ... and the message 00 01 02 ...
+        let data: Vec<u8> = (0..=254).collect();
+        let mut blake = Blake2b::new_mac(&data[..64], 64).unwrap();
+        assert_eq!(
+            blake.clone().finalize(),
+            hex(
+                "10ebb67700b1868efb4417987acf4690ae9d972fb7a590c2f02871799aaa4786
+                 b5e996e8f0f4eb981fc214b005f42d2ff4233499391653df7aefcbc13fc51568"
+            )
+        );
+        for chunk in data.chunks(50) {
+            blake.update(chunk);
+        }
+        assert_eq!(
+            blake.clone().finalize(),
+            hex(
+                "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e9248
+                 4be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461"
+            )
+        );
+        // Reset keeps the key.
+        blake.reset();
+        assert_eq!(blake.finalize()[..4], hex("10ebb677"));
+
+        let mut blake = Blake2s::new_mac(&data[..32], 32).unwrap();
+        assert_eq!(
+            blake.clone().finalize(),
+            hex("48a8997da407876b3d79c0d92325ad3b89cbb754d86ab71aee047ad345fd2c49")
+        );
+        blake.update(&data);
+        assert_eq!(
+            blake.finalize(),
+            hex("3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd")
+        );
+    }
+
+    #[test]
+    fn test_blake2_params() {
+        // Checked against Python's hashlib.
+        let data: Vec<u8> = (0..=255).collect();
+        let mut blake =
+            Blake2b::with_params(b"secret key", b"0123456789abcdef", b"my app", 32).unwrap();
+        blake.update(&data);
+        assert_eq!(
+            blake.finalize(),
+            hex("115b2c02f28526b0423b627f1cb533605911c6e21970dd1591efefea48356842")
+        );
+        let mut blake = Blake2s::with_params(&[], b"saltsalt", b"personal", 20).unwrap();
+        blake.update(&data);
+        assert_eq!(
+            blake.finalize(),
+            hex("55cec49740e63d0ce327b5a951320b36d379a3de")
+        );
+        // A short salt is zero-padded.
+        let mut blake = Blake2b::with_params(&[], b"salt", &[], 64).unwrap();
+        blake.update(b"abc");
+        assert_eq!(
+            blake.finalize(),
+            hex(
+                "e6844fcf0fbb2ef59cf0297642f11b32179f05c960ec5c14ad73910e6de2fdac
+                 ff2134c8cf75a5083bf29167996ba043ae7d5ee599beb3d72c12e3f92ef24596"
+            )
+        );
+
+        assert_eq!(Blake2b::new(0).err(), Some(CryptoError::InvalidParameter));
+        assert_eq!(Blake2b::new(65).err(), Some(CryptoError::InvalidParameter));
+        assert_eq!(Blake2s::new(33).err(), Some(CryptoError::InvalidParameter));
+        assert_eq!(
+            Blake2b::new_mac(&[0; 65], 64).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Blake2s::with_params(&[], &[0; 9], &[], 32).err(),
+            Some(CryptoError::InvalidParameter)
+        );
+    }
+}
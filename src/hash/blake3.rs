@@ -0,0 +1,724 @@
+//#######################################################################o
+// BLAKE3 is a hash function built on a reduced BLAKE2s compression (7   |
+// rounds, a fixed message permutation). The input is split in 1024      |
+// bytes chunks hashed independently into 32 bytes chaining values,      |
+// which are the leaves of a binary tree whose parent nodes compress the |
+// concatenation of their two children. Left subtrees are always full    |
+// and a power of two chunks wide, so large inputs can be hashed on      |
+// several threads. Flags tell chunks, parents and the root apart, the   |
+// root can be compressed again with any counter to produce an output of |
+// any length. A key or a context string gives the keyed hash and the    |
+// key derivation modes.                                                 |
+//                                                                       |
+// @Refrences:                                                           |
+// BLAKE3: https://github.com/BLAKE3-team/BLAKE3-specs/blob/master/blake3.pdf
+//#######################################################################o
+
+use super::Digest;
+
+const OUT_LEN: usize = 32;
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+
+// Domain flags of the compression function.
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+// The SHA-256 initial value, the key of the unkeyed modes.
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+// Permutation of the message words between rounds.
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(x);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(y);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function, returns the 16 words state whose first
+/// half is the new chaining value.
+///# Arguments
+///* `cv`: the chaining value or the key.
+///* `block`: the 16 message words.
+///* `counter`: the chunk index, or the output block index for the root.
+///* `block_len`: the number of message bytes in the block.
+///* `flags`: the domain flags.
+//////////////////////////////////////////////////////////////////////
+fn compress(
+    cv: &[u32; 8],
+    block: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 16] {
+    let mut state = [0_u32; 16];
+    state[..8].copy_from_slice(cv);
+    state[8..12].copy_from_slice(&IV[..4]);
+    state[12] = counter as u32;
+    state[13] = (counter >> 32) as u32;
+    state[14] = block_len;
+    state[15] = flags;
+    let mut m = *block;
+    for round in 0..7 {
+        g(&mut state, 0, 4, 8, 12, m[0], m[1]);
+        g(&mut state, 1, 5, 9, 13, m[2], m[3]);
+        g(&mut state, 2, 6, 10, 14, m[4], m[5]);
+        g(&mut state, 3, 7, 11, 15, m[6], m[7]);
+        g(&mut state, 0, 5, 10, 15, m[8], m[9]);
+        g(&mut state, 1, 6, 11, 12, m[10], m[11]);
+        g(&mut state, 2, 7, 8, 13, m[12], m[13]);
+        g(&mut state, 3, 4, 9, 14, m[14], m[15]);
+        if round < 6 {
+            m = MSG_PERMUTATION.map(|i| m[i]);
+        }
+    }
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= cv[i];
+    }
+    state
+}
+
+fn words_le<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    let mut words = [0_u32; N];
+    for (w, b) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        *w = u32::from_le_bytes(b.try_into().unwrap());
+    }
+    words
+}
+
+fn first_half(state: [u32; 16]) -> [u32; 8] {
+    state[..8].try_into().unwrap()
+}
+
+//////////////////////////////////////////////////////////////////////
+/// `Output` is a node whose last compression is pending, either the
+/// chaining value of a chunk or parent or the root output blocks.
+//////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+struct Output {
+    cv: [u32; 8],
+    block: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        first_half(compress(
+            &self.cv,
+            &self.block,
+            self.counter,
+            self.block_len,
+            self.flags,
+        ))
+    }
+
+    // The 64 bytes output block of index `counter`.
+    fn root_block(&self, counter: u64) -> [u8; 64] {
+        let state = compress(
+            &self.cv,
+            &self.block,
+            counter,
+            self.block_len,
+            self.flags | ROOT,
+        );
+        let mut out = [0_u8; 64];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+fn parent_output(left: [u32; 8], right: [u32; 8], key: &[u32; 8], flags: u32) -> Output {
+    let mut block = [0_u32; 16];
+    block[..8].copy_from_slice(&left);
+    block[8..].copy_from_slice(&right);
+    Output {
+        cv: *key,
+        block,
+        counter: 0,
+        block_len: BLOCK_LEN as u32,
+        flags: flags | PARENT,
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// `ChunkState` hashes the blocks of one chunk, the last block being
+/// kept until the chunk ends.
+//////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+struct ChunkState {
+    cv: [u32; 8],
+    chunk_counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+    flags: u32,
+}
+
+impl ChunkState {
+    fn new(key: &[u32; 8], chunk_counter: u64, flags: u32) -> ChunkState {
+        ChunkState {
+            cv: *key,
+            chunk_counter,
+            block: [0; BLOCK_LEN],
+            block_len: 0,
+            blocks_compressed: 0,
+            flags,
+        }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed + self.block_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 {
+            CHUNK_START
+        } else {
+            0
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            if self.block_len == BLOCK_LEN {
+                self.cv = first_half(compress(
+                    &self.cv,
+                    &words_le(&self.block),
+                    self.chunk_counter,
+                    BLOCK_LEN as u32,
+                    self.flags | self.start_flag(),
+                ));
+                self.blocks_compressed += 1;
+                self.block = [0; BLOCK_LEN];
+                self.block_len = 0;
+            }
+            let take = data.len().min(BLOCK_LEN - self.block_len);
+            self.block[self.block_len..self.block_len + take].copy_from_slice(&data[..take]);
+            self.block_len += take;
+            data = &data[take..];
+        }
+    }
+
+    fn output(&self) -> Output {
+        Output {
+            cv: self.cv,
+            block: words_le(&self.block),
+            counter: self.chunk_counter,
+            block_len: self.block_len as u32,
+            flags: self.flags | self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Returns the chaining value of a full subtree of chunks starting at
+/// `chunk_counter`, the two halves being hashed on separate threads
+/// while `threads` allows it.
+///# Note
+/// '''
+/// The length of `data` must be a power of two number of chunks.
+/// '''
+//////////////////////////////////////////////////////////////////////
+fn hash_subtree(
+    data: &[u8],
+    chunk_counter: u64,
+    key: &[u32; 8],
+    flags: u32,
+    threads: usize,
+) -> [u32; 8] {
+    if data.len() == CHUNK_LEN {
+        let mut chunk = ChunkState::new(key, chunk_counter, flags);
+        chunk.update(data);
+        return chunk.output().chaining_value();
+    }
+    let (left, right) = data.split_at(data.len() / 2);
+    let right_counter = chunk_counter + (left.len() / CHUNK_LEN) as u64;
+    let (left_cv, right_cv) = if threads > 1 {
+        std::thread::scope(|scope| {
+            let left_cv =
+                scope.spawn(|| hash_subtree(left, chunk_counter, key, flags, threads / 2));
+            let right_cv = hash_subtree(right, right_counter, key, flags, threads - threads / 2);
+            (left_cv.join().unwrap(), right_cv)
+        })
+    } else {
+        (
+            hash_subtree(left, chunk_counter, key, flags, 1),
+            hash_subtree(right, right_counter, key, flags, 1),
+        )
+    };
+    parent_output(left_cv, right_cv, key, flags).chaining_value()
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Blake3` is a streaming BLAKE3 hasher in one of its three modes:
+/// hashing, keyed hashing and key derivation.
+///# Example
+/// ```
+/// use cryptolib::hash::blake3::Blake3;
+/// use cryptolib::hash::Digest;
+///
+/// let mut blake = Blake3::new();
+/// blake.update(b"a");
+/// blake.update(b"bc");
+/// assert_eq!(blake.finalize(), Blake3::digest(b"abc"));
+///
+/// let key = Blake3::new_derive_key("example.com 2024-01-01 session key")
+///     .chain(b"input key material")
+///     .finalize_xof()
+///     .read_vec(64);
+/// assert_eq!(key.len(), 64);
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Blake3 {
+    key: [u32; 8],
+    chunk_state: ChunkState,
+    // Chaining values of the complete subtrees, from the largest to the
+    // smallest, one per bit set in the number of chunks.
+    cv_stack: Vec<[u32; 8]>,
+    flags: u32,
+}
+
+impl Default for Blake3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Blake3 {
+    fn with_key(key: [u32; 8], flags: u32) -> Blake3 {
+        Blake3 {
+            key,
+            chunk_state: ChunkState::new(&key, 0, flags),
+            cv_stack: Vec::new(),
+            flags,
+        }
+    }
+
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Blake3 {
+        Self::with_key(IV, 0)
+    }
+
+    /// Creates a keyed hasher, a MAC or a PRF, with a 32 bytes `key`.
+    pub fn new_keyed(key: &[u8; 32]) -> Blake3 {
+        Self::with_key(words_le(key), KEYED_HASH)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a key derivation hasher, the key material being given
+    /// to `update`.
+    ///# Note
+    /// '''
+    /// The context string should be hardcoded, globally unique and
+    /// application specific, never derived from the key material.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn new_derive_key(context: &str) -> Blake3 {
+        let mut hasher = Self::with_key(IV, DERIVE_KEY_CONTEXT);
+        hasher.update(context.as_bytes());
+        let context_key = hasher.finalize();
+        Self::with_key(words_le(&context_key), DERIVE_KEY_MATERIAL)
+    }
+
+    /// Absorbs `data` and returns the hasher, for one-line hashing.
+    pub fn chain(mut self, data: &[u8]) -> Blake3 {
+        self.update(data);
+        self
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Pushes the chaining value of a complete subtree, merging the
+    /// subtrees it completes.
+    ///# Arguments
+    ///* `cv`: the chaining value of the subtree.
+    ///* `total`: the number of chunks so far divided by the number
+    ///  of chunks in the subtree.
+    //////////////////////////////////////////////////////////////////
+    fn push_cv(&mut self, mut cv: [u32; 8], mut total: u64) {
+        while total & 1 == 0 {
+            let left = self.cv_stack.pop().unwrap();
+            cv = parent_output(left, cv, &self.key, self.flags).chaining_value();
+            total >>= 1;
+        }
+        self.cv_stack.push(cv);
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Absorbs `data`, the whole subtrees it contains being hashed on
+    /// up to `threads` threads. The digest is the same as with
+    /// `update`, it's worth it for inputs of many chunks.
+    ///# Example
+    /// ```
+    /// use cryptolib::hash::blake3::Blake3;
+    /// use cryptolib::hash::Digest;
+    ///
+    /// let data = vec![7_u8; 1 << 20];
+    /// let mut blake = Blake3::new();
+    /// blake.update_parallel(&data, 4);
+    /// assert_eq!(blake.finalize(), Blake3::digest(&data));
+    /// ```
+    ////////////////////////////////////////////////////////////////////
+    pub fn update_parallel(&mut self, mut data: &[u8], threads: usize) {
+        while !data.is_empty() {
+            // A full chunk is only pushed once more data follows, the
+            // last one is the root when it's alone.
+            if self.chunk_state.len() == CHUNK_LEN {
+                let cv = self.chunk_state.output().chaining_value();
+                let total = self.chunk_state.chunk_counter + 1;
+                self.push_cv(cv, total);
+                self.chunk_state = ChunkState::new(&self.key, total, self.flags);
+            }
+            let counter = self.chunk_state.chunk_counter;
+            if self.chunk_state.len() == 0 && data.len() > CHUNK_LEN {
+                // The largest aligned subtree, some data must remain for
+                // the chunk state.
+                let mut chunks = 1_u64 << ((data.len() - 1) / CHUNK_LEN).ilog2();
+                while counter & (chunks - 1) != 0 {
+                    chunks /= 2;
+                }
+                let len = chunks as usize * CHUNK_LEN;
+                let cv = hash_subtree(&data[..len], counter, &self.key, self.flags, threads);
+                self.push_cv(cv, (counter + chunks) >> chunks.trailing_zeros());
+                self.chunk_state = ChunkState::new(&self.key, counter + chunks, self.flags);
+                data = &data[len..];
+                continue;
+            }
+            let take = data.len().min(CHUNK_LEN - self.chunk_state.len());
+            self.chunk_state.update(&data[..take]);
+            data = &data[take..];
+        }
+    }
+
+    fn root_output(&self) -> Output {
+        let mut output = self.chunk_state.output();
+        for left in self.cv_stack.iter().rev() {
+            output = parent_output(*left, output.chaining_value(), &self.key, self.flags);
+        }
+        output
+    }
+
+    /// Returns the reader of an output of any length.
+    pub fn finalize_xof(self) -> OutputReader {
+        OutputReader {
+            output: self.root_output(),
+            position: 0,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// The digest is 32 bytes, longer outputs are read with `finalize_xof`.
+////////////////////////////////////////////////////////////////////////////
+impl Digest for Blake3 {
+    const OUTPUT_SIZE: usize = OUT_LEN;
+    const BLOCK_SIZE: usize = BLOCK_LEN;
+
+    type Output = [u8; OUT_LEN];
+
+    fn update(&mut self, data: &[u8]) {
+        self.update_parallel(data, 1);
+    }
+
+    fn finalize(self) -> [u8; OUT_LEN] {
+        self.root_output().root_block(0)[..OUT_LEN]
+            .try_into()
+            .unwrap()
+    }
+
+    /// Starts a new message in the same mode, with the same key.
+    fn reset(&mut self) {
+        *self = Self::with_key(self.key, self.flags);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `OutputReader` reads the output of BLAKE3 in pieces of any size, each
+/// 64 bytes block being computed from the root independently so any
+/// position can be sought.
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct OutputReader {
+    output: Output,
+    position: u64,
+}
+
+impl OutputReader {
+    /// Fills `out` with the next output bytes.
+    pub fn read(&mut self, out: &mut [u8]) {
+        for piece in out.chunks_mut(BLOCK_LEN) {
+            let mut written = 0;
+            while written < piece.len() {
+                let block = self.output.root_block(self.position / BLOCK_LEN as u64);
+                let offset = (self.position % BLOCK_LEN as u64) as usize;
+                let take = (piece.len() - written).min(BLOCK_LEN - offset);
+                piece[written..written + take].copy_from_slice(&block[offset..offset + take]);
+                written += take;
+                self.position += take as u64;
+            }
+        }
+    }
+
+    /// Returns the next `len` output bytes.
+    pub fn read_vec(&mut self, len: usize) -> Vec<u8> {
+        let mut out = vec![0; len];
+        self.read(&mut out);
+        out
+    }
+
+    /// Moves to the output byte `position`.
+    pub fn seek(&mut self, position: u64) {
+        self.position = position;
+    }
+
+    /// Returns the position of the next output byte.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    const KEY: &[u8; 32] = b"whats the Elvish word for friend";
+    const CONTEXT: &str = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+
+    fn input(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    // test_vectors.json of the reference implementation: the input length
+    // and the first 32 bytes of the hash, keyed hash and derived key.
+    const VECTORS: &[(usize, &str, &str, &str)] = &[
+        (
+            0,
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26",
+            "2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d",
+        ),
+        (
+            1,
+            "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213",
+            "6d7878dfff2f485635d39013278ae14f1454b8c0a3a2d34bc1ab38228a80c95b",
+            "b3e2e340a117a499c6cf2398a19ee0d29cca2bb7404c73063382693bf66cb06c",
+        ),
+        (
+            1023,
+            "10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11",
+            "c951ecdf03288d0fcc96ee3413563d8a6d3589547f2c2fb36d9786470f1b9d6e",
+            "74a16c1c3d44368a86e1ca6df64be6a2f64cce8f09220787450722d85725dea5",
+        ),
+        (
+            1024,
+            "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7",
+            "75c46f6f3d9eb4f55ecaaee480db732e6c2105546f1e675003687c31719c7ba4",
+            "7356cd7720d5b66b6d0697eb3177d9f8d73a4a5c5e968896eb6a689684302706",
+        ),
+        (
+            1025,
+            "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444",
+            "357dc55de0c7e382c900fd6e320acc04146be01db6a8ce7210b7189bd664ea69",
+            "effaa245f065fbf82ac186839a249707c3bddf6d3fdda22d1b95a3c970379bcb",
+        ),
+        (
+            2048,
+            "e776b6028c7cd22a4d0ba182a8bf62205d2ef576467e838ed6f2529b85fba24a",
+            "879cf1fa2ea0e79126cb1063617a05b6ad9d0b696d0d757cf053439f60a99dd1",
+            "7b2945cb4fef70885cc5d78a87bf6f6207dd901ff239201351ffac04e1088a23",
+        ),
+        (
+            2049,
+            "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030",
+            "9f29700902f7c86e514ddc4df1e3049f258b2472b6dd5267f61bf13983b78dd5",
+            "2ea477c5515cc3dd606512ee72bb3e0e758cfae7232826f35fb98ca1bcbdf273",
+        ),
+        (
+            3072,
+            "b98cb0ff3623be03326b373de6b9095218513e64f1ee2edd2525c7ad1e5cffd2",
+            "044a0e7b172a312dc02a4c9a818c036ffa2776368d7f528268d2e6b5df191770",
+            "050df97f8c2ead654d9bb3ab8c9178edcd902a32f8495949feadcc1e0480c46b",
+        ),
+        (
+            3073,
+            "7124b49501012f81cc7f11ca069ec9226cecb8a2c850cfe644e327d22d3e1cd3",
+            "68dede9bef00ba89e43f31a6825f4cf433389fedae75c04ee9f0cf16a427c95a",
+            "72613c9ec9ff7e40f8f5c173784c532ad852e827dba2bf85b2ab4b76f7079081",
+        ),
+        (
+            4096,
+            "015094013f57a5277b59d8475c0501042c0b642e531b0a1c8f58d2163229e969",
+            "befc660aea2f1718884cd8deb9902811d332f4fc4a38cf7c7300d597a081bfc0",
+            "1e0d7f3db8c414c97c6307cbda6cd27ac3b030949da8e23be1a1a924ad2f25b9",
+        ),
+        (
+            4097,
+            "9b4052b38f1c5fc8b1f9ff7ac7b27cd242487b3d890d15c96a1c25b8aa0fb995",
+            "00df940cd36bb9fa7cbbc3556744e0dbc8191401afe70520ba292ee3ca80abbc",
+            "aca51029626b55fda7117b42a7c211f8c6e9ba4fe5b7a8ca922f34299500ead8",
+        ),
+        (
+            5121,
+            "628bd2cb2004694adaab7bbd778a25df25c47b9d4155a55f8fbd79f2fe154cff",
+            "6ccf1c34753e7a044db80798ecd0782a8f76f33563accaddbfbb2e0ea4b2d024",
+            "b07f01e518e702f7ccb44a267e9e112d403a7b3f4883a47ffbed4b48339b3c34",
+        ),
+        (
+            8192,
+            "aae792484c8efe4f19e2ca7d371d8c467ffb10748d8a5a1ae579948f718a2a63",
+            "dc9637c8845a770b4cbf76b8daec0eebf7dc2eac11498517f08d44c8fc00d58a",
+            "ad01d7ae4ad059b0d33baa3c01319dcf8088094d0359e5fd45d6aeaa8b2d0c3d",
+        ),
+        (
+            8193,
+            "bab6c09cb8ce8cf459261398d2e7aef35700bf488116ceb94a36d0f5f1b7bc3b",
+            "954a2a75420c8d6547e3ba5b98d963e6fa6491addc8c023189cc519821b4a1f5",
+            "af1e0346e389b17c23200270a64aa4e1ead98c61695d917de7d5b00491c9b0f1",
+        ),
+        (
+            16384,
+            "f875d6646de28985646f34ee13be9a576fd515f76b5b0a26bb324735041ddde4",
+            "9e9fc4eb7cf081ea7c47d1807790ed211bfec56aa25bb7037784c13c4b707b0d",
+            "160e18b5878cd0df1c3af85eb25a0db5344d43a6fbd7a8ef4ed98d0714c3f7e1",
+        ),
+        (
+            31744,
+            "62b6960e1a44bcc1eb1a611a8d6235b6b4b78f32e7abc4fb4c6cdcce94895c47",
+            "efa53b389ab67c593dba624d898d0f7353ab99e4ac9d42302ee64cbf9939a419",
+            "39772aef80e0ebe60596361e45b061e8f417429d529171b6764468c22928e28e",
+        ),
+        (
+            102400,
+            "bc3e3d41a1146b069abffad3c0d44860cf664390afce4d9661f7902e7943e085",
+            "1c35d1a5811083fd7119f5d5d1ba027b4d01c0c6c49fb6ff2cf75393ea5db4a7",
+            "4652cff7a3f385a6103b5c260fc1593e13c778dbe608efb092fe7ee69df6e9c6",
+        ),
+    ];
+
+    #[test]
+    fn test_blake3() {
+        for &(len, hash, keyed, derive) in VECTORS {
+            let data = input(len);
+            assert_eq!(Blake3::digest(&data).to_vec(), hex(hash), "hash {}", len);
+            assert_eq!(
+                Blake3::new_keyed(KEY).chain(&data).finalize().to_vec(),
+                hex(keyed),
+                "keyed hash {}",
+                len
+            );
+            assert_eq!(
+                Blake3::new_derive_key(CONTEXT)
+                    .chain(&data)
+                    .finalize()
+                    .to_vec(),
+                hex(derive),
+                "derive_key {}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn test_blake3_xof() {
+        // The full 131 bytes outputs of the 3073 bytes input.
+        let data = input(3073);
+        let expected = [
+            hex(
+                "7124b49501012f81cc7f11ca069ec9226cecb8a2c850cfe644e327d22d3e1cd3
+                 9a27ae3b79d68d89da9bf25bc27139ae65a324918a5f9b7828181e52cf373c84
+                 f35b639b7fccbb985b6f2fa56aea0c18f531203497b8bbd3a07ceb5926f1cab7
+                 4d14bd66486d9a91eba99059a98bd1cd25876b2af5a76c3e9eed554ed72ea952
+                 b603bf",
+            ),
+            hex(
+                "68dede9bef00ba89e43f31a6825f4cf433389fedae75c04ee9f0cf16a427c95a
+                 96d6da3fe985054d3478865be9a092250839a697bbda74e279e8a9e69f0025e4
+                 cfddd6cfb434b1cd9543aaf97c635d1b451a4386041e4bb100f5e45407cbbc24
+                 fa53ea2de3536ccb329e4eb9466ec37093a42cf62b82903c696a93a50b702c80
+                 f3c3c5",
+            ),
+            hex(
+                "72613c9ec9ff7e40f8f5c173784c532ad852e827dba2bf85b2ab4b76f7079081
+                 576288e552647a9d86481c2cae75c2dd4e7c5195fb9ada1ef50e9c5098c249d7
+                 43929191441301c69e1f48505a4305ec1778450ee48b8e69dc23a25960fe3307
+                 0ea549119599760a8a2d28aeca06b8c5e9ba58bc19e11fe57b6ee98aa44b2a8e
+                 6b14a5",
+            ),
+        ];
+        let readers = [
+            Blake3::new().chain(&data).finalize_xof(),
+            Blake3::new_keyed(KEY).chain(&data).finalize_xof(),
+            Blake3::new_derive_key(CONTEXT).chain(&data).finalize_xof(),
+        ];
+        for (mut reader, expected) in readers.into_iter().zip(expected) {
+            assert_eq!(reader.clone().read_vec(131), expected);
+            // Reading in odd pieces and seeking give the same bytes.
+            let mut out = vec![0; 131];
+            for piece in out.chunks_mut(17) {
+                reader.read(piece);
+            }
+            assert_eq!(out, expected);
+            assert_eq!(reader.position(), 131);
+            reader.seek(70);
+            assert_eq!(reader.read_vec(61), expected[70..]);
+        }
+    }
+
+    #[test]
+    fn test_blake3_parallel() {
+        let data = input(102400);
+        let expected = Blake3::digest(&data);
+        for split in [0, 1, 1024, 1025, 3072, 5000, 65536, 102399] {
+            for threads in [1, 2, 3, 4] {
+                let mut hasher = Blake3::new();
+                hasher.update_parallel(&data[..split], threads);
+                hasher.update_parallel(&data[split..], threads);
+                assert_eq!(
+                    hasher.finalize(),
+                    expected,
+                    "split {} threads {}",
+                    split,
+                    threads
+                );
+            }
+        }
+        let mut hasher = Blake3::new();
+        for piece in data.chunks(1000) {
+            hasher.update(piece);
+        }
+        assert_eq!(hasher.finalize(), expected);
+    }
+
+    #[test]
+    fn test_blake3_reset() {
+        let data = input(2049);
+        let mut hasher = Blake3::new_keyed(KEY);
+        hasher.update(b"discarded");
+        hasher.reset();
+        hasher.update(&data);
+        assert_eq!(
+            hasher.finalize().to_vec(),
+            hex("9f29700902f7c86e514ddc4df1e3049f258b2472b6dd5267f61bf13983b78dd5")
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//#######################################################################o
+// Keccak-f[1600] is the permutation behind SHA-3 and SHAKE. Its state   |
+// is a 5x5 array of 64-bit lanes and each of its 24 rounds applies the  |
+// steps theta (column parity mixing), rho (lane rotations), pi (lane    |
+// transposition), chi (the only nonlinear step) and iota (a round       |
+// constant). The sponge construction absorbs the message in blocks of   |
+// `rate` bytes xored into the state, the remaining capacity bytes are   |
+// never touched directly, then squeezes the output the same way.        |
+//                                                                       |
+// @Refrences:                                                           |
+// FIPS 202: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf    |
+// Keccak team: https://keccak.team/keccak_specs_summary.html            |
+//#######################################################################o
+
+// Round constants of the iota step.
+const RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+// Rotations of the rho step and destinations of the pi step, following
+// the lane (1, 0) around the cycle of pi.
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+////////////////////////////////////////////////////////////////////////////
+/// Applies the Keccak-f[1600] permutation to `state`, the lane (x, y)
+/// being `state[x + 5 * y]`.
+///# Example
+/// ```
+/// use cryptolib::hash::keccak::keccak_f1600;
+///
+/// let mut state = [0_u64; 25];
+/// keccak_f1600(&mut state);
+/// assert_eq!(state[0], 0xf1258f7940e1dde7);
+/// ```
+////////////////////////////////////////////////////////////////////////////
+pub fn keccak_f1600(state: &mut [u64; 25]) {
+    for rc in RC {
+        // Theta.
+        let mut c = [0_u64; 5];
+        for (x, c) in c.iter_mut().enumerate() {
+            *c = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+        // Rho and pi.
+        let mut lane = state[1];
+        for (rho, pi) in RHO.iter().zip(PI) {
+            let next = state[pi];
+            state[pi] = lane.rotate_left(*rho);
+            lane = next;
+        }
+        // Chi.
+        for y in 0..5 {
+            let row: [u64; 5] = state[5 * y..5 * y + 5].try_into().unwrap();
+            for x in 0..5 {
+                state[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+        // Iota.
+        state[0] ^= rc;
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// `Sponge` is a Keccak sponge absorbing and squeezing `rate` bytes
+/// per permutation, the state bytes being the lanes in little-endian.
+//////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub(crate) struct Sponge {
+    state: [u64; 25],
+    rate: usize,
+    // Position in the current block.
+    pos: usize,
+}
+
+impl Sponge {
+    pub(crate) fn new(rate: usize) -> Sponge {
+        Sponge {
+            state: [0; 25],
+            rate,
+            pos: 0,
+        }
+    }
+
+    fn xor_byte(&mut self, i: usize, b: u8) {
+        self.state[i / 8] ^= (b as u64) << (8 * (i % 8));
+    }
+
+    pub(crate) fn absorb(&mut self, data: &[u8]) {
+        for &b in data {
+            self.xor_byte(self.pos, b);
+            self.pos += 1;
+            if self.pos == self.rate {
+                keccak_f1600(&mut self.state);
+                self.pos = 0;
+            }
+        }
+    }
+
+    /// Completes the current block with zeros.
+    pub(crate) fn fill_block(&mut self) {
+        if self.pos != 0 {
+            keccak_f1600(&mut self.state);
+            self.pos = 0;
+        }
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Ends the absorbing phase: the `domain` bits, which carry the
+    /// first bit of the pad10*1 padding, then its last bit.
+    //////////////////////////////////////////////////////////////////
+    pub(crate) fn pad(&mut self, domain: u8) {
+        self.xor_byte(self.pos, domain);
+        self.xor_byte(self.rate - 1, 0x80);
+        keccak_f1600(&mut self.state);
+        self.pos = 0;
+    }
+
+    pub(crate) fn squeeze(&mut self, out: &mut [u8]) {
+        for b in out {
+            if self.pos == self.rate {
+                keccak_f1600(&mut self.state);
+                self.pos = 0;
+            }
+            *b = (self.state[self.pos / 8] >> (8 * (self.pos % 8))) as u8;
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak_f1600() {
+        // KeccakF-1600-IntermediateValues.txt of the Keccak team, the
+        // permutation applied once and twice to the zero state.
+        let mut state = [0_u64; 25];
+        keccak_f1600(&mut state);
+        assert_eq!(state[0], 0xf1258f7940e1dde7);
+        assert_eq!(state[1], 0x84d5ccf933c0478a);
+        assert_eq!(state[24], 0xeaf1ff7b5ceca249);
+        keccak_f1600(&mut state);
+        assert_eq!(state[0], 0x2d5c954df96ecb3c);
+        assert_eq!(state[24], 0x20d06cd26a8fbf5c);
+    }
+}
@@ -0,0 +1,187 @@
+//#######################################################################o
+// LEGACY: MD4 is completely broken, collisions are computed by hand and |
+// preimages are cheaper than brute force. It's provided for the NT hash |
+// of NTLM (MD4 of the UTF-16LE password) and legacy protocols only.     |
+// MD4 is Rivest's predecessor of MD5: the message is padded with a 1    |
+// bit, zeros and its 64-bit length (little-endian), then processed in   |
+// 512-bit blocks by a compression function of three rounds of 16 steps  |
+// on four 32-bit words, giving a 128-bit digest.                        |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 1320: https://www.rfc-editor.org/rfc/rfc1320                      |
+// RFC 6150: https://www.rfc-editor.org/rfc/rfc6150                      |
+//#######################################################################o
+
+use super::{BlockBuffer, Digest};
+
+// Initial hash value, the one of MD5.
+const IV: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+// Constants added in the second and third rounds, sqrt(2) and sqrt(3).
+const K: [u32; 3] = [0, 0x5a827999, 0x6ed9eba1];
+
+// Order of the message words in each round.
+const M: [[usize; 16]; 3] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15],
+    [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15],
+];
+
+// Rotation amounts of the four steps of each round.
+const S: [[u32; 4]; 3] = [[3, 7, 11, 19], [3, 5, 9, 13], [3, 9, 11, 15]];
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function, updates `state` with a 64 bytes block.
+//////////////////////////////////////////////////////////////////////
+fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut m = [0_u32; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *m = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    let [mut a, mut b, mut c, mut d] = *state;
+    for i in 0..48 {
+        let round = i / 16;
+        let f = match round {
+            0 => (b & c) | (!b & d),
+            1 => (b & c) | (b & d) | (c & d),
+            _ => b ^ c ^ d,
+        };
+        let t = a
+            .wrapping_add(f)
+            .wrapping_add(m[M[round][i % 16]])
+            .wrapping_add(K[round])
+            .rotate_left(S[round][i % 4]);
+        a = d;
+        d = c;
+        c = b;
+        b = t;
+    }
+    for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Md4` is a streaming MD4 hasher. Legacy only, MD4 is broken.
+///# Example
+/// ```
+/// use cryptolib::hash::md4::Md4;
+/// use cryptolib::hash::Digest;
+///
+/// // The NT hash of NTLM.
+/// let password: Vec<u8> = "password".encode_utf16().flat_map(u16::to_le_bytes).collect();
+/// let nt_hash = Md4::digest(&password);
+/// assert_eq!(nt_hash[..4], [0x88, 0x46, 0xf7, 0xea]);
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Md4 {
+    state: [u32; 4],
+    buffer: BlockBuffer<64>,
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Md4 {
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Md4 {
+        Md4 {
+            state: IV,
+            buffer: BlockBuffer::new(),
+        }
+    }
+}
+
+impl Digest for Md4 {
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = [u8; 16];
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| compress(state, block));
+    }
+
+    fn finalize(mut self) -> [u8; 16] {
+        let state = &mut self.state;
+        self.buffer.pad(8, false, |block| compress(state, block));
+        let mut out = [0_u8; 16];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_md4_rfc1320() {
+        // RFC 1320 appendix A.5.
+        for (data, expected) in [
+            ("", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+            ("a", "bde52cb31de33e46245e05fbdbd6fb24"),
+            ("abc", "a448017aaf21d8525fc10ae87aa6729d"),
+            ("message digest", "d9130a8164549fe818874806e1c7014b"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "d79e1c308aa5bbcdeea8ed63df412da9",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "043f8582f241db351ce627e153e7f0e4",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "e33b4ddc9c38f2199c3e7b164fcc0536",
+            ),
+        ] {
+            assert_eq!(
+                Md4::digest(data.as_bytes()).to_vec(),
+                hex(expected),
+                "{}",
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_md4_streaming() {
+        // Checked against OpenSSL.
+        let data: Vec<u8> = (0..1000).map(|i| (i % 251) as u8).collect();
+        let expected = hex("9146d274cb46d791aefb861959aa5e98");
+        for split in [0, 1, 55, 56, 63, 64, 65, 500] {
+            let mut md4 = Md4::new();
+            md4.update(b"discarded");
+            md4.reset();
+            md4.update(&data[..split]);
+            md4.update(&data[split..]);
+            assert_eq!(md4.finalize().to_vec(), expected, "split at {}", split);
+        }
+        assert_eq!(
+            Md4::digest(&[b'a'; 1_000_000]).to_vec(),
+            hex("bbce80cc6bb65e5c6745e30d4eeca9a4")
+        );
+    }
+
+    #[test]
+    fn test_nt_hash() {
+        // The NT hash of "password", MD4 of its UTF-16LE encoding.
+        let password: Vec<u8> = "password"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        assert_eq!(
+            Md4::digest(&password).to_vec(),
+            hex("8846f7eaee8fb117ad06bdd830b7586c")
+        );
+    }
+}
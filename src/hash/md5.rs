@@ -0,0 +1,178 @@
+//#######################################################################o
+// LEGACY: MD5 collisions are found in seconds and chosen prefix         |
+// collisions are practical, it must not be used for signatures or       |
+// certificates. It's provided for checksums and legacy protocols.       |
+// MD5 is Rivest's Merkle-Damgard hash function: the message is padded   |
+// with a 1 bit, zeros and its 64-bit length (little-endian), then       |
+// processed in 512-bit blocks by a compression function of four rounds  |
+// of 16 steps on four 32-bit words, giving a 128-bit digest.            |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 1321: https://www.rfc-editor.org/rfc/rfc1321                      |
+// RFC 6151: https://www.rfc-editor.org/rfc/rfc6151                      |
+//#######################################################################o
+
+use super::{BlockBuffer, Digest};
+
+// Initial hash value.
+const IV: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+// Step constants, the integer part of 2^32 * |sin(i + 1)|.
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+// Rotation amounts of the four steps of each round.
+const S: [[u32; 4]; 4] = [
+    [7, 12, 17, 22],
+    [5, 9, 14, 20],
+    [4, 11, 16, 23],
+    [6, 10, 15, 21],
+];
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function, updates `state` with a 64 bytes block.
+//////////////////////////////////////////////////////////////////////
+fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut m = [0_u32; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *m = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    let [mut a, mut b, mut c, mut d] = *state;
+    for i in 0..64 {
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let t = a
+            .wrapping_add(f)
+            .wrapping_add(K[i])
+            .wrapping_add(m[g])
+            .rotate_left(S[i / 16][i % 4]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(t);
+    }
+    for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Md5` is a streaming MD5 hasher. Legacy only, MD5 is broken.
+///# Example
+/// ```
+/// use cryptolib::hash::md5::Md5;
+/// use cryptolib::hash::Digest;
+///
+/// let mut md5 = Md5::new();
+/// md5.update(b"a");
+/// md5.update(b"bc");
+/// assert_eq!(md5.finalize(), Md5::digest(b"abc"));
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Md5 {
+    state: [u32; 4],
+    buffer: BlockBuffer<64>,
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Md5 {
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Md5 {
+        Md5 {
+            state: IV,
+            buffer: BlockBuffer::new(),
+        }
+    }
+}
+
+impl Digest for Md5 {
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = [u8; 16];
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| compress(state, block));
+    }
+
+    fn finalize(mut self) -> [u8; 16] {
+        let state = &mut self.state;
+        self.buffer.pad(8, false, |block| compress(state, block));
+        let mut out = [0_u8; 16];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_md5_rfc1321() {
+        // RFC 1321 appendix A.5.
+        for (data, expected) in [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "c3fcd3d76192e4007dfb496cca67e13b",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "d174ab98d277d9f5a5611c2c9f419d9f",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ] {
+            assert_eq!(
+                Md5::digest(data.as_bytes()).to_vec(),
+                hex(expected),
+                "{}",
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn test_md5_streaming() {
+        // Checked against Python's hashlib.
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let expected = hex("cbecbdb0fdd5cec1e242493b6008cc79");
+        for split in [0, 1, 55, 56, 63, 64, 65, 500] {
+            let mut md5 = Md5::new();
+            md5.update(b"discarded");
+            md5.reset();
+            md5.update(&data[..split]);
+            md5.update(&[]);
+            md5.update(&data[split..]);
+            assert_eq!(md5.finalize().to_vec(), expected, "split at {}", split);
+        }
+    }
+}
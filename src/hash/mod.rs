@@ -0,0 +1,161 @@
+pub mod blake2;
+pub mod blake3;
+pub mod keccak;
+pub mod md4;
+pub mod md5;
+pub mod ripemd;
+pub mod sha1;
+pub mod sha2;
+pub mod sha3;
+pub mod skein;
+pub mod sm3;
+pub mod sp800_185;
+pub mod whirlpool;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Digest` is the interface of the streaming hash functions: data is
+/// absorbed in pieces of any size with `update` and `finalize` returns
+/// the digest of the whole message.
+///# Example
+/// ```
+/// use cryptolib::hash::md5::Md5;
+/// use cryptolib::hash::sha1::Sha1;
+/// use cryptolib::hash::Digest;
+///
+/// fn hash_twice<D: Digest>(data: &[u8]) -> Vec<u8> {
+///     let mut hasher = D::default();
+///     hasher.update(data);
+///     hasher.update(data);
+///     hasher.finalize().as_ref().to_vec()
+/// }
+/// assert_eq!(hash_twice::<Md5>(b"ab").len(), Md5::OUTPUT_SIZE);
+/// assert_eq!(hash_twice::<Sha1>(b"ab"), Sha1::digest(b"abab"));
+/// ```
+////////////////////////////////////////////////////////////////////////////
+pub trait Digest: Clone + Default {
+    /// Size of the digest in bytes.
+    const OUTPUT_SIZE: usize;
+    /// Size of the processed blocks in bytes.
+    const BLOCK_SIZE: usize;
+
+    /// The digest, a byte array or a `Vec` for a variable length.
+    type Output: AsRef<[u8]>;
+
+    /// Absorbs `data` into the hash.
+    fn update(&mut self, data: &[u8]);
+
+    /// Pads the message and returns its digest.
+    fn finalize(self) -> Self::Output;
+
+    /// Starts a new message with the same parameters.
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns the digest of `data`.
+    fn digest(data: &[u8]) -> Self::Output {
+        let mut hasher = Self::default();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// `BlockBuffer` splits the message of a Merkle-Damgard hash function
+/// in blocks of `B` bytes and appends its padding.
+//////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub(crate) struct BlockBuffer<const B: usize> {
+    // Pending bytes of the current block.
+    buffer: [u8; B],
+    buffer_len: usize,
+    // Total message length in bytes.
+    len: u128,
+}
+
+impl<const B: usize> BlockBuffer<B> {
+    pub(crate) const fn new() -> BlockBuffer<B> {
+        BlockBuffer {
+            buffer: [0; B],
+            buffer_len: 0,
+            len: 0,
+        }
+    }
+
+    /// Calls `compress` on each block completed by `data`.
+    pub(crate) fn update(&mut self, mut data: &[u8], mut compress: impl FnMut(&[u8])) {
+        self.len = self.len.wrapping_add(data.len() as u128);
+        if self.buffer_len > 0 {
+            let take = data.len().min(B - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < B {
+                return;
+            }
+            compress(&self.buffer);
+            self.buffer_len = 0;
+        }
+        let mut blocks = data.chunks_exact(B);
+        for block in &mut blocks {
+            compress(block);
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Appends a 1 bit, zeros and the message length in bits so that
+    /// the message ends on a block boundary.
+    ///# Arguments
+    ///* `length_size`: the size of the encoded length in bytes, up to 32.
+    ///* `big_endian`: the byte order of the length.
+    ///* `compress`: called on the remaining blocks.
+    //////////////////////////////////////////////////////////////////
+    pub(crate) fn pad(
+        &mut self,
+        length_size: usize,
+        big_endian: bool,
+        mut compress: impl FnMut(&[u8]),
+    ) {
+        let bit_len = self.len.wrapping_mul(8);
+        let mut length = [0_u8; 32];
+        let length = if big_endian {
+            length[16..].copy_from_slice(&bit_len.to_be_bytes());
+            &length[32 - length_size..]
+        } else {
+            length[..16].copy_from_slice(&bit_len.to_le_bytes());
+            &length[..length_size]
+        };
+        self.update(&[0x80], &mut compress);
+        let zeros = (2 * B - length_size - self.buffer_len) % B;
+        self.update(&[0; 128][..zeros], &mut compress);
+        self.update(length, &mut compress);
+        debug_assert_eq!(self.buffer_len, 0);
+    }
+
+    /// Appends the message length and the pending bytes to `out`.
+    pub(crate) fn export(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.len.to_be_bytes());
+        out.extend_from_slice(&self.buffer[..self.buffer_len]);
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Restores a buffer written by `export`.
+    ///# Returns
+    /// `None` if the number of pending bytes doesn't match the length.
+    //////////////////////////////////////////////////////////////////
+    pub(crate) fn import(data: &[u8]) -> Option<BlockBuffer<B>> {
+        let (len, pending) = data.split_first_chunk::<16>()?;
+        let len = u128::from_be_bytes(*len);
+        if pending.len() as u128 != len % B as u128 {
+            return None;
+        }
+        let mut buffer = BlockBuffer::new();
+        buffer.buffer[..pending.len()].copy_from_slice(pending);
+        buffer.buffer_len = pending.len();
+        buffer.len = len;
+        Some(buffer)
+    }
+}
@@ -0,0 +1,364 @@
+//#######################################################################o
+// RIPEMD-160 is the hash function of Dobbertin, Bosselaers and Preneel, |
+// used by Bitcoin addresses and OpenPGP. The message is padded like     |
+// MD4, then each 512-bit block is processed by two independent lines of |
+// five rounds of 16 steps on five 32-bit words, with different word     |
+// orders, rotations, constants and boolean functions, the two results   |
+// being mixed into the chaining value. RIPEMD-128 is the four rounds    |
+// variant on four words, legacy since its 128-bit output is too short.  |
+// RIPEMD-256 and RIPEMD-320 keep both lines as chaining value, they     |
+// exchange one word after each round, and only give longer digests,     |
+// not more security than RIPEMD-128 and RIPEMD-160.                     |
+//                                                                       |
+// @Refrences:                                                           |
+// RIPEMD-160: https://homes.esat.kuleuven.be/~bosselae/ripemd160.html   |
+// ISO/IEC 10118-3: https://www.iso.org/standard/67116.html              |
+//#######################################################################o
+
+use super::{BlockBuffer, Digest};
+
+// Order of the message words of the left and right lines.
+const R_LEFT: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5,
+    2, 14, 11, 8, 3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, 1, 9, 11, 10, 0, 8, 12, 4,
+    13, 3, 7, 15, 14, 5, 6, 2, 4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+const R_RIGHT: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, 6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12,
+    4, 9, 1, 2, 15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, 8, 6, 4, 1, 3, 11, 15, 0, 5,
+    12, 2, 13, 9, 7, 10, 14, 12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+// Rotation amounts of the left and right lines.
+const S_LEFT: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, 7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15,
+    9, 11, 7, 13, 12, 11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, 11, 12, 14, 15, 14,
+    15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, 9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+const S_RIGHT: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, 9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12,
+    7, 6, 15, 13, 11, 9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, 15, 5, 8, 11, 14, 14,
+    6, 14, 6, 9, 12, 9, 12, 5, 15, 8, 8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+// Round constants of the left line, then of the right line with four
+// and five rounds.
+const K_LEFT: [u32; 5] = [0, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+const K_RIGHT4: [u32; 4] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0];
+const K_RIGHT5: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0];
+
+// Initial values, the extended variants append the second line.
+const IV: [u32; 10] = [
+    0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0, 0x76543210, 0xfedcba98, 0x89abcdef,
+    0x01234567, 0x3c2d1e0f,
+];
+const IV256: [u32; 10] = [
+    0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0x76543210, 0xfedcba98, 0x89abcdef, 0x01234567,
+    0, 0,
+];
+
+// The boolean function of round `j`.
+fn f(j: usize, x: u32, y: u32, z: u32) -> u32 {
+    match j {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Runs the two lines on the message words `m`, the number of rounds
+/// being the number of words `W`.
+///# Arguments
+///* `left`, `right`: the words of the lines, the current A first.
+///* `m`: the message block.
+///* `swap`: the word exchanged after each round by the extended
+///  variants.
+//////////////////////////////////////////////////////////////////////
+fn lines<const W: usize>(
+    left: &mut [u32; W],
+    right: &mut [u32; W],
+    m: &[u32; 16],
+    swap: Option<[usize; W]>,
+) {
+    let k_right: &[u32] = if W == 4 { &K_RIGHT4 } else { &K_RIGHT5 };
+    for i in 0..16 * W {
+        let j = i / 16;
+        for (v, fj, x, k, s) in [
+            (&mut *left, j, m[R_LEFT[i]], K_LEFT[j], S_LEFT[i]),
+            (
+                &mut *right,
+                W - 1 - j,
+                m[R_RIGHT[i]],
+                k_right[j],
+                S_RIGHT[i],
+            ),
+        ] {
+            let t = v[0]
+                .wrapping_add(f(fj, v[1], v[2], v[3]))
+                .wrapping_add(x)
+                .wrapping_add(k)
+                .rotate_left(s);
+            if W == 4 {
+                // A = D, D = C, C = B, B = T.
+                v.rotate_right(1);
+                v[1] = t;
+            } else {
+                // A = E, E = D, D = C <<< 10, C = B, B = T + E.
+                v.rotate_right(1);
+                v[1] = t.wrapping_add(v[0]);
+                v[3] = v[3].rotate_left(10);
+            }
+        }
+        if let Some(swap) = swap {
+            if i % 16 == 15 {
+                std::mem::swap(&mut left[swap[j]], &mut right[swap[j]]);
+            }
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function of the variant with an `n` bytes digest,
+/// updates `state` with a 64 bytes block.
+//////////////////////////////////////////////////////////////////////
+fn compress(state: &mut [u32; 10], n: usize, block: &[u8]) {
+    let mut m = [0_u32; 16];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *m = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    match n {
+        16 => {
+            let (mut l, mut r) = ([0; 4], [0; 4]);
+            l.copy_from_slice(&state[..4]);
+            r.copy_from_slice(&state[..4]);
+            lines(&mut l, &mut r, &m, None);
+            let h = *state;
+            for i in 0..4 {
+                state[i] = h[(i + 1) % 4]
+                    .wrapping_add(l[(i + 2) % 4])
+                    .wrapping_add(r[(i + 3) % 4]);
+            }
+        }
+        20 => {
+            let (mut l, mut r) = ([0; 5], [0; 5]);
+            l.copy_from_slice(&state[..5]);
+            r.copy_from_slice(&state[..5]);
+            lines(&mut l, &mut r, &m, None);
+            let h = *state;
+            for i in 0..5 {
+                state[i] = h[(i + 1) % 5]
+                    .wrapping_add(l[(i + 2) % 5])
+                    .wrapping_add(r[(i + 3) % 5]);
+            }
+        }
+        32 => {
+            let (mut l, mut r) = ([0; 4], [0; 4]);
+            l.copy_from_slice(&state[..4]);
+            r.copy_from_slice(&state[4..8]);
+            lines(&mut l, &mut r, &m, Some([0, 1, 2, 3]));
+            for (s, v) in state.iter_mut().zip(l.into_iter().chain(r)) {
+                *s = s.wrapping_add(v);
+            }
+        }
+        _ => {
+            let (mut l, mut r) = ([0; 5], [0; 5]);
+            l.copy_from_slice(&state[..5]);
+            r.copy_from_slice(&state[5..]);
+            lines(&mut l, &mut r, &m, Some([1, 3, 0, 2, 4]));
+            for (s, v) in state.iter_mut().zip(l.into_iter().chain(r)) {
+                *s = s.wrapping_add(v);
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `RipemdCore` is a streaming hasher of the RIPEMD functions with an
+/// `N` bytes digest, use the `Ripemd128`, `Ripemd160`, `Ripemd256` and
+/// `Ripemd320` aliases.
+///# Example
+/// ```
+/// use cryptolib::hash::ripemd::Ripemd160;
+/// use cryptolib::hash::sha2::Sha256;
+/// use cryptolib::hash::Digest;
+///
+/// // The HASH160 of Bitcoin addresses.
+/// let public_key = [0x02; 33];
+/// let hash160 = Ripemd160::digest(&Sha256::digest(&public_key));
+/// assert_eq!(hash160.len(), 20);
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct RipemdCore<const N: usize> {
+    state: [u32; 10],
+    buffer: BlockBuffer<64>,
+}
+
+/// RIPEMD-128, legacy.
+pub type Ripemd128 = RipemdCore<16>;
+/// RIPEMD-160.
+pub type Ripemd160 = RipemdCore<20>;
+/// RIPEMD-256, the security of RIPEMD-128.
+pub type Ripemd256 = RipemdCore<32>;
+/// RIPEMD-320, the security of RIPEMD-160.
+pub type Ripemd320 = RipemdCore<40>;
+
+impl<const N: usize> Default for RipemdCore<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> RipemdCore<N> {
+    const IV: [u32; 10] = match N {
+        16 | 20 | 40 => IV,
+        32 => IV256,
+        _ => panic!("unsupported digest size"),
+    };
+
+    /// Creates a hasher for an empty message.
+    pub fn new() -> RipemdCore<N> {
+        RipemdCore {
+            state: Self::IV,
+            buffer: BlockBuffer::new(),
+        }
+    }
+}
+
+impl<const N: usize> Digest for RipemdCore<N> {
+    const OUTPUT_SIZE: usize = N;
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = [u8; N];
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| compress(state, N, block));
+    }
+
+    fn finalize(mut self) -> [u8; N] {
+        let state = &mut self.state;
+        self.buffer.pad(8, false, |block| compress(state, N, block));
+        let mut out = [0_u8; N];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    const INPUTS: [&str; 8] = [
+        "",
+        "a",
+        "abc",
+        "message digest",
+        "abcdefghijklmnopqrstuvwxyz",
+        "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+        "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+    ];
+
+    // Checks the digests of INPUTS, then of a million 'a'.
+    fn check<D: Digest>(expected: &[&str; 9]) {
+        for (data, expected) in INPUTS.iter().zip(expected) {
+            assert_eq!(
+                D::digest(data.as_bytes()).as_ref(),
+                hex(expected),
+                "{}",
+                data
+            );
+        }
+        let mut hasher = D::default();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(hasher.finalize().as_ref(), hex(expected[8]));
+    }
+
+    #[test]
+    fn test_ripemd128() {
+        // RIPEMD page test vectors.
+        check::<Ripemd128>(&[
+            "cdf26213a150dc3ecb610f18f6b38b46",
+            "86be7afa339d0fc7cfc785e72f578d33",
+            "c14a12199c66e4ba84636b0f69144c77",
+            "9e327b3d6e523062afc1132d7df9d1b8",
+            "fd2aa607f71dc8f510714922b371834e",
+            "a1aa0689d0fafa2ddc22e88b49133a06",
+            "d1e959eb179c911faea4624c60c5c702",
+            "3f45ef194732c2dbb2c4a2c769795fa3",
+            "4a7f5723f954eba1216c9d8f6320431f",
+        ]);
+    }
+
+    #[test]
+    fn test_ripemd160() {
+        // RIPEMD page test vectors.
+        check::<Ripemd160>(&[
+            "9c1185a5c5e9fc54612808977ee8f548b2258d31",
+            "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe",
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc",
+            "5d0689ef49d2fae572b881b123a85ffa21595f36",
+            "f71c27109c692c1b56bbdceb5b9d2865b3708dbc",
+            "12a053384a9c0c88e405a06c27dcf49ada62eb2b",
+            "b0e20b6e3116640286ed3a87a5713079b21f5189",
+            "9b752e45573d4b39f4dbd3323cab82bf63326bfb",
+            "52783243c1697bdbe16d37f97f68f08325dc1528",
+        ]);
+    }
+
+    #[test]
+    fn test_ripemd256() {
+        // RIPEMD page test vectors.
+        check::<Ripemd256>(&[
+            "02ba4c4e5f8ecd1877fc52d64d30e37a2d9774fb1e5d026380ae0168e3c5522d",
+            "f9333e45d857f5d90a91bab70a1eba0cfb1be4b0783c9acfcd883a9134692925",
+            "afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65",
+            "87e971759a1ce47a514d5c914c392c9018c7c46bc14465554afcdf54a5070c0e",
+            "649d3034751ea216776bf9a18acc81bc7896118a5197968782dd1fd97d8d5133",
+            "3843045583aac6c8c8d9128573e7a9809afb2a0f34ccc36ea9e72f16f6368e3f",
+            "5740a408ac16b720b84424ae931cbb1fe363d1d0bf4017f1a89f7ea6de77a0b8",
+            "06fdcc7a409548aaf91368c06a6275b553e3f099bf0ea4edfd6778df89a890dd",
+            "ac953744e10e31514c150d4d8d7b677342e33399788296e43ae4850ce4f97978",
+        ]);
+    }
+
+    #[test]
+    fn test_ripemd320() {
+        // RIPEMD page test vectors.
+        check::<Ripemd320>(&[
+            "22d65d5661536cdc75c1fdf5c6de7b41b9f27325ebc61e8557177d705a0ec880151c3a32a00899b8",
+            "ce78850638f92658a5a585097579926dda667a5716562cfcf6fbe77f63542f99b04705d6970dff5d",
+            "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82fa942d64cdbc4682d",
+            "3a8e28502ed45d422f68844f9dd316e7b98533fa3f2a91d29f84d425c88d6b4eff727df66a7c0197",
+            "cabdb1810b92470a2093aa6bce05952c28348cf43ff60841975166bb40ed234004b8824463e6b009",
+            "d034a7950cf722021ba4b84df769a5de2060e259df4c9bb4a4268c0e935bbc7470a969c9d072a1ac",
+            "ed544940c86d67f250d232c30b7b3e5770e0c60c8cb9a4cafe3b11388af9920e1b99230b843c86a4",
+            "557888af5f6d8ed62ab66945c6d2a0a47ecd5341e915eb8fea1d0524955f825dc717e4a008ab2d42",
+            "bdee37f4371e20646b8b0d862dda16292ae36f40965e8c8509e63d1dbddecc503e2b63eb9245bb66",
+        ]);
+    }
+
+    #[test]
+    fn test_ripemd_streaming() {
+        // Checked against the ripemd crate.
+        let data: Vec<u8> = (0..1000).map(|i| (i % 251) as u8).collect();
+        let expected = hex("6864b0b9f86a879be2680824c81dbce9c5350281");
+        for split in [0, 1, 55, 56, 63, 64, 65, 500] {
+            let mut hasher = Ripemd160::new();
+            hasher.update(b"discarded");
+            hasher.reset();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.finalize().to_vec(), expected, "split at {}", split);
+        }
+    }
+}
@@ -0,0 +1,429 @@
+//#######################################################################o
+// LEGACY: SHA-1 collisions are practical (SHAttered, 2017) and chosen   |
+// prefix collisions too (2020), it must not be used for signatures.     |
+// SHA-1 is the Merkle-Damgard hash function of FIPS 180: the message is |
+// padded with a 1 bit, zeros and its 64-bit length, then processed in   |
+// 512-bit blocks by 80 steps on five 32-bit words, giving a 160-bit     |
+// digest.                                                               |
+// The collision detection mode is Stevens and Shumow's counter          |
+// cryptanalysis: the known attacks need a message difference following  |
+// one of 32 disturbance vectors, so each block is also recompressed     |
+// with each of these differences from a middle step. If the altered     |
+// block gives the same output from another chaining value, the block is |
+// the second half of a collision attack. It's then compressed twice     |
+// more (the "safe hash"), so both colliding messages get different      |
+// digests. The unavoidable bit conditions filter of the reference code  |
+// isn't used, all vectors are checked at the cost of a slower hash.     |
+//                                                                       |
+// @Refrences:                                                           |
+// FIPS 180-4: https://doi.org/10.6028/NIST.FIPS.180-4                   |
+// Stevens, Shumow: https://marc-stevens.nl/research/papers/C13-S.pdf    |
+// sha1collisiondetection: https://github.com/cr-marcstevens/sha1collisiondetection
+//#######################################################################o
+
+use super::{BlockBuffer, Digest};
+
+// Initial hash value.
+const IV: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+// Step constants of the four rounds.
+const K: [u32; 4] = [0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xca62c1d6];
+
+// The disturbance vectors I(K, b) and II(K, b) checked by the detection.
+const DVS: [(bool, usize, u32); 32] = [
+    (false, 43, 0),
+    (false, 44, 0),
+    (false, 45, 0),
+    (false, 46, 0),
+    (false, 46, 2),
+    (false, 47, 0),
+    (false, 47, 2),
+    (false, 48, 0),
+    (false, 48, 2),
+    (false, 49, 0),
+    (false, 49, 2),
+    (false, 50, 0),
+    (false, 50, 2),
+    (false, 51, 0),
+    (false, 51, 2),
+    (false, 52, 0),
+    (true, 45, 0),
+    (true, 46, 0),
+    (true, 46, 2),
+    (true, 47, 0),
+    (true, 48, 0),
+    (true, 49, 0),
+    (true, 49, 2),
+    (true, 50, 0),
+    (true, 50, 2),
+    (true, 51, 0),
+    (true, 51, 2),
+    (true, 52, 0),
+    (true, 53, 0),
+    (true, 54, 0),
+    (true, 55, 0),
+    (true, 56, 0),
+];
+
+//////////////////////////////////////////////////////////////////////
+/// Returns the message difference of a disturbance vector. The vector
+/// is 16 words of zeros from word K but for the bit b of word K + 15
+/// (and bit b - 1 of words K + 1 and K + 3 for type II), extended
+/// both ways by the message expansion. Each disturbance bit starts a
+/// local collision corrected in the next five words.
+//////////////////////////////////////////////////////////////////////
+const fn message_difference(type_ii: bool, k: usize, b: u32) -> [u32; 80] {
+    // dv[i + 5] is the word i, from -5 to 79.
+    let mut dv = [0_u32; 85];
+    dv[k + 5 + 15] = 1 << b;
+    if type_ii {
+        dv[k + 5 + 1] = 1_u32.rotate_left(b + 31);
+        dv[k + 5 + 3] = 1_u32.rotate_left(b + 31);
+    }
+    let mut i = k + 5 + 16;
+    while i < 85 {
+        dv[i] = (dv[i - 3] ^ dv[i - 8] ^ dv[i - 14] ^ dv[i - 16]).rotate_left(1);
+        i += 1;
+    }
+    let mut i = k + 5;
+    while i > 0 {
+        i -= 1;
+        dv[i] = dv[i + 16].rotate_right(1) ^ dv[i + 13] ^ dv[i + 8] ^ dv[i + 2];
+    }
+    let mut dm = [0_u32; 80];
+    let mut i = 0;
+    while i < 80 {
+        dm[i] = dv[i + 5]
+            ^ dv[i + 4].rotate_left(5)
+            ^ dv[i + 3]
+            ^ dv[i + 2].rotate_left(30)
+            ^ dv[i + 1].rotate_left(30)
+            ^ dv[i].rotate_left(30);
+        i += 1;
+    }
+    dm
+}
+
+// The message differences of `DVS`.
+const DMS: [[u32; 80]; 32] = {
+    let mut dms = [[0_u32; 80]; 32];
+    let mut i = 0;
+    while i < 32 {
+        let (type_ii, k, b) = DVS[i];
+        dms[i] = message_difference(type_ii, k, b);
+        i += 1;
+    }
+    dms
+};
+
+fn f(t: usize, b: u32, c: u32, d: u32) -> u32 {
+    match t / 20 {
+        0 => (b & c) | (!b & d),
+        2 => (b & c) | (b & d) | (c & d),
+        _ => b ^ c ^ d,
+    }
+}
+
+fn step(t: usize, [a, b, c, d, e]: [u32; 5], w: &[u32; 80]) -> [u32; 5] {
+    let t_a = a
+        .rotate_left(5)
+        .wrapping_add(f(t, b, c, d))
+        .wrapping_add(e)
+        .wrapping_add(K[t / 20])
+        .wrapping_add(w[t]);
+    [t_a, a, b.rotate_left(30), c, d]
+}
+
+// The inverse of `step`.
+fn step_back(t: usize, [t_a, a, b, c, d]: [u32; 5], w: &[u32; 80]) -> [u32; 5] {
+    let b = b.rotate_right(30);
+    let e = t_a
+        .wrapping_sub(a.rotate_left(5))
+        .wrapping_sub(f(t, b, c, d))
+        .wrapping_sub(K[t / 20])
+        .wrapping_sub(w[t]);
+    [a, b, c, d, e]
+}
+
+fn add(state: &mut [u32; 5], v: [u32; 5]) {
+    for (s, v) in state.iter_mut().zip(v) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Runs the 80 steps on the expanded block `w`.
+///# Returns
+/// The final working state and the working states before the steps
+/// 58 and 65, where the detection starts its recompressions.
+//////////////////////////////////////////////////////////////////////
+fn steps(state: &[u32; 5], w: &[u32; 80]) -> ([u32; 5], [[u32; 5]; 2]) {
+    let mut s = *state;
+    let mut saved = [[0; 5]; 2];
+    for t in 0..80 {
+        match t {
+            58 => saved[0] = s,
+            65 => saved[1] = s,
+            _ => (),
+        }
+        s = step(t, s, w);
+    }
+    (s, saved)
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function, updates `state` with a 64 bytes block.
+///# Returns
+/// `true` if `detect` is set and the block is part of a collision
+/// attack, `state` is then the safe hash.
+//////////////////////////////////////////////////////////////////////
+fn compress(state: &mut [u32; 5], block: &[u8], detect: bool) -> bool {
+    let mut w = [0_u32; 80];
+    for (w, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for t in 16..80 {
+        w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+    }
+    let (s, saved) = steps(state, &w);
+    add(state, s);
+    if !detect {
+        return false;
+    }
+    for ((_, k, _), dm) in DVS.iter().zip(&DMS) {
+        let (test_step, middle) = if *k < 50 {
+            (58, saved[0])
+        } else {
+            (65, saved[1])
+        };
+        let mut w2 = w;
+        for (w2, dm) in w2.iter_mut().zip(dm) {
+            *w2 ^= dm;
+        }
+        // The chaining value and the output of the altered block if it
+        // shares the working state at `test_step`.
+        let mut other = middle;
+        for t in (0..test_step).rev() {
+            other = step_back(t, other, &w2);
+        }
+        let mut s = middle;
+        for t in test_step..80 {
+            s = step(t, s, &w2);
+        }
+        add(&mut other, s);
+        if other == *state {
+            for _ in 0..2 {
+                let (s, _) = steps(state, &w);
+                add(state, s);
+            }
+            return true;
+        }
+    }
+    false
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Sha1` is a streaming SHA-1 hasher, optionally with collision
+/// detection. Legacy only, SHA-1 is broken.
+///# Example
+/// ```
+/// use cryptolib::hash::sha1::Sha1;
+/// use cryptolib::hash::Digest;
+///
+/// let mut sha1 = Sha1::with_collision_detection();
+/// sha1.update(b"abc");
+/// assert!(!sha1.collision_detected());
+/// assert_eq!(sha1.finalize(), Sha1::digest(b"abc"));
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    buffer: BlockBuffer<64>,
+    detection: bool,
+    collision: bool,
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sha1 {
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Sha1 {
+        Sha1 {
+            state: IV,
+            buffer: BlockBuffer::new(),
+            detection: false,
+            collision: false,
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a hasher that detects the collision attacks, the digest
+    /// differs from SHA-1 only for a message that is part of an attack.
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_collision_detection() -> Sha1 {
+        Sha1 {
+            detection: true,
+            ..Self::new()
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Returns `true` if the data absorbed so far is part of a collision
+    /// attack, the padding of `finalize` is checked too.
+    ////////////////////////////////////////////////////////////////////
+    pub fn collision_detected(&self) -> bool {
+        self.collision
+    }
+
+    fn process(&mut self, data: &[u8], pad: bool) {
+        let (state, detection, collision) = (&mut self.state, self.detection, &mut self.collision);
+        let compress = |block: &[u8]| *collision |= compress(state, block, detection);
+        if pad {
+            self.buffer.pad(8, true, compress);
+        } else {
+            self.buffer.update(data, compress);
+        }
+    }
+}
+
+impl Digest for Sha1 {
+    const OUTPUT_SIZE: usize = 20;
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = [u8; 20];
+
+    fn update(&mut self, data: &[u8]) {
+        self.process(data, false);
+    }
+
+    fn finalize(mut self) -> [u8; 20] {
+        self.process(&[], true);
+        let mut out = [0_u8; 20];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+
+    /// Starts a new message, keeping the collision detection mode.
+    fn reset(&mut self) {
+        *self = Sha1 {
+            detection: self.detection,
+            ..Self::new()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    // The first 320 bytes of the SHAttered PDF files, they differ in the
+    // two near-collision blocks after the 192 bytes prefix.
+    const SHATTERED_PREFIX: &str = "
+        255044462d312e330a25e2e3cfd30a0a0a312030206f626a0a3c3c2f57696474
+        682032203020522f4865696768742033203020522f547970652034203020522f
+        537562747970652035203020522f46696c7465722036203020522f436f6c6f72
+        53706163652037203020522f4c656e6774682038203020522f42697473506572
+        436f6d706f6e656e7420383e3e0a73747265616d0affd8fffe00245348412d31
+        20697320646561642121212121852fec092339759c39b1a1c63c4c97e1fffe01";
+    const SHATTERED_1: &str = "
+        7346dc9166b67e118f029ab621b2560ff9ca67cca8c7f85ba84c79030c2b3de2
+        18f86db3a90901d5df45c14f26fedfb3dc38e96ac22fe7bd728f0e45bce046d2
+        3c570feb141398bb552ef5a0a82be331fea48037b8b5d71f0e332edf93ac3500
+        eb4ddc0decc1a864790c782c76215660dd309791d06bd0af3f98cda4bc4629b1";
+    const SHATTERED_2: &str = "
+        7f46dc93a6b67e013b029aaa1db2560b45ca67d688c7f84b8c4c791fe02b3df6
+        14f86db1690901c56b45c1530afedfb76038e972722fe7ad728f0e4904e046c2
+        30570fe9d41398abe12ef5bc942be33542a4802d98b5d70f2a332ec37fac3514
+        e74ddc0f2cc1a874cd0c78305a21566461309789606bd0bf3f98cda8044629a1";
+
+    #[test]
+    fn test_sha1_fips180() {
+        // FIPS 180 examples.
+        for (data, expected) in [
+            ("", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            ("abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            ),
+        ] {
+            assert_eq!(
+                Sha1::digest(data.as_bytes()).to_vec(),
+                hex(expected),
+                "{}",
+                data
+            );
+        }
+        let mut sha1 = Sha1::new();
+        for _ in 0..1000 {
+            sha1.update(&[b'a'; 1000]);
+        }
+        assert_eq!(
+            sha1.finalize().to_vec(),
+            hex("34aa973cd4c4daa4f61eeb2bdbad27316534016f")
+        );
+    }
+
+    #[test]
+    fn test_sha1_streaming() {
+        // Checked against Python's hashlib.
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let expected = hex("af0b191c2de46fe13fe0908f5a6a4e90e0cafc46");
+        for split in [0, 1, 55, 56, 63, 64, 65, 500] {
+            for mut sha1 in [Sha1::new(), Sha1::with_collision_detection()] {
+                sha1.update(b"discarded");
+                sha1.reset();
+                sha1.update(&data[..split]);
+                sha1.update(&data[split..]);
+                assert!(!sha1.collision_detected());
+                assert_eq!(sha1.finalize().to_vec(), expected, "split at {}", split);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sha1_collision_detection() {
+        // Both SHAttered prefixes collide under SHA-1, the detection
+        // flags them and returns the safe hashes, cross-checked against
+        // the sha1collisiondetection crate 0.3.4 (a port of sha1dc) with
+        // the safe hash enabled.
+        let expected = hex("f92d74e3874587aaf443d1db961d4e26dde13e9c");
+        for (blocks, safe_hash) in [
+            (SHATTERED_1, "7117b3cb9225aaf0d8ef1a40e493957b0bf8693d"),
+            (SHATTERED_2, "29f38ae9fd98e2931120fa0bf213e024250d3f6a"),
+        ] {
+            let mut data = hex(SHATTERED_PREFIX);
+            data.extend(hex(blocks));
+            assert_eq!(Sha1::digest(&data).to_vec(), expected);
+            let mut sha1 = Sha1::with_collision_detection();
+            sha1.update(&data[..256]);
+            assert!(!sha1.collision_detected());
+            sha1.update(&data[256..]);
+            assert!(sha1.collision_detected());
+            assert_eq!(sha1.clone().finalize().to_vec(), hex(safe_hash));
+            sha1.reset();
+            assert!(!sha1.collision_detected());
+        }
+    }
+
+    #[test]
+    fn test_sha1_message_differences() {
+        // Spot checks against the table of sha1collisiondetection.
+        assert_eq!(
+            DMS[0][..8],
+            [
+                0x08000000, 0x9800000c, 0xd8000010, 0x08000010, 0xb8000010, 0x98000000, 0x60000000,
+                0x8
+            ]
+        );
+        assert_eq!(DMS[0][58..62], [0x1, 0x20, 0x1, 0x40000002]);
+    }
+}
@@ -0,0 +1,794 @@
+//#######################################################################o
+// SHA-2 is the family of hash functions of FIPS 180-4. SHA-224 and      |
+// SHA-256 process 512-bit blocks with 64 rounds on eight 32-bit words,  |
+// SHA-384, SHA-512, SHA-512/224 and SHA-512/256 process 1024-bit blocks |
+// with 80 rounds on eight 64-bit words. The message is padded with a 1  |
+// bit, zeros and its length on 64 or 128 bits. The truncated variants   |
+// only differ by their initial value and the number of output bytes.    |
+// The intermediate state can be exported to checkpoint a long message   |
+// and resumed later.                                                    |
+//                                                                       |
+// @Refrences:                                                           |
+// FIPS 180-4: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+// CAVP: https://csrc.nist.gov/projects/cryptographic-algorithm-validation-program/secure-hashing
+//#######################################################################o
+
+use super::{BlockBuffer, Digest};
+use crate::error::CryptoError;
+
+// Round constants, the fractional parts of the cube roots of the first
+// 64 and 80 primes.
+const K256: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const K512: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+// Initial hash values, the SHA-512/224 and SHA-512/256 ones are generated
+// by the function of FIPS 180-4 section 5.3.6.
+const IV224: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
+const IV256: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+const IV384: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+const IV512: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+const IV512_224: [u64; 8] = [
+    0x8c3d37c819544da2,
+    0x73e1996689dcd4d6,
+    0x1dfab7ae32ff9c82,
+    0x679dd514582f9fcf,
+    0x0f6d2b697bd44da8,
+    0x77e36f7304c48942,
+    0x3f9d85a86a1d36c8,
+    0x1112e6ad91d692a1,
+];
+const IV512_256: [u64; 8] = [
+    0x22312194fc2bf72c,
+    0x9f555fa3c84c64c2,
+    0x2393b86b6f53b151,
+    0x963877195940eabd,
+    0x96283ee2a88effe3,
+    0xbe5e1e2553863992,
+    0x2b0199fc2c85b8aa,
+    0x0eb72ddc81c52ca2,
+];
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function of SHA-224 and SHA-256, updates `state`
+/// with a 64 bytes block.
+//////////////////////////////////////////////////////////////////////
+fn compress256(state: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0_u32; 64];
+    for (w, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for t in 16..64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for t in 0..64 {
+        let sum1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(sum1)
+            .wrapping_add(ch)
+            .wrapping_add(K256[t])
+            .wrapping_add(w[t]);
+        let sum0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(sum0).wrapping_add(maj);
+    }
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function of the SHA-512 variants, updates `state`
+/// with a 128 bytes block.
+//////////////////////////////////////////////////////////////////////
+fn compress512(state: &mut [u64; 8], block: &[u8]) {
+    let mut w = [0_u64; 80];
+    for (w, bytes) in w.iter_mut().zip(block.chunks_exact(8)) {
+        *w = u64::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for t in 16..80 {
+        let s0 = w[t - 15].rotate_right(1) ^ w[t - 15].rotate_right(8) ^ (w[t - 15] >> 7);
+        let s1 = w[t - 2].rotate_right(19) ^ w[t - 2].rotate_right(61) ^ (w[t - 2] >> 6);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for t in 0..80 {
+        let sum1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(sum1)
+            .wrapping_add(ch)
+            .wrapping_add(K512[t])
+            .wrapping_add(w[t]);
+        let sum0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(sum0).wrapping_add(maj);
+    }
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+// An exported state is the word size and the digest size, the eight
+// state words and the message buffer. Returns the words and the buffer
+// after checking the sizes.
+fn split_state(state: &[u8], word_size: usize, n: usize) -> Result<(&[u8], &[u8]), CryptoError> {
+    match state {
+        [w, size, rest @ ..]
+            if *w as usize == word_size && *size as usize == n && rest.len() >= 8 * word_size =>
+        {
+            Ok(rest.split_at(8 * word_size))
+        }
+        _ => Err(CryptoError::InvalidParameter),
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Sha256Core` is a streaming hasher of the 32-bit SHA-2 functions
+/// with an `N` bytes digest, use the `Sha224` and `Sha256` aliases.
+///# Example
+/// ```
+/// use cryptolib::hash::sha2::Sha256;
+/// use cryptolib::hash::Digest;
+///
+/// let mut sha = Sha256::new();
+/// sha.update(b"a");
+/// // Checkpoint the message and resume it later.
+/// let checkpoint = sha.export_state();
+/// let mut sha = Sha256::import_state(&checkpoint).unwrap();
+/// sha.update(b"bc");
+/// assert_eq!(sha.finalize(), Sha256::digest(b"abc"));
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Sha256Core<const N: usize> {
+    state: [u32; 8],
+    buffer: BlockBuffer<64>,
+}
+
+/// SHA-224, a 28 bytes digest.
+pub type Sha224 = Sha256Core<28>;
+/// SHA-256, a 32 bytes digest.
+pub type Sha256 = Sha256Core<32>;
+
+impl<const N: usize> Default for Sha256Core<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Sha256Core<N> {
+    const IV: [u32; 8] = match N {
+        28 => IV224,
+        32 => IV256,
+        _ => panic!("unsupported digest size"),
+    };
+
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Sha256Core<N> {
+        Sha256Core {
+            state: Self::IV,
+            buffer: BlockBuffer::new(),
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Exports the intermediate state, to be restored by `import_state`.
+    ///# Note
+    /// '''
+    /// The state holds the pending bytes of the last incomplete block,
+    /// it must be protected like the message.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn export_state(&self) -> Vec<u8> {
+        let mut out = vec![4, N as u8];
+        for word in self.state {
+            out.extend_from_slice(&word.to_be_bytes());
+        }
+        self.buffer.export(&mut out);
+        out
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Resumes a message from a state returned by `export_state`.
+    ///# Returns
+    /// The hasher or `CryptoError::InvalidParameter` if `state` isn't
+    /// a state of this function.
+    ////////////////////////////////////////////////////////////////////
+    pub fn import_state(state: &[u8]) -> Result<Sha256Core<N>, CryptoError> {
+        let (words, buffer) = split_state(state, 4, N)?;
+        let mut sha = Sha256Core {
+            state: [0; 8],
+            buffer: BlockBuffer::import(buffer).ok_or(CryptoError::InvalidParameter)?,
+        };
+        for (word, bytes) in sha.state.iter_mut().zip(words.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        Ok(sha)
+    }
+}
+
+impl<const N: usize> Digest for Sha256Core<N> {
+    const OUTPUT_SIZE: usize = N;
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = [u8; N];
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| compress256(state, block));
+    }
+
+    fn finalize(mut self) -> [u8; N] {
+        let state = &mut self.state;
+        self.buffer.pad(8, true, |block| compress256(state, block));
+        let mut full = [0_u8; 32];
+        for (bytes, word) in full.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        full[..N].try_into().unwrap()
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Sha512Core` is a streaming hasher of the 64-bit SHA-2 functions
+/// with an `N` bytes digest, use the `Sha384`, `Sha512`, `Sha512_224`
+/// and `Sha512_256` aliases.
+///# Example
+/// ```
+/// use cryptolib::hash::sha2::{Sha512, Sha512_256};
+/// use cryptolib::hash::Digest;
+///
+/// let mut sha = Sha512::new();
+/// sha.update(b"a");
+/// sha.update(b"bc");
+/// assert_eq!(sha.finalize(), Sha512::digest(b"abc"));
+/// assert_eq!(Sha512_256::digest(b"abc").len(), 32);
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Sha512Core<const N: usize> {
+    state: [u64; 8],
+    buffer: BlockBuffer<128>,
+}
+
+/// SHA-384, a 48 bytes digest.
+pub type Sha384 = Sha512Core<48>;
+/// SHA-512, a 64 bytes digest.
+pub type Sha512 = Sha512Core<64>;
+/// SHA-512/224, a 28 bytes digest.
+pub type Sha512_224 = Sha512Core<28>;
+/// SHA-512/256, a 32 bytes digest.
+pub type Sha512_256 = Sha512Core<32>;
+
+impl<const N: usize> Default for Sha512Core<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Sha512Core<N> {
+    const IV: [u64; 8] = match N {
+        28 => IV512_224,
+        32 => IV512_256,
+        48 => IV384,
+        64 => IV512,
+        _ => panic!("unsupported digest size"),
+    };
+
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Sha512Core<N> {
+        Sha512Core {
+            state: Self::IV,
+            buffer: BlockBuffer::new(),
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Exports the intermediate state, to be restored by `import_state`.
+    ///# Note
+    /// '''
+    /// The state holds the pending bytes of the last incomplete block,
+    /// it must be protected like the message.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn export_state(&self) -> Vec<u8> {
+        let mut out = vec![8, N as u8];
+        for word in self.state {
+            out.extend_from_slice(&word.to_be_bytes());
+        }
+        self.buffer.export(&mut out);
+        out
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Resumes a message from a state returned by `export_state`.
+    ///# Returns
+    /// The hasher or `CryptoError::InvalidParameter` if `state` isn't
+    /// a state of this function.
+    ////////////////////////////////////////////////////////////////////
+    pub fn import_state(state: &[u8]) -> Result<Sha512Core<N>, CryptoError> {
+        let (words, buffer) = split_state(state, 8, N)?;
+        let mut sha = Sha512Core {
+            state: [0; 8],
+            buffer: BlockBuffer::import(buffer).ok_or(CryptoError::InvalidParameter)?,
+        };
+        for (word, bytes) in sha.state.iter_mut().zip(words.chunks_exact(8)) {
+            *word = u64::from_be_bytes(bytes.try_into().unwrap());
+        }
+        Ok(sha)
+    }
+}
+
+impl<const N: usize> Digest for Sha512Core<N> {
+    const OUTPUT_SIZE: usize = N;
+    const BLOCK_SIZE: usize = 128;
+
+    type Output = [u8; N];
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| compress512(state, block));
+    }
+
+    fn finalize(mut self) -> [u8; N] {
+        let state = &mut self.state;
+        self.buffer.pad(16, true, |block| compress512(state, block));
+        let mut full = [0_u8; 64];
+        for (bytes, word) in full.chunks_exact_mut(8).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        full[..N].try_into().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    fn check<D: Digest>(vectors: &[(&str, &str)]) {
+        for (msg, md) in vectors {
+            assert_eq!(D::digest(&hex(msg)).as_ref(), hex(md), "message {}", msg);
+        }
+    }
+
+    fn million_a<D: Digest>() -> Vec<u8> {
+        let mut hasher = D::default();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        hasher.finalize().as_ref().to_vec()
+    }
+
+    #[test]
+    fn test_sha224_cavp() {
+        // SHA224ShortMsg.rsp around the padding boundary and the first
+        // vector of SHA224LongMsg.rsp.
+        check::<Sha224>(&[
+            (
+                "",
+                "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f",
+            ),
+            (
+                "84",
+                "3cd36921df5d6963e73739cf4d20211e2d8877c19cff087ade9d0e3a",
+            ),
+            (
+                "445e8698eeb8accbaac4ffa7d934fffd16014a430ef70f3a9174c6cfe96d1e3f
+                 6ab1377f4a7212dbb30146dd17d9f470c4dffc45b8e871",
+                "4c7ae028c0fe61f2a9cada61fae30685b77f04c6442576e912af9fa6",
+            ),
+            (
+                "52839f2f0853a30df14ec897a1914c685c1ac21470d00654c8c37663bfb65fa7
+                 32dbb694d9dd09ced723b48d8f545846ba168988b61cc724",
+                "2f755a57674b49d5c25cb37348f35b6fd2de2552c749f2645ba63d20",
+            ),
+            (
+                "a3310ba064be2e14ad32276e18cd0310c933a6e650c3c754d0243c6c61207865
+                 b4b65248f66a08edf6e0832689a9dc3a2e5d2095eeea50bd862bac88c8bd318d",
+                "b2a5586d9cbf0baa999157b4af06d88ae08d7c9faab4bc1a96829d65",
+            ),
+            (
+                "f149e41d848f59276cfddd743bafa9a90e1ee4a263a118142b33e3702176ef0a
+                 59f8237a1cb51b42f3ded6b202d9af0997898fdd03cf60bda951c514547a0850
+                 cec25444ae2f24cb711bfbafcc3956c941d3de69f155e3f8b10f06db5f37359b
+                 772ddd43e1035a0a0d3db33242d5843033833b0dd43b870c6bf60e8deab55f31
+                 7cc3273f5e3ba747f0cb65050cb7228796210d9254873643008d45f29cfd6c5b
+                 060c9a",
+                "9db6dc3a23abd7b6c3d72c38f4843c7de48a71d0ba91a86b18393e5f",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_sha256_cavp() {
+        // SHA256ShortMsg.rsp around the padding boundary and the first
+        // vector of SHA256LongMsg.rsp.
+        check::<Sha256>(&[
+            (
+                "",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (
+                "d3",
+                "28969cdfa74a12c82f3bad960b0b000aca2ac329deea5c2328ebc6f2ba9802c1",
+            ),
+            (
+                "3ebfb06db8c38d5ba037f1363e118550aad94606e26835a01af05078533cc25f
+                 2f39573c04b632f62f68c294ab31f2a3e2a1a0d8c2be51",
+                "6595a2ef537a69ba8583dfbf7f5bec0ab1f93ce4c8ee1916eff44a93af5749c4",
+            ),
+            (
+                "2d52447d1244d2ebc28650e7b05654bad35b3a68eedc7f8515306b496d75f3e7
+                 3385dd1b002625024b81a02f2fd6dffb6e6d561cb7d0bd7a",
+                "cfb88d6faf2de3a69d36195acec2e255e2af2b7d933997f348e09f6ce5758360",
+            ),
+            (
+                "5a86b737eaea8ee976a0a24da63e7ed7eefad18a101c1211e2b3650c5187c2a8
+                 a650547208251f6d4237e661c7bf4c77f335390394c37fa1a9f9be836ac28509",
+                "42e61e174fbb3897d6dd6cef3dd2802fe67b331953b06114a65c772859dfc1aa",
+            ),
+            (
+                "451101250ec6f26652249d59dc974b7361d571a8101cdfd36aba3b5854d3ae08
+                 6b5fdd4597721b66e3c0dc5d8c606d9657d0e323283a5217d1f53f2f284f57b8
+                 5c8a61ac8924711f895c5ed90ef17745ed2d728abd22a5f7a13479a462d71b56
+                 c19a74a40b655c58edfe0a188ad2cf46cbf30524f65d423c837dd1ff2bf462ac
+                 4198007345bb44dbb7b1c861298cdf61982a833afc728fae1eda2f87aa2c9480
+                 858bec",
+                "3c593aa539fdcdae516cdf2f15000f6634185c88f505b39775fb9ab137a10aa2",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_sha384_cavp() {
+        // SHA384ShortMsg.rsp around the padding boundary and the first
+        // vector of SHA384LongMsg.rsp.
+        check::<Sha384>(&[
+            (
+                "",
+                "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da
+                 274edebfe76f65fbd51ad2f14898b95b",
+            ),
+            (
+                "c5",
+                "b52b72da75d0666379e20f9b4a79c33a329a01f06a2fb7865c9062a28c1de860
+                 ba432edfd86b4cb1cb8a75b46076e3b1",
+            ),
+            (
+                "a04f390a9cc2effad05db80d9076a8d4b6cc8bba97b27b423670b290b8e69c2b
+                 187230011c1481ac88d090f39154659494db5e410851c6e8b2b8a93717cae760
+                 37e0881978124fe7e1a0929d8891491f4e99646cc94062dc82411fa66130eda4
+                 6560e75b98048236439465125e737b",
+                "e7089d72945cef851e689b4409cfb63d135f0b5cdfb0dac6c3a292dd70371ab4
+                 b79da1997d7992906ac7213502662920",
+            ),
+            (
+                "f419494c3c6d0727b3395a483a2167182a7252f4fd099c2d4b71b053f94bb8b3
+                 adf3b51e8460cfec084ce9415c95798fbae4975c208c544645b54c44d2b97f2e
+                 cfce5c805be61f5ba1d35dcc07afdd51a87baa990506668cf710e18be9b0ebf9
+                 43f366fa29c69f7a6616de72a3353b66",
+                "aead8688c58c6ba4e9cadb4756b465dce0fb06f1cfaa478197f2ea89414e47e9
+                 572034adfed160703c79b82b3fd7ab78",
+            ),
+            (
+                "3bf52cc5ee86b9a0190f390a5c0366a560b557000dbe5115fd9ee11630a62769
+                 011575f15881198f227876e8fe685a6939bc8b89fd48a34ec5e71e131462b288
+                 6794dffa68ccc6d564733e67ffef25e627c6f4b5460796e3bce67bf58ca6e8e5
+                 55bc916a8531697ac948b90dc8616f25101db90b50c3d3dbc9e21e42ff387187",
+                "12b6cb35eda92ee37356ddee77781a17b3d90e563824a984faffc6fdd1693bd7
+                 626039635563cfc3b9a2b00f9c65eefd",
+            ),
+            (
+                "62c6a169b9be02b3d7b471a964fc0bcc72b480d26aecb2ed460b7f50016ddaf0
+                 4c51218783f3aadfdff5a04ded030d7b3fb7376b61ba30b90e2da921a4470740
+                 d63fb99fa16cc8ed81abaf8ce4016e50df81da832070372c24a80890aa3a26fa
+                 675710b8fb718266249d496f313c55d0bada101f8f56eeccee4345a8f98f60a3
+                 6662cfda794900d12f9414fcbdfdeb85388a814996b47e24d5c8086e7a8edcc5
+                 3d299d0d033e6bb60c58b83d6e8b57f6c258d6081dd10eb942fdf8ec157ec3e7
+                 5371235a8196eb9d22b1de3a2d30c2abbe0db7650cf6c7159bacbe29b3a93c92
+                 100508",
+                "0730e184e7795575569f87030260bb8e54498e0e5d096b18285e988d245b6f34
+                 86d1f2447d5f85bcbe59d5689fc49425",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_sha512_cavp() {
+        // SHA512ShortMsg.rsp around the padding boundary and the first
+        // vector of SHA512LongMsg.rsp.
+        check::<Sha512>(&[
+            (
+                "",
+                "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce
+                 47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+            ),
+            (
+                "21",
+                "3831a6a6155e509dee59a7f451eb35324d8f8f2df6e3708894740f98fdee2388
+                 9f4de5adb0c5010dfb555cda77c8ab5dc902094c52de3278f35a75ebc25f093a",
+            ),
+            (
+                "324533e685f1852e358eea8ea8b81c288b3f3beb1f2bc2b8d3fdbac318382e3d
+                 7120de30c9c237aa0a34831deb1e5e060a7969cd3a9742ec1e64b354f7eb290c
+                 ba1c681c66cc7ea994fdf5614f604d1a2718aab581c1c94931b1387e4b7dc736
+                 35bf3a7301174075fa70a9227d85d3",
+                "3b26c5170729d0814153becb95f1b65cd42f9a6d0649d914e4f69d938b5e9dc0
+                 41cd0f5c8da0b484d7c7bc7b1bdefb08fe8b1bfedc81109345bc9e9a399feedf",
+            ),
+            (
+                "518985977ee21d2bf622a20567124fcbf11c72df805365835ab3c041f4a9cd8a
+                 0ad63c9dee1018aa21a9fa3720f47dc48006f1aa3dba544950f87e627f369bc2
+                 793ede21223274492cceb77be7eea50e5a509059929a16d33a9f54796cde5770
+                 c74bd3ecc25318503f1a41976407aff2",
+                "c00926a374cde55b8fbd77f50da1363da19744d3f464e07ce31794c5a61b6f9c
+                 85689fa1cfe136553527fd876be91673c2cac2dd157b2defea360851b6d92cf4",
+            ),
+            (
+                "fd2203e467574e834ab07c9097ae164532f24be1eb5d88f1af7748ceff0d2c67
+                 a21f4e4097f9d3bb4e9fbf97186e0db6db0100230a52b453d421f8ab9c9a6043
+                 aa3295ea20d2f06a2f37470d8a99075f1b8a8336f6228cf08b5942fc1fb4299c
+                 7d2480e8e82bce175540bdfad7752bc95b577f229515394f3ae5cec870a4b2f8",
+                "a21b1077d52b27ac545af63b32746c6e3c51cb0cb9f281eb9f3580a6d4996d5c
+                 9917d2a6e484627a9d5a06fa1b25327a9d710e027387fc3e07d7c4d14c6086cc",
+            ),
+            (
+                "4f05600950664d5190a2ebc29c9edb89c20079a4d3e6bc3b27d75e34e2fa3d02
+                 768502bd69790078598d5fcf3d6779bfed1284bbe5ad72fb456015181d9587d6
+                 e864c940564eaafb4f2fead4346ea09b6877d9340f6b82eb1515880872213da3
+                 ad88feba9f4f13817a71d6f90a1a17c43a15c038d988b5b29edffe2d6a062813
+                 cedbe852cde302b3e33b696846d2a8e36bd680efcc6cd3f9e9a4c1ae8cac10cc
+                 5244d131677140399176ed46700019a004a163806f7fa467fc4e17b4617bbd76
+                 41aaff7ff56396ba8c08a8be100b33a20b5daf134a2aefa5e1c3496770dcf6ba
+                 a4f7bb",
+                "a9db490c708cc72548d78635aa7da79bb253f945d710e5cb677a474efc7c65a2
+                 aab45bc7ca1113c8ce0f3c32e1399de9c459535e8816521ab714b2a6cd200525",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_sha512_t() {
+        // FIPS 180-4 examples, checked against OpenSSL.
+        check::<Sha512_224>(&[
+            (
+                "",
+                "6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4",
+            ),
+            (
+                "616263",
+                "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa",
+            ),
+            (
+                "61626364656667686263646566676869636465666768696a6465666768696a6b
+                 65666768696a6b6c666768696a6b6c6d6768696a6b6c6d6e68696a6b6c6d6e6f
+                 696a6b6c6d6e6f706a6b6c6d6e6f70716b6c6d6e6f7071726c6d6e6f70717273
+                 6d6e6f70717273746e6f707172737475",
+                "23fec5bb94d60b23308192640b0c453335d664734fe40e7268674af9",
+            ),
+        ]);
+        check::<Sha512_256>(&[
+            (
+                "",
+                "c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a",
+            ),
+            (
+                "616263",
+                "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23",
+            ),
+            (
+                "61626364656667686263646566676869636465666768696a6465666768696a6b
+                 65666768696a6b6c666768696a6b6c6d6768696a6b6c6d6e68696a6b6c6d6e6f
+                 696a6b6c6d6e6f706a6b6c6d6e6f70716b6c6d6e6f7071726c6d6e6f70717273
+                 6d6e6f70717273746e6f707172737475",
+                "3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_sha2_million_a() {
+        // FIPS 180-4 examples, the SHA-512/t ones checked against OpenSSL.
+        assert_eq!(
+            million_a::<Sha224>(),
+            hex("20794655980c91d8bbb4c1ea97618a4bf03f42581948b2ee4ee7ad67")
+        );
+        assert_eq!(
+            million_a::<Sha256>(),
+            hex("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0")
+        );
+        assert_eq!(
+            million_a::<Sha384>(),
+            hex(
+                "9d0e1809716474cb086e834e310a4a1ced149e9c00f248527972cec5704c2a5b
+             07b8b3dc38ecc4ebae97ddd87f3d8985"
+            )
+        );
+        assert_eq!(
+            million_a::<Sha512>(),
+            hex(
+                "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973eb
+             de0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b"
+            )
+        );
+        assert_eq!(
+            million_a::<Sha512_224>(),
+            hex("37ab331d76f0d36de422bd0edeb22a28accd487b7a8453ae965dd287")
+        );
+        assert_eq!(
+            million_a::<Sha512_256>(),
+            hex("9a59a052930187a97038cae692f30708aa6491923ef5194394dc68d56c74fb21")
+        );
+    }
+
+    #[test]
+    fn test_sha2_export_import() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let expected = Sha384::digest(&data);
+        for split in [0, 1, 111, 112, 128, 129, 999] {
+            let mut sha = Sha384::new();
+            sha.update(&data[..split]);
+            let mut sha = Sha384::import_state(&sha.export_state()).unwrap();
+            sha.update(&data[split..]);
+            assert_eq!(sha.finalize(), expected, "split at {}", split);
+        }
+        let mut sha = Sha224::new();
+        sha.update(&data[..70]);
+        let state = sha.export_state();
+        assert_eq!(state.len(), 2 + 32 + 16 + 6);
+        let mut resumed = Sha224::import_state(&state).unwrap();
+        resumed.update(&data[70..]);
+        assert_eq!(resumed.finalize(), Sha224::digest(&data));
+
+        // Another function, a truncated state or inconsistent pending bytes.
+        let invalid = Some(CryptoError::InvalidParameter);
+        assert_eq!(Sha256::import_state(&state).err(), invalid);
+        assert_eq!(Sha512_224::import_state(&state).err(), invalid);
+        assert_eq!(
+            Sha224::import_state(&state[..state.len() - 1]).err(),
+            invalid
+        );
+        assert_eq!(Sha224::import_state(&state[..20]).err(), invalid);
+        assert_eq!(Sha224::import_state(&[]).err(), invalid);
+    }
+}
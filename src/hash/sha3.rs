@@ -0,0 +1,439 @@
+//#######################################################################o
+// SHA-3 (FIPS 202) is the Keccak sponge with a 1600-bit state: the      |
+// capacity is twice the security level and the rest of the state, the   |
+// rate, absorbs the message. SHA3-224/256/384/512 have fixed outputs,   |
+// SHAKE128 and SHAKE256 are extendable-output functions (XOF) read for  |
+// as long as needed. cSHAKE (SP 800-185) is SHAKE with a function name  |
+// and a customization string absorbed first, padded to a full block,    |
+// so different applications get independent functions. Distinct domain  |
+// separation bits are appended to the message of each family.           |
+//                                                                       |
+// @Refrences:                                                           |
+// FIPS 202: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf    |
+// SP 800-185: https://doi.org/10.6028/NIST.SP.800-185                   |
+//#######################################################################o
+
+use super::keccak::Sponge;
+use super::Digest;
+
+// Domain separation bits, followed by the first bit of the padding.
+const SHA3_DOMAIN: u8 = 0x06;
+const SHAKE_DOMAIN: u8 = 0x1f;
+const CSHAKE_DOMAIN: u8 = 0x04;
+
+//////////////////////////////////////////////////////////////////////
+/// Encodes `x` as its byte length followed by its big-endian bytes.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn left_encode(x: u64) -> Vec<u8> {
+    let mut out = right_encode(x);
+    out.rotate_right(1);
+    out
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Encodes `x` as its big-endian bytes followed by their number.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn right_encode(x: u64) -> Vec<u8> {
+    let bytes = x.to_be_bytes();
+    let skip = (x.leading_zeros() as usize / 8).min(7);
+    let mut out = bytes[skip..].to_vec();
+    out.push((8 - skip) as u8);
+    out
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Encodes `s` as its length in bits followed by its bytes, so that
+/// concatenated strings can be parsed back unambiguously.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn encode_string(s: &[u8]) -> Vec<u8> {
+    let mut out = left_encode(8 * s.len() as u64);
+    out.extend_from_slice(s);
+    out
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Sha3` is a streaming SHA-3 hasher with an `N` bytes digest, use
+/// the `Sha3_224`, `Sha3_256`, `Sha3_384` and `Sha3_512` aliases.
+///# Example
+/// ```
+/// use cryptolib::hash::sha3::Sha3_256;
+/// use cryptolib::hash::Digest;
+///
+/// let mut sha3 = Sha3_256::new();
+/// sha3.update(b"a");
+/// sha3.update(b"bc");
+/// assert_eq!(sha3.finalize(), Sha3_256::digest(b"abc"));
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Sha3<const N: usize> {
+    sponge: Sponge,
+}
+
+/// SHA3-224, a 28 bytes digest.
+pub type Sha3_224 = Sha3<28>;
+/// SHA3-256, a 32 bytes digest.
+pub type Sha3_256 = Sha3<32>;
+/// SHA3-384, a 48 bytes digest.
+pub type Sha3_384 = Sha3<48>;
+/// SHA3-512, a 64 bytes digest.
+pub type Sha3_512 = Sha3<64>;
+
+impl<const N: usize> Default for Sha3<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Sha3<N> {
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Sha3<N> {
+        Sha3 {
+            sponge: Sponge::new(200 - 2 * N),
+        }
+    }
+}
+
+impl<const N: usize> Digest for Sha3<N> {
+    const OUTPUT_SIZE: usize = N;
+    const BLOCK_SIZE: usize = 200 - 2 * N;
+
+    type Output = [u8; N];
+
+    fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    fn finalize(mut self) -> [u8; N] {
+        self.sponge.pad(SHA3_DOMAIN);
+        let mut out = [0_u8; N];
+        self.sponge.squeeze(&mut out);
+        out
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `XofReader` reads the output of an extendable-output function, in
+/// pieces of any size.
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct XofReader {
+    sponge: Sponge,
+}
+
+impl XofReader {
+    /// Fills `out` with the next output bytes.
+    pub fn read(&mut self, out: &mut [u8]) {
+        self.sponge.squeeze(out);
+    }
+
+    /// Returns the next `len` output bytes.
+    pub fn read_vec(&mut self, len: usize) -> Vec<u8> {
+        let mut out = vec![0; len];
+        self.read(&mut out);
+        out
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Shake` is a streaming SHAKE hasher absorbing `RATE` bytes blocks,
+/// use the `Shake128` and `Shake256` aliases.
+///# Example
+/// ```
+/// use cryptolib::hash::sha3::Shake128;
+///
+/// let mut reader = Shake128::new().chain(b"abc").finalize_xof();
+/// let mut out = [0_u8; 100];
+/// reader.read(&mut out[..30]);
+/// reader.read(&mut out[30..]);
+/// let mut shake = Shake128::new();
+/// shake.update(b"abc");
+/// assert_eq!(out.to_vec(), shake.finalize_xof().read_vec(100));
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Shake<const RATE: usize> {
+    sponge: Sponge,
+}
+
+/// SHAKE128, 128-bit security.
+pub type Shake128 = Shake<168>;
+/// SHAKE256, 256-bit security.
+pub type Shake256 = Shake<136>;
+
+impl<const RATE: usize> Default for Shake<RATE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const RATE: usize> Shake<RATE> {
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Shake<RATE> {
+        Shake {
+            sponge: Sponge::new(RATE),
+        }
+    }
+
+    /// Absorbs `data` into the hash.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    /// Absorbs `data` and returns the hasher, for one-line hashing.
+    pub fn chain(mut self, data: &[u8]) -> Shake<RATE> {
+        self.update(data);
+        self
+    }
+
+    /// Pads the message and returns the reader of its output.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.sponge.pad(SHAKE_DOMAIN);
+        XofReader {
+            sponge: self.sponge,
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `CShake` is a streaming cSHAKE hasher absorbing `RATE` bytes blocks,
+/// use the `CShake128` and `CShake256` aliases.
+///# Example
+/// ```
+/// use cryptolib::hash::sha3::{CShake128, Shake128};
+///
+/// let mut cshake = CShake128::new(b"", b"Email Signature");
+/// cshake.update(&[0, 1, 2, 3]);
+/// let mut reader = cshake.finalize_xof();
+/// let mut out = [0_u8; 32];
+/// reader.read(&mut out);
+/// // Without function name nor customization cSHAKE is SHAKE.
+/// let plain = CShake128::new(b"", b"").chain(b"abc").finalize_xof().read_vec(32);
+/// assert_eq!(plain, Shake128::new().chain(b"abc").finalize_xof().read_vec(32));
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct CShake<const RATE: usize> {
+    sponge: Sponge,
+    domain: u8,
+}
+
+/// cSHAKE128, 128-bit security.
+pub type CShake128 = CShake<168>;
+/// cSHAKE256, 256-bit security.
+pub type CShake256 = CShake<136>;
+
+impl<const RATE: usize> CShake<RATE> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a hasher for an empty message.
+    ///# Arguments
+    ///* `function_name`: the name of a function defined by NIST on
+    ///  top of cSHAKE, empty otherwise.
+    ///* `customization`: a string chosen by the application.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(function_name: &[u8], customization: &[u8]) -> CShake<RATE> {
+        let mut cshake = CShake {
+            sponge: Sponge::new(RATE),
+            domain: SHAKE_DOMAIN,
+        };
+        if !function_name.is_empty() || !customization.is_empty() {
+            cshake.domain = CSHAKE_DOMAIN;
+            cshake.absorb_bytepad(&[function_name, customization]);
+        }
+        cshake
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Absorbs bytepad(encode_string(s_1) || ... , RATE): the encoded
+    /// strings then zeros up to the end of the block.
+    //////////////////////////////////////////////////////////////////
+    pub(crate) fn absorb_bytepad(&mut self, strings: &[&[u8]]) {
+        self.sponge.absorb(&left_encode(RATE as u64));
+        for s in strings {
+            self.sponge.absorb(&encode_string(s));
+        }
+        self.sponge.fill_block();
+    }
+
+    /// Absorbs `data` into the hash.
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    /// Absorbs `data` and returns the hasher, for one-line hashing.
+    pub fn chain(mut self, data: &[u8]) -> CShake<RATE> {
+        self.update(data);
+        self
+    }
+
+    /// Pads the message and returns the reader of its output.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.sponge.pad(self.domain);
+        XofReader {
+            sponge: self.sponge,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_sha3() {
+        // FIPS 202 examples, checked against Python's hashlib.
+        let a3 = [0xa3; 200];
+        assert_eq!(
+            Sha3_224::digest(b"").to_vec(),
+            hex("6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7")
+        );
+        assert_eq!(
+            Sha3_224::digest(&a3).to_vec(),
+            hex("9376816aba503f72f96ce7eb65ac095deee3be4bf9bbc2a1cb7e11e0")
+        );
+        assert_eq!(
+            Sha3_256::digest(b"abc").to_vec(),
+            hex("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532")
+        );
+        assert_eq!(
+            Sha3_256::digest(&a3).to_vec(),
+            hex("79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787")
+        );
+        assert_eq!(
+            Sha3_384::digest(b"abc").to_vec(),
+            hex(
+                "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b2
+                 98d88cea927ac7f539f1edf228376d25"
+            )
+        );
+        assert_eq!(
+            Sha3_384::digest(&a3).to_vec(),
+            hex(
+                "1881de2ca7e41ef95dc4732b8f5f002b189cc1e42b74168ed1732649ce1dbcdd
+                 76197a31fd55ee989f2d7050dd473e8f"
+            )
+        );
+        assert_eq!(
+            Sha3_512::digest(b"").to_vec(),
+            hex(
+                "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a6
+                 15b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26"
+            )
+        );
+        assert_eq!(
+            Sha3_512::digest(&a3).to_vec(),
+            hex(
+                "e76dfad22084a8b1467fcf2ffa58361bec7628edf5f3fdc0e4805dc48caeeca8
+                 1b7c13c30adf52a3659584739a2df46be589c51ca1a4a8416df6545a1ce8ba00"
+            )
+        );
+        // Split around the 136 bytes block of SHA3-256.
+        let expected = Sha3_256::digest(&a3);
+        for split in [1, 135, 136, 137] {
+            let mut sha3 = Sha3_256::new();
+            sha3.update(&a3[..split]);
+            sha3.update(&a3[split..]);
+            assert_eq!(sha3.finalize(), expected, "split at {}", split);
+        }
+    }
+
+    #[test]
+    fn test_shake() {
+        // FIPS 202 examples, checked against Python's hashlib.
+        assert_eq!(
+            Shake128::new().finalize_xof().read_vec(32),
+            hex("7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26")
+        );
+        assert_eq!(
+            Shake256::new().finalize_xof().read_vec(64),
+            hex(
+                "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f
+                 d75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"
+            )
+        );
+        // The last 32 of 512 bytes, read in uneven pieces.
+        for (out, expected) in [
+            (
+                Shake128::new().chain(&[0xa3; 200]).finalize_xof(),
+                "44c9fb359fd56ac0a9a75a743cff6862f17d7259ab075216c0699511643b6439",
+            ),
+            (
+                Shake256::new().chain(&[0xa3; 200]).finalize_xof(),
+                "6a1a9d7846436e4dca5728b6f760eef0ca92bf0be5615e96959d767197a0beeb",
+            ),
+        ] {
+            let mut reader = out.clone();
+            let mut output = [0_u8; 512];
+            for chunk in output.chunks_mut(100) {
+                reader.read(chunk);
+            }
+            assert_eq!(output[480..].to_vec(), hex(expected));
+            assert_eq!(out.clone().read_vec(512), output);
+        }
+    }
+
+    #[test]
+    fn test_cshake() {
+        // SP 800-185 cSHAKE samples.
+        let data: Vec<u8> = (0..200).collect();
+        assert_eq!(
+            CShake128::new(b"", b"Email Signature")
+                .chain(&data[..4])
+                .finalize_xof()
+                .read_vec(32),
+            hex("c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5")
+        );
+        assert_eq!(
+            CShake128::new(b"", b"Email Signature")
+                .chain(&data)
+                .finalize_xof()
+                .read_vec(32),
+            hex("c5221d50e4f822d96a2e8881a961420f294b7b24fe3d2094baed2c6524cc166b")
+        );
+        assert_eq!(
+            CShake256::new(b"", b"Email Signature")
+                .chain(&data[..4])
+                .finalize_xof()
+                .read_vec(64),
+            hex(
+                "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd1
+                 64020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c"
+            )
+        );
+        assert_eq!(
+            CShake256::new(b"", b"Email Signature")
+                .chain(&data)
+                .finalize_xof()
+                .read_vec(64),
+            hex(
+                "07dc27b11e51fbac75bc7b3c1d983e8b4b85fb1defaf218912ac864302730917
+                 27f42b17ed1df63e8ec118f04b23633c1dfb1574c8fb55cb45da8e25afb092bb"
+            )
+        );
+        // A function name alone, checked against a Python model.
+        assert_eq!(
+            CShake128::new(b"my function", b"")
+                .chain(b"abc")
+                .finalize_xof()
+                .read_vec(32),
+            hex("6f053edc4119dc8144e5651798e74076745a4dd5f843c6b04b30d8f288b021af")
+        );
+    }
+
+    #[test]
+    fn test_encodings() {
+        // SP 800-185 section 2.3.
+        assert_eq!(left_encode(0), [1, 0]);
+        assert_eq!(right_encode(0), [0, 1]);
+        assert_eq!(left_encode(168), [1, 168]);
+        assert_eq!(left_encode(256), [2, 1, 0]);
+        assert_eq!(right_encode(256), [1, 0, 2]);
+        assert_eq!(
+            right_encode(u64::MAX),
+            [[0xff; 8].as_slice(), &[8]].concat()
+        );
+        assert_eq!(encode_string(b""), [1, 0]);
+        assert_eq!(encode_string(b"ab"), [1, 16, b'a', b'b']);
+    }
+}
@@ -0,0 +1,372 @@
+//#######################################################################o
+// Skein is a hash function family built from Threefish with the Unique  |
+// Block Iteration (UBI) chaining mode: each block is encrypted under    |
+// the chaining value with a tweak holding the position, the type of     |
+// the data and first/final flags, then xored with itself. A config      |
+// block selects the output length, an optional key turns it into a MAC  |
+// and the output is produced by a counter mode UBI so any number of     |
+// bytes can be requested. Skein-256, Skein-512 and Skein-1024 are the   |
+// three internal state sizes.                                           |
+//                                                                       |
+// @Refrences:                                                           |
+// Skein 1.3: https://www.schneier.com/wp-content/uploads/2015/01/skein.pdf
+//#######################################################################o
+
+use super::Digest;
+use crate::block::threefish::Threefish;
+use crate::error::CryptoError;
+
+// UBI types of the data.
+const TYPE_KEY: u64 = 0;
+const TYPE_CFG: u64 = 4;
+const TYPE_MSG: u64 = 48;
+const TYPE_OUT: u64 = 63;
+
+const FIRST: u64 = 1 << 62;
+const FINAL: u64 = 1 << 63;
+
+// The schema identifier "SHA3" and the version of the config block.
+const SCHEMA: &[u8; 4] = b"SHA3";
+const VERSION: u16 = 1;
+
+//////////////////////////////////////////////////////////////////////
+/// Processes one block of a UBI chain.
+///# Arguments
+///* `chain`: the chaining value, updated in place.
+///* `block`: up to `8 * W` bytes, zero padded.
+///* `position`: the number of bytes processed including this block.
+///* `flags`: the type and the first/final bits of the tweak.
+//////////////////////////////////////////////////////////////////////
+fn ubi_block<const W: usize>(chain: &mut [u64; W], block: &[u8], position: u128, flags: u64) {
+    let mut m = [0_u64; W];
+    for (i, &b) in block.iter().enumerate() {
+        m[i / 8] |= (b as u64) << (8 * (i % 8));
+    }
+    let tweak = [
+        position as u64,
+        ((position >> 64) as u64 & 0xffffffff) | flags,
+    ];
+    let c = Threefish::from_words(*chain, tweak).encrypt_words(m, tweak);
+    for ((h, c), m) in chain.iter_mut().zip(c).zip(m) {
+        *h = c ^ m;
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Applies UBI to the whole of `data` with the type `ty`.
+//////////////////////////////////////////////////////////////////////
+fn ubi<const W: usize>(chain: &mut [u64; W], data: &[u8], ty: u64) {
+    let block_size = 8 * W;
+    let blocks = data.len().div_ceil(block_size).max(1);
+    let mut position = 0;
+    for i in 0..blocks {
+        let block = &data[i * block_size..data.len().min((i + 1) * block_size)];
+        position += block.len() as u128;
+        let mut flags = ty << 56;
+        if i == 0 {
+            flags |= FIRST;
+        }
+        if i == blocks - 1 {
+            flags |= FINAL;
+        }
+        ubi_block(chain, block, position, flags);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Skein` is a streaming Skein hasher or MAC with a `W` words state,
+/// use `Skein256`, `Skein512` or `Skein1024`.
+///# Example
+/// ```
+/// use cryptolib::hash::skein::Skein512;
+/// use cryptolib::hash::Digest;
+///
+/// let mut skein = Skein512::new(64).unwrap();
+/// skein.update(b"message");
+/// let digest = skein.finalize();
+/// assert_eq!(digest.len(), 64);
+///
+/// let mut mac = Skein512::new_mac(b"key", 32).unwrap();
+/// mac.update(b"message");
+/// assert_eq!(mac.finalize().len(), 32);
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Skein<const W: usize> {
+    chain: [u64; W],
+    // The chaining value after the key and the config, `reset` starts
+    // again from it.
+    initial: [u64; W],
+    // Pending message bytes, the last block is kept until finalize.
+    buffer: [u8; 128],
+    buffer_len: usize,
+    // Message bytes processed so far.
+    position: u128,
+    output_len: usize,
+}
+
+/// Skein-256.
+pub type Skein256 = Skein<4>;
+/// Skein-512.
+pub type Skein512 = Skein<8>;
+/// Skein-1024.
+pub type Skein1024 = Skein<16>;
+
+impl<const W: usize> Skein<W> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a hasher producing `output_len` bytes.
+    ///# Returns
+    /// The hasher or `CryptoError::InvalidParameter` if `output_len`
+    /// is zero.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(output_len: usize) -> Result<Skein<W>, CryptoError> {
+        Self::new_mac(&[], output_len)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a Skein-MAC producing `output_len` bytes.
+    ///# Arguments
+    ///* `key`: the MAC key of any length, empty for plain hashing.
+    ///* `output_len`: the length of the tag in bytes.
+    ///# Returns
+    /// The MAC or `CryptoError::InvalidParameter` if `output_len` is
+    /// zero.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new_mac(key: &[u8], output_len: usize) -> Result<Skein<W>, CryptoError> {
+        if output_len == 0 || output_len > usize::MAX / 8 {
+            return Err(CryptoError::InvalidParameter);
+        }
+        let mut chain = [0_u64; W];
+        if !key.is_empty() {
+            ubi(&mut chain, key, TYPE_KEY);
+        }
+        let mut config = [0_u8; 32];
+        config[..4].copy_from_slice(SCHEMA);
+        config[4..6].copy_from_slice(&VERSION.to_le_bytes());
+        config[8..16].copy_from_slice(&(8 * output_len as u64).to_le_bytes());
+        ubi(&mut chain, &config, TYPE_CFG);
+        Ok(Skein {
+            chain,
+            initial: chain,
+            buffer: [0; 128],
+            buffer_len: 0,
+            position: 0,
+            output_len,
+        })
+    }
+
+    fn process_buffer(&mut self, last: bool) {
+        let first = if self.position == 0 { FIRST } else { 0 };
+        let fin = if last { FINAL } else { 0 };
+        self.position += self.buffer_len as u128;
+        let block = self.buffer;
+        ubi_block(
+            &mut self.chain,
+            &block[..self.buffer_len],
+            self.position,
+            (TYPE_MSG << 56) | first | fin,
+        );
+        self.buffer_len = 0;
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// The default hasher outputs `OUTPUT_SIZE` bytes, the state size, and
+/// `finalize` returns the output length given to `new` or `new_mac`.
+////////////////////////////////////////////////////////////////////////////
+impl<const W: usize> Digest for Skein<W> {
+    const OUTPUT_SIZE: usize = 8 * W;
+    const BLOCK_SIZE: usize = 8 * W;
+
+    type Output = Vec<u8>;
+
+    fn update(&mut self, mut data: &[u8]) {
+        let block_size = Self::BLOCK_SIZE;
+        while !data.is_empty() {
+            // A full buffer is only processed once more data follows.
+            if self.buffer_len == block_size {
+                self.process_buffer(false);
+            }
+            let take = data.len().min(block_size - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+        }
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        self.process_buffer(true);
+        let block_size = Self::BLOCK_SIZE;
+        let mut out = Vec::with_capacity(self.output_len.next_multiple_of(block_size));
+        let mut counter = 0_u64;
+        while out.len() < self.output_len {
+            let mut chain = self.chain;
+            ubi(&mut chain, &counter.to_le_bytes(), TYPE_OUT);
+            out.extend(chain.iter().flat_map(|w| w.to_le_bytes()));
+            counter += 1;
+        }
+        out.truncate(self.output_len);
+        out
+    }
+
+    /// Starts a new message with the same key and output length.
+    fn reset(&mut self) {
+        self.chain = self.initial;
+        self.buffer_len = 0;
+        self.position = 0;
+    }
+}
+
+impl<const W: usize> Default for Skein<W> {
+    fn default() -> Self {
+        Self::new(8 * W).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    // The message FF FE FD ... of `len` bytes used by the known answers.
+    fn message(len: usize) -> Vec<u8> {
+        (0..len).map(|i| 0xff - i as u8).collect()
+    }
+
+    fn skein<const W: usize>(data: &[u8], output_len: usize) -> Vec<u8> {
+        let mut skein = Skein::<W>::new(output_len).unwrap();
+        skein.update(data);
+        skein.finalize()
+    }
+
+    #[test]
+    fn test_skein_256() {
+        // Skein 1.3 Appendix C.1.
+        assert_eq!(
+            skein::<4>(&message(1), 32),
+            hex("0b98dcd198ea0e50a7a244c444e25c23da30c10fc9a1f270a6637f1f34e67ed2")
+        );
+        assert_eq!(
+            skein::<4>(&message(32), 32),
+            hex("8d0fa4ef777fd759dfd4044e6f6a5ac3c774aec943dcfc07927b723b5dbf408b")
+        );
+        assert_eq!(
+            skein::<4>(&message(64), 32),
+            hex("df28e916630d0b44c4a849dc9a02f07a07cb30f732318256b15d865ac4ae162f")
+        );
+    }
+
+    #[test]
+    fn test_skein_512() {
+        // Skein 1.3 Appendix C.2.
+        assert_eq!(
+            skein::<8>(&message(1), 64),
+            hex(
+                "71b7bce6fe6452227b9ced6014249e5bf9a9754c3ad618ccc4e0aae16b316cc8
+                 ca698d864307ed3e80b6ef1570812ac5272dc409b5a012df2a579102f340617a"
+            )
+        );
+        assert_eq!(
+            skein::<8>(&message(64), 64),
+            hex(
+                "45863ba3be0c4dfc27e75d358496f4ac9a736a505d9313b42b2f5eada79fc17f
+                 63861e947afb1d056aa199575ad3f8c9a3cc1780b5e5fa4cae050e989876625b"
+            )
+        );
+        assert_eq!(
+            skein::<8>(&message(128), 64),
+            hex(
+                "91cca510c263c4ddd010530a33073309628631f308747e1bcbaa90e451cab92e
+                 5188087af4188773a332303e6667a7a210856f742139000071f48e8ba2a5adb7"
+            )
+        );
+    }
+
+    #[test]
+    fn test_skein_1024() {
+        // Skein 1.3 Appendix C.3.
+        assert_eq!(
+            skein::<16>(&message(128), 128),
+            hex(
+                "1f3e02c46fb80a3fcd2dfbbc7c173800b40c60c2354af551189ebf433c3d85f9
+                 ff1803e6d920493179ed7ae7fce69c3581a5a2f82d3e0c7a295574d0cd7d217c
+                 484d2f6313d59a7718ead07d0729c24851d7e7d2491b902d489194e6b7d369db
+                 0ab7aa106f0ee0a39a42efc54f18d93776080985f907574f995ec6a37153a578"
+            )
+        );
+        assert_eq!(
+            skein::<16>(&message(256), 128),
+            hex(
+                "842a53c99c12b0cf80cf69491be5e2f7515de8733b6ea9422dfd676665b5fa42
+                 ffb3a9c48c217777950848cecdb48f640f81fb92bef6f88f7a85c1f7cd1446c9
+                 161c0afe8f25ae444f40d3680081c35aa43f640fd5fa3c3c030bcc06abac01d0
+                 98bcc984ebd8322712921e00b1ba07d6d01f26907050255ef2c8e24f716c52a5"
+            )
+        );
+    }
+
+    #[test]
+    fn test_skein_streaming() {
+        let data = message(300);
+        for output_len in [1, 20, 64, 65, 200] {
+            let expected = skein::<8>(&data, output_len);
+            assert_eq!(expected.len(), output_len);
+            for split in [0, 1, 63, 64, 65, 128, 299] {
+                let mut skein = Skein512::new(output_len).unwrap();
+                skein.update(&data[..split]);
+                skein.update(&[]);
+                skein.update(&data[split..]);
+                assert_eq!(skein.finalize(), expected, "split at {}", split);
+            }
+        }
+        // The output length is part of the config, not a truncation.
+        assert_ne!(skein::<8>(&data, 32)[..], skein::<8>(&data, 64)[..32]);
+    }
+
+    #[test]
+    fn test_skein_mac() {
+        // Keyed known answers, checked against an independent model of
+        // the specification which gives the Appendix C digests.
+        let key: Vec<u8> = (0..64).collect();
+        let mut mac = Skein256::new_mac(&key[..32], 32).unwrap();
+        mac.update(&message(64));
+        assert_eq!(
+            mac.finalize(),
+            hex("555776c582e6a575ca54c3777eda51aaba9069f9fc1eb9420de220d8c39f75d2")
+        );
+        let mut mac = Skein512::new_mac(&key, 64).unwrap();
+        mac.update(&message(128));
+        assert_eq!(
+            mac.finalize(),
+            hex(
+                "bcf2fdcd78f354d82489246702427d263a5704470c8d6ab32e592ef10243490e
+                 7c2c3117b3b8957b09d516d19cdea483bf7b888f9447f3af644cebca0925de5f"
+            )
+        );
+        let mut mac = Skein512::new_mac(&key[..16], 64).unwrap();
+        mac.update(&message(1));
+        assert_eq!(
+            mac.finalize(),
+            hex(
+                "beec5bdd37e3024a3013df8be430ee83f3d48f320f0784004af926a53ff55939
+                 8ae1053dad5e2ff5963c5c999445a314f112acd8e84621f7cd983b94c5965c30"
+            )
+        );
+
+        let mut mac = Skein256::new_mac(b"key", 32).unwrap();
+        mac.update(b"message");
+        let tag = mac.finalize();
+        assert_ne!(tag, skein::<4>(b"message", 32));
+        // Reset keeps the key and the output length.
+        let mut mac = Skein256::new_mac(b"key", 32).unwrap();
+        mac.update(b"other message");
+        mac.reset();
+        mac.update(b"message");
+        assert_eq!(mac.finalize(), tag);
+        let mut mac = Skein256::new_mac(b"kex", 32).unwrap();
+        mac.update(b"message");
+        assert_ne!(mac.finalize(), tag);
+        assert_eq!(Skein512::digest(b"message"), skein::<8>(b"message", 64));
+        assert_eq!(Skein1024::new(0).err(), Some(CryptoError::InvalidParameter));
+    }
+}
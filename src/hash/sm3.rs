@@ -0,0 +1,173 @@
+//#######################################################################o
+// SM3 is the Chinese national standard hash function (GB/T 32905-2016). |
+// It's a Merkle-Damgard construction like SHA-256: the message is       |
+// padded with a 1 bit, zeros and its 64-bit length, then processed in   |
+// 512-bit blocks by a compression function of 64 rounds on eight        |
+// 32-bit words, giving a 256-bit digest. The message schedule expands   |
+// each block to 132 words.                                              |
+//                                                                       |
+// @Refrences:                                                           |
+// GB/T 32905-2016: https://openstd.samr.gov.cn/bzgk/gb/newGbInfo?hcno=45B1A67F20F3BF339211C391E9278F5E
+// draft-sca-cfrg-sm3: https://datatracker.ietf.org/doc/html/draft-sca-cfrg-sm3-02
+//#######################################################################o
+
+use super::{BlockBuffer, Digest};
+
+// Initial hash value.
+const IV: [u32; 8] = [
+    0x7380166f, 0x4914b2b9, 0x172442d7, 0xda8a0600, 0xa96f30bc, 0x163138aa, 0xe38dee4d, 0xb0fb0e4e,
+];
+
+// Round constants of the first 16 rounds and of the last 48.
+const T0: u32 = 0x79cc4519;
+const T1: u32 = 0x7a879d8a;
+
+fn p0(x: u32) -> u32 {
+    x ^ x.rotate_left(9) ^ x.rotate_left(17)
+}
+
+fn p1(x: u32) -> u32 {
+    x ^ x.rotate_left(15) ^ x.rotate_left(23)
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function, updates `state` with a 64 bytes block.
+//////////////////////////////////////////////////////////////////////
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0_u32; 68];
+    for (w, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for j in 16..68 {
+        w[j] = p1(w[j - 16] ^ w[j - 9] ^ w[j - 3].rotate_left(15))
+            ^ w[j - 13].rotate_left(7)
+            ^ w[j - 6];
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for j in 0..64 {
+        let t = if j < 16 { T0 } else { T1 };
+        let ss1 = a
+            .rotate_left(12)
+            .wrapping_add(e)
+            .wrapping_add(t.rotate_left(j as u32 % 32))
+            .rotate_left(7);
+        let ss2 = ss1 ^ a.rotate_left(12);
+        let (ff, gg) = if j < 16 {
+            (a ^ b ^ c, e ^ f ^ g)
+        } else {
+            ((a & b) | (a & c) | (b & c), (e & f) | (!e & g))
+        };
+        let tt1 = ff
+            .wrapping_add(d)
+            .wrapping_add(ss2)
+            .wrapping_add(w[j] ^ w[j + 4]);
+        let tt2 = gg.wrapping_add(h).wrapping_add(ss1).wrapping_add(w[j]);
+        d = c;
+        c = b.rotate_left(9);
+        b = a;
+        a = tt1;
+        h = g;
+        g = f.rotate_left(19);
+        f = e;
+        e = p0(tt2);
+    }
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s ^= v;
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Sm3` is a streaming SM3 hasher.
+///# Example
+/// ```
+/// use cryptolib::hash::sm3::Sm3;
+/// use cryptolib::hash::Digest;
+///
+/// let mut sm3 = Sm3::new();
+/// sm3.update(b"a");
+/// sm3.update(b"bc");
+/// assert_eq!(sm3.finalize(), Sm3::digest(b"abc"));
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Sm3 {
+    state: [u32; 8],
+    buffer: BlockBuffer<64>,
+}
+
+impl Default for Sm3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sm3 {
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Sm3 {
+        Sm3 {
+            state: IV,
+            buffer: BlockBuffer::new(),
+        }
+    }
+}
+
+impl Digest for Sm3 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = [u8; 32];
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| compress(state, block));
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        let state = &mut self.state;
+        self.buffer.pad(8, true, |block| compress(state, block));
+        let mut out = [0_u8; 32];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_sm3_standard() {
+        // GB/T 32905-2016 Appendix A.
+        assert_eq!(
+            Sm3::digest(b"abc").to_vec(),
+            hex("66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0")
+        );
+        assert_eq!(
+            Sm3::digest(&b"abcd".repeat(16)).to_vec(),
+            hex("debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732")
+        );
+    }
+
+    #[test]
+    fn test_sm3_streaming() {
+        // Checked against OpenSSL.
+        assert_eq!(
+            Sm3::digest(b"").to_vec(),
+            hex("1ab21d8355cfa17f8e61194831e81a8f22bec8c728fefb747ed035eb5082aa2b")
+        );
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let expected = hex("e1043d6f7910a57e49c10eb042760c060d07ea26866cb067cc5eecb42f9056a3");
+        assert_eq!(Sm3::digest(&data).to_vec(), expected);
+        for split in [1, 55, 56, 63, 64, 65, 500] {
+            let mut sm3 = Sm3::new();
+            sm3.update(&data[..split]);
+            sm3.update(&[]);
+            sm3.update(&data[split..]);
+            assert_eq!(sm3.finalize().to_vec(), expected, "split at {}", split);
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//#######################################################################o
+// SP 800-185 derives functions from cSHAKE, each with its own function  |
+// name and an optional customization string. TupleHash hashes a tuple   |
+// of strings, each one encoded with its length so that ("ab", "c") and  |
+// ("a", "bc") give different digests. ParallelHash splits the message   |
+// in blocks of B bytes hashed independently with SHAKE, their chaining  |
+// values being hashed by cSHAKE, so the blocks can be processed in      |
+// parallel. The requested output length is absorbed last, the XOF       |
+// variants absorb a length of zero and read any length instead.         |
+//                                                                       |
+// @Refrences:                                                           |
+// SP 800-185: https://doi.org/10.6028/NIST.SP.800-185                   |
+//#######################################################################o
+
+use super::sha3::{left_encode, right_encode, CShake, Shake, XofReader};
+use crate::error::CryptoError;
+
+////////////////////////////////////////////////////////////////////////////
+/// `TupleHash` hashes a tuple of strings, each `update` adding one
+/// element. Use the `TupleHash128` and `TupleHash256` aliases.
+///# Example
+/// ```
+/// use cryptolib::hash::sp800_185::TupleHash128;
+///
+/// let mut hasher = TupleHash128::new(b"My Tuple App");
+/// hasher.update(b"ab");
+/// hasher.update(b"c");
+/// let mut other = TupleHash128::new(b"My Tuple App");
+/// other.update(b"a");
+/// other.update(b"bc");
+/// assert_ne!(hasher.finalize(32), other.finalize(32));
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct TupleHash<const RATE: usize> {
+    cshake: CShake<RATE>,
+}
+
+/// TupleHash128, 128-bit security.
+pub type TupleHash128 = TupleHash<168>;
+/// TupleHash256, 256-bit security.
+pub type TupleHash256 = TupleHash<136>;
+
+impl<const RATE: usize> TupleHash<RATE> {
+    /// Creates a hasher for an empty tuple.
+    pub fn new(customization: &[u8]) -> TupleHash<RATE> {
+        TupleHash {
+            cshake: CShake::new(b"TupleHash", customization),
+        }
+    }
+
+    /// Appends the element `data` to the tuple.
+    pub fn update(&mut self, data: &[u8]) {
+        self.cshake.update(&left_encode(8 * data.len() as u64));
+        self.cshake.update(data);
+    }
+
+    /// Returns the `out_len` bytes digest of the tuple.
+    pub fn finalize(mut self, out_len: usize) -> Vec<u8> {
+        self.cshake.update(&right_encode(8 * out_len as u64));
+        self.cshake.finalize_xof().read_vec(out_len)
+    }
+
+    /// Returns the reader of TupleHashXOF.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.cshake.update(&right_encode(0));
+        self.cshake.finalize_xof()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `ParallelHash` hashes a message in blocks of a chosen size, use the
+/// `ParallelHash128` and `ParallelHash256` aliases.
+///# Example
+/// ```
+/// use cryptolib::hash::sp800_185::ParallelHash128;
+///
+/// let mut hasher = ParallelHash128::new(b"", 8).unwrap();
+/// hasher.update(b"any length");
+/// hasher.update(b" in pieces");
+/// let mut other = ParallelHash128::new(b"", 8).unwrap();
+/// other.update(b"any length in pieces");
+/// assert_eq!(hasher.finalize(32), other.finalize(32));
+/// ```
+///# Note
+/// '''
+/// The blocks are hashed one after the other, the digest is the same
+/// as the one of a parallel implementation.
+/// '''
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct ParallelHash<const RATE: usize> {
+    cshake: CShake<RATE>,
+    block_size: usize,
+    // Pending bytes of the current block.
+    buffer: Vec<u8>,
+    blocks: u64,
+}
+
+/// ParallelHash128, 128-bit security.
+pub type ParallelHash128 = ParallelHash<168>;
+/// ParallelHash256, 256-bit security.
+pub type ParallelHash256 = ParallelHash<136>;
+
+impl<const RATE: usize> ParallelHash<RATE> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates a hasher for an empty message.
+    ///# Arguments
+    ///* `customization`: a string chosen by the application.
+    ///* `block_size`: the size B of the blocks in bytes.
+    ///# Returns
+    /// The hasher or `CryptoError::InvalidParameter` if `block_size`
+    /// is zero.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(customization: &[u8], block_size: usize) -> Result<ParallelHash<RATE>, CryptoError> {
+        if block_size == 0 {
+            return Err(CryptoError::InvalidParameter);
+        }
+        let mut cshake = CShake::new(b"ParallelHash", customization);
+        cshake.update(&left_encode(block_size as u64));
+        Ok(ParallelHash {
+            cshake,
+            block_size,
+            buffer: Vec::with_capacity(block_size),
+            blocks: 0,
+        })
+    }
+
+    // Absorbs the chaining value of a block, twice the security level.
+    fn hash_block(&mut self, block: &[u8]) {
+        let chaining = Shake::<RATE>::new()
+            .chain(block)
+            .finalize_xof()
+            .read_vec(200 - RATE);
+        self.cshake.update(&chaining);
+        self.blocks += 1;
+    }
+
+    /// Absorbs `data` into the hash.
+    pub fn update(&mut self, mut data: &[u8]) {
+        if !self.buffer.is_empty() {
+            let take = data.len().min(self.block_size - self.buffer.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.buffer.len() < self.block_size {
+                return;
+            }
+            let block = std::mem::take(&mut self.buffer);
+            self.hash_block(&block);
+        }
+        let mut blocks = data.chunks_exact(self.block_size);
+        for block in &mut blocks {
+            self.hash_block(block);
+        }
+        self.buffer.extend_from_slice(blocks.remainder());
+    }
+
+    // Hashes the last partial block and absorbs the block count and the
+    // output length in bits.
+    fn finish(mut self, out_bits: u64) -> XofReader {
+        if !self.buffer.is_empty() {
+            let block = std::mem::take(&mut self.buffer);
+            self.hash_block(&block);
+        }
+        self.cshake.update(&right_encode(self.blocks));
+        self.cshake.update(&right_encode(out_bits));
+        self.cshake.finalize_xof()
+    }
+
+    /// Returns the `out_len` bytes digest of the message.
+    pub fn finalize(self, out_len: usize) -> Vec<u8> {
+        self.finish(8 * out_len as u64).read_vec(out_len)
+    }
+
+    /// Returns the reader of ParallelHashXOF.
+    pub fn finalize_xof(self) -> XofReader {
+        self.finish(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    fn tuple_hash128(elements: &[&[u8]], customization: &[u8]) -> Vec<u8> {
+        let mut hasher = TupleHash128::new(customization);
+        for element in elements {
+            hasher.update(element);
+        }
+        hasher.finalize(32)
+    }
+
+    #[test]
+    fn test_tuple_hash() {
+        // SP 800-185 TupleHash samples.
+        let (e3, e6, e9) = (
+            hex("000102"),
+            hex("101112131415"),
+            hex("202122232425262728"),
+        );
+        assert_eq!(
+            tuple_hash128(&[&e3, &e6], b""),
+            hex("c5d8786c1afb9b82111ab34b65b2c0048fa64e6d48e263264ce1707d3ffc8ed1")
+        );
+        assert_eq!(
+            tuple_hash128(&[&e3, &e6], b"My Tuple App"),
+            hex("75cdb20ff4db1154e841d758e24160c54bae86eb8c13e7f5f40eb35588e96dfb")
+        );
+        assert_eq!(
+            tuple_hash128(&[&e3, &e6, &e9], b"My Tuple App"),
+            hex("e60f202c89a2631eda8d4c588ca5fd07f39e5151998deccf973adb3804bb6e84")
+        );
+        let mut hasher = TupleHash256::new(b"");
+        hasher.update(&e3);
+        hasher.update(&e6);
+        assert_eq!(
+            hasher.finalize(64),
+            hex(
+                "cfb7058caca5e668f81a12a20a2195ce97a925f1dba3e7449a56f82201ec6073
+                 11ac2696b1ab5ea2352df1423bde7bd4bb78c9aed1a853c78672f9eb23bbe194"
+            )
+        );
+
+        // Checked against a Python model: TupleHashXOF and empty elements
+        // which still count.
+        let mut hasher = TupleHash128::new(b"My Tuple App");
+        hasher.update(&e3);
+        hasher.update(&e6);
+        assert_eq!(
+            hasher.finalize_xof().read_vec(32),
+            hex("3fc8ad69453128292859a18b6c67d7ad85f01b32815e22ce839c49ec374e9b9a")
+        );
+        assert_eq!(
+            tuple_hash128(&[], b""),
+            hex("786aa3d4fcaadf0aa723a4818a1a72de2330d613e5de7ae4eb6cb4cdd26adba2")
+        );
+        assert_eq!(
+            tuple_hash128(&[b""], b""),
+            hex("549330469327c593eb95b1d467c48e5781939e135e10632c804ef8a69c73281c")
+        );
+        assert_eq!(
+            tuple_hash128(&[b"", b""], b""),
+            hex("bba3b0b0d207713b1c507afca7c64492e6a0b43b7d76b1b3ad593a5ab0fa98ac")
+        );
+    }
+
+    #[test]
+    fn test_parallel_hash() {
+        // SP 800-185 ParallelHash samples.
+        let data = hex("000102030405060710111213141516172021222324252627");
+        let mut hasher = ParallelHash128::new(b"", 8).unwrap();
+        hasher.update(&data);
+        assert_eq!(
+            hasher.finalize(32),
+            hex("ba8dc1d1d979331d3f813603c67f72609ab5e44b94a0b8f9af46514454a2b4f5")
+        );
+        let mut hasher = ParallelHash128::new(b"Parallel Data", 8).unwrap();
+        hasher.update(&data[..13]);
+        hasher.update(&data[13..]);
+        assert_eq!(
+            hasher.finalize(32),
+            hex("fc484dcb3f84dceedc353438151bee58157d6efed0445a81f165e495795b7206")
+        );
+        let mut hasher = ParallelHash256::new(b"Parallel Data", 8).unwrap();
+        hasher.update(&data);
+        assert_eq!(
+            hasher.finalize(64),
+            hex(
+                "cdf15289b54f6212b4bc270528b49526006dd9b54e2b6add1ef6900dda3963bb
+                 33a72491f236969ca8afaea29c682d47a393c065b38e29fae651a2091c833110"
+            )
+        );
+
+        // Checked against a Python model: ParallelHashXOF, a partial last
+        // block and an empty message.
+        let mut hasher = ParallelHash128::new(b"Parallel Data", 8).unwrap();
+        hasher.update(&data);
+        assert_eq!(
+            hasher.finalize_xof().read_vec(32),
+            hex("ea2a793140820f7a128b8eb70a9439f93257c6e6e79b4a540d291d6dae7098d7")
+        );
+        let data: Vec<u8> = (0..1000).map(|i| (i % 251) as u8).collect();
+        let expected = hex("6b0da4c1f571ee3039a4c2b7c5741578869cfbfceac59a98dbbb36a28dec236c");
+        for split in [0, 1, 63, 64, 65, 999] {
+            let mut hasher = ParallelHash128::new(b"", 64).unwrap();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.finalize(32), expected, "split at {}", split);
+        }
+        assert_eq!(
+            ParallelHash128::new(b"", 8).unwrap().finalize(32),
+            hex("96427c30224408859f95e89e4fa84e1c7a1478dbf2008ac982ce61a77f37a272")
+        );
+        assert_eq!(
+            ParallelHash128::new(b"", 0).err(),
+            Some(CryptoError::InvalidParameter)
+        );
+    }
+}
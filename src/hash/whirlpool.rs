@@ -0,0 +1,279 @@
+//#######################################################################o
+// Whirlpool is the 512-bit hash function of Barreto and Rijmen, adopted |
+// by ISO/IEC 10118-3 and NESSIE. It's a Miyaguchi-Preneel construction  |
+// on W, an AES-like 512-bit block cipher: the state is an 8x8 matrix of |
+// bytes transformed by 10 rounds of S-box, shifting of the columns,     |
+// multiplication of the rows by a circulant MDS matrix over GF(2^8) and |
+// addition of the round key, the key schedule being W itself with round |
+// constants as keys. The message is padded with a 1 bit, zeros and its  |
+// 256-bit length (big-endian). This is the final version (2003), not    |
+// Whirlpool-0 or Whirlpool-T which have another S-box or matrix.        |
+//                                                                       |
+// @Refrences:                                                           |
+// Whirlpool: https://web.archive.org/web/20171129084214/http://www.larc.usp.br/~pbarreto/WhirlpoolPage.html
+// ISO/IEC 10118-3: https://www.iso.org/standard/67116.html              |
+//#######################################################################o
+
+use super::{BlockBuffer, Digest};
+
+const ROUNDS: usize = 10;
+
+// The S-box, built from the 4-bit mini boxes E, E^-1 and R.
+const SBOX: [u8; 256] = {
+    const E: [u8; 16] = [1, 11, 9, 12, 13, 6, 15, 3, 14, 8, 7, 4, 10, 2, 5, 0];
+    const R: [u8; 16] = [7, 12, 11, 13, 14, 4, 9, 15, 6, 3, 8, 10, 2, 5, 1, 0];
+    let mut e_inv = [0_u8; 16];
+    let mut i = 0;
+    while i < 16 {
+        e_inv[E[i] as usize] = i as u8;
+        i += 1;
+    }
+    let mut sbox = [0_u8; 256];
+    let mut x = 0;
+    while x < 256 {
+        let u = E[x >> 4];
+        let l = e_inv[x & 15];
+        let r = R[(u ^ l) as usize];
+        sbox[x] = (E[(u ^ r) as usize] << 4) | e_inv[(l ^ r) as usize];
+        x += 1;
+    }
+    sbox
+};
+
+// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1.
+const fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        a = (a << 1) ^ if a & 0x80 != 0 { 0x1d } else { 0 };
+        b >>= 1;
+    }
+    product
+}
+
+// T[t][x] is the row contribution of the byte x of column t: the S-box
+// output multiplied by the circulant matrix cir(1, 1, 4, 1, 8, 5, 2, 9),
+// rotated by t bytes.
+const T: [[u64; 256]; 8] = {
+    const MDS: [u8; 8] = [1, 1, 4, 1, 8, 5, 2, 9];
+    let mut table = [[0_u64; 256]; 8];
+    let mut x = 0;
+    while x < 256 {
+        let mut row = 0_u64;
+        let mut j = 0;
+        while j < 8 {
+            row = (row << 8) | gf_mul(SBOX[x], MDS[j]) as u64;
+            j += 1;
+        }
+        let mut t = 0;
+        while t < 8 {
+            table[t][x] = row.rotate_right(8 * t as u32);
+            t += 1;
+        }
+        x += 1;
+    }
+    table
+};
+
+// One round of W on the rows of `state` with the round key `key`.
+fn round(state: &[u64; 8], key: &[u64; 8]) -> [u64; 8] {
+    let mut out = *key;
+    for (i, out) in out.iter_mut().enumerate() {
+        for (t, table) in T.iter().enumerate() {
+            *out ^= table[(state[(i + 8 - t) % 8] >> (56 - 8 * t)) as usize & 0xff];
+        }
+    }
+    out
+}
+
+//////////////////////////////////////////////////////////////////////
+/// The compression function, updates `state` with a 64 bytes block:
+/// the block is encrypted by W keyed with the state, then added to it
+/// with the block.
+//////////////////////////////////////////////////////////////////////
+fn compress(state: &mut [u64; 8], block: &[u8]) {
+    let mut m = [0_u64; 8];
+    for (m, bytes) in m.iter_mut().zip(block.chunks_exact(8)) {
+        *m = u64::from_be_bytes(bytes.try_into().unwrap());
+    }
+    let mut key = *state;
+    let mut w = [0_u64; 8];
+    for i in 0..8 {
+        w[i] = m[i] ^ key[i];
+    }
+    for r in 0..ROUNDS {
+        // The round constant is the next 8 bytes of the S-box on the
+        // first row.
+        let mut constant = [0_u64; 8];
+        constant[0] = u64::from_be_bytes(SBOX[8 * r..8 * r + 8].try_into().unwrap());
+        key = round(&key, &constant);
+        w = round(&w, &key);
+    }
+    for i in 0..8 {
+        state[i] ^= w[i] ^ m[i];
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+/// `Whirlpool` is a streaming Whirlpool hasher.
+///# Example
+/// ```
+/// use cryptolib::hash::whirlpool::Whirlpool;
+/// use cryptolib::hash::Digest;
+///
+/// let mut whirlpool = Whirlpool::new();
+/// whirlpool.update(b"a");
+/// whirlpool.update(b"bc");
+/// assert_eq!(whirlpool.finalize(), Whirlpool::digest(b"abc"));
+/// ```
+///////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Whirlpool {
+    state: [u64; 8],
+    buffer: BlockBuffer<64>,
+}
+
+impl Default for Whirlpool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Whirlpool {
+    /// Creates a hasher for an empty message.
+    pub fn new() -> Whirlpool {
+        Whirlpool {
+            state: [0; 8],
+            buffer: BlockBuffer::new(),
+        }
+    }
+}
+
+impl Digest for Whirlpool {
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 64;
+
+    type Output = [u8; 64];
+
+    fn update(&mut self, data: &[u8]) {
+        let state = &mut self.state;
+        self.buffer.update(data, |block| compress(state, block));
+    }
+
+    fn finalize(mut self) -> [u8; 64] {
+        let state = &mut self.state;
+        self.buffer.pad(32, true, |block| compress(state, block));
+        let mut out = [0_u8; 64];
+        for (bytes, word) in out.chunks_exact_mut(8).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_sbox() {
+        // The first and last entries of the table of the specification.
+        assert_eq!(SBOX[..8], [0x18, 0x23, 0xc6, 0xe8, 0x87, 0xb8, 0x01, 0x4f]);
+        assert_eq!(
+            SBOX[248..],
+            [0xcc, 0x42, 0x98, 0xa4, 0x28, 0x5c, 0xf8, 0x86]
+        );
+        assert_eq!(T[0][0], 0x18186018c07830d8);
+    }
+
+    #[test]
+    fn test_whirlpool_iso() {
+        // ISO/IEC 10118-3 test vectors of the reference implementation.
+        for (data, expected) in [
+            (
+                "",
+                "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a7
+                 3e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3",
+            ),
+            (
+                "a",
+                "8aca2602792aec6f11a67206531fb7d7f0dff59413145e6973c45001d0087b42
+                 d11bc645413aeff63a42391a39145a591a92200d560195e53b478584fdae231a",
+            ),
+            (
+                "abc",
+                "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c
+                 7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5",
+            ),
+            (
+                "message digest",
+                "378c84a4126e2dc6e56dcc7458377aac838d00032230f53ce1f5700c0ffb4d3b
+                 8421557659ef55c106b4b52ac5a4aaa692ed920052838f3362e86dbd37a8903e",
+            ),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "f1d754662636ffe92c82ebb9212a484a8d38631ead4238f5442ee13b8054e41b
+                 08bf2a9251c30b6a0b8aae86177ab4a6f68f673e7207865d5d9819a3dba4eb3b",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "dc37e008cf9ee69bf11f00ed9aba26901dd7c28cdec066cc6af42e40f82f3a1e
+                 08eba26629129d8fb7cb57211b9281a65517cc879d7b962142c65f5a7af01467",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "466ef18babb0154d25b9d38a6414f5c08784372bccb204d6549c4afadb601429
+                 4d5bd8df2a6c44e538cd047b2681a51a2c60481e88c5a20b2c2a80cf3a9a083b",
+            ),
+            (
+                "abcdbcdecdefdefgefghfghighijhijk",
+                "2a987ea40f917061f5d6f0a0e4644f488a7a5a52deee656207c562f988e95c69
+                 16bdc8031bc5be1b7b947639fe050b56939baaa0adff9ae6745b7b181c3be3fd",
+            ),
+        ] {
+            assert_eq!(
+                Whirlpool::digest(data.as_bytes()).to_vec(),
+                hex(expected),
+                "{}",
+                data
+            );
+        }
+        let mut whirlpool = Whirlpool::new();
+        for _ in 0..1000 {
+            whirlpool.update(&[b'a'; 1000]);
+        }
+        assert_eq!(
+            whirlpool.finalize().to_vec(),
+            hex(
+                "0c99005beb57eff50a7cf005560ddf5d29057fd86b20bfd62deca0f1ccea4af5
+                 1fc15490eddc47af32bb2b66c34ff9ad8c6008ad677f77126953b226e4ed8b01"
+            )
+        );
+    }
+
+    #[test]
+    fn test_whirlpool_streaming() {
+        // Checked against OpenSSL.
+        let data: Vec<u8> = (0..1000).map(|i| (i % 251) as u8).collect();
+        let expected = hex(
+            "9780c3fa4c818db4ad07280667e910203f2c01fcb3980a059ab227d0468f8da0
+             ebe26629da70082750698549ef20ac643d8309058597184b5a9516c1a682f14e",
+        );
+        for split in [0, 1, 31, 32, 63, 64, 65, 500] {
+            let mut whirlpool = Whirlpool::new();
+            whirlpool.update(b"discarded");
+            whirlpool.reset();
+            whirlpool.update(&data[..split]);
+            whirlpool.update(&data[split..]);
+            assert_eq!(
+                whirlpool.finalize().to_vec(),
+                expected,
+                "split at {}",
+                split
+            );
+        }
+    }
+}
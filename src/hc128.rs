@@ -0,0 +1,236 @@
+//#######################################################################o
+// HC-128 is Hongjun Wu's software oriented stream cipher from the       |
+// eSTREAM portfolio, with a 128-bit key and a 128-bit IV. Its state is  |
+// two secret tables P and Q of 512 32-bit words, initialized by a       |
+// SHA-256 like expansion of the key and the IV and 1024 steps of the    |
+// cipher. Each step updates one table entry with nonlinear feedback and |
+// outputs it masked through the other table.                            |
+// The tables evolve nonlinearly, seeking replays the keystream.         |
+//                                                                       |
+// @Refrences:                                                           |
+// Wu: https://www.ecrypt.eu.org/stream/p3ciphers/hc/hc128_p3.pdf        |
+//#######################################################################o
+
+use crate::error::CryptoError;
+use crate::stream::{advance, skip, StreamCipher};
+
+fn f1(x: u32) -> u32 {
+    x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
+}
+
+fn f2(x: u32) -> u32 {
+    x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Hc128` is a keyed HC-128 keystream, encryption and decryption are
+/// the same operation.
+///# Example
+/// ```
+/// use cryptolib::hc128::Hc128;
+/// use cryptolib::stream::StreamCipher;
+///
+/// let mut data = *b"any length";
+/// let mut hc = Hc128::new(&[1; 16], &[2; 16]).unwrap();
+/// hc.apply_keystream(&mut data).unwrap();
+/// hc.seek(0).unwrap();
+/// hc.apply_keystream(&mut data).unwrap();
+/// assert_eq!(&data, b"any length");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Hc128 {
+    p: [u32; 512],
+    q: [u32; 512],
+    // Step counter modulo 1024.
+    step: usize,
+    // The last keystream word and how many of its bytes were used.
+    keystream: [u8; 4],
+    used: usize,
+    // The initialized tables, seeking backwards starts again from them.
+    start: Box<([u32; 512], [u32; 512])>,
+    pos: u64,
+}
+
+impl Hc128 {
+    ////////////////////////////////////////////////////////////////////
+    /// Initializes the cipher with a 16 bytes `key` and a 16 bytes `iv`.
+    ///# Returns
+    /// The keystream, `CryptoError::InvalidKeyLength` or
+    /// `CryptoError::InvalidNonceLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8], iv: &[u8]) -> Result<Hc128, CryptoError> {
+        if key.len() != 16 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        if iv.len() != 16 {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        let mut w = [0_u32; 1280];
+        for (i, bytes) in key.chunks_exact(4).chain(iv.chunks_exact(4)).enumerate() {
+            let word = u32::from_le_bytes(bytes.try_into().unwrap());
+            // K[i + 4] = K[i] and IV[i + 4] = IV[i].
+            w[i + 4 * (i / 4)] = word;
+            w[i + 4 * (i / 4) + 4] = word;
+        }
+        for i in 16..1280 {
+            w[i] = f2(w[i - 2])
+                .wrapping_add(w[i - 7])
+                .wrapping_add(f1(w[i - 15]))
+                .wrapping_add(w[i - 16])
+                .wrapping_add(i as u32);
+        }
+        let mut hc = Hc128 {
+            p: w[256..768].try_into().unwrap(),
+            q: w[768..1280].try_into().unwrap(),
+            step: 0,
+            keystream: [0; 4],
+            used: 4,
+            start: Box::new(([0; 512], [0; 512])),
+            pos: 0,
+        };
+        // The outputs of the first 1024 steps replace the table entries.
+        for step in 0..1024 {
+            let s = hc.next_word();
+            if step < 512 {
+                hc.p[step] = s;
+            } else {
+                hc.q[step - 512] = s;
+            }
+        }
+        hc.start = Box::new((hc.p, hc.q));
+        Ok(hc)
+    }
+
+    fn next_word(&mut self) -> u32 {
+        let j = self.step % 512;
+        let at = |k: usize| (j + 512 - k) % 512;
+        let s = if self.step < 512 {
+            let (x, y, z) = (self.p[at(3)], self.p[at(10)], self.p[at(511)]);
+            let g1 = (x.rotate_right(10) ^ z.rotate_right(23)).wrapping_add(y.rotate_right(8));
+            self.p[j] = self.p[j].wrapping_add(g1);
+            let u = self.p[at(12)].to_le_bytes();
+            let h1 = self.q[u[0] as usize].wrapping_add(self.q[256 + u[2] as usize]);
+            h1 ^ self.p[j]
+        } else {
+            let (x, y, z) = (self.q[at(3)], self.q[at(10)], self.q[at(511)]);
+            let g2 = (x.rotate_left(10) ^ z.rotate_left(23)).wrapping_add(y.rotate_left(8));
+            self.q[j] = self.q[j].wrapping_add(g2);
+            let u = self.q[at(12)].to_le_bytes();
+            let h2 = self.p[u[0] as usize].wrapping_add(self.p[256 + u[2] as usize]);
+            h2 ^ self.q[j]
+        };
+        self.step = (self.step + 1) % 1024;
+        s
+    }
+}
+
+impl StreamCipher for Hc128 {
+    fn apply_keystream(&mut self, data: &mut [u8]) -> Result<(), CryptoError> {
+        self.pos = advance(self.pos, data.len(), None)?;
+        for b in data {
+            if self.used == 4 {
+                self.keystream = self.next_word().to_le_bytes();
+                self.used = 0;
+            }
+            *b ^= self.keystream[self.used];
+            self.used += 1;
+        }
+        Ok(())
+    }
+
+    /// Seeking backwards replays the keystream from the start.
+    fn seek(&mut self, pos: u64) -> Result<(), CryptoError> {
+        if pos < self.pos {
+            (self.p, self.q) = *self.start;
+            self.step = 0;
+            self.used = 4;
+            self.pos = 0;
+        }
+        skip(self, pos - self.pos)
+    }
+
+    fn current_pos(&self) -> u64 {
+        self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    fn keystream(key: &[u8], iv: &[u8], len: usize) -> Vec<u8> {
+        let mut out = vec![0; len];
+        Hc128::new(key, iv)
+            .unwrap()
+            .apply_keystream(&mut out)
+            .unwrap();
+        out
+    }
+
+    // The test vectors of the specification are printed as 32-bit words.
+    fn words(s: &str) -> Vec<u8> {
+        hex(s)
+            .chunks_exact(4)
+            .flat_map(|w| [w[3], w[2], w[1], w[0]])
+            .collect()
+    }
+
+    #[test]
+    fn test_hc128_spec() {
+        // Section 2.4 of the specification.
+        let mut key = [0_u8; 16];
+        let mut iv = [0_u8; 16];
+        assert_eq!(
+            keystream(&key, &iv, 64),
+            words(
+                "731500823bfd03a0fb2fd77faa63af0ede122fc6a7dc29b662a685278b75ec68
+                 9036db1e8189600500ade078491fbf9a1cdc30136c3d6e2490f664b29cd57102"
+            )
+        );
+        iv[0] = 1;
+        assert_eq!(
+            keystream(&key, &iv, 64),
+            words(
+                "c01893d5b7dbe9588f65ec986417660436fc6724c82c6eec1b1c38a7c9b42a95
+                 323ef1230a6a908bce757b689f14f7bbe4cde011aeb5173f89608c94b5cf46ca"
+            )
+        );
+        key[0] = 0x55;
+        assert_eq!(
+            keystream(&key, &[0; 16], 64),
+            words(
+                "518251a404b4930ab02af9310639f032bcb4a47a5722480b2bf99f72cdc0e566
+                 310f0c56d3cc83e8663db8ef62dfe07f593e1790c5ceaa9cab03806fc9a6e5a0"
+            )
+        );
+    }
+
+    #[test]
+    fn test_hc128_streaming() {
+        // Past the 1024 steps of a table cycle, in uneven pieces.
+        let expected = keystream(&[7; 16], &[9; 16], 5000);
+        let mut hc = Hc128::new(&[7; 16], &[9; 16]).unwrap();
+        let mut out = vec![0; 5000];
+        for chunk in out.chunks_mut(333) {
+            hc.apply_keystream(chunk).unwrap();
+        }
+        assert_eq!(out, expected);
+        for pos in [4999, 4097, 1, 0, 2048] {
+            hc.seek(pos).unwrap();
+            let mut byte = [0];
+            hc.apply_keystream(&mut byte).unwrap();
+            assert_eq!(byte[0], expected[pos as usize], "seek to {}", pos);
+            assert_eq!(hc.current_pos(), pos + 1);
+        }
+        assert_eq!(
+            Hc128::new(&[0; 32], &[0; 16]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Hc128::new(&[0; 16], &[0; 8]).err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//#######################################################################o
+// HKDF is the HMAC-based key derivation function of RFC 5869. It works  |
+// in two steps: extract concentrates the entropy of the input keying    |
+// material in a pseudorandom key, PRK = HMAC(salt, IKM), then expand    |
+// stretches the PRK into any number of keys, each one bound to an info  |
+// string: T(i) = HMAC(PRK, T(i - 1) || info || i), the output being     |
+// T(1) || T(2) || ... up to 255 blocks. A missing salt is a string of   |
+// zeros, which gives the same HMAC key as an empty salt.                |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 5869: https://www.rfc-editor.org/rfc/rfc5869                      |
+//#######################################################################o
+
+use crate::error::CryptoError;
+use crate::hash::Digest;
+use crate::mac::hmac::Hmac;
+
+// The expand counter is a byte.
+const MAX_BLOCKS: usize = 255;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Hkdf` holds a pseudorandom key from which keys are expanded, `D`
+/// being the hash function of HMAC.
+///# Example
+/// ```
+/// use cryptolib::hash::sha2::Sha256;
+/// use cryptolib::kdf::hkdf::Hkdf;
+///
+/// let hkdf = Hkdf::<Sha256>::new(b"salt", b"shared secret");
+/// let mut client_key = [0_u8; 32];
+/// let mut server_key = [0_u8; 32];
+/// hkdf.expand(b"client key", &mut client_key).unwrap();
+/// hkdf.expand(b"server key", &mut server_key).unwrap();
+/// assert_ne!(client_key, server_key);
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Hkdf<D: Digest> {
+    // HMAC keyed with the PRK.
+    prk: Hmac<D>,
+}
+
+impl<D: Digest> Hkdf<D> {
+    ////////////////////////////////////////////////////////////////////
+    /// Returns the pseudorandom key of the extract step.
+    ///# Arguments
+    ///* `salt`: a non-secret random value, may be empty.
+    ///* `ikm`: the input keying material, a shared secret.
+    ////////////////////////////////////////////////////////////////////
+    pub fn extract(salt: &[u8], ikm: &[u8]) -> D::Output {
+        let mut mac = Hmac::<D>::new(salt);
+        mac.update(ikm);
+        mac.finalize()
+    }
+
+    /// Extracts the pseudorandom key from `salt` and `ikm`.
+    pub fn new(salt: &[u8], ikm: &[u8]) -> Hkdf<D> {
+        Hkdf {
+            prk: Hmac::new(Self::extract(salt, ikm).as_ref()),
+        }
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Skips the extract step, `prk` being already uniformly random.
+    ///# Returns
+    /// The KDF or `CryptoError::InvalidKeyLength` if `prk` is shorter
+    /// than the digest.
+    ////////////////////////////////////////////////////////////////////
+    pub fn from_prk(prk: &[u8]) -> Result<Hkdf<D>, CryptoError> {
+        if prk.len() < D::OUTPUT_SIZE {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        Ok(Hkdf {
+            prk: Hmac::new(prk),
+        })
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Fills `out` with the key bound to `info`.
+    ///# Returns
+    /// `CryptoError::InvalidParameter` if `out` is longer than 255
+    /// digests.
+    ////////////////////////////////////////////////////////////////////
+    pub fn expand(&self, info: &[u8], out: &mut [u8]) -> Result<(), CryptoError> {
+        if out.len() > MAX_BLOCKS * D::OUTPUT_SIZE {
+            return Err(CryptoError::InvalidParameter);
+        }
+        let mut previous: Option<D::Output> = None;
+        for (i, chunk) in out.chunks_mut(D::OUTPUT_SIZE).enumerate() {
+            let mut mac = self.prk.clone();
+            if let Some(previous) = &previous {
+                mac.update(previous.as_ref());
+            }
+            mac.update(info);
+            mac.update(&[i as u8 + 1]);
+            let block = mac.finalize();
+            chunk.copy_from_slice(&block.as_ref()[..chunk.len()]);
+            previous = Some(block);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::sha1::Sha1;
+    use crate::hash::sha2::Sha256;
+    use crate::test_utils::hex;
+
+    // Checks the PRK and the OKM of an RFC 5869 test case.
+    fn check<D: Digest>(ikm: &[u8], salt: &[u8], info: &[u8], prk: &str, okm: &str) {
+        assert_eq!(Hkdf::<D>::extract(salt, ikm).as_ref(), hex(prk));
+        let expected = hex(okm);
+        let mut out = vec![0; expected.len()];
+        Hkdf::<D>::new(salt, ikm).expand(info, &mut out).unwrap();
+        assert_eq!(out, expected);
+        let mut out = vec![0; expected.len()];
+        Hkdf::<D>::from_prk(&hex(prk))
+            .unwrap()
+            .expand(info, &mut out)
+            .unwrap();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_hkdf_sha256() {
+        // RFC 5869 test cases 1 to 3.
+        let ikm: Vec<u8> = (0..0x50).collect();
+        let salt: Vec<u8> = (0x60..0xb0).collect();
+        let info: Vec<u8> = (0xb0..=0xff).collect();
+        check::<Sha256>(
+            &[0x0b; 22],
+            &(0..13).collect::<Vec<u8>>(),
+            &(0xf0..0xfa).collect::<Vec<u8>>(),
+            "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf
+             34007208d5b887185865",
+        );
+        check::<Sha256>(
+            &ikm,
+            &salt,
+            &info,
+            "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+            "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c
+             59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71
+             cc30c58179ec3e87c14c01d5c1f3434f1d87",
+        );
+        check::<Sha256>(
+            &[0x0b; 22],
+            b"",
+            b"",
+            "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d
+             9d201395faa4b61a96c8",
+        );
+    }
+
+    #[test]
+    fn test_hkdf_sha1() {
+        // RFC 5869 test cases 4 to 7, case 7 has no salt.
+        let ikm: Vec<u8> = (0..0x50).collect();
+        let salt: Vec<u8> = (0x60..0xb0).collect();
+        let info: Vec<u8> = (0xb0..=0xff).collect();
+        check::<Sha1>(
+            &[0x0b; 11],
+            &(0..13).collect::<Vec<u8>>(),
+            &(0xf0..0xfa).collect::<Vec<u8>>(),
+            "9b6c18c432a7bf8f0e71c8eb88f4b30baa2ba243",
+            "085a01ea1b10f36933068b56efa5ad81a4f14b822f5b091568a9cdd4f155fda2
+             c22e422478d305f3f896",
+        );
+        check::<Sha1>(
+            &ikm,
+            &salt,
+            &info,
+            "8adae09a2a307059478d309b26c4115a224cfaf6",
+            "0bd770a74d1160f7c9f12cd5912a06ebff6adcae899d92191fe4305673ba2ffe
+             8fa3f1a4e5ad79f3f334b3b202b2173c486ea37ce3d397ed034c7f9dfeb15c5e
+             927336d0441f4c4300e2cff0d0900b52d3b4",
+        );
+        check::<Sha1>(
+            &[0x0b; 22],
+            b"",
+            b"",
+            "da8c8a73c7fa77288ec6f5e7c297786aa0d32d01",
+            "0ac1af7002b3d761d1e55298da9d0506b9ae52057220a306e07b6b87e8df21d0
+             ea00033de03984d34918",
+        );
+        check::<Sha1>(
+            &[0x0c; 22],
+            &[0; 20],
+            b"",
+            "2adccada18779e7c2077ad2eb19d3f3e731385dd",
+            "2c91117204d745f3500d636a62f64f0ab3bae548aa53d423b0d1f27ebba6f5e5
+             673a081d70cce7acfc48",
+        );
+    }
+
+    #[test]
+    fn test_hkdf_limits() {
+        let hkdf = Hkdf::<Sha256>::new(b"salt", b"ikm");
+        let mut out = vec![0; 255 * 32];
+        assert!(hkdf.expand(b"", &mut out).is_ok());
+        // A prefix of the output is the output of a shorter length.
+        let mut short = [0; 40];
+        hkdf.expand(b"", &mut short).unwrap();
+        assert_eq!(short, out[..40]);
+        let mut out = vec![0; 255 * 32 + 1];
+        assert_eq!(
+            hkdf.expand(b"", &mut out),
+            Err(CryptoError::InvalidParameter)
+        );
+        assert_eq!(
+            Hkdf::<Sha256>::from_prk(&[0; 31]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+}
@@ -0,0 +1,141 @@
+pub mod hkdf;
+pub mod sp800_108;
+
+use crate::block::aes::Aes;
+use crate::block::des::Des;
+use crate::block::triple_des::TripleDes;
+use crate::error::CryptoError;
+
+//////////////////////////////////////////////////////////////////////
+/// Sets the parity bit (the lowest bit) of each byte of a DES or
+/// triple DES `key` so that each byte has an odd number of bits set.
+///# Example
+/// ```
+/// use cryptolib::kdf::set_des_parity;
+///
+/// let mut key = [0x00, 0x01, 0xfe, 0xff];
+/// set_des_parity(&mut key);
+/// assert_eq!(key, [0x01, 0x01, 0xfe, 0xfe]);
+/// ```
+//////////////////////////////////////////////////////////////////////
+pub fn set_des_parity(key: &mut [u8]) {
+    for byte in key {
+        *byte = (*byte & 0xfe) | (((*byte >> 1).count_ones() as u8 & 1) ^ 1);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `FromKeyMaterial` is implemented by the ciphers whose key can be
+/// created from the output of a key derivation function.
+////////////////////////////////////////////////////////////////////////////
+pub trait FromKeyMaterial: Sized {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates the keyed cipher from derived bytes.
+    ///# Returns
+    /// The cipher or `CryptoError::InvalidKeyLength` if the length of
+    /// `material` isn't a key length of the cipher.
+    ////////////////////////////////////////////////////////////////////
+    fn from_key_material(material: &[u8]) -> Result<Self, CryptoError>;
+}
+
+/// The parity bits are set, the 8 bytes key is a valid DES key.
+impl FromKeyMaterial for Des {
+    fn from_key_material(material: &[u8]) -> Result<Des, CryptoError> {
+        let mut key = material.to_vec();
+        set_des_parity(&mut key);
+        Des::new(&key)
+    }
+}
+
+/// The parity bits are set, the 16 or 24 bytes key is a valid triple
+/// DES key.
+impl FromKeyMaterial for TripleDes {
+    fn from_key_material(material: &[u8]) -> Result<TripleDes, CryptoError> {
+        let mut key = material.to_vec();
+        set_des_parity(&mut key);
+        TripleDes::new(&key)
+    }
+}
+
+impl FromKeyMaterial for Aes {
+    fn from_key_material(material: &[u8]) -> Result<Aes, CryptoError> {
+        Aes::new(material)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Derives the key of a cipher.
+///# Arguments
+///* `key_len`: the key length in bytes.
+///* `derive`: fills its argument with the output of a KDF.
+///# Returns
+/// The keyed cipher, the error of `derive` or
+/// `CryptoError::InvalidKeyLength`.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::block::triple_des::TripleDes;
+/// use cryptolib::hash::sha2::Sha256;
+/// use cryptolib::kdf::derive_key;
+/// use cryptolib::kdf::hkdf::Hkdf;
+///
+/// let hkdf = Hkdf::<Sha256>::new(b"salt", b"shared secret");
+/// let aes: Aes = derive_key(32, |key| hkdf.expand(b"session aes", key)).unwrap();
+/// let des: TripleDes = derive_key(24, |key| hkdf.expand(b"session 3des", key)).unwrap();
+/// ```
+////////////////////////////////////////////////////////////////////////////
+pub fn derive_key<K: FromKeyMaterial>(
+    key_len: usize,
+    derive: impl FnOnce(&mut [u8]) -> Result<(), CryptoError>,
+) -> Result<K, CryptoError> {
+    let mut material = vec![0_u8; key_len];
+    derive(&mut material)?;
+    K::from_key_material(&material)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::BlockCipher;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_des_parity() {
+        let mut key: Vec<u8> = (0..=255).collect();
+        set_des_parity(&mut key);
+        for (i, byte) in key.iter().enumerate() {
+            assert_eq!(byte.count_ones() % 2, 1, "{:#04x}", byte);
+            assert_eq!(byte & 0xfe, i as u8 & 0xfe);
+        }
+    }
+
+    #[test]
+    fn test_derive_key() {
+        // The parity only changes the ignored bits, the cipher is the one
+        // of the raw material.
+        let material = hex("cc53c94c53a84cb71d19af18c626ed9e2bbafb825dd03cb1");
+        let des: TripleDes = derive_key(24, |key| {
+            key.copy_from_slice(&material);
+            Ok(())
+        })
+        .unwrap();
+        let mut block = [0_u8; 8];
+        let mut expected = block;
+        des.encrypt_block(&mut block);
+        TripleDes::new(&hex("cd52c84c52a84cb61c19ae19c726ec9e2abafb835dd03db0"))
+            .unwrap()
+            .encrypt_block(&mut expected);
+        assert_eq!(block, expected);
+
+        assert!(derive_key::<Des>(8, |_| Ok(())).is_ok());
+        assert!(derive_key::<Aes>(24, |_| Ok(())).is_ok());
+        assert_eq!(
+            derive_key::<Aes>(20, |_| Ok(())).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            derive_key::<Des>(8, |_| Err(CryptoError::InvalidParameter)).err(),
+            Some(CryptoError::InvalidParameter)
+        );
+    }
+}
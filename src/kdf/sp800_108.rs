@@ -0,0 +1,295 @@
+//#######################################################################o
+// SP 800-108 defines key derivation functions iterating a pseudorandom  |
+// function (HMAC or CMAC) keyed with the key derivation key. Each       |
+// iteration produces one PRF output of the derived key from the fixed   |
+// input data Label || 0x00 || Context || [L]_32, L being the output     |
+// length in bits, and a 32-bit big-endian counter [i]_32:               |
+// - counter mode: K(i) = PRF([i]_32 || fixed data),                     |
+// - feedback mode: K(i) = PRF(K(i - 1) || [i]_32 || fixed data), K(0)   |
+//   being an IV,                                                        |
+// - double-pipeline mode: A(i) = PRF(A(i - 1)), A(0) being the fixed    |
+//   data, and K(i) = PRF(A(i) || [i]_32 || fixed data).                 |
+// The counter is optional in the last two modes, it's always included   |
+// here, which is the default of OpenSSL.                                |
+//                                                                       |
+// @Refrences:                                                           |
+// SP 800-108r1: https://doi.org/10.6028/NIST.SP.800-108r1-upd1          |
+//#######################################################################o
+
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::hash::Digest;
+use crate::mac::cmac::Cmac;
+use crate::mac::hmac::Hmac;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Prf` is a pseudorandom function keyed with the key derivation key.
+////////////////////////////////////////////////////////////////////////////
+pub trait Prf {
+    /// Size of the output in bytes.
+    fn output_size(&self) -> usize;
+
+    /// Returns the output of the concatenation of `parts`.
+    fn compute(&self, parts: &[&[u8]]) -> Vec<u8>;
+}
+
+impl<D: Digest> Prf for Hmac<D> {
+    fn output_size(&self) -> usize {
+        D::OUTPUT_SIZE
+    }
+
+    fn compute(&self, parts: &[&[u8]]) -> Vec<u8> {
+        let mut mac = self.clone();
+        for part in parts {
+            mac.update(part);
+        }
+        mac.finalize().as_ref().to_vec()
+    }
+}
+
+impl<C: BlockCipher> Prf for Cmac<C> {
+    fn output_size(&self) -> usize {
+        C::BLOCK_SIZE
+    }
+
+    fn compute(&self, parts: &[&[u8]]) -> Vec<u8> {
+        self.compute_parts(parts)[..C::BLOCK_SIZE].to_vec()
+    }
+}
+
+// Label || 0x00 || Context || [L]_32, the length of the output being
+// encoded on 32 bits.
+fn fixed_input(label: &[u8], context: &[u8], out_len: usize) -> Result<Vec<u8>, CryptoError> {
+    let bits = u32::try_from(out_len)
+        .ok()
+        .and_then(|len| len.checked_mul(8))
+        .ok_or(CryptoError::InvalidParameter)?;
+    let mut fixed = Vec::with_capacity(label.len() + context.len() + 5);
+    fixed.extend_from_slice(label);
+    fixed.push(0);
+    fixed.extend_from_slice(context);
+    fixed.extend_from_slice(&bits.to_be_bytes());
+    Ok(fixed)
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Fills `out` with a key derived in counter mode.
+///# Arguments
+///* `prf`: the PRF keyed with the key derivation key.
+///* `label`: the purpose of the derived key.
+///* `context`: the parties and other information bound to the key.
+///* `out`: the derived key.
+///# Returns
+/// `CryptoError::InvalidParameter` if `out` is longer than 2^32 - 1
+/// bits.
+///# Example
+/// ```
+/// use cryptolib::hash::sha2::Sha256;
+/// use cryptolib::kdf::sp800_108::counter_mode;
+/// use cryptolib::mac::hmac::Hmac;
+///
+/// let prf = Hmac::<Sha256>::new(b"key derivation key");
+/// let mut key = [0_u8; 42];
+/// counter_mode(&prf, b"encryption", b"alice bob", &mut key).unwrap();
+/// ```
+////////////////////////////////////////////////////////////////////////////
+pub fn counter_mode<P: Prf>(
+    prf: &P,
+    label: &[u8],
+    context: &[u8],
+    out: &mut [u8],
+) -> Result<(), CryptoError> {
+    let fixed = fixed_input(label, context, out.len())?;
+    for (i, chunk) in out.chunks_mut(prf.output_size()).enumerate() {
+        let counter = (i as u32 + 1).to_be_bytes();
+        let block = prf.compute(&[&counter, &fixed]);
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Fills `out` with a key derived in feedback mode.
+///# Arguments
+///* `prf`: the PRF keyed with the key derivation key.
+///* `iv`: K(0), may be empty.
+///* `label`: the purpose of the derived key.
+///* `context`: the parties and other information bound to the key.
+///* `out`: the derived key.
+///# Returns
+/// `CryptoError::InvalidParameter` if `out` is longer than 2^32 - 1
+/// bits.
+////////////////////////////////////////////////////////////////////////////
+pub fn feedback_mode<P: Prf>(
+    prf: &P,
+    iv: &[u8],
+    label: &[u8],
+    context: &[u8],
+    out: &mut [u8],
+) -> Result<(), CryptoError> {
+    let fixed = fixed_input(label, context, out.len())?;
+    let mut previous = iv.to_vec();
+    for (i, chunk) in out.chunks_mut(prf.output_size()).enumerate() {
+        let counter = (i as u32 + 1).to_be_bytes();
+        previous = prf.compute(&[&previous, &counter, &fixed]);
+        chunk.copy_from_slice(&previous[..chunk.len()]);
+    }
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// Fills `out` with a key derived in double-pipeline mode.
+///# Arguments
+///* `prf`: the PRF keyed with the key derivation key.
+///* `label`: the purpose of the derived key.
+///* `context`: the parties and other information bound to the key.
+///* `out`: the derived key.
+///# Returns
+/// `CryptoError::InvalidParameter` if `out` is longer than 2^32 - 1
+/// bits.
+////////////////////////////////////////////////////////////////////////////
+pub fn double_pipeline_mode<P: Prf>(
+    prf: &P,
+    label: &[u8],
+    context: &[u8],
+    out: &mut [u8],
+) -> Result<(), CryptoError> {
+    let fixed = fixed_input(label, context, out.len())?;
+    let mut a = fixed.clone();
+    for (i, chunk) in out.chunks_mut(prf.output_size()).enumerate() {
+        a = prf.compute(&[&a]);
+        let counter = (i as u32 + 1).to_be_bytes();
+        let block = prf.compute(&[&a, &counter, &fixed]);
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::block::triple_des::TripleDes;
+    use crate::hash::sha2::{Sha256, Sha512};
+    use crate::test_utils::hex;
+
+    fn hmac_sha256() -> Hmac<Sha256> {
+        Hmac::new(&(0..32).collect::<Vec<u8>>())
+    }
+
+    fn cmac_aes() -> Cmac<Aes> {
+        Cmac::new(Aes::new(&(0..16).collect::<Vec<u8>>()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_counter_mode() {
+        // Checked against OpenSSL's KBKDF and pyca/cryptography.
+        let mut out = [0; 42];
+        counter_mode(&hmac_sha256(), b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out.to_vec(),
+            hex(
+                "b9cd5f6323f01f4680650855f1ebea9b4c54c08131b506fc28c856364a38a2f4
+                 fb680c12ea51696887d9"
+            )
+        );
+        counter_mode(&cmac_aes(), b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out.to_vec(),
+            hex(
+                "1f1d75c243366a6ca7f2aebaa33a76bb4f35af82d949a29ee20637d72dc7d0a7
+                 2c42363090d0023211d5"
+            )
+        );
+        let prf = Hmac::<Sha512>::new(&(0..32).collect::<Vec<u8>>());
+        let mut out = [0; 100];
+        counter_mode(&prf, b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out.to_vec(),
+            hex(
+                "eaafe0ba51282679fb2db5fd4a892caa37bcaf2503fb9f0879d8008853cbcb94
+                 15e1605485b9db2fc57e950d3973c7b3ef2c4a23937714645d8f1936becfd858
+                 83a2b38728b96cb14d37eb3034c44acb4f12c1c5c4901fafdafe6babee4ad80c
+                 cf290aa4"
+            )
+        );
+        // CMAC with a 64-bit block cipher.
+        let prf = Cmac::new(TripleDes::new(&(0..24).collect::<Vec<u8>>()).unwrap()).unwrap();
+        let mut out = [0; 24];
+        counter_mode(&prf, b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out.to_vec(),
+            hex("cc53c94c53a84cb71d19af18c626ed9e2bbafb825dd03cb1")
+        );
+    }
+
+    #[test]
+    fn test_feedback_mode() {
+        // Checked against OpenSSL's KBKDF.
+        let iv: Vec<u8> = (100..132).collect();
+        let mut out = [0; 42];
+        feedback_mode(&hmac_sha256(), &iv, b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out.to_vec(),
+            hex(
+                "1e6759d4e20cf3f148fbd520ebf54f3b93a776c9997cb6a65aaa052808902290
+                 d5450e61e96e5416e3f3"
+            )
+        );
+        // Checked against a Python model: without IV the first block is the
+        // one of the counter mode.
+        feedback_mode(&hmac_sha256(), b"", b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out.to_vec(),
+            hex(
+                "b9cd5f6323f01f4680650855f1ebea9b4c54c08131b506fc28c856364a38a2f4
+                 9d3375ecb5601a70f313"
+            )
+        );
+        feedback_mode(&cmac_aes(), &[0; 16], b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out.to_vec(),
+            hex(
+                "4eff090eb5cf3ce424bcdc6d25f1ae0e8d3c526acb0a68946ad7b0e946e9e2dc
+                 4f21f3647e1d5c0d8015"
+            )
+        );
+    }
+
+    #[test]
+    fn test_double_pipeline_mode() {
+        // Checked against a Python model.
+        let mut out = [0; 42];
+        double_pipeline_mode(&hmac_sha256(), b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out.to_vec(),
+            hex(
+                "76e610b7ccf1e56845e527cfdcbd74231fe34eee312dd6bc1a9ac8933771f7b7
+                 d276a4fd588e72ce030f"
+            )
+        );
+        double_pipeline_mode(&cmac_aes(), b"label", b"context", &mut out).unwrap();
+        assert_eq!(
+            out.to_vec(),
+            hex(
+                "c78ff5d4ad3c50c5418690ccf092e8856178bf5652dd02f99e9d9c481dda87df
+                 a405c33444e9606d1e56"
+            )
+        );
+    }
+
+    #[test]
+    fn test_output_length() {
+        // L is part of the input, a shorter key isn't a prefix.
+        let mut long = [0; 32];
+        let mut short = [0; 16];
+        counter_mode(&hmac_sha256(), b"label", b"", &mut long).unwrap();
+        counter_mode(&hmac_sha256(), b"label", b"", &mut short).unwrap();
+        assert_ne!(short, long[..16]);
+        assert_eq!(
+            fixed_input(b"", b"", 1 << 29).err(),
+            Some(CryptoError::InvalidParameter)
+        );
+        assert!(fixed_input(b"", b"", (1 << 29) - 1).is_ok());
+    }
+}
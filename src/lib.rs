@@ -1,4 +1,21 @@
+pub mod bcrypt;
 pub mod block;
-pub mod encoder;
 pub mod caesar;
+pub mod chacha;
+pub mod encoder;
+pub mod error;
+pub mod hash;
+pub mod hc128;
+pub mod kdf;
+pub mod mac;
+pub mod rabbit;
+pub mod rc4;
+pub mod salsa20;
+pub mod stream;
+pub mod trivium;
 pub mod vigenere;
+
+mod util;
+
+#[cfg(test)]
+mod test_utils;
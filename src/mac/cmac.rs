@@ -0,0 +1,234 @@
+//#######################################################################o
+// CMAC (also called OMAC1) is a message authentication code built on a  |
+// block cipher: the message is processed in CBC mode with a zero IV and |
+// the last block is masked with one of two subkeys derived from         |
+// E_K(0), depending on whether it had to be padded or not.              |
+// It's defined for 64-bit and 128-bit block ciphers.                    |
+//                                                                       |
+// @Refrences:                                                           |
+// NIST SP 800-38B: https://doi.org/10.6028/NIST.SP.800-38B              |
+// RFC 4493: https://www.rfc-editor.org/rfc/rfc4493                      |
+//#######################################################################o
+
+use crate::block::BlockCipher;
+use crate::error::CryptoError;
+use crate::util::{ct_eq, xor_in_place};
+
+// Shortest truncated tag, SP 800-38B recommends at least 64 bits.
+const MIN_TAG_LEN: usize = 8;
+
+//////////////////////////////////////////////////////////////////////
+/// Multiplies `block` by x in GF(2^n), n being the block size in bits.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn dbl(block: &[u8]) -> [u8; 16] {
+    let rb = if block.len() == 16 { 0x87 } else { 0x1b };
+    let mut out = [0_u8; 16];
+    let n = block.len();
+    for i in 0..n - 1 {
+        out[i] = (block[i] << 1) | (block[i + 1] >> 7);
+    }
+    out[n - 1] = (block[n - 1] << 1) ^ (rb & 0_u8.wrapping_sub(block[0] >> 7));
+    out
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Cmac` computes the CMAC of messages under the key of `cipher`.
+///# Example
+/// ```
+/// use cryptolib::block::aes::Aes;
+/// use cryptolib::mac::cmac::Cmac;
+///
+/// let cmac = Cmac::new(Aes::new(&[0x42; 16]).unwrap()).unwrap();
+/// let tag = cmac.compute(b"message");
+/// assert!(cmac.verify(b"message", &tag).is_ok());
+/// assert!(cmac.verify_truncated(b"message", &tag[..8], 8).is_ok());
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Cmac<C: BlockCipher> {
+    cipher: C,
+    k1: [u8; 16],
+    k2: [u8; 16],
+}
+
+impl<C: BlockCipher> Cmac<C> {
+    ////////////////////////////////////////////////////////////////////
+    /// Derives the subkeys from the keyed `cipher`.
+    ///# Returns
+    /// The mac or `CryptoError::InvalidBlockSize` if the cipher
+    /// doesn't use 64-bit or 128-bit blocks.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(cipher: C) -> Result<Cmac<C>, CryptoError> {
+        let block_size = C::BLOCK_SIZE;
+        if block_size != 8 && block_size != 16 {
+            return Err(CryptoError::InvalidBlockSize);
+        }
+        let mut l = [0_u8; 16];
+        cipher.encrypt_block(&mut l[..block_size]);
+        let k1 = dbl(&l[..block_size]);
+        let k2 = dbl(&k1[..block_size]);
+        Ok(Cmac { cipher, k1, k2 })
+    }
+
+    /// Returns the underlying block cipher.
+    pub(crate) fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Computes the CMAC of the concatenation of `parts`.
+    ///# Returns
+    /// The tag in the first `BLOCK_SIZE` bytes, the rest is zero.
+    ////////////////////////////////////////////////////////////////////
+    pub(crate) fn compute_parts(&self, parts: &[&[u8]]) -> [u8; 16] {
+        let block_size = C::BLOCK_SIZE;
+        let mut x = [0_u8; 16];
+        let mut block = [0_u8; 16];
+        let mut len = 0;
+        for part in parts {
+            let mut data = *part;
+            while !data.is_empty() {
+                // A full block is only processed once more data follows,
+                // the last block gets a subkey.
+                if len == block_size {
+                    xor_in_place(&mut x, &block);
+                    self.cipher.encrypt_block(&mut x[..block_size]);
+                    len = 0;
+                }
+                let take = data.len().min(block_size - len);
+                block[len..len + take].copy_from_slice(&data[..take]);
+                len += take;
+                data = &data[take..];
+            }
+        }
+        if len == block_size {
+            xor_in_place(&mut block, &self.k1);
+        } else {
+            block[len] = 0x80;
+            block[len + 1..].fill(0);
+            xor_in_place(&mut block, &self.k2);
+        }
+        xor_in_place(&mut x, &block[..block_size]);
+        self.cipher.encrypt_block(&mut x[..block_size]);
+        x
+    }
+
+    /// Returns the `BLOCK_SIZE` bytes tag of `data`.
+    pub fn compute(&self, data: &[u8]) -> Vec<u8> {
+        self.compute_parts(&[data])[..C::BLOCK_SIZE].to_vec()
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Checks the full `BLOCK_SIZE` bytes tag of `data`.
+    ///# Returns
+    /// `CryptoError::InvalidTagLength` if `tag` isn't a block and
+    /// `CryptoError::AuthenticationFailed` if it doesn't match.
+    ////////////////////////////////////////////////////////////////////
+    pub fn verify(&self, data: &[u8], tag: &[u8]) -> Result<(), CryptoError> {
+        self.verify_truncated(data, tag, C::BLOCK_SIZE)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Checks the tag of `data` truncated to its leftmost bytes.
+    ///# Arguments
+    ///* `data`: the authenticated data.
+    ///* `tag`: the received tag.
+    ///* `len`: the tag length expected by the verifier.
+    ///# Returns
+    /// `CryptoError::InvalidTagLength` if `tag` isn't `len` bytes or if
+    /// `len` is shorter than 8 bytes or longer than a block, and
+    /// `CryptoError::AuthenticationFailed` if it doesn't match.
+    ////////////////////////////////////////////////////////////////////
+    pub fn verify_truncated(&self, data: &[u8], tag: &[u8], len: usize) -> Result<(), CryptoError> {
+        if len < MIN_TAG_LEN || len > C::BLOCK_SIZE || tag.len() != len {
+            return Err(CryptoError::InvalidTagLength);
+        }
+        if ct_eq(&self.compute_parts(&[data])[..len], tag) {
+            Ok(())
+        } else {
+            Err(CryptoError::AuthenticationFailed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::aes::Aes;
+    use crate::block::triple_des::TripleDes;
+    use crate::test_utils::hex;
+
+    const MESSAGE: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51
+                           30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+
+    #[test]
+    fn test_cmac_aes() {
+        // RFC 4493 section 4.
+        let cmac = Cmac::new(Aes::new(&hex("2b7e151628aed2a6abf7158809cf4f3c")).unwrap()).unwrap();
+        let message = hex(MESSAGE);
+        assert_eq!(cmac.k1[..], hex("fbeed618357133667c85e08f7236a8de")[..]);
+        assert_eq!(cmac.k2[..], hex("f7ddac306ae266ccf90bc11ee46d513b")[..]);
+        assert_eq!(cmac.compute(&[]), hex("bb1d6929e95937287fa37d129b756746"));
+        assert_eq!(
+            cmac.compute(&message[..16]),
+            hex("070a16b46b4d4144f79bdd9dd04a287c")
+        );
+        assert_eq!(
+            cmac.compute(&message[..40]),
+            hex("dfa66747de9ae63030ca32611497c827")
+        );
+        assert_eq!(
+            cmac.compute(&message),
+            hex("51f0bebf7e3b9d92fc49741779363cfe")
+        );
+        assert_eq!(
+            cmac.compute_parts(&[&message[..7], &[], &message[7..33], &message[33..]]),
+            cmac.compute_parts(&[&message])
+        );
+    }
+
+    #[test]
+    fn test_cmac_triple_des() {
+        // SP 800-38B Appendix D.2, three keys.
+        let key = hex("8aa83bf8cbda10620bc1bf19fbb6cd58bc313d4a371ca8b5");
+        let cmac = Cmac::new(TripleDes::new(&key).unwrap()).unwrap();
+        assert_eq!(cmac.compute(&hex(MESSAGE)[..20]), hex("743ddbe0ce2dc2ed"));
+    }
+
+    #[test]
+    fn test_cmac_verify() {
+        let cmac = Cmac::new(Aes::new(&hex("2b7e151628aed2a6abf7158809cf4f3c")).unwrap()).unwrap();
+        let message = hex(MESSAGE);
+        let tag = hex("51f0bebf7e3b9d92fc49741779363cfe");
+        assert!(cmac.verify(&message, &tag).is_ok());
+        assert_eq!(
+            cmac.verify(&message, &hex("51f0bebf7e3b9d92fc49741779363cff")),
+            Err(CryptoError::AuthenticationFailed)
+        );
+        // A prefix of a valid tag is only accepted at the length chosen
+        // by the verifier.
+        for len in [0, 1, 8, 15] {
+            assert_eq!(
+                cmac.verify(&message, &tag[..len]),
+                Err(CryptoError::InvalidTagLength)
+            );
+        }
+        assert!(cmac.verify_truncated(&message, &tag[..8], 8).is_ok());
+        assert_eq!(
+            cmac.verify_truncated(&message, &hex("51f0bebf7e3b9d93"), 8),
+            Err(CryptoError::AuthenticationFailed)
+        );
+        assert_eq!(
+            cmac.verify_truncated(&message, &tag[..8], 12),
+            Err(CryptoError::InvalidTagLength)
+        );
+        assert_eq!(
+            cmac.verify_truncated(&message, &tag[..1], 1),
+            Err(CryptoError::InvalidTagLength)
+        );
+        assert_eq!(
+            cmac.verify(&message, &[0; 17]),
+            Err(CryptoError::InvalidTagLength)
+        );
+    }
+}
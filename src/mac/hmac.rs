@@ -0,0 +1,371 @@
+//#######################################################################o
+// HMAC is a message authentication code built on a hash function H:     |
+// HMAC(K, m) = H((K ^ opad) || H((K ^ ipad) || m)), the key being       |
+// hashed first if it's longer than the block of H and padded with       |
+// zeros to a full block. Any hash of the crate implementing `Digest`    |
+// can be used, the tag is its digest and may be truncated to its        |
+// leftmost bytes. HMAC-SM3 is used by the Chinese standards, with the   |
+// 32 bytes SM3 digest as tag.                                           |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 2104: https://www.rfc-editor.org/rfc/rfc2104                      |
+// RFC 2202: https://www.rfc-editor.org/rfc/rfc2202                      |
+// RFC 4231: https://www.rfc-editor.org/rfc/rfc4231                      |
+//#######################################################################o
+
+use crate::error::CryptoError;
+use crate::hash::sm3::Sm3;
+use crate::hash::Digest;
+use crate::util::ct_eq;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Hmac` is a streaming HMAC computation over the hash function `D`.
+///# Example
+/// ```
+/// use cryptolib::hash::sha2::Sha256;
+/// use cryptolib::mac::hmac::Hmac;
+///
+/// let mut mac = Hmac::<Sha256>::new(b"key");
+/// mac.update(b"message");
+/// let tag = mac.finalize();
+/// let mut mac = Hmac::<Sha256>::new(b"key");
+/// mac.update(b"message");
+/// assert!(mac.clone().verify(&tag).is_ok());
+/// assert!(mac.verify_truncated(&tag[..16], 16).is_ok());
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Hmac<D: Digest> {
+    inner: D,
+    outer: D,
+}
+
+/// HMAC-SM3.
+pub type HmacSm3 = Hmac<Sm3>;
+
+impl<D: Digest> Hmac<D> {
+    /// Creates the MAC from a `key` of any length.
+    pub fn new(key: &[u8]) -> Hmac<D> {
+        let mut block = vec![0_u8; D::BLOCK_SIZE];
+        if key.len() > D::BLOCK_SIZE {
+            let digest = D::digest(key);
+            block[..digest.as_ref().len()].copy_from_slice(digest.as_ref());
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+        let mut inner = D::default();
+        let mut outer = D::default();
+        inner.update(&block.iter().map(|b| b ^ IPAD).collect::<Vec<u8>>());
+        outer.update(&block.iter().map(|b| b ^ OPAD).collect::<Vec<u8>>());
+        Hmac { inner, outer }
+    }
+
+    /// Absorbs `data` into the MAC.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Returns the tag, a digest of `D`.
+    pub fn finalize(self) -> D::Output {
+        let mut outer = self.outer;
+        outer.update(self.inner.finalize().as_ref());
+        outer.finalize()
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Checks the full digest `tag` in constant time.
+    ///# Returns
+    /// `CryptoError::InvalidTagLength` if `tag` isn't a digest and
+    /// `CryptoError::AuthenticationFailed` if it doesn't match.
+    ////////////////////////////////////////////////////////////////////
+    pub fn verify(self, tag: &[u8]) -> Result<(), CryptoError> {
+        self.verify_truncated(tag, D::OUTPUT_SIZE)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Checks a tag truncated to its leftmost bytes in constant time.
+    ///# Arguments
+    ///* `tag`: the received tag.
+    ///* `len`: the tag length expected by the verifier.
+    ///# Returns
+    /// `CryptoError::InvalidTagLength` if `tag` isn't `len` bytes or if
+    /// `len` is shorter than half the digest or 10 bytes, or longer than
+    /// the digest, and `CryptoError::AuthenticationFailed` if it doesn't
+    /// match.
+    ///# Note
+    /// '''
+    /// The minimum is the one advised by RFC 2104, shorter tags are
+    /// easier to forge.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn verify_truncated(self, tag: &[u8], len: usize) -> Result<(), CryptoError> {
+        let min_len = (D::OUTPUT_SIZE / 2).max(10);
+        if len < min_len || len > D::OUTPUT_SIZE || tag.len() != len {
+            return Err(CryptoError::InvalidTagLength);
+        }
+        let expected = self.finalize();
+        if ct_eq(&expected.as_ref()[..len], tag) {
+            Ok(())
+        } else {
+            Err(CryptoError::AuthenticationFailed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::blake2::Blake2b;
+    use crate::hash::md5::Md5;
+    use crate::hash::sha1::Sha1;
+    use crate::hash::sha2::{Sha224, Sha256, Sha384, Sha512};
+    use crate::hash::sha3::Sha3_256;
+    use crate::test_utils::hex;
+
+    fn hmac<D: Digest>(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut mac = Hmac::<D>::new(key);
+        mac.update(data);
+        mac.finalize().as_ref().to_vec()
+    }
+
+    // Checks the seven test cases of RFC 4231 or RFC 2202, whose keys of
+    // cases 1, 3 and 5 have the digest size of SHA-1 or MD5 and whose
+    // long keys are 131 or 80 bytes. The tag of case 5 may be truncated.
+    fn check<D: Digest>(short_key: usize, long_key: usize, data7: &[u8], expected: &[&str; 7]) {
+        let cases: [(Vec<u8>, &[u8]); 7] = [
+            (vec![0x0b; short_key], b"Hi There"),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?"),
+            (vec![0xaa; short_key], &[0xdd; 50]),
+            ((1..=25).collect(), &[0xcd; 50]),
+            (vec![0x0c; short_key], b"Test With Truncation"),
+            (
+                vec![0xaa; long_key],
+                b"Test Using Larger Than Block-Size Key - Hash Key First",
+            ),
+            (vec![0xaa; long_key], data7),
+        ];
+        for (i, ((key, data), expected)) in cases.iter().zip(expected).enumerate() {
+            let expected = hex(expected);
+            let tag = hmac::<D>(key, data);
+            assert_eq!(tag[..expected.len()], expected, "test case {}", i + 1);
+        }
+    }
+
+    const RFC4231_DATA7: &[u8] = b"This is a test using a larger than block-size key and a larger \
+        than block-size data. The key needs to be hashed before being used by the HMAC algorithm.";
+    const RFC2202_DATA7: &[u8] =
+        b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data";
+
+    #[test]
+    fn test_hmac_sha2() {
+        // RFC 4231 test cases.
+        check::<Sha224>(
+            20,
+            131,
+            RFC4231_DATA7,
+            &[
+                "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+                "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+                "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+                "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+                "0e2aea68a90c8d37c988bcdb9fca6fa8",
+                "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+                "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+            ],
+        );
+        check::<Sha256>(
+            20,
+            131,
+            RFC4231_DATA7,
+            &[
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+                "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+                "a3b6167473100ee06e0c796c2955552b",
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+                "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            ],
+        );
+        check::<Sha384>(
+            20,
+            131,
+            RFC4231_DATA7,
+            &[
+                "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59c
+                 faea9ea9076ede7f4af152e8b2fa9cb6",
+                "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e
+                 8e2240ca5e69e2c78b3239ecfab21649",
+                "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b
+                 2a5ab39dc13814b94e3ab6e101a34f27",
+                "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e
+                 6801dd23c4a7d679ccf8a386c674cffb",
+                "3abf34c3503b2a23a46efc619baef897",
+                "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c6
+                 0c2ef6ab4030fe8296248df163f44952",
+                "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5
+                 a678cc31e799176d3860e6110c46523e",
+            ],
+        );
+        check::<Sha512>(
+            20,
+            131,
+            RFC4231_DATA7,
+            &[
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde
+                 daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554
+                 9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+                "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39
+                 bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+                "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3db
+                 a91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+                "415fad6271580a531d4179bc891d87a6",
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352
+                 6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+                "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944
+                 b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_hmac_md5_sha1() {
+        // RFC 2202 test cases.
+        check::<Md5>(
+            16,
+            80,
+            RFC2202_DATA7,
+            &[
+                "9294727a3638bb1c13f48ef8158bfc9d",
+                "750c783e6ab0b503eaa86e310a5db738",
+                "56be34521d144c88dbb8c733f0e8b3f6",
+                "697eaf0aca3a3aea3a75164746ffaa79",
+                "56461ef2342edc00f9bab995",
+                "6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd",
+                "6f630fad67cda0ee1fb1f562db3aa53e",
+            ],
+        );
+        check::<Sha1>(
+            20,
+            80,
+            RFC2202_DATA7,
+            &[
+                "b617318655057264e28bc0b6fb378c8ef146be00",
+                "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+                "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+                "4c9007f4026250c6bc8414f9bf50c86c2d7235da",
+                "4c1a03424b55e07fe7f27be1",
+                "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+                "e8e99d0f45237d786d6bbaa7965c7808bbff1a91",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_hmac_other_digests() {
+        // Checked against Python's hmac module, the block of SHA3-256 is
+        // its 136 bytes rate and the one of BLAKE2b is 128 bytes.
+        let data = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            hmac::<Sha3_256>(b"key", data),
+            hex("8c6e0683409427f8931711b10ca92a506eb1fafa48fadd66d76126f47ac2c333")
+        );
+        assert_eq!(
+            hmac::<Sha3_256>(&[0xaa; 200], b"message"),
+            hex("f37c9ab547e28e567d5c910515b53726630839241b4bdc85cc772773a5af85c4")
+        );
+        assert_eq!(
+            hmac::<Blake2b>(b"key", data),
+            hex(
+                "92294f92c0dfb9b00ec9ae8bd94d7e7d8a036b885a499f149dfe2fd2199394aa
+                 af6b8894a1730cccb2cd050f9bcf5062a38b51b0dab33207f8ef35ae2c9df51b"
+            )
+        );
+        assert_eq!(
+            hmac::<Blake2b>(&[0xaa; 200], b"message"),
+            hex(
+                "52d7ec1e29068ea784f5243a7b37d3a3d87b514bf532e4e6e249f1b1b1329134
+                 deaf4bcb681e72923f7a37f67ea8630c2d342741cd84c523921468604e7ca9d0"
+            )
+        );
+    }
+
+    #[test]
+    fn test_hmac_sm3() {
+        // RFC 4231 inputs, checked against OpenSSL.
+        assert_eq!(
+            hmac::<Sm3>(&[0x0b; 20], b"Hi There"),
+            hex("51b00d1fb49832bfb01c3ce27848e59f871d9ba938dc563b338ca964755cce70")
+        );
+        assert_eq!(
+            hmac::<Sm3>(b"Jefe", b"what do ya want for nothing?"),
+            hex("2e87f1d16862e6d964b50a5200bf2b10b764faa9680a296a2405f24bec39f882")
+        );
+        // A key longer than the block is hashed first.
+        let long_key = [0xaa; 131];
+        let data = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        assert_eq!(
+            hmac::<Sm3>(&long_key, data),
+            hex("b4fd844e13342002f0b2e0690ea7741f1497d993a70494cea601e657bedf67a0")
+        );
+        assert_eq!(
+            hmac::<Sm3>(&long_key, data),
+            hmac::<Sm3>(&Sm3::digest(&long_key), data)
+        );
+        let mut mac = HmacSm3::new(b"Jefe");
+        mac.update(b"what do ya want for nothing?");
+        let tag: [u8; 32] = mac.finalize();
+        assert_eq!(tag[..4], [0x2e, 0x87, 0xf1, 0xd1]);
+    }
+
+    #[test]
+    fn test_hmac_verify() {
+        let tag = hmac::<Sha256>(b"Jefe", b"what do ya want for nothing?");
+        let mut mac = Hmac::<Sha256>::new(b"Jefe");
+        mac.update(b"what do ya ");
+        mac.update(b"want for nothing?");
+        assert!(mac.clone().verify(&tag).is_ok());
+        assert_eq!(
+            mac.clone().verify(&[0; 32]),
+            Err(CryptoError::AuthenticationFailed)
+        );
+        // The verifier decides the length, a valid prefix isn't enough.
+        for len in [0, 1, 10, 31] {
+            assert_eq!(
+                mac.clone().verify(&tag[..len]),
+                Err(CryptoError::InvalidTagLength)
+            );
+        }
+        assert_eq!(
+            mac.clone().verify(&[0; 33]),
+            Err(CryptoError::InvalidTagLength)
+        );
+        assert!(mac.clone().verify_truncated(&tag[..16], 16).is_ok());
+        assert!(mac.clone().verify_truncated(&tag[..20], 20).is_ok());
+        assert_eq!(
+            mac.clone().verify_truncated(&tag[..16], 20),
+            Err(CryptoError::InvalidTagLength)
+        );
+        assert_eq!(
+            mac.clone().verify_truncated(&tag[..1], 1),
+            Err(CryptoError::InvalidTagLength)
+        );
+        assert_eq!(
+            mac.clone().verify_truncated(&tag[..15], 15),
+            Err(CryptoError::InvalidTagLength)
+        );
+        // Half of the 16 bytes MD5 digest is below 10 bytes.
+        let md5_tag = hmac::<Md5>(b"Jefe", b"what do ya want for nothing?");
+        let mut mac = Hmac::<Md5>::new(b"Jefe");
+        mac.update(b"what do ya want for nothing?");
+        assert!(mac.clone().verify_truncated(&md5_tag[..10], 10).is_ok());
+        assert_eq!(
+            mac.verify_truncated(&md5_tag[..9], 9),
+            Err(CryptoError::InvalidTagLength)
+        );
+    }
+}
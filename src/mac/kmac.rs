@@ -0,0 +1,233 @@
+//#######################################################################o
+// KMAC is the Keccak message authentication code of SP 800-185. It's    |
+// cSHAKE with the function name "KMAC" and a customization string, the  |
+// key being absorbed first in a block of its own, then the message and  |
+// the requested tag length. Since the length is hashed, a shorter tag   |
+// isn't a prefix of a longer one. KMACXOF absorbs a length of zero and  |
+// reads an output of any length.                                        |
+//                                                                       |
+// @Refrences:                                                           |
+// SP 800-185: https://doi.org/10.6028/NIST.SP.800-185                   |
+//#######################################################################o
+
+use crate::error::CryptoError;
+use crate::hash::sha3::{right_encode, CShake, XofReader};
+use crate::util::ct_eq;
+
+// Shortest tag accepted by `verify`.
+const MIN_TAG_LEN: usize = 32;
+
+////////////////////////////////////////////////////////////////////////////
+/// `Kmac` is a streaming KMAC computation, use the `Kmac128` and
+/// `Kmac256` aliases.
+///# Example
+/// ```
+/// use cryptolib::mac::kmac::Kmac256;
+///
+/// let mut mac = Kmac256::new(b"key", b"My Tagged Application");
+/// mac.update(b"message");
+/// let tag = mac.finalize(64);
+/// let mut mac = Kmac256::new(b"key", b"My Tagged Application");
+/// mac.update(b"message");
+/// assert!(mac.verify(&tag, 64).is_ok());
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Kmac<const RATE: usize> {
+    cshake: CShake<RATE>,
+}
+
+/// KMAC128, 128-bit security.
+pub type Kmac128 = Kmac<168>;
+/// KMAC256, 256-bit security.
+pub type Kmac256 = Kmac<136>;
+
+impl<const RATE: usize> Kmac<RATE> {
+    ////////////////////////////////////////////////////////////////////
+    /// Creates the MAC.
+    ///# Arguments
+    ///* `key`: a key of any length.
+    ///* `customization`: a string chosen by the application.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8], customization: &[u8]) -> Kmac<RATE> {
+        let mut cshake = CShake::new(b"KMAC", customization);
+        cshake.absorb_bytepad(&[key]);
+        Kmac { cshake }
+    }
+
+    /// Absorbs `data` into the MAC.
+    pub fn update(&mut self, data: &[u8]) {
+        self.cshake.update(data);
+    }
+
+    /// Returns the `out_len` bytes tag.
+    pub fn finalize(mut self, out_len: usize) -> Vec<u8> {
+        self.cshake.update(&right_encode(8 * out_len as u64));
+        self.cshake.finalize_xof().read_vec(out_len)
+    }
+
+    /// Returns the reader of KMACXOF.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.cshake.update(&right_encode(0));
+        self.cshake.finalize_xof()
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Checks the tag in constant time.
+    ///# Arguments
+    ///* `tag`: the received tag.
+    ///* `tag_len`: the tag length L expected by the verifier.
+    ///# Returns
+    /// `CryptoError::InvalidTagLength` if `tag` isn't `tag_len` bytes or
+    /// `tag_len` is shorter than 32 bytes, and
+    /// `CryptoError::AuthenticationFailed` if it doesn't match.
+    ///# Note
+    /// '''
+    /// The length comes from the verifier, not from the received tag:
+    /// otherwise a forger could send a 1 byte tag, which matches the
+    /// 1 byte KMAC of the message with probability 1/256.
+    /// '''
+    ////////////////////////////////////////////////////////////////////
+    pub fn verify(self, tag: &[u8], tag_len: usize) -> Result<(), CryptoError> {
+        if tag_len < MIN_TAG_LEN || tag.len() != tag_len {
+            return Err(CryptoError::InvalidTagLength);
+        }
+        if ct_eq(&self.finalize(tag_len), tag) {
+            Ok(())
+        } else {
+            Err(CryptoError::AuthenticationFailed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    // The samples use the same key, RATE is 168 for KMAC128 and 136 for
+    // KMAC256.
+    fn kmac<const RATE: usize>(data: &[u8], customization: &[u8], out_len: usize) -> Vec<u8> {
+        let key: Vec<u8> = (0x40..0x60).collect();
+        let mut mac = Kmac::<RATE>::new(&key, customization);
+        mac.update(data);
+        mac.finalize(out_len)
+    }
+
+    fn kmac_xof<const RATE: usize>(data: &[u8], customization: &[u8], out_len: usize) -> Vec<u8> {
+        let key: Vec<u8> = (0x40..0x60).collect();
+        let mut mac = Kmac::<RATE>::new(&key, customization);
+        mac.update(data);
+        mac.finalize_xof().read_vec(out_len)
+    }
+
+    #[test]
+    fn test_kmac() {
+        // SP 800-185 KMAC samples.
+        let data: Vec<u8> = (0..200).collect();
+        assert_eq!(
+            kmac::<168>(&data[..4], b"", 32),
+            hex("e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e")
+        );
+        assert_eq!(
+            kmac::<168>(&data[..4], b"My Tagged Application", 32),
+            hex("3b1fba963cd8b0b59e8c1a6d71888b7143651af8ba0a7070c0979e2811324aa5")
+        );
+        assert_eq!(
+            kmac::<168>(&data, b"My Tagged Application", 32),
+            hex("1f5b4e6cca02209e0dcb5ca635b89a15e271ecc760071dfd805faa38f9729230")
+        );
+        assert_eq!(
+            kmac::<136>(&data[..4], b"My Tagged Application", 64),
+            hex(
+                "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7
+                 f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd"
+            )
+        );
+        assert_eq!(
+            kmac::<136>(&data, b"", 64),
+            hex(
+                "75358cf39e41494e949707927cee0af20a3ff553904c86b08f21cc414bcfd691
+                 589d27cf5e15369cbbff8b9a4c2eb17800855d0235ff635da82533ec6b759b69"
+            )
+        );
+        assert_eq!(
+            kmac::<136>(&data, b"My Tagged Application", 64),
+            hex(
+                "b58618f71f92e1d56c1b8c55ddd7cd188b97b4ca4d99831eb2699a837da2e4d9
+                 70fbacfde50033aea585f1a2708510c32d07880801bd182898fe476876fc8965"
+            )
+        );
+    }
+
+    #[test]
+    fn test_kmac_xof() {
+        // SP 800-185 KMACXOF samples.
+        let data: Vec<u8> = (0..200).collect();
+        assert_eq!(
+            kmac_xof::<168>(&data[..4], b"", 32),
+            hex("cd83740bbd92ccc8cf032b1481a0f4460e7ca9dd12b08a0c4031178bacd6ec35")
+        );
+        assert_eq!(
+            kmac_xof::<136>(&data[..4], b"My Tagged Application", 64),
+            hex(
+                "1755133f1534752aad0748f2c706fb5c784512cab835cd15676b16c0c6647fa9
+                 6faa7af634a0bf8ff6df39374fa00fad9a39e322a7c92065a64eb1fb0801eb2b"
+            )
+        );
+        assert_eq!(
+            kmac_xof::<136>(&data, b"My Tagged Application", 64),
+            hex(
+                "d5be731c954ed7732846bb59dbe3a8e30f83e77a4bff4459f2f1c2b4ecebb8ce
+                 67ba01c62e8ab8578d2d499bd1bb276768781190020a306a97de281dcc30305d"
+            )
+        );
+    }
+
+    #[test]
+    fn test_kmac_verify() {
+        let key = b"key";
+        let tag = {
+            let mut mac = Kmac256::new(key, b"");
+            mac.update(b"message");
+            mac.finalize(32)
+        };
+        let mut mac = Kmac256::new(key, b"");
+        mac.update(b"mess");
+        mac.update(b"age");
+        assert!(mac.clone().verify(&tag, 32).is_ok());
+        // The received tag doesn't choose L.
+        for len in [0, 1, 16, 31] {
+            assert_eq!(
+                mac.clone().verify(&tag[..len], 32),
+                Err(CryptoError::InvalidTagLength)
+            );
+        }
+        let short = {
+            let mut mac = Kmac256::new(key, b"");
+            mac.update(b"message");
+            mac.finalize(1)
+        };
+        assert_eq!(
+            mac.clone().verify(&short, 1),
+            Err(CryptoError::InvalidTagLength)
+        );
+        // A tag of another length or customization doesn't match.
+        let long = {
+            let mut mac = Kmac256::new(key, b"");
+            mac.update(b"message");
+            mac.finalize(48)
+        };
+        assert!(mac.clone().verify(&long, 48).is_ok());
+        assert_eq!(
+            mac.clone().verify(&long[..32], 32),
+            Err(CryptoError::AuthenticationFailed)
+        );
+        let mut other = Kmac256::new(key, b"other");
+        other.update(b"message");
+        assert_eq!(
+            other.verify(&tag, 32),
+            Err(CryptoError::AuthenticationFailed)
+        );
+    }
+}
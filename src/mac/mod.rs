@@ -0,0 +1,3 @@
+pub mod cmac;
+pub mod hmac;
+pub mod kmac;
@@ -0,0 +1,290 @@
+//#######################################################################o
+// Rabbit is a stream cipher from the eSTREAM portfolio with a 128-bit   |
+// key and an optional 64-bit IV. Its state is eight 32-bit variables    |
+// and eight 32-bit counters with a carry bit. Each iteration advances   |
+// the counters and mixes the squares of variable + counter (the g       |
+// function) into the variables, then 128 keystream bits are extracted.  |
+// The IV setup starts again from the keyed state so a single key setup  |
+// serves many IVs. The state evolves nonlinearly, seeking replays the   |
+// keystream from the start.                                             |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 4503: https://www.rfc-editor.org/rfc/rfc4503                      |
+//#######################################################################o
+
+use crate::error::CryptoError;
+use crate::stream::{advance, skip, StreamCipher};
+
+// Counter increments.
+const A: [u32; 8] = [
+    0x4d34d34d, 0xd34d34d3, 0x34d34d34, 0x4d34d34d, 0xd34d34d3, 0x34d34d34, 0x4d34d34d, 0xd34d34d3,
+];
+
+#[derive(Clone)]
+struct State {
+    x: [u32; 8],
+    c: [u32; 8],
+    carry: u32,
+}
+
+fn g(u: u32, v: u32) -> u32 {
+    let square = (u.wrapping_add(v) as u64).pow(2);
+    (square ^ (square >> 32)) as u32
+}
+
+impl State {
+    fn next_state(&mut self) {
+        // The carry of the last counter is kept for the next iteration.
+        for (c, a) in self.c.iter_mut().zip(A) {
+            let t = *c as u64 + a as u64 + self.carry as u64;
+            self.carry = (t >> 32) as u32;
+            *c = t as u32;
+        }
+        let mut gs = [0_u32; 8];
+        for (j, gj) in gs.iter_mut().enumerate() {
+            *gj = g(self.x[j], self.c[j]);
+        }
+        for j in 0..8 {
+            // Even variables take two rotated neighbours, odd ones one.
+            let (g1, g2) = (gs[(j + 7) % 8], gs[(j + 6) % 8]);
+            self.x[j] = if j % 2 == 0 {
+                gs[j]
+                    .wrapping_add(g1.rotate_left(16))
+                    .wrapping_add(g2.rotate_left(16))
+            } else {
+                gs[j].wrapping_add(g1.rotate_left(8)).wrapping_add(g2)
+            };
+        }
+    }
+
+    // The 16 bytes extracted after an iteration.
+    fn extract(&self) -> [u8; 16] {
+        let x = &self.x;
+        let mut out = [0_u8; 16];
+        for (i, bytes) in out.chunks_exact_mut(4).enumerate() {
+            let (a, b, c) = (x[2 * i], x[(2 * i + 5) % 8], x[(2 * i + 3) % 8]);
+            let s = (a ^ (b >> 16) ^ (c << 16)) as u16 as u32 | ((a >> 16) ^ c) << 16;
+            bytes.copy_from_slice(&s.to_le_bytes());
+        }
+        out
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Rabbit` is a keyed Rabbit keystream, encryption and decryption are
+/// the same operation.
+///# Example
+/// ```
+/// use cryptolib::rabbit::Rabbit;
+/// use cryptolib::stream::StreamCipher;
+///
+/// let mut data = *b"any length";
+/// let mut rabbit = Rabbit::new(&[1; 16], Some(&[2; 8])).unwrap();
+/// rabbit.apply_keystream(&mut data).unwrap();
+/// rabbit.seek(0).unwrap();
+/// rabbit.apply_keystream(&mut data).unwrap();
+/// assert_eq!(&data, b"any length");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Rabbit {
+    state: State,
+    keystream: [u8; 16],
+    used: usize,
+    // The state at position 0, seeking backwards starts again from it.
+    start: State,
+    pos: u64,
+}
+
+impl Rabbit {
+    ////////////////////////////////////////////////////////////////////
+    /// Initializes the cipher.
+    ///# Arguments
+    ///* `key`: the 16 bytes key.
+    ///* `iv`: an optional 8 bytes IV.
+    ///# Returns
+    /// The keystream, `CryptoError::InvalidKeyLength` or
+    /// `CryptoError::InvalidNonceLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8], iv: Option<&[u8]>) -> Result<Rabbit, CryptoError> {
+        if key.len() != 16 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let mut k = [0_u32; 8];
+        for (k, bytes) in k.iter_mut().zip(key.chunks_exact(2)) {
+            *k = u16::from_le_bytes([bytes[0], bytes[1]]) as u32;
+        }
+        let mut state = State {
+            x: [0; 8],
+            c: [0; 8],
+            carry: 0,
+        };
+        for j in 0..8 {
+            if j % 2 == 0 {
+                state.x[j] = (k[(j + 1) % 8] << 16) | k[j];
+                state.c[j] = (k[(j + 4) % 8] << 16) | k[(j + 5) % 8];
+            } else {
+                state.x[j] = (k[(j + 5) % 8] << 16) | k[(j + 4) % 8];
+                state.c[j] = (k[j] << 16) | k[(j + 1) % 8];
+            }
+        }
+        for _ in 0..4 {
+            state.next_state();
+        }
+        for j in 0..8 {
+            state.c[j] ^= state.x[(j + 4) % 8];
+        }
+
+        if let Some(iv) = iv {
+            let iv: [u8; 8] = iv.try_into().map_err(|_| CryptoError::InvalidNonceLength)?;
+            let iv = u64::from_le_bytes(iv);
+            let (low, high) = (iv as u32, (iv >> 32) as u32);
+            let mid1 = (high & 0xffff0000) | (low >> 16);
+            let mid0 = (high << 16) | (low & 0xffff);
+            for (c, v) in state
+                .c
+                .iter_mut()
+                .zip([low, mid1, high, mid0, low, mid1, high, mid0])
+            {
+                *c ^= v;
+            }
+            for _ in 0..4 {
+                state.next_state();
+            }
+        }
+        Ok(Rabbit {
+            start: state.clone(),
+            state,
+            keystream: [0; 16],
+            used: 16,
+            pos: 0,
+        })
+    }
+}
+
+impl StreamCipher for Rabbit {
+    fn apply_keystream(&mut self, data: &mut [u8]) -> Result<(), CryptoError> {
+        self.pos = advance(self.pos, data.len(), None)?;
+        for b in data {
+            if self.used == 16 {
+                self.state.next_state();
+                self.keystream = self.state.extract();
+                self.used = 0;
+            }
+            *b ^= self.keystream[self.used];
+            self.used += 1;
+        }
+        Ok(())
+    }
+
+    /// Seeking backwards replays the keystream from the start.
+    fn seek(&mut self, pos: u64) -> Result<(), CryptoError> {
+        if pos < self.pos {
+            self.state = self.start.clone();
+            self.used = 16;
+            self.pos = 0;
+        }
+        skip(self, pos - self.pos)
+    }
+
+    fn current_pos(&self) -> u64 {
+        self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    // RFC 4503 prints the key, the IV and each 16 bytes keystream block
+    // as big-endian integers.
+    fn reversed(s: &str, chunk: usize) -> Vec<u8> {
+        hex(s)
+            .chunks(chunk)
+            .flat_map(|c| c.iter().rev().copied().collect::<Vec<u8>>())
+            .collect()
+    }
+
+    fn keystream(key: &str, iv: Option<&str>, len: usize) -> Vec<u8> {
+        let iv = iv.map(|iv| reversed(iv, 8));
+        let mut rabbit = Rabbit::new(&reversed(key, 16), iv.as_deref()).unwrap();
+        let mut out = vec![0; len];
+        rabbit.apply_keystream(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_rabbit_rfc4503() {
+        // Appendix A.1, without IV.
+        assert_eq!(
+            keystream("00000000000000000000000000000000", None, 48),
+            reversed(
+                "b15754f036a5d6ecf56b45261c4af702
+                 88e8d815c59c0c397b696c4789c68aa7
+                 f416a1c3700cd451da68d1881673d696",
+                16
+            )
+        );
+        assert_eq!(
+            keystream("912813292e3d36fe3bfc62f1dc51c3ac", None, 48),
+            reversed(
+                "3d2df3c83ef627a1e97fc38487e2519c
+                 f576cd61f4405b8896bf53aa8554fc19
+                 e5547473fbdb43508ae53b20204d4c5e",
+                16
+            )
+        );
+        // Appendix A.2, with IV.
+        assert_eq!(
+            keystream(
+                "00000000000000000000000000000000",
+                Some("0000000000000000"),
+                48
+            ),
+            reversed(
+                "c6a7275ef85495d87ccd5d376705b7ed
+                 5f29a6ac04f5efd47b8f293270dc4a8d
+                 2ade822b29de6c1ee52bdb8a47bf8f66",
+                16
+            )
+        );
+        assert_eq!(
+            keystream(
+                "00000000000000000000000000000000",
+                Some("c373f575c1267e59"),
+                16
+            ),
+            reversed("1fcd4eb9580012e2e0dccc9222017d6d", 16)
+        );
+    }
+
+    #[test]
+    fn test_rabbit_streaming() {
+        let expected = keystream(
+            "00000000000000000000000000000000",
+            Some("c373f575c1267e59"),
+            100,
+        );
+        let mut rabbit = Rabbit::new(&[0; 16], Some(&reversed("c373f575c1267e59", 8))).unwrap();
+        let mut out = vec![0; 100];
+        for chunk in out.chunks_mut(7) {
+            rabbit.apply_keystream(chunk).unwrap();
+        }
+        assert_eq!(out, expected);
+        for pos in [99, 33, 0, 16] {
+            rabbit.seek(pos).unwrap();
+            let mut byte = [0];
+            rabbit.apply_keystream(&mut byte).unwrap();
+            assert_eq!(byte[0], expected[pos as usize], "seek to {}", pos);
+        }
+        assert_eq!(
+            Rabbit::new(&[0; 15], None).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Rabbit::new(&[0; 16], Some(&[0; 16])).err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+    }
+}
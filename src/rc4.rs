@@ -0,0 +1,193 @@
+//#######################################################################o
+// LEGACY: RC4 is broken and must not be used to protect new data. It's  |
+// provided only to read WEP captures, RC4 encrypted PDF files and       |
+// Kerberos RC4-HMAC tickets.                                            |
+// RC4 is a byte oriented stream cipher: the key schedule (KSA) shuffles |
+// a 256 bytes permutation with the key and the generator (PRGA) keeps   |
+// swapping its entries to output one keystream byte at a time. The      |
+// first keystream bytes are strongly biased, RC4-drop[n] discards the   |
+// first n bytes (RFC 4345 uses 1536).                                   |
+//                                                                       |
+// @Refrences:                                                           |
+// RFC 6229: https://www.rfc-editor.org/rfc/rfc6229                      |
+// RFC 7465: https://www.rfc-editor.org/rfc/rfc7465                      |
+//#######################################################################o
+
+use crate::error::CryptoError;
+use crate::stream::{advance, skip, StreamCipher};
+
+#[derive(Clone)]
+struct State {
+    s: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl State {
+    fn next_byte(&mut self) -> u8 {
+        self.i = self.i.wrapping_add(1);
+        self.j = self.j.wrapping_add(self.s[self.i as usize]);
+        self.s.swap(self.i as usize, self.j as usize);
+        self.s[self.s[self.i as usize].wrapping_add(self.s[self.j as usize]) as usize]
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Rc4` is a keyed RC4 keystream, encryption and decryption are the
+/// same operation. Legacy only, RC4 is broken.
+///# Example
+/// ```
+/// use cryptolib::rc4::Rc4;
+/// use cryptolib::stream::StreamCipher;
+///
+/// let mut data = *b"Plaintext";
+/// Rc4::new(b"Key").unwrap().apply_keystream(&mut data).unwrap();
+/// assert_eq!(data, [0xbb, 0xf3, 0x16, 0xe8, 0xd9, 0x40, 0xaf, 0x0a, 0xd3]);
+/// Rc4::new(b"Key").unwrap().apply_keystream(&mut data).unwrap();
+/// assert_eq!(&data, b"Plaintext");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Rc4 {
+    state: State,
+    // The state at position 0, seeking backwards starts again from it.
+    start: State,
+    pos: u64,
+}
+
+impl Rc4 {
+    ////////////////////////////////////////////////////////////////////
+    /// Runs the key schedule with a 1 to 256 bytes `key`.
+    ///# Returns
+    /// The keystream or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8]) -> Result<Rc4, CryptoError> {
+        Self::with_drop(key, 0)
+    }
+
+    ////////////////////////////////////////////////////////////////////
+    /// Creates an RC4-drop[n] keystream, the first `drop` bytes of the
+    /// keystream are discarded and position 0 is the byte after them.
+    ///# Returns
+    /// The keystream or `CryptoError::InvalidKeyLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn with_drop(key: &[u8], drop: usize) -> Result<Rc4, CryptoError> {
+        if key.is_empty() || key.len() > 256 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        let mut s = [0_u8; 256];
+        for (i, s) in s.iter_mut().enumerate() {
+            *s = i as u8;
+        }
+        let mut j = 0_u8;
+        for i in 0..256 {
+            j = j.wrapping_add(s[i]).wrapping_add(key[i % key.len()]);
+            s.swap(i, j as usize);
+        }
+        let mut state = State { s, i: 0, j: 0 };
+        for _ in 0..drop {
+            state.next_byte();
+        }
+        Ok(Rc4 {
+            start: state.clone(),
+            state,
+            pos: 0,
+        })
+    }
+}
+
+impl StreamCipher for Rc4 {
+    fn apply_keystream(&mut self, data: &mut [u8]) -> Result<(), CryptoError> {
+        self.pos = advance(self.pos, data.len(), None)?;
+        for b in data {
+            *b ^= self.state.next_byte();
+        }
+        Ok(())
+    }
+
+    /// Seeking backwards replays the keystream from the start.
+    fn seek(&mut self, pos: u64) -> Result<(), CryptoError> {
+        if pos < self.pos {
+            self.state = self.start.clone();
+            self.pos = 0;
+        }
+        skip(self, pos - self.pos)
+    }
+
+    fn current_pos(&self) -> u64 {
+        self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    fn keystream(rc4: &mut Rc4, len: usize) -> Vec<u8> {
+        let mut out = vec![0; len];
+        rc4.apply_keystream(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_rc4_rfc6229() {
+        // RFC 6229 section 2, 40-bit and 256-bit keys.
+        let mut rc4 = Rc4::new(&hex("0102030405")).unwrap();
+        let stream = keystream(&mut rc4, 4112);
+        for (offset, expected) in [
+            (0, "b2396305f03dc027ccc3524a0a1118a8"),
+            (16, "6982944f18fc82d589c403a47a0d0919"),
+            (240, "28cb1132c96ce286421dcaadb8b69eae"),
+            (256, "1cfcf62b03eddb641d77dfcf7f8d8c93"),
+            (1536, "d8729db41882259bee4f825325f5a130"),
+            (4096, "ff25b58995996707e51fbdf08b34d875"),
+        ] {
+            assert_eq!(
+                stream[offset..offset + 16],
+                hex(expected)[..],
+                "offset {}",
+                offset
+            );
+        }
+        let key: Vec<u8> = (1..=32).collect();
+        let stream = keystream(&mut Rc4::new(&key).unwrap(), 4112);
+        assert_eq!(stream[..16], hex("eaa6bd25880bf93d3f5d1e4ca2611d91")[..]);
+        assert_eq!(stream[4096..], hex("f3e4c0a2e02d1d01f7f0a74618af2b48")[..]);
+    }
+
+    #[test]
+    fn test_rc4_drop() {
+        // RC4-drop[1536] starts at offset 1536 of the RC4 keystream.
+        let mut rc4 = Rc4::with_drop(&hex("0102030405"), 1536).unwrap();
+        assert_eq!(
+            keystream(&mut rc4, 16),
+            hex("d8729db41882259bee4f825325f5a130")
+        );
+        // Pieces of any size continue the keystream.
+        let mut rc4 = Rc4::new(b"Secret").unwrap();
+        let mut data = *b"Attack at dawn";
+        let (a, b) = data.split_at_mut(5);
+        rc4.apply_keystream(a).unwrap();
+        rc4.apply_keystream(b).unwrap();
+        assert_eq!(data.to_vec(), hex("45a01f645fc35b383552544b9bf5"));
+        // Seeking, backwards replays from the dropped position.
+        let mut rc4 = Rc4::with_drop(&hex("0102030405"), 256).unwrap();
+        rc4.seek(1280).unwrap();
+        assert_eq!(
+            keystream(&mut rc4, 16),
+            hex("d8729db41882259bee4f825325f5a130")
+        );
+        assert_eq!(rc4.current_pos(), 1296);
+        rc4.seek(0).unwrap();
+        assert_eq!(
+            keystream(&mut rc4, 16),
+            hex("1cfcf62b03eddb641d77dfcf7f8d8c93")
+        );
+        assert_eq!(Rc4::new(&[]).err(), Some(CryptoError::InvalidKeyLength));
+        assert_eq!(
+            Rc4::new(&[0; 257]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+    }
+}
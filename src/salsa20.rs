@@ -0,0 +1,238 @@
+//#######################################################################o
+// Salsa20/20 is Bernstein's stream cipher from the eSTREAM portfolio,   |
+// with a 128-bit or 256-bit key, a 64-bit nonce and a 64-bit block      |
+// counter. Each 64 bytes keystream block is the 4x4 input matrix of     |
+// 32-bit words (constants on the diagonal, key, nonce and counter) plus |
+// the matrix after 20 rounds of quarter rounds (add, rotate, xor),      |
+// alternating over the columns and the rows.                            |
+// Blocks are independent so seeking is immediate.                       |
+//                                                                       |
+// @Refrences:                                                           |
+// Bernstein: https://cr.yp.to/snuffle/spec.pdf                          |
+//#######################################################################o
+
+use crate::error::CryptoError;
+use crate::stream::{advance, StreamCipher};
+use crate::util::xor_in_place;
+
+// "expand 32-byte k" and "expand 16-byte k".
+const SIGMA: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+const TAU: [u32; 4] = [0x61707865, 0x3120646e, 0x79622d36, 0x6b206574];
+
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+    x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+    x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+    x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Salsa20` is a keyed Salsa20/20 keystream, encryption and decryption
+/// are the same operation.
+///# Example
+/// ```
+/// use cryptolib::salsa20::Salsa20;
+/// use cryptolib::stream::StreamCipher;
+///
+/// let mut salsa = Salsa20::new(&[1; 32], &[2; 8]).unwrap();
+/// let mut data = *b"any length";
+/// salsa.apply_keystream(&mut data).unwrap();
+/// salsa.seek(0).unwrap();
+/// salsa.apply_keystream(&mut data).unwrap();
+/// assert_eq!(&data, b"any length");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Salsa20 {
+    // The input matrix, the counter words are set for each block.
+    state: [u32; 16],
+    // The next block to generate.
+    counter: u64,
+    // The last keystream block and how many of its bytes were used.
+    keystream: [u8; 64],
+    used: usize,
+    pos: u64,
+}
+
+impl Salsa20 {
+    ////////////////////////////////////////////////////////////////////
+    /// Initializes the cipher with a 16 or 32 bytes `key` and an 8 bytes
+    /// `nonce`.
+    ///# Returns
+    /// The keystream, `CryptoError::InvalidKeyLength` or
+    /// `CryptoError::InvalidNonceLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8], nonce: &[u8]) -> Result<Salsa20, CryptoError> {
+        let (constants, second_half) = match key.len() {
+            16 => (TAU, key),
+            32 => (SIGMA, &key[16..]),
+            _ => return Err(CryptoError::InvalidKeyLength),
+        };
+        if nonce.len() != 8 {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        let word = |bytes: &[u8]| u32::from_le_bytes(bytes.try_into().unwrap());
+        let mut state = [0_u32; 16];
+        for (i, c) in constants.into_iter().enumerate() {
+            state[5 * i] = c;
+        }
+        for i in 0..4 {
+            state[1 + i] = word(&key[4 * i..4 * i + 4]);
+            state[11 + i] = word(&second_half[4 * i..4 * i + 4]);
+        }
+        state[6] = word(&nonce[..4]);
+        state[7] = word(&nonce[4..]);
+        Ok(Salsa20 {
+            state,
+            counter: 0,
+            keystream: [0; 64],
+            used: 64,
+            pos: 0,
+        })
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Generates the keystream block `self.counter` and increments the
+    /// counter.
+    //////////////////////////////////////////////////////////////////
+    fn next_block(&mut self) {
+        let mut input = self.state;
+        input[8] = self.counter as u32;
+        input[9] = (self.counter >> 32) as u32;
+        let mut x = input;
+        for _ in 0..10 {
+            quarter_round(&mut x, 0, 4, 8, 12);
+            quarter_round(&mut x, 5, 9, 13, 1);
+            quarter_round(&mut x, 10, 14, 2, 6);
+            quarter_round(&mut x, 15, 3, 7, 11);
+            quarter_round(&mut x, 0, 1, 2, 3);
+            quarter_round(&mut x, 5, 6, 7, 4);
+            quarter_round(&mut x, 10, 11, 8, 9);
+            quarter_round(&mut x, 15, 12, 13, 14);
+        }
+        for (i, bytes) in self.keystream.chunks_exact_mut(4).enumerate() {
+            bytes.copy_from_slice(&x[i].wrapping_add(input[i]).to_le_bytes());
+        }
+        self.counter = self.counter.wrapping_add(1);
+    }
+}
+
+impl StreamCipher for Salsa20 {
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts or decrypts `data` in place. The 64-bit block counter
+    /// outlasts the `u64` byte position, which is the limit.
+    ////////////////////////////////////////////////////////////////////
+    fn apply_keystream(&mut self, data: &mut [u8]) -> Result<(), CryptoError> {
+        self.pos = advance(self.pos, data.len(), None)?;
+        for chunk in data.chunks_mut(64) {
+            // Leftover bytes of the last keystream block first.
+            let n = chunk.len().min(64 - self.used);
+            let (head, tail) = chunk.split_at_mut(n);
+            xor_in_place(head, &self.keystream[self.used..]);
+            self.used += n;
+            if !tail.is_empty() {
+                self.next_block();
+                xor_in_place(tail, &self.keystream);
+                self.used = tail.len();
+            }
+        }
+        Ok(())
+    }
+
+    fn seek(&mut self, pos: u64) -> Result<(), CryptoError> {
+        self.counter = pos / 64;
+        self.used = 64;
+        if !pos.is_multiple_of(64) {
+            self.next_block();
+            self.used = (pos % 64) as usize;
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    fn current_pos(&self) -> u64 {
+        self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    fn keystream(key: &[u8], nonce: &[u8], pos: u64, len: usize) -> Vec<u8> {
+        let mut salsa = Salsa20::new(key, nonce).unwrap();
+        salsa.seek(pos).unwrap();
+        let mut out = vec![0; len];
+        salsa.apply_keystream(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_salsa20_estream() {
+        // eSTREAM set 1 vector 0, 256-bit and 128-bit keys.
+        let mut key = [0_u8; 32];
+        key[0] = 0x80;
+        assert_eq!(
+            keystream(&key, &[0; 8], 0, 64),
+            hex(
+                "e3be8fdd8beca2e3ea8ef9475b29a6e7003951e1097a5c38d23b7a5fad9f6844
+                 b22c97559e2723c7cbbd3fe4fc8d9a0744652a83e72a9c461876af4d7ef1a117"
+            )
+        );
+        assert_eq!(
+            keystream(&key[..16], &[0; 8], 0, 64),
+            hex(
+                "4dfa5e481da23ea09a31022050859936da52fcee218005164f267cb65f5cfd7f
+                 2b4f97e0ff16924a52df269515110a07f9e460bc65ef95da58f740b7d1dbb0aa"
+            )
+        );
+    }
+
+    #[test]
+    fn test_salsa20_seek() {
+        // The counter carries into its second word, checked against
+        // libsodium.
+        let key: Vec<u8> = (0..32).collect();
+        let nonce = [0, 1, 2, 3, 4, 5, 6, 7];
+        let expected = hex(
+            "60d0f601a5a3aedec240597b0138bb8272eb17d524c523f5f514d83bd7217805
+             17678be2a6578459b8325dbfbe8650d4ae3a739423bab1faf0b0347bdb8bb3f8
+             e58a3ce12a19d89b151819eec0956ae8b8ba7df7d537480a39b6678cbbda10f3
+             f095aa1bc8e860392de7b267fb1245d1ff12efd12887cd1c797ea18bb7261e74",
+        );
+        let start = 0xffffffff * 64;
+        assert_eq!(keystream(&key, &nonce, start, 128), expected);
+        // Pieces of any size, and seeking anywhere.
+        let mut salsa = Salsa20::new(&key, &nonce).unwrap();
+        salsa.seek(start).unwrap();
+        let mut out = vec![0; 128];
+        for chunk in out.chunks_mut(17) {
+            salsa.apply_keystream(chunk).unwrap();
+        }
+        assert_eq!(out, expected);
+        for offset in [127, 63, 0, 64, 1] {
+            assert_eq!(
+                keystream(&key, &nonce, start + offset, 1)[0],
+                expected[offset as usize],
+                "seek to {}",
+                offset
+            );
+        }
+        // The position ends at u64::MAX.
+        salsa.seek(u64::MAX - 1).unwrap();
+        assert_eq!(
+            salsa.apply_keystream(&mut [0; 2]),
+            Err(CryptoError::CounterOverflow)
+        );
+        salsa.apply_keystream(&mut [0; 1]).unwrap();
+        assert_eq!(
+            Salsa20::new(&[0; 24], &[0; 8]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Salsa20::new(&[0; 32], &[0; 12]).err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+    }
+}
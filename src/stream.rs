@@ -0,0 +1,147 @@
+//#######################################################################o
+// A stream cipher xors the data with a keystream generated from the     |
+// key and the nonce, encryption and decryption are the same operation.  |
+// `StreamCipher` is the interface shared by the stream ciphers of the   |
+// crate and the CTR and OFB modes, so a cipher can be picked at runtime |
+// behind a `Box<dyn StreamCipher>`.                                     |
+// The position is counted in keystream bytes from the start. Counter    |
+// based ciphers (ChaCha20, Salsa20, CTR) seek in constant time, the     |
+// others go back to their initial state and generate the keystream up   |
+// to the position.                                                      |
+// A keystream must never be reused, the ciphers return an error rather  |
+// than wrapping their counter around.                                   |
+//#######################################################################o
+
+use crate::error::CryptoError;
+
+////////////////////////////////////////////////////////////////////////////
+/// `StreamCipher` is the interface of the keyed keystream generators.
+///# Example
+/// ```
+/// use cryptolib::rc4::Rc4;
+/// use cryptolib::stream::StreamCipher;
+///
+/// let mut cipher: Box<dyn StreamCipher> = Box::new(Rc4::new(b"Key").unwrap());
+/// let mut data = *b"Plaintext";
+/// cipher.apply_keystream(&mut data).unwrap();
+/// assert_eq!(cipher.current_pos(), 9);
+/// cipher.seek(0).unwrap();
+/// cipher.apply_keystream(&mut data).unwrap();
+/// assert_eq!(&data, b"Plaintext");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+pub trait StreamCipher {
+    ////////////////////////////////////////////////////////////////////
+    /// Encrypts or decrypts `data` in place, continuing the keystream
+    /// at the current position.
+    ///# Returns
+    /// `CryptoError::CounterOverflow` if the keystream would run past
+    /// the end of the counter, in which case `data` is left unchanged.
+    ////////////////////////////////////////////////////////////////////
+    fn apply_keystream(&mut self, data: &mut [u8]) -> Result<(), CryptoError>;
+
+    ////////////////////////////////////////////////////////////////////
+    /// Moves to the byte `pos` of the keystream.
+    ///# Returns
+    /// `CryptoError::CounterOverflow` if `pos` is past the end of the
+    /// counter.
+    ////////////////////////////////////////////////////////////////////
+    fn seek(&mut self, pos: u64) -> Result<(), CryptoError>;
+
+    /// Returns the position in the keystream, in bytes.
+    fn current_pos(&self) -> u64;
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Returns the position after `len` more keystream bytes.
+///# Arguments
+///* `pos`: the current position.
+///* `len`: the number of bytes to process.
+///* `max`: the length of the keystream, `None` if it only ends at
+///  `u64::MAX`.
+///# Returns
+/// The new position or `CryptoError::CounterOverflow`.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn advance(pos: u64, len: usize, max: Option<u64>) -> Result<u64, CryptoError> {
+    let end = pos
+        .checked_add(len as u64)
+        .ok_or(CryptoError::CounterOverflow)?;
+    match max {
+        Some(max) if end > max => Err(CryptoError::CounterOverflow),
+        _ => Ok(end),
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+/// Discards the next `len` keystream bytes of `cipher`, used by the
+/// ciphers that can't seek directly.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn skip<S: StreamCipher>(cipher: &mut S, mut len: u64) -> Result<(), CryptoError> {
+    let mut scratch = [0_u8; 256];
+    while len > 0 {
+        let n = len.min(scratch.len() as u64) as usize;
+        cipher.apply_keystream(&mut scratch[..n])?;
+        len -= n as u64;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::des::Des;
+    use crate::block::mode::ctr::Ctr;
+    use crate::block::mode::ofb::Ofb;
+    use crate::chacha::ChaCha20;
+    use crate::hc128::Hc128;
+    use crate::rabbit::Rabbit;
+    use crate::rc4::Rc4;
+    use crate::salsa20::Salsa20;
+    use crate::trivium::Trivium;
+
+    // The ciphers picked by name, as a configuration would.
+    fn by_name(name: &str) -> Box<dyn StreamCipher> {
+        match name {
+            "chacha20" => Box::new(ChaCha20::new(&[1; 32], &[2; 12]).unwrap()),
+            "salsa20" => Box::new(Salsa20::new(&[1; 32], &[2; 8]).unwrap()),
+            "rc4" => Box::new(Rc4::with_drop(&[1; 16], 1536).unwrap()),
+            "hc128" => Box::new(Hc128::new(&[1; 16], &[2; 16]).unwrap()),
+            "rabbit" => Box::new(Rabbit::new(&[1; 16], Some(&[2; 8])).unwrap()),
+            "trivium" => Box::new(Trivium::new(&[1; 10], &[2; 10]).unwrap()),
+            "des-ctr" => Box::new(Ctr::new(Des::new(&[1; 8]).unwrap(), &[2; 8]).unwrap()),
+            "des-ofb" => Box::new(Ofb::new(Des::new(&[1; 8]).unwrap(), &[2; 8]).unwrap()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_stream_cipher_seek() {
+        for name in [
+            "chacha20", "salsa20", "rc4", "hc128", "rabbit", "trivium", "des-ctr", "des-ofb",
+        ] {
+            let mut expected = vec![0_u8; 300];
+            by_name(name).apply_keystream(&mut expected).unwrap();
+            let mut cipher = by_name(name);
+            for (pos, len) in [(0, 300), (299, 1), (7, 100), (0, 1), (128, 172), (1, 64)] {
+                cipher.seek(pos).unwrap();
+                let mut buffer = vec![0_u8; len];
+                cipher.apply_keystream(&mut buffer).unwrap();
+                let pos = pos as usize;
+                assert_eq!(buffer, expected[pos..pos + len], "{} at {}", name, pos);
+                assert_eq!(cipher.current_pos(), (pos + len) as u64, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_advance() {
+        assert_eq!(advance(10, 5, None), Ok(15));
+        assert_eq!(advance(10, 5, Some(15)), Ok(15));
+        assert_eq!(advance(10, 6, Some(15)), Err(CryptoError::CounterOverflow));
+        assert_eq!(advance(u64::MAX, 0, None), Ok(u64::MAX));
+        assert_eq!(
+            advance(u64::MAX, 1, None),
+            Err(CryptoError::CounterOverflow)
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//////////////////////////////////////////////////////////////////////
+/// Decodes a hexadecimal string used by the test vectors,
+/// whitespaces are ignored so long vectors can be split.
+//////////////////////////////////////////////////////////////////////
+pub fn hex(data: &str) -> Vec<u8> {
+    let digits: Vec<u8> = data
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .map(|b| (b as char).to_digit(16).expect("invalid hex digit") as u8)
+        .collect();
+    assert!(digits.len().is_multiple_of(2), "odd number of hex digits");
+    digits
+        .chunks_exact(2)
+        .map(|pair| (pair[0] << 4) | pair[1])
+        .collect()
+}
@@ -0,0 +1,226 @@
+//#######################################################################o
+// Trivium is the hardware oriented stream cipher of the eSTREAM         |
+// portfolio by De Canniere and Preneel, with an 80-bit key and an       |
+// 80-bit IV. Its 288-bit state is three shift registers of 93, 84 and   |
+// 111 bits, each one fed by a nonlinear (one AND gate) combination of   |
+// the others. The key and the IV are loaded in the first two registers  |
+// and the state is clocked 1152 times before any output.                |
+// No tap is closer than 64 bits to the register input so 64 steps are   |
+// computed at once. Seeking replays the keystream from the start.       |
+//                                                                       |
+// @Refrences:                                                           |
+// De Canniere, Preneel: https://www.ecrypt.eu.org/stream/p3ciphers/trivium/trivium_p3.pdf
+//#######################################################################o
+
+use crate::error::CryptoError;
+use crate::stream::{advance, skip, StreamCipher};
+
+// Register lengths.
+const LEN_A: usize = 93;
+const LEN_B: usize = 84;
+const LEN_C: usize = 111;
+
+//////////////////////////////////////////////////////////////////////
+/// The values of s_i (i counted from the start of `reg`) over the next
+/// 64 steps, bit k being the value at step k. The registers keep s_1
+/// in their top bit and the last bit in bit 0.
+//////////////////////////////////////////////////////////////////////
+fn tap(reg: u128, len: usize, i: usize) -> u64 {
+    (reg >> (len - i)) as u64
+}
+
+////////////////////////////////////////////////////////////////////////////
+/// `Trivium` is a keyed Trivium keystream, encryption and decryption are
+/// the same operation. As in the eSTREAM reference code, K_1 and IV_1
+/// are the most significant bit of the last byte of the key and the IV,
+/// and the keystream bits are output least significant bit first.
+///# Example
+/// ```
+/// use cryptolib::stream::StreamCipher;
+/// use cryptolib::trivium::Trivium;
+///
+/// let mut data = *b"any length";
+/// let mut trivium = Trivium::new(&[1; 10], &[2; 10]).unwrap();
+/// trivium.apply_keystream(&mut data).unwrap();
+/// trivium.seek(0).unwrap();
+/// trivium.apply_keystream(&mut data).unwrap();
+/// assert_eq!(&data, b"any length");
+/// ```
+////////////////////////////////////////////////////////////////////////////
+#[derive(Clone)]
+pub struct Trivium {
+    a: u128,
+    b: u128,
+    c: u128,
+    keystream: [u8; 8],
+    used: usize,
+    // The registers at position 0, seeking backwards starts again
+    // from them.
+    start: [u128; 3],
+    pos: u64,
+}
+
+impl Trivium {
+    ////////////////////////////////////////////////////////////////////
+    /// Initializes the cipher with a 10 bytes `key` and a 10 bytes `iv`.
+    ///# Returns
+    /// The keystream, `CryptoError::InvalidKeyLength` or
+    /// `CryptoError::InvalidNonceLength`.
+    ////////////////////////////////////////////////////////////////////
+    pub fn new(key: &[u8], iv: &[u8]) -> Result<Trivium, CryptoError> {
+        if key.len() != 10 {
+            return Err(CryptoError::InvalidKeyLength);
+        }
+        if iv.len() != 10 {
+            return Err(CryptoError::InvalidNonceLength);
+        }
+        let load = |bytes: &[u8], len: usize| {
+            (0..80).fold(0_u128, |reg, i| {
+                let bit = (bytes[9 - i / 8] >> (7 - i % 8)) & 1;
+                reg | ((bit as u128) << (len - 1 - i))
+            })
+        };
+        let mut trivium = Trivium {
+            a: load(key, LEN_A),
+            b: load(iv, LEN_B),
+            // s_286, s_287 and s_288 are set.
+            c: 0b111,
+            keystream: [0; 8],
+            used: 8,
+            start: [0; 3],
+            pos: 0,
+        };
+        for _ in 0..1152 / 64 {
+            trivium.clock();
+        }
+        trivium.start = [trivium.a, trivium.b, trivium.c];
+        Ok(trivium)
+    }
+
+    //////////////////////////////////////////////////////////////////
+    /// Clocks the registers 64 times.
+    ///# Returns
+    /// The 64 keystream bits, the first one in bit 0.
+    //////////////////////////////////////////////////////////////////
+    fn clock(&mut self) -> u64 {
+        let (a, b, c) = (self.a, self.b, self.c);
+        let mut t1 = tap(a, LEN_A, 66) ^ tap(a, LEN_A, 93);
+        let mut t2 = tap(b, LEN_B, 69) ^ tap(b, LEN_B, 84);
+        let mut t3 = tap(c, LEN_C, 66) ^ tap(c, LEN_C, 111);
+        let z = t1 ^ t2 ^ t3;
+        t1 ^= (tap(a, LEN_A, 91) & tap(a, LEN_A, 92)) ^ tap(b, LEN_B, 78);
+        t2 ^= (tap(b, LEN_B, 82) & tap(b, LEN_B, 83)) ^ tap(c, LEN_C, 87);
+        t3 ^= (tap(c, LEN_C, 109) & tap(c, LEN_C, 110)) ^ tap(a, LEN_A, 69);
+        self.a = (a >> 64) | ((t3 as u128) << (LEN_A - 64));
+        self.b = (b >> 64) | ((t1 as u128) << (LEN_B - 64));
+        self.c = (c >> 64) | ((t2 as u128) << (LEN_C - 64));
+        z
+    }
+}
+
+impl StreamCipher for Trivium {
+    fn apply_keystream(&mut self, data: &mut [u8]) -> Result<(), CryptoError> {
+        self.pos = advance(self.pos, data.len(), None)?;
+        for b in data {
+            if self.used == 8 {
+                self.keystream = self.clock().to_le_bytes();
+                self.used = 0;
+            }
+            *b ^= self.keystream[self.used];
+            self.used += 1;
+        }
+        Ok(())
+    }
+
+    /// Seeking backwards replays the keystream from the start.
+    fn seek(&mut self, pos: u64) -> Result<(), CryptoError> {
+        if pos < self.pos {
+            [self.a, self.b, self.c] = self.start;
+            self.used = 8;
+            self.pos = 0;
+        }
+        skip(self, pos - self.pos)
+    }
+
+    fn current_pos(&self) -> u64 {
+        self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    fn keystream(key: &str, iv: &str, len: usize) -> Vec<u8> {
+        let mut out = vec![0; len];
+        Trivium::new(&hex(key), &hex(iv))
+            .unwrap()
+            .apply_keystream(&mut out)
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn test_trivium_estream() {
+        // eSTREAM, all zero key and IV.
+        assert_eq!(
+            keystream("00000000000000000000", "00000000000000000000", 64),
+            hex(
+                "fbe0bf265859051b517a2e4e239fc97f563203161907cf2de7a8790fa1b2e9cd
+                 f75292030268b7382b4c1a759aa2599a285549986e74805903801a4cb5a5d4f2"
+            )
+        );
+    }
+
+    #[test]
+    fn test_trivium_bit_order() {
+        // eSTREAM Set 1 vector 0 and Set 6 vector 0, stream[0..63].
+        assert_eq!(
+            keystream("80000000000000000000", "00000000000000000000", 64),
+            hex(
+                "38eb86ff730d7a9caf8df13a4420540dbb7b651464c87501552041c249f29a64
+                 d2fbf515610921ebe06c8f92cecf7f8098ff20cccc6a62b97be8ef7454fc80f9"
+            )
+        );
+        assert_eq!(
+            keystream("0053a6f94c9ff24598eb", "0d74db42a91077de45ac", 64),
+            hex(
+                "f4cd954a717f26a7d6930830c4e7cf0819f80e03f25f342c64adc66aba7f8a8e
+                 6eaa49f23632ae3cd41a7bd290a0132f81c6d4043b6e397d7388f3a03b5fe358"
+            )
+        );
+    }
+
+    #[test]
+    fn test_trivium_streaming() {
+        let expected = keystream("0053a6f94c9ff24598eb", "0d74db42a91077de45ac", 100);
+        for split in [1, 7, 8, 9, 63] {
+            let mut trivium =
+                Trivium::new(&hex("0053a6f94c9ff24598eb"), &hex("0d74db42a91077de45ac")).unwrap();
+            let mut buffer = [0_u8; 100];
+            let (a, b) = buffer.split_at_mut(split);
+            trivium.apply_keystream(a).unwrap();
+            trivium.apply_keystream(b).unwrap();
+            assert_eq!(buffer.to_vec(), expected, "split at {}", split);
+            for pos in [99, 8, 0, 50] {
+                trivium.seek(pos).unwrap();
+                let mut byte = [0];
+                trivium.apply_keystream(&mut byte).unwrap();
+                assert_eq!(byte[0], expected[pos as usize], "seek to {}", pos);
+            }
+        }
+    }
+
+    #[test]
+    fn test_trivium_errors() {
+        assert_eq!(
+            Trivium::new(&[0; 16], &[0; 10]).err(),
+            Some(CryptoError::InvalidKeyLength)
+        );
+        assert_eq!(
+            Trivium::new(&[0; 10], &[0; 8]).err(),
+            Some(CryptoError::InvalidNonceLength)
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//////////////////////////////////////////////////////////////////////
+/// Compares two byte sequences in a time that only depends on their
+/// length, used to check authentication tags.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    std::hint::black_box(diff) == 0
+}
+
+//////////////////////////////////////////////////////////////////////
+/// XORs `data` with the first `data.len()` bytes of `other`.
+//////////////////////////////////////////////////////////////////////
+pub(crate) fn xor_in_place(data: &mut [u8], other: &[u8]) {
+    for (d, o) in data.iter_mut().zip(other.iter()) {
+        *d ^= o;
+    }
+}
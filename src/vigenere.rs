@@ -22,6 +22,7 @@
 /// any non ascii alphabetic character in the message is left as is.
 /// '''
 /////////////////////////////////////////////////////////////////////////////
+#[allow(clippy::len_zero, clippy::manual_div_ceil)]
 pub fn vigenere_enc(msg:&str,key:&str)->String 
 {
     if key.len() == 0 || msg.len() == 0
@@ -68,6 +69,7 @@ pub fn vigenere_enc(msg:&str,key:&str)->String
 /// any non ascii alphabetic character in the message is left as is.
 /// '''
 /////////////////////////////////////////////////////////////////////////
+#[allow(clippy::len_zero, clippy::manual_div_ceil)]
 pub fn vigenere_dec(msg:&str,key:&str)->String 
 {
     if key.len() == 0 || msg.len() == 0